@@ -0,0 +1,153 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+/// Error returned by fallible commands. Serializes as `{ code, message }` so
+/// the UI can branch on `code` and still show `message` as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepTankError {
+    /// A fish, species, tank or other record doesn't exist
+    NotFound(String),
+    /// The request itself is malformed or out of range
+    InvalidArgument(String),
+    /// The request clashes with the current state, e.g. a tank name already in use
+    Conflict(String),
+    /// File, database or window-system failure
+    Io(String),
+    /// No tank database is open
+    Unavailable(String),
+    /// The user dismissed a file dialog
+    Cancelled,
+}
+
+impl DeepTankError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::InvalidArgument(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict(message.into())
+    }
+
+    pub fn no_database() -> Self {
+        Self::Unavailable("No database".to_string())
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "not_found",
+            Self::InvalidArgument(_) => "invalid_argument",
+            Self::Conflict(_) => "conflict",
+            Self::Io(_) => "io",
+            Self::Unavailable(_) => "unavailable",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(m) | Self::InvalidArgument(m) | Self::Conflict(m) | Self::Io(m) | Self::Unavailable(m) => m,
+            Self::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl fmt::Display for DeepTankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for DeepTankError {}
+
+impl Serialize for DeepTankError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("DeepTankError", 2)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", self.message())?;
+        s.end()
+    }
+}
+
+/// Simulation-level checks report plain strings; they reject the request's
+/// arguments against the tank as it stands
+impl From<String> for DeepTankError {
+    fn from(message: String) -> Self {
+        Self::InvalidArgument(message)
+    }
+}
+
+impl From<rusqlite::Error> for DeepTankError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for DeepTankError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<tauri::Error> for DeepTankError {
+    fn from(e: tauri::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for DeepTankError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<crate::simulation::persistence::ImportError> for DeepTankError {
+    fn from(e: crate::simulation::persistence::ImportError) -> Self {
+        use crate::simulation::persistence::ImportError;
+        match e {
+            ImportError::Exists => Self::conflict("Tank already exists"),
+            ImportError::Io(e) => e.into(),
+            ImportError::NotATank(e) => Self::invalid(format!("Not a valid tank save: {}", e)),
+        }
+    }
+}
+
+impl From<crate::simulation::ReviveError> for DeepTankError {
+    fn from(e: crate::simulation::ReviveError) -> Self {
+        use crate::simulation::ReviveError;
+        match e {
+            ReviveError::NotExtinct => Self::conflict("Species is not extinct"),
+            ReviveError::NoRoom => Self::conflict("No room in the tank"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_code_and_message() {
+        let v = serde_json::to_value(DeepTankError::not_found("Fish not found")).unwrap();
+        assert_eq!(v, serde_json::json!({ "code": "not_found", "message": "Fish not found" }));
+        let v = serde_json::to_value(DeepTankError::Cancelled).unwrap();
+        assert_eq!(v["code"], "cancelled");
+    }
+
+    #[test]
+    fn plain_strings_are_invalid_arguments() {
+        let e: DeepTankError = "Both fish must be mature".to_string().into();
+        assert_eq!(e.code(), "invalid_argument");
+        assert_eq!(e.to_string(), "Both fish must be mature");
+    }
+
+    #[test]
+    fn refused_revivals_are_conflicts() {
+        use crate::simulation::ReviveError;
+        assert_eq!(DeepTankError::from(ReviveError::NotExtinct).code(), "conflict");
+        assert_eq!(DeepTankError::from(ReviveError::NoRoom).message(), "No room in the tank");
+    }
+}
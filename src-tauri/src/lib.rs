@@ -1,5 +1,10 @@
-mod simulation;
+/// Headless simulation core, usable without the Tauri shell (e.g. from integration tests)
+// The enums' `from_str` helpers are lenient lookups, not `FromStr` parsers
+#[allow(clippy::should_implement_trait)]
+pub mod simulation;
+mod error;
 
+use error::DeepTankError;
 use simulation::SimulationState;
 use simulation::achievements::{self, Achievement};
 use simulation::genome::FishGenome;
@@ -37,6 +42,84 @@ fn feed(state: tauri::State<'_, Mutex<SimulationState>>, x: f32, y: f32, food_ty
     }
 }
 
+#[tauri::command]
+fn predict_food_landing(state: tauri::State<'_, Mutex<SimulationState>>, x: f32, y: f32, food_type: String) -> (f32, f32) {
+    let sim = state.lock().unwrap();
+    simulation::ecosystem::FoodParticle::predict_landing(
+        x, y, simulation::ecosystem::FoodType::from_str(&food_type), &sim.config, sim.tick,
+    )
+}
+
+#[tauri::command]
+fn get_loop_diagnostics(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    stats: tauri::State<'_, Mutex<simulation::pacing::LoopStats>>,
+) -> serde_json::Value {
+    let speed_multiplier = state.lock().unwrap().speed_multiplier;
+    let stats = stats.lock().unwrap();
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    let steps_per_frame = simulation::pacing::steps_per_frame(speed_multiplier);
+    let target_tps = simulation::pacing::target_ticks_per_second(speed_multiplier);
+    let achieved_tps = stats.ticks_per_second();
+    serde_json::json!({
+        "frame_budget_ms": ms(simulation::pacing::FRAME_DURATION),
+        "avg_step_ms": ms(stats.avg_step_time()),
+        "avg_frame_ms": ms(stats.avg_frame_time()),
+        "max_frame_ms": ms(stats.max_frame_time()),
+        "keeping_up": stats.keeping_up(),
+        "speed_multiplier": speed_multiplier,
+        "steps_per_frame": steps_per_frame,
+        "target_ticks_per_second": target_tps,
+        "achieved_ticks_per_second": achieved_tps,
+        "sampled_frames": stats.frame_count(),
+    })
+}
+
+/// The simulation RNG's exact position, for reproducing a run from here
+#[tauri::command]
+fn get_rng_checkpoint(state: tauri::State<'_, Mutex<SimulationState>>) -> String {
+    simulation::rng::checkpoint(&state.lock().unwrap().rng)
+}
+
+/// Rewind or advance the simulation RNG to a checkpoint and save it with the tank
+#[tauri::command]
+fn restore_rng_checkpoint(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    checkpoint: String,
+) -> Result<(), DeepTankError> {
+    let rng = simulation::rng::from_checkpoint(&checkpoint)?;
+    state.lock().unwrap().rng = rng;
+    if let Some(ref conn) = *db.lock().unwrap() {
+        persistence::save_rng_state(conn, &checkpoint)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_boids_diagnostics(state: tauri::State<'_, Mutex<SimulationState>>) -> serde_json::Value {
+    serde_json::to_value(state.lock().unwrap().boids_diagnostics()).unwrap_or_default()
+}
+
+#[tauri::command]
+fn get_noise_seed(state: tauri::State<'_, Mutex<SimulationState>>) -> u32 {
+    state.lock().unwrap().noise_seed
+}
+
+/// Reseed this tank's wander field; the same seed reproduces the same wander paths
+#[tauri::command]
+fn set_noise_seed(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    seed: u32,
+) -> Result<(), DeepTankError> {
+    state.lock().unwrap().set_noise_seed(seed);
+    if let Some(ref conn) = *db.lock().unwrap() {
+        persistence::save_noise_seed(conn, seed)?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 fn step_forward(state: tauri::State<'_, Mutex<SimulationState>>) -> simulation::FrameUpdate {
     let mut sim = state.lock().unwrap();
@@ -68,28 +151,48 @@ fn tap_glass(state: tauri::State<'_, Mutex<SimulationState>>, x: f32, y: f32) {
 }
 
 #[tauri::command]
-fn trigger_event(state: tauri::State<'_, Mutex<SimulationState>>, event_type: String) -> Result<(), String> {
+fn trigger_event(state: tauri::State<'_, Mutex<SimulationState>>, event_type: String) -> Result<(), DeepTankError> {
     let event = simulation::events::EnvironmentalEvent::from_str(&event_type)
-        .ok_or_else(|| format!("Unknown event type: {}", event_type))?;
+        .ok_or_else(|| DeepTankError::invalid(format!("Unknown event type: {}", event_type)))?;
     let mut sim = state.lock().unwrap();
-    sim.event_system.trigger(event);
+    let max_concurrent = sim.config.max_concurrent_events;
+    sim.event_system.trigger(event, max_concurrent);
     Ok(())
 }
 
 #[tauri::command]
-fn breed_fish(state: tauri::State<'_, Mutex<SimulationState>>, fish_a_id: u32, fish_b_id: u32) -> Result<u32, String> {
+fn breed_fish(state: tauri::State<'_, Mutex<SimulationState>>, fish_a_id: u32, fish_b_id: u32) -> Result<u32, DeepTankError> {
     let mut sim = state.lock().unwrap();
+    for (label, id) in [("A", fish_a_id), ("B", fish_b_id)] {
+        let parent = sim.fish.iter().find(|f| f.id == id && f.is_alive)
+            .ok_or_else(|| DeepTankError::not_found(format!("Fish {} not found or dead", label)))?;
+        if !sim.genomes.contains_key(&parent.genome_id) {
+            return Err(DeepTankError::not_found(format!("Genome {} not found", label)));
+        }
+    }
     let tick = sim.tick;
     let config = sim.config.clone();
     let SimulationState { ref mut ecosystem, ref mut fish, ref mut genomes, ref mut rng, .. } = *sim;
-    ecosystem.force_breed(fish, genomes, &config, tick, rng, fish_a_id, fish_b_id)
+    ecosystem.force_breed(fish, genomes, &config, tick, rng, fish_a_id, fish_b_id).map_err(DeepTankError::from)
+}
+
+#[tauri::command]
+fn introduce_predator(state: tauri::State<'_, Mutex<SimulationState>>, count: u32) -> Vec<u32> {
+    let mut sim = state.lock().unwrap();
+    sim.introduce_predators(count)
 }
 
 #[tauri::command]
-fn get_breed_preview(state: tauri::State<'_, Mutex<SimulationState>>, genome_a_id: u32, genome_b_id: u32) -> Result<serde_json::Value, String> {
+fn clone_fish(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32, count: u32) -> Vec<u32> {
+    let mut sim = state.lock().unwrap();
+    sim.clone_fish(fish_id, count)
+}
+
+#[tauri::command]
+fn get_breed_preview(state: tauri::State<'_, Mutex<SimulationState>>, genome_a_id: u32, genome_b_id: u32) -> Result<serde_json::Value, DeepTankError> {
     let sim = state.lock().unwrap();
-    let ga = sim.genomes.get(&genome_a_id).ok_or("Genome A not found")?;
-    let gb = sim.genomes.get(&genome_b_id).ok_or("Genome B not found")?;
+    let ga = sim.genomes.get(&genome_a_id).ok_or_else(|| DeepTankError::not_found("Genome A not found"))?;
+    let gb = sim.genomes.get(&genome_b_id).ok_or_else(|| DeepTankError::not_found("Genome B not found"))?;
     // Predict offspring trait ranges (midpoint +/- 10% variance)
     let mid = |a: f32, b: f32| -> (f32, f32, f32) {
         let m = (a + b) / 2.0;
@@ -111,6 +214,35 @@ fn get_breed_preview(state: tauri::State<'_, Mutex<SimulationState>>, genome_a_i
     }))
 }
 
+#[tauri::command]
+fn diff_genomes(state: tauri::State<'_, Mutex<SimulationState>>, genome_a_id: u32, genome_b_id: u32) -> Result<simulation::genome::GenomeDiff, DeepTankError> {
+    let sim = state.lock().unwrap();
+    let ga = sim.genomes.get(&genome_a_id).ok_or_else(|| DeepTankError::not_found("Genome A not found"))?;
+    let gb = sim.genomes.get(&genome_b_id).ok_or_else(|| DeepTankError::not_found("Genome B not found"))?;
+    Ok(simulation::genome::diff_genomes(ga, gb, sim.config.distance_weights(), sim.config.species_threshold))
+}
+
+#[tauri::command]
+fn recommend_pairing(state: tauri::State<'_, Mutex<SimulationState>>, trait_name: String, maximize: bool) -> Result<simulation::breeding::PairingReport, DeepTankError> {
+    let mut sim = state.lock().unwrap();
+    let SimulationState { ref fish, ref genomes, ref config, ref ecosystem, ref mut rng, .. } = *sim;
+    simulation::breeding::recommend_pairings(fish, genomes, config, &ecosystem.conservation, &trait_name, maximize, rng).map_err(DeepTankError::from)
+}
+
+#[tauri::command]
+fn inheritance_table(state: tauri::State<'_, Mutex<SimulationState>>, genome_a_id: u32, genome_b_id: u32, trait_name: String) -> Result<simulation::breeding::InheritanceTable, DeepTankError> {
+    let sim = state.lock().unwrap();
+    let ga = sim.genomes.get(&genome_a_id).ok_or_else(|| DeepTankError::not_found(format!("Genome {} not found", genome_a_id)))?;
+    let gb = sim.genomes.get(&genome_b_id).ok_or_else(|| DeepTankError::not_found(format!("Genome {} not found", genome_b_id)))?;
+    simulation::breeding::inheritance_table(ga, gb, &sim.genomes, &sim.config, &sim.ecosystem.conservation, &trait_name).map_err(DeepTankError::from)
+}
+
+/// Range and simulation effects of every genome trait, for tooltips
+#[tauri::command]
+fn get_trait_info() -> serde_json::Value {
+    serde_json::json!(simulation::genome::TRAIT_INFO)
+}
+
 #[tauri::command]
 fn get_genome(state: tauri::State<'_, Mutex<SimulationState>>, genome_id: u32) -> Option<FishGenome> {
     state.lock().unwrap().get_genome(genome_id).cloned()
@@ -143,11 +275,81 @@ fn get_species_history(state: tauri::State<'_, Mutex<SimulationState>>) -> Vec<s
             "centroid_size": s.centroid_size,
             "centroid_pattern": s.centroid_pattern,
             "member_count": s.member_count,
+            "distinct_marker": s.distinct_marker,
             "representative_genome_id": rep_genome_id,
         })
     }).collect()
 }
 
+#[tauri::command]
+fn get_species_average_genome(state: tauri::State<'_, Mutex<SimulationState>>, species_id: u32) -> Result<FishGenome, DeepTankError> {
+    let sim = state.lock().unwrap();
+    let species = sim.ecosystem.species.iter().find(|s| s.id == species_id)
+        .ok_or_else(|| DeepTankError::not_found("Species not found"))?;
+    let members: Vec<&FishGenome> = sim.fish.iter()
+        .filter(|f| f.is_alive && species.member_genome_ids.contains(&f.genome_id))
+        .filter_map(|f| sim.genomes.get(&f.genome_id))
+        .collect();
+    FishGenome::average(&members).ok_or_else(|| DeepTankError::conflict("Species has no living members"))
+}
+
+/// Genetic profile of an extinct species, averaged over its last members
+#[tauri::command]
+fn get_extinct_species_profile(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    species_id: u32,
+) -> Result<simulation::persistence::ExtinctSpeciesProfile, DeepTankError> {
+    extinct_species_profile(&state, &db, species_id)
+}
+
+/// Spawn fish from an extinct species' fossil genome and return their ids
+#[tauri::command]
+fn revive_species(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    species_id: u32,
+    count: u32,
+) -> Result<Vec<u32>, DeepTankError> {
+    if count == 0 {
+        return Err(DeepTankError::invalid("Count must be at least 1"));
+    }
+    let profile = extinct_species_profile(&state, &db, species_id)?;
+    state.lock().unwrap().revive_species(&profile, count).map_err(DeepTankError::from)
+}
+
+fn extinct_species_profile(
+    state: &Mutex<SimulationState>,
+    db: &Mutex<Option<rusqlite::Connection>>,
+    species_id: u32,
+) -> Result<simulation::persistence::ExtinctSpeciesProfile, DeepTankError> {
+    // Recent extinctions may not be saved yet; long-past ones are only on disk
+    {
+        let sim = state.lock().unwrap();
+        if let Some(s) = sim.ecosystem.species.iter().find(|s| s.id == species_id) {
+            if s.extinct_at_tick.is_none() {
+                return Err(DeepTankError::conflict("Species is not extinct"));
+            }
+            if let Some(profile) = simulation::persistence::ExtinctSpeciesProfile::of(s) {
+                return Ok(profile);
+            }
+        }
+    }
+    let guard = db.lock().unwrap();
+    let conn = guard.as_ref().ok_or_else(DeepTankError::no_database)?;
+    simulation::persistence::extinct_species_profile(conn, species_id)?
+        .ok_or_else(|| DeepTankError::not_found("No profile recorded for this species"))
+}
+
+#[tauri::command]
+fn get_trait_histogram(state: tauri::State<'_, Mutex<SimulationState>>, trait_name: String, bins: u32) -> Result<Vec<u32>, DeepTankError> {
+    let sim = state.lock().unwrap();
+    let living = sim.fish.iter()
+        .filter(|f| f.is_alive)
+        .filter_map(|f| sim.genomes.get(&f.genome_id));
+    simulation::scenarios::trait_histogram(living, &trait_name, bins).map_err(DeepTankError::from)
+}
+
 #[tauri::command]
 fn get_fish_detail(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32) -> Option<serde_json::Value> {
     let sim = state.lock().unwrap();
@@ -156,6 +358,8 @@ fn get_fish_detail(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32
     let species_name = sim.ecosystem.species.iter()
         .find(|s| s.extinct_at_tick.is_none() && s.member_genome_ids.contains(&fish.genome_id))
         .and_then(|s| s.name.clone());
+    let diet = simulation::ecosystem::Diet::of(genome);
+    let remaining_lifespan = fish.remaining_lifespan(genome, sim.config.max_lifespan);
 
     Some(serde_json::json!({
         "id": fish.id,
@@ -165,6 +369,8 @@ fn get_fish_detail(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32
         "z": fish.z,
         "heading": fish.heading,
         "age": fish.age,
+        "remaining_lifespan": remaining_lifespan,
+        "remaining_lifespan_seconds": simulation::pacing::ticks_to_seconds(remaining_lifespan as f32, sim.speed_multiplier),
         "hunger": fish.hunger,
         "health": fish.health,
         "energy": fish.energy,
@@ -172,32 +378,136 @@ fn get_fish_detail(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32
         "meals_eaten": fish.meals_eaten,
         "is_alive": fish.is_alive,
         "is_infected": fish.is_infected,
+        "acquired_immunity": fish.acquired_immunity,
+        "diet": diet,
+        "preferred_food": diet.preferred_food().map(|f| f.as_str()),
+        "disliked_food": diet.disliked_food().map(|f| f.as_str()),
         "custom_name": fish.custom_name,
         "is_favorite": fish.is_favorite,
+        "is_quarantined": fish.is_quarantined,
+        "protected_lineage": sim.ecosystem.conservation.founder_for(genome.id).map(|g| g.id),
         "genome": genome,
         "species_name": species_name,
     }))
 }
 
 #[tauri::command]
-fn name_fish(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32, name: String) -> Result<(), String> {
+fn name_fish(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32, name: String) -> Result<(), DeepTankError> {
     let mut sim = state.lock().unwrap();
     let fish = sim.fish.iter_mut().find(|f| f.id == fish_id && f.is_alive)
-        .ok_or("Fish not found")?;
-    let trimmed = name.trim();
-    fish.custom_name = if trimmed.is_empty() { None } else { Some(trimmed.chars().take(20).collect()) };
+        .ok_or_else(|| DeepTankError::not_found("Fish not found"))?;
+    fish.set_name(&name);
     Ok(())
 }
 
 #[tauri::command]
-fn toggle_favorite(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32) -> Result<bool, String> {
+fn name_fish_batch(state: tauri::State<'_, Mutex<SimulationState>>, assignments: Vec<(u32, String)>) -> serde_json::Value {
+    let mut sim = state.lock().unwrap();
+    let mut named = Vec::new();
+    let mut failed = Vec::new();
+    for (fish_id, name) in assignments {
+        match sim.fish.iter_mut().find(|f| f.id == fish_id && f.is_alive) {
+            Some(fish) => {
+                fish.set_name(&name);
+                named.push(fish_id);
+            }
+            None => failed.push(serde_json::json!({ "fish_id": fish_id, "error": "Fish not found" })),
+        }
+    }
+    serde_json::json!({ "named": named, "failed": failed })
+}
+
+/// Name every living member of a species `Prefix-1`, `Prefix-2`, ... in fish id order
+#[tauri::command]
+fn auto_name_species(state: tauri::State<'_, Mutex<SimulationState>>, species_id: u32, prefix: String) -> Result<u32, DeepTankError> {
+    let mut sim = state.lock().unwrap();
+    let SimulationState { ref mut fish, ref ecosystem, .. } = *sim;
+    let species = ecosystem.species.iter().find(|s| s.id == species_id)
+        .ok_or_else(|| DeepTankError::not_found("Species not found"))?;
+    let mut members: Vec<&mut simulation::fish::Fish> = fish.iter_mut()
+        .filter(|f| f.is_alive && species.member_genome_ids.contains(&f.genome_id))
+        .collect();
+    members.sort_by_key(|f| f.id);
+
+    // Shorten the prefix so the largest number still fits in a name
+    let suffix_len = members.len().to_string().len() + 1;
+    let prefix: String = prefix.trim().chars().take(simulation::fish::MAX_NAME_LEN.saturating_sub(suffix_len)).collect();
+    let prefix = prefix.trim_end();
+    if prefix.is_empty() {
+        return Err(DeepTankError::invalid("Prefix cannot be empty"));
+    }
+    for (n, f) in members.iter_mut().enumerate() {
+        f.set_name(&format!("{}-{}", prefix, n + 1));
+    }
+    Ok(members.len() as u32)
+}
+
+#[tauri::command]
+fn toggle_favorite(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32) -> Result<bool, DeepTankError> {
     let mut sim = state.lock().unwrap();
     let fish = sim.fish.iter_mut().find(|f| f.id == fish_id && f.is_alive)
-        .ok_or("Fish not found")?;
+        .ok_or_else(|| DeepTankError::not_found("Fish not found"))?;
     fish.is_favorite = !fish.is_favorite;
     Ok(fish.is_favorite)
 }
 
+#[tauri::command]
+fn quarantine_fish(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32) -> Result<(), DeepTankError> {
+    let mut sim = state.lock().unwrap();
+    let fish = sim.fish.iter_mut().find(|f| f.id == fish_id && f.is_alive)
+        .ok_or_else(|| DeepTankError::not_found("Fish not found"))?;
+    if fish.is_quarantined {
+        return Err(DeepTankError::conflict("Fish is already quarantined"));
+    }
+    fish.quarantine();
+    Ok(())
+}
+
+#[tauri::command]
+fn release_fish(state: tauri::State<'_, Mutex<SimulationState>>, fish_id: u32) -> Result<(), DeepTankError> {
+    let mut sim = state.lock().unwrap();
+    let fish = sim.fish.iter_mut().find(|f| f.id == fish_id && f.is_alive)
+        .ok_or_else(|| DeepTankError::not_found("Fish not found"))?;
+    if !fish.is_quarantined {
+        return Err(DeepTankError::conflict("Fish is not quarantined"));
+    }
+    fish.release();
+    Ok(())
+}
+
+/// Start a conservation program for a genome and its descendants
+#[tauri::command]
+fn protect_lineage(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    genome_id: u32,
+) -> Result<(), DeepTankError> {
+    let mut sim = state.lock().unwrap();
+    let founder = sim.genomes.get(&genome_id).cloned().ok_or_else(|| DeepTankError::not_found("Genome not found"))?;
+    let SimulationState { ref mut ecosystem, ref genomes, .. } = *sim;
+    ecosystem.conservation.protect(&founder, genomes);
+    if let Some(conn) = db.lock().unwrap().as_ref() {
+        persistence::save_conservation(conn, &sim.ecosystem.conservation)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn unprotect_lineage(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    genome_id: u32,
+) -> Result<(), DeepTankError> {
+    let mut sim = state.lock().unwrap();
+    if !sim.ecosystem.conservation.unprotect(genome_id) {
+        return Err(DeepTankError::conflict("Lineage is not protected"));
+    }
+    if let Some(conn) = db.lock().unwrap().as_ref() {
+        persistence::save_conservation(conn, &sim.ecosystem.conservation)?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 fn get_favorites(state: tauri::State<'_, Mutex<SimulationState>>) -> Vec<serde_json::Value> {
     let sim = state.lock().unwrap();
@@ -221,12 +531,119 @@ fn get_favorites(state: tauri::State<'_, Mutex<SimulationState>>) -> Vec<serde_j
         .collect()
 }
 
+/// Returns a warning when the tank is so large the spatial grid had to use
+/// coarser cells than it had before
+#[tauri::command]
+fn update_tank_size(state: tauri::State<'_, Mutex<SimulationState>>, width: f32, height: f32) -> Option<String> {
+    state.lock().unwrap().resize_tank(width, height)
+}
+
 #[tauri::command]
-fn update_tank_size(state: tauri::State<'_, Mutex<SimulationState>>, width: f32, height: f32) {
+fn set_current(state: tauri::State<'_, Mutex<SimulationState>>, strength: f32, direction_degrees: f32) -> Result<simulation::config::ConfigUpdate, DeepTankError> {
     let mut sim = state.lock().unwrap();
-    sim.config.tank_width = width;
-    sim.config.tank_height = height;
-    sim.boids.grid = simulation::boids::SpatialGrid::new(width, height, sim.config.cohesion_radius);
+    sim.config.set_current(strength, direction_degrees).map_err(DeepTankError::from)
+}
+
+/// Fixed seed so benchmark runs are comparable across builds
+const BENCHMARK_SEED: u64 = 0xDEE9_7A4C;
+
+/// Time `step()` on a throwaway seeded tank, independent of the live one
+#[tauri::command]
+async fn benchmark_step(population: u32, iterations: u32) -> Result<serde_json::Value, DeepTankError> {
+    if !(1..=5000).contains(&population) {
+        return Err(DeepTankError::invalid("Population must be 1-5000"));
+    }
+    if !(1..=100_000).contains(&iterations) {
+        return Err(DeepTankError::invalid("Iterations must be 1-100000"));
+    }
+    // A long run would otherwise stall an async worker for its whole duration
+    let (times, total, final_population) = tauri::async_runtime::spawn_blocking(move || {
+        let mut sim = SimulationState::with_seed_and_population(BENCHMARK_SEED, population);
+        let mut times = Vec::with_capacity(iterations as usize);
+        let total_start = std::time::Instant::now();
+        for _ in 0..iterations {
+            let start = std::time::Instant::now();
+            sim.step();
+            times.push(start.elapsed().as_secs_f64() * 1000.0);
+        }
+        (times, total_start.elapsed().as_secs_f64(), sim.fish.len())
+    })
+    .await?;
+    let min = times.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = times.iter().copied().fold(0.0, f64::max);
+    let avg = times.iter().sum::<f64>() / times.len() as f64;
+    Ok(serde_json::json!({
+        "population": population,
+        "final_population": final_population,
+        "iterations": iterations,
+        "min_ms": min,
+        "avg_ms": avg,
+        "max_ms": max,
+        "steps_per_second": if total > 0.0 { iterations as f64 / total } else { 0.0 },
+    }))
+}
+
+#[tauri::command]
+fn get_grid_stats(state: tauri::State<'_, Mutex<SimulationState>>) -> serde_json::Value {
+    let sim = state.lock().unwrap();
+    let stats = sim.grid_stats();
+    serde_json::json!({
+        "cell_size": stats.cell_size,
+        "cols": stats.cols,
+        "rows": stats.rows,
+        "total_cells": stats.total_cells,
+        "empty_cells": stats.empty_cells,
+        "max_per_cell": stats.max_per_cell,
+        "avg_per_occupied_cell": stats.avg_per_occupied_cell,
+        "population": sim.fish.len(),
+        "cohesion_radius": sim.config.cohesion_radius,
+    })
+}
+
+#[tauri::command]
+fn get_camera_suggestion(state: tauri::State<'_, Mutex<SimulationState>>) -> serde_json::Value {
+    let sim = state.lock().unwrap();
+    serde_json::to_value(simulation::camera::suggest(&sim.fish, &sim.config)).unwrap_or_default()
+}
+
+#[tauri::command]
+fn get_environmental_outlook(state: tauri::State<'_, Mutex<SimulationState>>) -> serde_json::Value {
+    let sim = state.lock().unwrap();
+    let outlook = simulation::outlook::forecast(&sim.config, &sim.event_system, sim.time_of_day, sim.speed_multiplier);
+    serde_json::to_value(outlook).unwrap_or_default()
+}
+
+#[tauri::command]
+fn set_grid_cell_size(state: tauri::State<'_, Mutex<SimulationState>>, cell_size: f32) -> Result<(), DeepTankError> {
+    state.lock().unwrap().set_grid_cell_size(cell_size).map_err(DeepTankError::from)
+}
+
+const SNAPSHOT_COLUMNS: &str =
+    "tick, population, species_count, water_quality, avg_hue, avg_speed, avg_size, avg_aggression,
+     avg_boldness, avg_school_affinity, avg_disease_resistance, min_speed, max_speed, min_size, max_size,
+     genetic_diversity, avg_temp_tolerance";
+
+/// One `population_snapshots` row selected with `SNAPSHOT_COLUMNS`
+fn snapshot_json(row: &rusqlite::Row) -> serde_json::Value {
+    serde_json::json!({
+        "tick": row.get::<_, i64>(0).unwrap_or(0),
+        "population": row.get::<_, i32>(1).unwrap_or(0),
+        "species_count": row.get::<_, i32>(2).unwrap_or(0),
+        "water_quality": row.get::<_, f64>(3).unwrap_or(0.0),
+        "avg_hue": row.get::<_, f64>(4).unwrap_or(0.0),
+        "avg_speed": row.get::<_, f64>(5).unwrap_or(0.0),
+        "avg_size": row.get::<_, f64>(6).unwrap_or(0.0),
+        "avg_aggression": row.get::<_, f64>(7).unwrap_or(0.0),
+        "avg_boldness": row.get::<_, f64>(8).unwrap_or(0.5),
+        "avg_school_affinity": row.get::<_, f64>(9).unwrap_or(0.5),
+        "avg_disease_resistance": row.get::<_, f64>(10).unwrap_or(0.5),
+        "min_speed": row.get::<_, f64>(11).unwrap_or(0.5),
+        "max_speed": row.get::<_, f64>(12).unwrap_or(2.0),
+        "min_size": row.get::<_, f64>(13).unwrap_or(0.6),
+        "max_size": row.get::<_, f64>(14).unwrap_or(2.0),
+        "genetic_diversity": row.get::<_, f64>(15).unwrap_or(0.5),
+        "avg_temp_tolerance": row.get::<_, f64>(16).unwrap_or(0.5),
+    })
 }
 
 #[tauri::command]
@@ -237,36 +654,14 @@ fn get_snapshots(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>) -> V
         None => return Vec::new(),
     };
     let mut stmt = match conn.prepare(
-        "SELECT tick, population, species_count, water_quality, avg_hue, avg_speed, avg_size, avg_aggression,
-                avg_boldness, avg_school_affinity, avg_disease_resistance, min_speed, max_speed, min_size, max_size,
-                genetic_diversity
-         FROM population_snapshots ORDER BY tick DESC LIMIT 200"
+        &format!("SELECT {} FROM population_snapshots ORDER BY tick DESC LIMIT 200", SNAPSHOT_COLUMNS)
     ) {
         Ok(s) => s,
         Err(_) => return Vec::new(),
     };
 
     let mut results = Vec::new();
-    if let Ok(rows) = stmt.query_map([], |row| {
-        Ok(serde_json::json!({
-            "tick": row.get::<_, i64>(0).unwrap_or(0),
-            "population": row.get::<_, i32>(1).unwrap_or(0),
-            "species_count": row.get::<_, i32>(2).unwrap_or(0),
-            "water_quality": row.get::<_, f64>(3).unwrap_or(0.0),
-            "avg_hue": row.get::<_, f64>(4).unwrap_or(0.0),
-            "avg_speed": row.get::<_, f64>(5).unwrap_or(0.0),
-            "avg_size": row.get::<_, f64>(6).unwrap_or(0.0),
-            "avg_aggression": row.get::<_, f64>(7).unwrap_or(0.0),
-            "avg_boldness": row.get::<_, f64>(8).unwrap_or(0.5),
-            "avg_school_affinity": row.get::<_, f64>(9).unwrap_or(0.5),
-            "avg_disease_resistance": row.get::<_, f64>(10).unwrap_or(0.5),
-            "min_speed": row.get::<_, f64>(11).unwrap_or(0.5),
-            "max_speed": row.get::<_, f64>(12).unwrap_or(2.0),
-            "min_size": row.get::<_, f64>(13).unwrap_or(0.6),
-            "max_size": row.get::<_, f64>(14).unwrap_or(2.0),
-            "genetic_diversity": row.get::<_, f64>(15).unwrap_or(0.5),
-        }))
-    }) {
+    if let Ok(rows) = stmt.query_map([], |row| Ok(snapshot_json(row))) {
         for r in rows.flatten() {
             results.push(r);
         }
@@ -283,36 +678,14 @@ fn get_all_snapshots(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>)
         None => return Vec::new(),
     };
     let mut stmt = match conn.prepare(
-        "SELECT tick, population, species_count, water_quality, avg_hue, avg_speed, avg_size, avg_aggression,
-                avg_boldness, avg_school_affinity, avg_disease_resistance, min_speed, max_speed, min_size, max_size,
-                genetic_diversity
-         FROM population_snapshots ORDER BY tick ASC LIMIT 10000"
+        &format!("SELECT {} FROM population_snapshots ORDER BY tick ASC LIMIT 10000", SNAPSHOT_COLUMNS)
     ) {
         Ok(s) => s,
         Err(_) => return Vec::new(),
     };
 
     let mut results = Vec::new();
-    if let Ok(rows) = stmt.query_map([], |row| {
-        Ok(serde_json::json!({
-            "tick": row.get::<_, i64>(0).unwrap_or(0),
-            "population": row.get::<_, i32>(1).unwrap_or(0),
-            "species_count": row.get::<_, i32>(2).unwrap_or(0),
-            "water_quality": row.get::<_, f64>(3).unwrap_or(0.0),
-            "avg_hue": row.get::<_, f64>(4).unwrap_or(0.0),
-            "avg_speed": row.get::<_, f64>(5).unwrap_or(0.0),
-            "avg_size": row.get::<_, f64>(6).unwrap_or(0.0),
-            "avg_aggression": row.get::<_, f64>(7).unwrap_or(0.0),
-            "avg_boldness": row.get::<_, f64>(8).unwrap_or(0.5),
-            "avg_school_affinity": row.get::<_, f64>(9).unwrap_or(0.5),
-            "avg_disease_resistance": row.get::<_, f64>(10).unwrap_or(0.5),
-            "min_speed": row.get::<_, f64>(11).unwrap_or(0.5),
-            "max_speed": row.get::<_, f64>(12).unwrap_or(2.0),
-            "min_size": row.get::<_, f64>(13).unwrap_or(0.6),
-            "max_size": row.get::<_, f64>(14).unwrap_or(2.0),
-            "genetic_diversity": row.get::<_, f64>(15).unwrap_or(0.5),
-        }))
-    }) {
+    if let Ok(rows) = stmt.query_map([], |row| Ok(snapshot_json(row))) {
         for r in rows.flatten() {
             results.push(r);
         }
@@ -320,56 +693,69 @@ fn get_all_snapshots(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>)
     results
 }
 
+/// What the tank looked like around `tick`: the nearest recorded snapshot,
+/// the species populations logged with it, and the living fish that were
+/// already alive back then
+#[tauri::command]
+fn preview_snapshot_state(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    tick: u64,
+) -> Result<serde_json::Value, DeepTankError> {
+    let (current_tick, survivors) = {
+        let sim = state.lock().unwrap();
+        let survivors: Vec<(u32, u32, u64)> = sim.fish.iter()
+            .filter(|f| f.is_alive)
+            .map(|f| (f.id, f.genome_id, sim.tick.saturating_sub(f.age as u64)))
+            .collect();
+        (sim.tick, survivors)
+    };
+
+    let guard = db.lock().unwrap();
+    let conn = guard.as_ref().ok_or_else(DeepTankError::no_database)?;
+    let snapshot_tick = persistence::nearest_snapshot_tick(conn, tick)?
+        .ok_or_else(|| DeepTankError::not_found("No snapshots recorded yet"))?;
+    let snapshot = conn.query_row(
+        &format!("SELECT {} FROM population_snapshots WHERE tick = ?1 LIMIT 1", SNAPSHOT_COLUMNS),
+        [snapshot_tick as i64],
+        |row| Ok(snapshot_json(row)),
+    )?;
+    let species: Vec<serde_json::Value> = persistence::species_snapshot_at(conn, snapshot_tick)
+        .into_iter()
+        .map(|(id, name, population)| serde_json::json!({ "species_id": id, "species_name": name, "population": population }))
+        .collect();
+    let survivors: Vec<serde_json::Value> = survivors.into_iter()
+        .filter(|&(_, _, born)| born <= snapshot_tick)
+        .map(|(fish_id, genome_id, born)| serde_json::json!({ "fish_id": fish_id, "genome_id": genome_id, "born_at_tick": born }))
+        .collect();
+
+    Ok(serde_json::json!({
+        "requested_tick": tick,
+        "snapshot_tick": snapshot_tick,
+        "ticks_ago": current_tick.saturating_sub(snapshot_tick),
+        "snapshot": snapshot,
+        "species": species,
+        "surviving_fish": survivors,
+    }))
+}
+
 #[tauri::command]
 fn get_config(state: tauri::State<'_, Mutex<SimulationState>>) -> serde_json::Value {
     let sim = state.lock().unwrap();
     serde_json::to_value(&sim.config).unwrap_or_default()
 }
 
+/// Only the settings changed from their defaults, with both values
+#[tauri::command]
+fn get_config_diff(state: tauri::State<'_, Mutex<SimulationState>>) -> serde_json::Value {
+    let sim = state.lock().unwrap();
+    serde_json::Value::Object(sim.config.diff_from_default())
+}
+
 #[tauri::command]
-fn update_config(state: tauri::State<'_, Mutex<SimulationState>>, key: String, value: serde_json::Value) {
+fn update_config(state: tauri::State<'_, Mutex<SimulationState>>, key: String, value: serde_json::Value) -> Result<simulation::config::ConfigUpdate, DeepTankError> {
     let mut sim = state.lock().unwrap();
-    let c = &mut sim.config;
-    match key.as_str() {
-        "separation_weight" => if let Some(v) = value.as_f64() { c.separation_weight = v as f32; },
-        "alignment_weight" => if let Some(v) = value.as_f64() { c.alignment_weight = v as f32; },
-        "cohesion_weight" => if let Some(v) = value.as_f64() { c.cohesion_weight = v as f32; },
-        "wander_strength" => if let Some(v) = value.as_f64() { c.wander_strength = v as f32; },
-        "hunger_rate" => if let Some(v) = value.as_f64() { c.hunger_rate = v as f32; },
-        "mutation_rate_small" => if let Some(v) = value.as_f64() { c.mutation_rate_small = v as f32; },
-        "mutation_rate_large" => if let Some(v) = value.as_f64() { c.mutation_rate_large = v as f32; },
-        "species_threshold" => if let Some(v) = value.as_f64() { c.species_threshold = v as f32; },
-        "day_night_cycle" => if let Some(v) = value.as_bool() { c.day_night_cycle = v; },
-        "day_night_speed" => if let Some(v) = value.as_f64() { c.day_night_speed = v as f32; },
-        "bubble_rate" => if let Some(v) = value.as_f64() { c.bubble_rate = v as f32; },
-        "current_strength" => if let Some(v) = value.as_f64() { c.current_strength = v as f32; },
-        "auto_feed_enabled" => if let Some(v) = value.as_bool() { c.auto_feed_enabled = v; },
-        "auto_feed_interval" => if let Some(v) = value.as_f64() { c.auto_feed_interval = v as u32; },
-        "auto_feed_amount" => if let Some(v) = value.as_f64() { c.auto_feed_amount = v as u32; },
-        "ollama_enabled" => if let Some(v) = value.as_bool() { c.ollama_enabled = v; },
-        "ollama_url" => if let Some(v) = value.as_str() {
-            // Basic URL validation: must start with http:// or https://
-            if v.starts_with("http://") || v.starts_with("https://") {
-                c.ollama_url = v.to_string();
-            }
-        },
-        "ollama_model" => if let Some(v) = value.as_str() { c.ollama_model = v.to_string(); },
-        "master_volume" => if let Some(v) = value.as_f64() { c.master_volume = v as f32; },
-        "ambient_enabled" => if let Some(v) = value.as_bool() { c.ambient_enabled = v; },
-        "event_sounds_enabled" => if let Some(v) = value.as_bool() { c.event_sounds_enabled = v; },
-        "theme" => if let Some(v) = value.as_str() { c.theme = v.to_string(); },
-        "environmental_events_enabled" => if let Some(v) = value.as_bool() { c.environmental_events_enabled = v; },
-        "event_frequency" => if let Some(v) = value.as_f64() { c.event_frequency = v as f32; },
-        "territory_enabled" => if let Some(v) = value.as_bool() { c.territory_enabled = v; },
-        "territory_claim_radius" => if let Some(v) = value.as_f64() { c.territory_claim_radius = v as f32; },
-        "disease_enabled" => if let Some(v) = value.as_bool() { c.disease_enabled = v; },
-        "disease_infection_chance" => if let Some(v) = value.as_f64() { c.disease_infection_chance = v as f32; },
-        "disease_spontaneous_chance" => if let Some(v) = value.as_f64() { c.disease_spontaneous_chance = v as f32; },
-        "disease_duration" => if let Some(v) = value.as_u64() { c.disease_duration = v as u32; },
-        "disease_damage" => if let Some(v) = value.as_f64() { c.disease_damage = v as f32; },
-        "disease_spread_radius" => if let Some(v) = value.as_f64() { c.disease_spread_radius = v as f32; },
-        _ => {}
-    }
+    sim.config.set_value(&key, value).map_err(DeepTankError::from)
 }
 
 #[tauri::command]
@@ -438,6 +824,63 @@ fn get_events(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>, event_t
     results
 }
 
+#[tauri::command]
+fn get_death_stats(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>, from_tick: u64, to_tick: u64) -> serde_json::Value {
+    let guard = db.lock().unwrap();
+    let conn = match guard.as_ref() {
+        Some(c) => c,
+        None => return serde_json::json!({ "from_tick": from_tick, "to_tick": to_tick, "total": 0, "by_cause": {} }),
+    };
+    let counts = persistence::death_counts(conn, from_tick, to_tick).unwrap_or_default();
+    let total: u32 = counts.iter().map(|(_, n)| n).sum();
+    let by_cause: serde_json::Map<String, serde_json::Value> = counts.into_iter()
+        .map(|(cause, n)| (cause, serde_json::json!(n)))
+        .collect();
+    serde_json::json!({ "from_tick": from_tick, "to_tick": to_tick, "total": total, "by_cause": by_cause })
+}
+
+#[tauri::command]
+fn get_survivorship_curve(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>) -> Vec<serde_json::Value> {
+    let guard = db.lock().unwrap();
+    let conn = match guard.as_ref() {
+        Some(c) => c,
+        None => return Vec::new(),
+    };
+    persistence::survivorship_curve(conn, 10).unwrap_or_default()
+        .into_iter()
+        .map(|(age_fraction, surviving, deaths)| serde_json::json!({
+            "age_fraction": age_fraction,
+            "surviving": surviving,
+            "deaths": deaths,
+        }))
+        .collect()
+}
+
+/// Deaths further back than this don't count toward the selection report
+const SELECTION_WINDOW_TICKS: u64 = 9000;
+
+#[tauri::command]
+fn get_selection_report(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+) -> serde_json::Value {
+    let sim = state.lock().unwrap();
+    let since = sim.tick.saturating_sub(SELECTION_WINDOW_TICKS);
+    let mut reproduced = simulation::breeding::living_selection_samples(&sim.fish, &sim.genomes);
+    drop(sim);
+
+    let mut failed = Vec::new();
+    if let Some(conn) = db.lock().unwrap().as_ref() {
+        for (traits, bred) in persistence::death_selection_samples(conn, since).unwrap_or_default() {
+            if bred { reproduced.push(traits) } else { failed.push(traits) }
+        }
+    }
+    let report = simulation::breeding::selection_report(&reproduced, &failed);
+    let mut value = serde_json::to_value(report).unwrap_or_default();
+    value["window_ticks"] = serde_json::json!(SELECTION_WINDOW_TICKS);
+    value
+}
+
 #[tauri::command]
 fn get_journal_entries(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>) -> Vec<serde_json::Value> {
     let guard = db.lock().unwrap();
@@ -446,12 +889,13 @@ fn get_journal_entries(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>
         None => return Vec::new(),
     };
     let mut results = Vec::new();
-    if let Ok(mut stmt) = conn.prepare("SELECT tick, entry_text, timestamp FROM journal_entries ORDER BY tick DESC LIMIT 50") {
+    if let Ok(mut stmt) = conn.prepare("SELECT tick, entry_text, timestamp, mood FROM journal_entries ORDER BY tick DESC LIMIT 50") {
         if let Ok(rows) = stmt.query_map([], |row| {
             Ok(serde_json::json!({
                 "tick": row.get::<_, i64>(0).unwrap_or(0),
                 "text": row.get::<_, String>(1).unwrap_or_default(),
                 "timestamp": row.get::<_, String>(2).unwrap_or_default(),
+                "mood": row.get::<_, Option<String>>(3).unwrap_or(None),
             }))
         }) {
             for r in rows.flatten() {
@@ -522,6 +966,74 @@ fn get_achievements(state: tauri::State<'_, Mutex<Vec<Achievement>>>) -> Vec<Ach
     state.lock().unwrap().clone()
 }
 
+#[tauri::command]
+fn get_currency(state: tauri::State<'_, Mutex<SimulationState>>) -> u32 {
+    state.lock().unwrap().currency
+}
+
+#[tauri::command]
+fn get_shop_items() -> Vec<serde_json::Value> {
+    simulation::economy::ShopItem::all().iter().map(|i| {
+        serde_json::json!({
+            "id": i.as_str(),
+            "name": i.display_name(),
+            "price": i.price(),
+            "kind": if i.food().is_some() { "food" } else { "decoration" },
+        })
+    }).collect()
+}
+
+#[tauri::command]
+fn purchase(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    item: String,
+) -> Result<(), DeepTankError> {
+    let shop_item = simulation::economy::ShopItem::from_str(&item)
+        .ok_or_else(|| DeepTankError::not_found(format!("Unknown item: {}", item)))?;
+    let mut sim = state.lock().unwrap();
+    let placed = sim.purchase(shop_item)?;
+    // Persist balance and any placed decoration immediately
+    let guard = db.lock().unwrap();
+    if let Some(ref conn) = *guard {
+        persistence::save_currency(conn, sim.currency).ok();
+        if let Some(d) = placed {
+            conn.execute(
+                "INSERT INTO decorations (id, decoration_type, position_x, position_y, scale, flip_x) VALUES (?1,?2,?3,?4,?5,?6)",
+                rusqlite::params![d.id, d.decoration_type.as_str(), d.x, d.y, d.scale, d.flip_x as i32],
+            ).ok();
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn recompute_species(state: tauri::State<'_, Mutex<SimulationState>>) -> u32 {
+    let mut sim = state.lock().unwrap();
+    let tick = sim.tick;
+    let SimulationState { ecosystem, fish, genomes, config, .. } = &mut *sim;
+    ecosystem.recompute_species(fish, genomes, config, tick)
+}
+
+#[tauri::command]
+fn get_founder_analysis(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+) -> serde_json::Value {
+    let sim = state.lock().unwrap();
+    let db_guard = db.lock().unwrap();
+
+    // Stored genomes cover ancestors already pruned from memory
+    let mut links = db_guard.as_ref()
+        .and_then(|conn| persistence::genome_parent_links(conn).ok())
+        .unwrap_or_default();
+    for g in sim.genomes.values() {
+        links.insert(g.id, (g.parent_a, g.parent_b));
+    }
+    let living: Vec<u32> = sim.fish.iter().filter(|f| f.is_alive).map(|f| f.genome_id).collect();
+    serde_json::to_value(simulation::genome::founder_analysis(&living, &links)).unwrap_or_default()
+}
+
 #[tauri::command]
 fn get_lineage(
     state: tauri::State<'_, Mutex<SimulationState>>,
@@ -564,6 +1076,11 @@ fn get_lineage(
                         speed: row.get(9)?,
                         aggression: row.get(10)?,
                         // Defaults for fields we don't need for lineage display
+                        gape_size: simulation::genome::DEFAULT_GAPE_SIZE,
+                        temp_tolerance: simulation::genome::DEFAULT_TEMP_TOLERANCE,
+                        ideal_school_size: simulation::genome::DEFAULT_IDEAL_SCHOOL_SIZE,
+                        polarization: simulation::genome::DEFAULT_POLARIZATION,
+                        parental_care: simulation::genome::DEFAULT_PARENTAL_CARE,
                         body_width: 0.5,
                         tail_size: 0.5,
                         dorsal_fin_size: 0.5,
@@ -579,7 +1096,7 @@ fn get_lineage(
                         fertility: 0.5,
                         lifespan_factor: 1.0,
                         maturity_age: 0.2,
-                        disease_resistance: 0.5,
+                        disease_resistance: simulation::genome::DEFAULT_DISEASE_RESISTANCE,
                     })
                 }).ok()
             } else {
@@ -616,15 +1133,94 @@ fn get_lineage(
         }
     }
 
+    // Color whole branches by the earliest ancestor each node reaches in this tree
+    let links: simulation::genome::ParentLinks = result.iter()
+        .filter_map(|n| {
+            let id = n["genome_id"].as_u64()? as u32;
+            let parent = |key: &str| n[key].as_u64().map(|p| p as u32);
+            Some((id, (parent("parent_a"), parent("parent_b"))))
+        })
+        .collect();
+    let roots = simulation::genome::lineage_roots(&links);
+    for node in &mut result {
+        if let Some(&root) = node["genome_id"].as_u64().and_then(|id| roots.get(&(id as u32))) {
+            node["lineage_root"] = serde_json::json!(root);
+            node["lineage_color"] = serde_json::json!(simulation::genome::lineage_color(root));
+        }
+    }
+
     result
 }
 
+/// The whole live tank as a portable JSON document, independent of the DB schema
+#[tauri::command]
+fn export_state_json(state: tauri::State<'_, Mutex<SimulationState>>) -> Result<String, DeepTankError> {
+    let sim = state.lock().unwrap();
+    simulation::portable::PortableState::capture(&sim).to_json().map_err(DeepTankError::Io)
+}
+
+/// Load an `export_state_json` document as a new tank called `name` and switch
+/// to it, leaving the current tank's saves untouched. Returns config keys the
+/// import skipped.
+#[tauri::command]
+fn import_state_json(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    active_tank: tauri::State<'_, Mutex<String>>,
+    name: String,
+    json: String,
+) -> Result<Vec<String>, DeepTankError> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.len() > 20 { return Err(DeepTankError::invalid("Name must be 1-20 characters")); }
+    let new_path = resolve_tank_path(&name);
+    if new_path.exists() { return Err(DeepTankError::conflict("Tank already exists")); }
+
+    let doc = simulation::portable::PortableState::from_json(&json)?;
+    let (restored, skipped) = doc.restore()?;
+
+    // Save current tank
+    {
+        let sim = state.lock().unwrap();
+        let db_guard = db.lock().unwrap();
+        if let Some(ref conn) = *db_guard {
+            save_current_state(&sim, conn);
+        }
+    }
+
+    let new_conn = persistence::open_db(&new_path)?;
+    let written = persistence::init_schema(&new_conn)
+        .and_then(|_| persistence::save_state(&new_conn, restored.tick, restored.ecosystem.water_quality, restored.currency,
+            &restored.fish, &restored.genomes, &restored.ecosystem.species, &restored.ecosystem.eggs))
+        .and_then(|_| persistence::save_noise_seed(&new_conn, restored.noise_seed))
+        .and_then(|_| persistence::save_decorations(&new_conn, &restored.ecosystem.decorations));
+    if let Err(e) = written {
+        drop(new_conn);
+        std::fs::remove_file(&new_path).ok();
+        return Err(e.into());
+    }
+
+    {
+        let mut sim = state.lock().unwrap();
+        let mut db_guard = db.lock().unwrap();
+        let mut active = active_tank.lock().unwrap();
+        *sim = restored;
+        *db_guard = Some(new_conn);
+        *active = name;
+    }
+
+    if let Some(w) = app.get_webview_window("main") {
+        w.eval("window.location.reload()").ok();
+    }
+    Ok(skipped)
+}
+
 #[tauri::command]
 async fn export_tank(
     state: tauri::State<'_, Mutex<SimulationState>>,
     db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, DeepTankError> {
     use tauri_plugin_dialog::DialogExt;
 
     // Force save first
@@ -632,8 +1228,8 @@ async fn export_tank(
         let sim = state.lock().unwrap();
         let db_guard = db.lock().unwrap();
         if let Some(ref conn) = *db_guard {
-            persistence::save_state(conn, sim.tick, sim.ecosystem.water_quality, &sim.fish, &sim.genomes, &sim.ecosystem.species, &sim.ecosystem.eggs)
-                .map_err(|e| e.to_string())?;
+            persistence::save_state(conn, sim.tick, sim.ecosystem.water_quality, sim.currency, &sim.fish, &sim.genomes, &sim.ecosystem.species, &sim.ecosystem.eggs)?;
+            persistence::save_conservation(conn, &sim.ecosystem.conservation)?;
         }
     }
 
@@ -646,11 +1242,44 @@ async fn export_tank(
     let path = dialog.blocking_save_file();
     match path {
         Some(p) => {
-            let dest = p.as_path().ok_or("Invalid path")?;
-            std::fs::copy(&db_path, dest).map_err(|e| e.to_string())?;
+            let dest = p.as_path().ok_or_else(|| DeepTankError::invalid("Invalid path"))?;
+            std::fs::copy(&db_path, dest)?;
             Ok(dest.display().to_string())
         }
-        None => Err("Cancelled".to_string()),
+        None => Err(DeepTankError::Cancelled),
+    }
+}
+
+#[tauri::command]
+async fn export_chronicle(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    active_tank: tauri::State<'_, Mutex<String>>,
+    app: tauri::AppHandle,
+) -> Result<String, DeepTankError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let ticks_per_day = state.lock().unwrap().config.day_length_ticks as u64;
+    let tank_name = active_tank.lock().unwrap().clone();
+    let text = {
+        let db_guard = db.lock().unwrap();
+        let conn = db_guard.as_ref().ok_or_else(DeepTankError::no_database)?;
+        persistence::build_chronicle(conn, &tank_name, ticks_per_day)?
+    };
+
+    let dialog = tauri_plugin_dialog::FileDialogBuilder::new(app.dialog().clone())
+        .add_filter("Markdown", &["md"])
+        .set_file_name("chronicle.md")
+        .set_title("Export Chronicle");
+
+    let path = dialog.blocking_save_file();
+    match path {
+        Some(p) => {
+            let dest = p.as_path().ok_or_else(|| DeepTankError::invalid("Invalid path"))?;
+            std::fs::write(dest, text)?;
+            Ok(dest.display().to_string())
+        }
+        None => Err(DeepTankError::Cancelled),
     }
 }
 
@@ -658,7 +1287,7 @@ async fn export_tank(
 async fn import_tank(
     db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, DeepTankError> {
     use tauri_plugin_dialog::DialogExt;
 
     let dialog = tauri_plugin_dialog::FileDialogBuilder::new(app.dialog().clone())
@@ -668,7 +1297,7 @@ async fn import_tank(
     let path = dialog.blocking_pick_file();
     match path {
         Some(p) => {
-            let src = p.as_path().ok_or("Invalid path")?;
+            let src = p.as_path().ok_or_else(|| DeepTankError::invalid("Invalid path"))?;
             let db_path = get_db_path();
 
             // Close current DB connection
@@ -678,7 +1307,7 @@ async fn import_tank(
             }
 
             // Copy imported file over the DB
-            std::fs::copy(src, &db_path).map_err(|e| e.to_string())?;
+            std::fs::copy(src, &db_path)?;
 
             // Reopen DB
             {
@@ -694,7 +1323,43 @@ async fn import_tank(
 
             Ok(src.display().to_string())
         }
-        None => Err("Cancelled".to_string()),
+        None => Err(DeepTankError::Cancelled),
+    }
+}
+
+/// Import a save into a new named tank slot, leaving the current tank untouched
+#[tauri::command]
+async fn import_tank_as(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+    active_tank: tauri::State<'_, Mutex<String>>,
+    name: String,
+    switch_to: bool,
+) -> Result<String, DeepTankError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let name = name.trim().to_string();
+    if name.is_empty() || name.len() > 20 { return Err(DeepTankError::invalid("Name must be 1-20 characters")); }
+    let dest = resolve_tank_path(&name);
+    if dest.exists() { return Err(DeepTankError::conflict("Tank already exists")); }
+
+    let dialog = tauri_plugin_dialog::FileDialogBuilder::new(app.dialog().clone())
+        .add_filter("DeepTank Save", &["deeptank"])
+        .set_title("Import Tank As");
+
+    let path = dialog.blocking_pick_file();
+    match path {
+        Some(p) => {
+            let src = p.as_path().ok_or_else(|| DeepTankError::invalid("Invalid path"))?;
+            persistence::import_tank_file(src, &dest)?;
+
+            if switch_to {
+                switch_tank(app, state, db, active_tank, name)?;
+            }
+            Ok(src.display().to_string())
+        }
+        None => Err(DeepTankError::Cancelled),
     }
 }
 
@@ -733,6 +1398,15 @@ fn get_tank_db_path(name: &str) -> std::path::PathBuf {
     path
 }
 
+/// DB path for a tank by display name; the default tank keeps the legacy filename.
+fn resolve_tank_path(name: &str) -> std::path::PathBuf {
+    if name == "My Aquarium" {
+        get_db_path()
+    } else {
+        get_tank_db_path(name)
+    }
+}
+
 /// Save current simulation state to the currently open DB connection.
 fn save_current_state(
     sim: &SimulationState,
@@ -742,19 +1416,60 @@ fn save_current_state(
         conn,
         sim.tick,
         sim.ecosystem.water_quality,
+        sim.currency,
         &sim.fish,
         &sim.genomes,
         &sim.ecosystem.species,
         &sim.ecosystem.eggs,
     ).ok();
+    persistence::save_conservation(conn, &sim.ecosystem.conservation).ok();
+    persistence::save_rng_state(conn, &simulation::rng::checkpoint(&sim.rng)).ok();
+}
+
+/// Tray icon id, so the sim loop can find it to refresh the tooltip
+const TRAY_ID: &str = "main";
+/// Loop frames between tray status refreshes (~2s at 30Hz)
+const TRAY_STATUS_INTERVAL: u32 = 60;
+
+/// The disabled status line at the top of the tray menu
+struct TrayStatusItem(MenuItem<tauri::Wry>);
+
+/// Show live tank stats in the tray tooltip and menu status line
+fn update_tray_status(app: &tauri::AppHandle, status: &str) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        tray.set_tooltip(Some(format!("DeepTank — {}", status))).ok();
+    }
+    if let Some(item) = app.try_state::<TrayStatusItem>() {
+        item.0.set_text(status).ok();
+    }
+}
+
+/// Adopt the tank's stored wander seed, or store this state's seed if the tank has none yet.
+fn sync_noise_seed(sim: &mut SimulationState, conn: &rusqlite::Connection) {
+    match persistence::load_noise_seed(conn) {
+        Some(seed) => sim.set_noise_seed(seed),
+        None => {
+            persistence::save_noise_seed(conn, sim.noise_seed).ok();
+        }
+    }
+}
+
+/// Continue the tank's saved random sequence, if it has one and it's readable.
+fn restore_saved_rng(sim: &mut SimulationState, conn: &rusqlite::Connection) {
+    if let Some(checkpoint) = persistence::load_rng_state(conn) {
+        match simulation::rng::from_checkpoint(&checkpoint) {
+            Ok(rng) => sim.rng = rng,
+            Err(e) => log::warn!("{}; reseeding", e),
+        }
+    }
 }
 
 /// Load a tank from a DB path into the SimulationState, returning the new connection.
-fn load_tank_from_db(db_path: &std::path::Path) -> Result<(SimulationState, rusqlite::Connection), String> {
-    let conn = persistence::open_db(db_path).map_err(|e| format!("Failed to open DB: {}", e))?;
-    persistence::init_schema(&conn).map_err(|e| format!("Schema init failed: {}", e))?;
+fn load_tank_from_db(db_path: &std::path::Path) -> Result<(SimulationState, rusqlite::Connection), DeepTankError> {
+    let conn = persistence::open_db(db_path).map_err(|e| DeepTankError::Io(format!("Failed to open DB: {}", e)))?;
+    persistence::init_schema(&conn).map_err(|e| DeepTankError::Io(format!("Schema init failed: {}", e)))?;
 
-    let state = match persistence::load_state(&conn) {
+    let mut state = match persistence::load_state(&conn) {
         Ok(Some((tick, wq, fish, genomes, species, eggs, max_species_id))) => {
             let mut s = SimulationState::new();
             s.tick = tick;
@@ -765,6 +1480,8 @@ fn load_tank_from_db(db_path: &std::path::Path) -> Result<(SimulationState, rusq
             s.ecosystem.eggs = eggs;
             s.ecosystem.restore_species_counter(max_species_id + 1);
             s.ecosystem.restore_speciation_tick(tick);
+            s.currency = persistence::load_currency(&conn);
+            s.ecosystem.conservation = persistence::load_conservation(&conn);
             // Load decorations
             if let Ok(mut stmt) = conn.prepare("SELECT id, decoration_type, position_x, position_y, scale, flip_x FROM decorations") {
                 if let Ok(rows) = stmt.query_map([], |row| {
@@ -793,6 +1510,8 @@ fn load_tank_from_db(db_path: &std::path::Path) -> Result<(SimulationState, rusq
         }
         _ => SimulationState::new(),
     };
+    sync_noise_seed(&mut state, &conn);
+    restore_saved_rng(&mut state, &conn);
     Ok((state, conn))
 }
 
@@ -849,12 +1568,12 @@ fn create_tank(
     db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
     active_tank: tauri::State<'_, Mutex<String>>,
     name: String,
-) -> Result<(), String> {
+) -> Result<(), DeepTankError> {
     let name = name.trim().to_string();
-    if name.is_empty() || name.len() > 20 { return Err("Name must be 1-20 characters".to_string()); }
+    if name.is_empty() || name.len() > 20 { return Err(DeepTankError::invalid("Name must be 1-20 characters")); }
 
     let new_path = get_tank_db_path(&name);
-    if new_path.exists() { return Err("Tank already exists".to_string()); }
+    if new_path.exists() { return Err(DeepTankError::conflict("Tank already exists")); }
 
     // Save current tank
     {
@@ -866,15 +1585,17 @@ fn create_tank(
     }
 
     // Create new tank DB
-    let new_conn = persistence::open_db(&new_path).map_err(|e| e.to_string())?;
-    persistence::init_schema(&new_conn).map_err(|e| e.to_string())?;
+    let new_conn = persistence::open_db(&new_path)?;
+    persistence::init_schema(&new_conn)?;
+    let mut new_state = SimulationState::new();
+    sync_noise_seed(&mut new_state, &new_conn);
 
     // Switch to new fresh state atomically
     {
         let mut sim = state.lock().unwrap();
         let mut db_guard = db.lock().unwrap();
         let mut active = active_tank.lock().unwrap();
-        *sim = SimulationState::new();
+        *sim = new_state;
         *db_guard = Some(new_conn);
         *active = name;
     }
@@ -893,7 +1614,7 @@ fn switch_tank(
     db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
     active_tank: tauri::State<'_, Mutex<String>>,
     name: String,
-) -> Result<(), String> {
+) -> Result<(), DeepTankError> {
     let current_name = active_tank.lock().unwrap().clone();
     if current_name == name { return Ok(()); }
 
@@ -907,14 +1628,10 @@ fn switch_tank(
     }
 
     // Determine DB path for target tank
-    let target_path = if name == "My Aquarium" {
-        get_db_path()
-    } else {
-        get_tank_db_path(&name)
-    };
+    let target_path = resolve_tank_path(&name);
 
     if !target_path.exists() {
-        return Err(format!("Tank '{}' not found", name));
+        return Err(DeepTankError::not_found(format!("Tank '{}' not found", name)));
     }
 
     // Load new tank
@@ -942,41 +1659,174 @@ fn switch_tank(
 fn delete_tank(
     active_tank: tauri::State<'_, Mutex<String>>,
     name: String,
-) -> Result<(), String> {
+) -> Result<(), DeepTankError> {
     let active = active_tank.lock().unwrap().clone();
-    if active == name { return Err("Cannot delete the active tank".to_string()); }
-    if name == "My Aquarium" { return Err("Cannot delete the default tank".to_string()); }
+    if active == name { return Err(DeepTankError::conflict("Cannot delete the active tank")); }
+    if name == "My Aquarium" { return Err(DeepTankError::conflict("Cannot delete the default tank")); }
 
     let path = get_tank_db_path(&name);
     if path.exists() {
-        std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+        std::fs::remove_file(&path)?;
     }
     Ok(())
 }
 
+#[tauri::command]
+fn compare_tanks(name_a: String, name_b: String) -> serde_json::Value {
+    // Secondary read-only connections leave the active tank's connection untouched
+    let summarize = |name: &str| -> serde_json::Value {
+        let path = resolve_tank_path(name);
+        if !path.exists() {
+            return serde_json::json!({ "name": name, "error": format!("Tank '{}' not found", name) });
+        }
+        match persistence::open_db_readonly(&path).and_then(|c| persistence::tank_summary(&c)) {
+            Ok(s) => serde_json::json!({
+                "name": name,
+                "tick": s.tick,
+                "population": s.population,
+                "species_count": s.species_count,
+                "max_generation": s.max_generation,
+                "genetic_diversity": s.genetic_diversity,
+                "water_quality": s.water_quality,
+            }),
+            Err(e) => serde_json::json!({ "name": name, "error": e.to_string() }),
+        }
+    };
+    serde_json::json!({ "a": summarize(&name_a), "b": summarize(&name_b) })
+}
+
+#[tauri::command]
+fn get_tank_metadata(name: String) -> Result<persistence::TankMetadata, DeepTankError> {
+    let path = resolve_tank_path(&name);
+    if !path.exists() {
+        return Err(DeepTankError::not_found(format!("Tank '{}' not found", name)));
+    }
+    persistence::open_db_readonly(&path)
+        .and_then(|c| persistence::tank_metadata(&c))
+        .map_err(DeepTankError::from)
+}
+
+/// Consistency report for a saved tank, checked against the current tank size.
+/// Read-only, so it's safe on the active tank (as of its last save).
+#[tauri::command]
+fn validate_tank(state: tauri::State<'_, Mutex<SimulationState>>, name: String) -> Result<serde_json::Value, DeepTankError> {
+    let path = resolve_tank_path(&name);
+    if !path.exists() {
+        return Err(DeepTankError::not_found(format!("Tank '{}' not found", name)));
+    }
+    let (w, h) = {
+        let sim = state.lock().unwrap();
+        (sim.config.tank_width, sim.config.tank_height)
+    };
+    let conn = persistence::open_db_readonly(&path)?;
+    let report = persistence::check_integrity(&conn, w, h)?;
+    Ok(serde_json::json!({ "name": name, "clean": report.is_clean(), "issues": report }))
+}
+
+/// Fix what `validate_tank` finds. The active tank is rewritten from memory on
+/// every save, so it has to be switched away from first.
+#[tauri::command]
+fn repair_tank(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    active_tank: tauri::State<'_, Mutex<String>>,
+    name: String,
+) -> Result<serde_json::Value, DeepTankError> {
+    if *active_tank.lock().unwrap() == name {
+        return Err(DeepTankError::conflict("Switch to another tank before repairing this one"));
+    }
+    let path = resolve_tank_path(&name);
+    if !path.exists() {
+        return Err(DeepTankError::not_found(format!("Tank '{}' not found", name)));
+    }
+    let (w, h) = {
+        let sim = state.lock().unwrap();
+        (sim.config.tank_width, sim.config.tank_height)
+    };
+    let conn = persistence::open_db(&path)?;
+    persistence::init_schema(&conn)?;
+    let repaired = persistence::repair_integrity(&conn, w, h)?;
+    Ok(serde_json::json!({ "name": name, "repaired": repaired }))
+}
+
+/// Describe the active tank; shown under its name in the tank picker
+#[tauri::command]
+fn set_tank_description(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>, description: String) -> Result<(), DeepTankError> {
+    let guard = db.lock().unwrap();
+    let conn = guard.as_ref().ok_or_else(DeepTankError::no_database)?;
+    let trimmed: String = description.trim().chars().take(200).collect();
+    Ok(persistence::save_tank_description(conn, &trimmed)?)
+}
+
+/// The keeper's own objective for the active tank, e.g. "breed an all-gold
+/// line"; the journal keeps it in mind. Empty text clears it.
+#[tauri::command]
+fn set_tank_goal(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>, text: String) -> Result<(), DeepTankError> {
+    let guard = db.lock().unwrap();
+    let conn = guard.as_ref().ok_or_else(DeepTankError::no_database)?;
+    let trimmed: String = text.trim().chars().take(200).collect();
+    Ok(persistence::save_setting(conn, persistence::TANK_GOAL_KEY, &trimmed)?)
+}
+
+#[tauri::command]
+fn get_tank_goal(db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>) -> Option<String> {
+    let guard = db.lock().unwrap();
+    persistence::load_setting(guard.as_ref()?, persistence::TANK_GOAL_KEY).ok().flatten()
+}
+
 #[tauri::command]
 fn get_active_tank(active_tank: tauri::State<'_, Mutex<String>>) -> String {
     active_tank.lock().unwrap().clone()
 }
 
 #[tauri::command]
-fn toggle_widget_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+fn toggle_widget_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), DeepTankError> {
     if let Some(w) = app.get_webview_window("main") {
         if enabled {
-            w.set_always_on_top(true).map_err(|e| e.to_string())?;
-            w.set_decorations(false).map_err(|e| e.to_string())?;
-            w.set_size(tauri::LogicalSize::new(400.0, 300.0)).map_err(|e| e.to_string())?;
-            w.set_min_size(None::<tauri::LogicalSize<f64>>).map_err(|e| e.to_string())?;
+            w.set_always_on_top(true)?;
+            w.set_decorations(false)?;
+            w.set_size(tauri::LogicalSize::new(400.0, 300.0))?;
+            w.set_min_size(None::<tauri::LogicalSize<f64>>)?;
         } else {
-            w.set_always_on_top(false).map_err(|e| e.to_string())?;
-            w.set_decorations(true).map_err(|e| e.to_string())?;
-            w.set_min_size(Some(tauri::LogicalSize::new(800.0, 600.0))).map_err(|e| e.to_string())?;
-            w.set_size(tauri::LogicalSize::new(1200.0, 800.0)).map_err(|e| e.to_string())?;
+            w.set_always_on_top(false)?;
+            w.set_decorations(true)?;
+            w.set_min_size(Some(tauri::LogicalSize::new(800.0, 600.0)))?;
+            w.set_size(tauri::LogicalSize::new(1200.0, 800.0))?;
         }
     }
     Ok(())
 }
 
+#[tauri::command]
+fn capture_snapshot(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    active_tank: tauri::State<'_, Mutex<String>>,
+) -> Result<String, DeepTankError> {
+    let tank_name = active_tank.lock().unwrap().clone();
+    let sim = state.lock().unwrap();
+    let scene = sim.capture_scene(&tank_name);
+    Ok(serde_json::to_string(&scene)?)
+}
+
+#[tauri::command]
+fn list_presets() -> Vec<serde_json::Value> {
+    simulation::presets::all_presets().iter().map(|p| {
+        serde_json::json!({
+            "id": p.id,
+            "name": p.name,
+            "description": p.description,
+        })
+    }).collect()
+}
+
+#[tauri::command]
+fn apply_preset(state: tauri::State<'_, Mutex<SimulationState>>, name: String) -> Result<(), DeepTankError> {
+    let preset = simulation::presets::all_presets().into_iter()
+        .find(|p| p.id == name || p.name.eq_ignore_ascii_case(&name))
+        .ok_or_else(|| DeepTankError::not_found("Preset not found"))?;
+    let mut sim = state.lock().unwrap();
+    preset.apply(&mut sim.config).map_err(DeepTankError::from)
+}
+
 #[tauri::command]
 fn get_scenarios() -> Vec<serde_json::Value> {
     simulation::scenarios::all_scenarios().iter().map(|s| {
@@ -989,6 +1839,26 @@ fn get_scenarios() -> Vec<serde_json::Value> {
     }).collect()
 }
 
+/// Today's (UTC) shared challenge; start it with `start_scenario` using the returned id
+#[tauri::command]
+fn get_daily_challenge() -> serde_json::Value {
+    let date = chrono::Utc::now().date_naive();
+    let scenario = simulation::scenarios::daily_challenge(date);
+    serde_json::json!({
+        "id": simulation::scenarios::daily_challenge_id(date),
+        "date": date.format("%Y-%m-%d").to_string(),
+        // As a string: a u64 doesn't survive a JS number
+        "seed": scenario.seed.map(|s| s.to_string()),
+        "name": scenario.name,
+        "description": scenario.description,
+        "goals": scenario.goals.iter().map(|g| g.description()).collect::<Vec<_>>(),
+        "initial_fish_count": scenario.initial_fish_count,
+        "config_overrides": scenario.config_overrides.iter()
+            .map(|(k, v)| (k.to_string(), serde_json::json!(v)))
+            .collect::<serde_json::Map<_, _>>(),
+    })
+}
+
 #[tauri::command]
 fn start_scenario(
     app: tauri::AppHandle,
@@ -996,10 +1866,9 @@ fn start_scenario(
     db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
     active_tank: tauri::State<'_, Mutex<String>>,
     scenario_id: String,
-) -> Result<(), String> {
-    let scenarios = simulation::scenarios::all_scenarios();
-    let scenario = scenarios.iter().find(|s| s.id == scenario_id)
-        .ok_or("Scenario not found")?;
+) -> Result<(), DeepTankError> {
+    let scenario = simulation::scenarios::find_scenario(&scenario_id)
+        .ok_or_else(|| DeepTankError::not_found("Scenario not found"))?;
 
     let tank_name = format!("Scenario: {}", scenario.name);
 
@@ -1014,11 +1883,14 @@ fn start_scenario(
 
     // Create or switch to scenario tank
     let tank_path = get_tank_db_path(&tank_name);
-    let new_conn = persistence::open_db(&tank_path).map_err(|e| e.to_string())?;
-    persistence::init_schema(&new_conn).map_err(|e| e.to_string())?;
+    let new_conn = persistence::open_db(&tank_path)?;
+    persistence::init_schema(&new_conn)?;
 
     // Create fresh state with scenario config overrides
-    let mut new_state = SimulationState::new();
+    let mut new_state = match scenario.seed {
+        Some(seed) => SimulationState::with_seed(seed),
+        None => SimulationState::new(),
+    };
 
     // Apply config overrides
     for (key, val) in &scenario.config_overrides {
@@ -1045,6 +1917,12 @@ fn start_scenario(
 
     // Store active scenario ID in state
     new_state.active_scenario_id = Some(scenario_id);
+    if scenario.seed.is_some() {
+        // A seeded tank keeps its own wander field rather than a stale one from the DB
+        persistence::save_noise_seed(&new_conn, new_state.noise_seed).ok();
+    } else {
+        sync_noise_seed(&mut new_state, &new_conn);
+    }
 
     {
         let mut sim = state.lock().unwrap();
@@ -1064,38 +1942,93 @@ fn start_scenario(
 #[tauri::command]
 fn get_scenario_progress(state: tauri::State<'_, Mutex<SimulationState>>) -> Option<serde_json::Value> {
     let sim = state.lock().unwrap();
-    let scenario_id = sim.active_scenario_id.as_ref()?;
-    let scenarios = simulation::scenarios::all_scenarios();
-    let scenario = scenarios.iter().find(|s| s.id == *scenario_id)?;
+    sim.scenario_progress()
+}
 
-    let population = sim.fish.len() as u32;
-    let max_gen = sim.genomes.values().map(|g| g.generation).max().unwrap_or(0);
-    let species_count = sim.ecosystem.species.iter().filter(|s| s.extinct_at_tick.is_none()).count() as u32;
+/// Snapshots the goal ETA trend is fitted over
+const GOAL_ETA_SNAPSHOTS: usize = 20;
 
-    let goal_status = simulation::scenarios::check_goals(
-        scenario, population, max_gen, species_count,
-        sim.tick, sim.genetic_diversity, &sim.genomes, &sim.fish,
-    );
+/// Estimated ticks until each unmet goal of the active scenario completes,
+/// projected from the trend over recent population snapshots
+#[tauri::command]
+fn get_goal_eta(
+    state: tauri::State<'_, Mutex<SimulationState>>,
+    db: tauri::State<'_, Mutex<Option<rusqlite::Connection>>>,
+) -> Option<serde_json::Value> {
+    use simulation::scenarios::{self, ScenarioGoal};
 
-    let all_complete = goal_status.iter().all(|(_, met)| *met);
+    let (scenario_id, scenario, tick, max_gen, values, status) = {
+        let sim = state.lock().unwrap();
+        let scenario_id = sim.active_scenario_id.clone()?;
+        let scenario = scenarios::find_scenario(&scenario_id)?;
+        let population = sim.fish.len() as u32;
+        let max_gen = sim.genomes.values().map(|g| g.generation).max().unwrap_or(0);
+        let species_count = sim.ecosystem.species.iter().filter(|s| s.extinct_at_tick.is_none()).count() as u32;
+        let status = scenarios::check_goals(
+            &scenario, population, max_gen, species_count,
+            sim.tick, sim.genetic_diversity, &sim.genomes, &sim.fish,
+        );
+        let values: Vec<f32> = scenario.goals.iter().map(|g| scenarios::goal_value(
+            g, population, max_gen, species_count, sim.tick, sim.genetic_diversity, &sim.genomes, &sim.fish,
+        )).collect();
+        (scenario_id, scenario, sim.tick, max_gen, values, status)
+    };
+
+    let snapshots: Vec<serde_json::Value> = {
+        let guard = db.lock().unwrap();
+        guard.as_ref().and_then(|conn| {
+            let mut stmt = conn.prepare(
+                &format!("SELECT {} FROM population_snapshots ORDER BY tick DESC LIMIT ?1", SNAPSHOT_COLUMNS)
+            ).ok()?;
+            let rows = stmt.query_map([GOAL_ETA_SNAPSHOTS as i64], |row| Ok(snapshot_json(row))).ok()?;
+            Some(rows.filter_map(|r| r.ok()).collect())
+        }).unwrap_or_default()
+    };
+
+    let goals: Vec<serde_json::Value> = scenario.goals.iter().enumerate().map(|(i, goal)| {
+        let complete = status.iter().find(|(gi, _)| *gi == i).map(|(_, m)| *m).unwrap_or(false);
+        let rate = match goal {
+            ScenarioGoal::SurviveTicks(_) => Some(1.0),
+            // Generations are not snapshotted; use the tank's lifetime average
+            ScenarioGoal::ReachGeneration(_) => (tick > 0).then(|| max_gen as f32 / tick as f32),
+            _ => goal.snapshot_metric().and_then(|metric| {
+                let samples: Vec<(u64, f32)> = snapshots.iter().filter_map(|s| {
+                    Some((s["tick"].as_i64()? as u64, s[metric].as_f64()? as f32))
+                }).collect();
+                scenarios::trend_per_tick(&samples)
+            }),
+        };
+        serde_json::json!({
+            "description": goal.description(),
+            "complete": complete,
+            "current": values[i],
+            "target": goal.target(),
+            "eta": scenarios::estimate_eta(complete, values[i], goal.target(), goal.rising(), rate),
+        })
+    }).collect();
 
     Some(serde_json::json!({
         "scenario_id": scenario_id,
-        "scenario_name": scenario.name,
-        "goals": scenario.goals.iter().enumerate().map(|(i, g)| {
-            serde_json::json!({
-                "description": g.description(),
-                "complete": goal_status.iter().find(|(gi, _)| *gi == i).map(|(_, m)| *m).unwrap_or(false),
-            })
-        }).collect::<Vec<_>>(),
-        "all_complete": all_complete,
+        "tick": tick,
+        "goals": goals,
     }))
 }
 
+#[tauri::command]
+fn get_effective_population_size(state: tauri::State<'_, Mutex<SimulationState>>) -> f32 {
+    state.lock().unwrap().effective_population_size()
+}
+
+/// Tank vital signs for the dashboard, gathered under a single lock
+#[tauri::command]
+fn get_dashboard(state: tauri::State<'_, Mutex<SimulationState>>) -> serde_json::Value {
+    state.lock().unwrap().dashboard()
+}
+
 #[tauri::command]
 fn abandon_scenario(
     state: tauri::State<'_, Mutex<SimulationState>>,
-) -> Result<(), String> {
+) -> Result<(), DeepTankError> {
     let mut sim = state.lock().unwrap();
     sim.active_scenario_id = None;
     Ok(())
@@ -1119,7 +2052,7 @@ pub fn run() {
             }
 
             // Try to load saved state
-            let state = if let Some(ref c) = conn {
+            let mut state = if let Some(ref c) = conn {
                 match persistence::load_state(c) {
                     Ok(Some((tick, wq, fish, genomes, species, eggs, max_species_id))) => {
                         log::info!("Loaded saved state: tick={}, fish={}, eggs={}", tick, fish.len(), eggs.len());
@@ -1132,6 +2065,8 @@ pub fn run() {
                         s.ecosystem.eggs = eggs;
                         s.ecosystem.restore_species_counter(max_species_id + 1);
                         s.ecosystem.restore_speciation_tick(tick);
+                        s.currency = persistence::load_currency(c);
+                        s.ecosystem.conservation = persistence::load_conservation(c);
                         // Load decorations
                         if let Ok(mut stmt) = c.prepare("SELECT id, decoration_type, position_x, position_y, scale, flip_x FROM decorations") {
                             if let Ok(rows) = stmt.query_map([], |row| {
@@ -1167,6 +2102,10 @@ pub fn run() {
             } else {
                 SimulationState::new()
             };
+            if let Some(ref c) = conn {
+                sync_noise_seed(&mut state, c);
+                restore_saved_rng(&mut state, c);
+            }
 
             // Load or init achievements
             let mut achievement_list = achievements::default_achievements();
@@ -1189,30 +2128,39 @@ pub fn run() {
             app.manage(Mutex::new(conn));
             app.manage(Mutex::new(achievement_list));
             app.manage(Mutex::new("My Aquarium".to_string())); // active tank name
+            app.manage(Mutex::new(simulation::pacing::LoopStats::new()));
 
             // Start simulation loop
             let app_handle = app.handle().clone();
             std::thread::spawn(move || {
-                let tick_duration = Duration::from_micros(33_333); // 30Hz
+                let tick_duration = simulation::pacing::FRAME_DURATION;
                 let mut last_save_tick: u64 = 0;
                 let mut last_snapshot_tick: u64 = 0;
                 let mut last_journal_tick: u64 = 0;
+                let mut last_triggered_journal_tick: Option<u64> = None;
+                let mut last_reading: Option<simulation::journal::TankReading> = None;
                 let mut last_narration_tick: u64 = 0;
                 let mut last_achievement_tick: u64 = 0;
                 let mut births_since_snapshot: u32 = 0;
                 let mut deaths_since_snapshot: u32 = 0;
                 let mut slow_accumulator: f32 = 0.0;
                 let mut high_wq_streak: u32 = 0;
+                let mut save_health = simulation::autosave::SaveHealth::new();
                 // Track events between achievement checks so we don't miss any
                 let mut had_birth_since_check = false;
                 let mut had_speciation_since_check = false;
                 let mut had_extinction_since_check = false;
                 let mut had_predation_since_check = false;
+                let mut frames_since_tray_update: u32 = 0;
+                let mut last_tray_status = String::new();
+                let mut webhook_queue = simulation::webhook::WebhookQueue::new();
 
                 loop {
                     let start = std::time::Instant::now();
+                    let mut step_time = Duration::ZERO;
+                    let mut steps_taken: u32 = 0;
 
-                    let (frame, tick, should_save, should_snapshot, should_name_species, should_journal, should_narrate) = {
+                    let (frame, selected, tick, should_save, should_snapshot, should_name_species, should_journal, journal_occasion, should_narrate) = {
                         let state = app_handle.state::<Mutex<SimulationState>>();
                         let mut sim = state.lock().unwrap();
                         let multiplier = sim.speed_multiplier;
@@ -1235,11 +2183,14 @@ pub fn run() {
                             Some(sim.build_frame(Vec::new()))
                         } else {
                             let mut last_frame = None;
+                            let step_start = std::time::Instant::now();
                             for _ in 0..steps {
                                 let f = sim.step();
                                 accumulated_events.extend(f.events.clone());
                                 last_frame = Some(f);
                             }
+                            step_time = step_start.elapsed();
+                            steps_taken = if sim.paused { 0 } else { steps };
                             if let Some(ref mut f) = last_frame {
                                 f.events = accumulated_events.clone();
                             }
@@ -1277,14 +2228,42 @@ pub fn run() {
                             .filter(|s| s.name.is_none() && s.extinct_at_tick.is_none())
                             .map(|s| (s.id, s.centroid_hue, s.centroid_speed, s.centroid_size, s.centroid_pattern.clone(), s.member_count))
                             .collect();
-                        let journal = tick - last_journal_tick >= 3000 && sim.config.ollama_enabled;
+                        // User-defined journal triggers, checked against the previous frame's readings
+                        let reading = simulation::journal::TankReading {
+                            population: sim.fish.len() as u32,
+                            water_quality: sim.ecosystem.water_quality,
+                        };
+                        let cooled_down = match last_triggered_journal_tick {
+                            Some(t) => tick.saturating_sub(t) >= simulation::journal::JOURNAL_TRIGGER_COOLDOWN,
+                            None => true,
+                        };
+                        let occasion = match last_reading {
+                            Some(prev) if sim.config.ollama_enabled && cooled_down => {
+                                simulation::journal::first_fired(&sim.config.journal_triggers, prev, reading, &f.events)
+                            }
+                            _ => None,
+                        };
+                        last_reading = Some(reading);
+
+                        let journal = (tick - last_journal_tick >= 3000 || occasion.is_some()) && sim.config.ollama_enabled;
                         let narrate = tick - last_narration_tick >= 1500 && sim.config.ollama_enabled;
 
-                        (frame, tick, save, snap, unnamed, journal, narrate)
+                        let selected = sim.selected_fish_update();
+
+                        (frame, selected, tick, save, snap, unnamed, journal, occasion, narrate)
                     };
 
                     if let Some(ref frame) = frame {
                         let _ = app_handle.emit("frame-update", frame);
+                        if let Some(ref selected) = selected {
+                            let _ = app_handle.emit("selected-fish-update", selected);
+                        }
+
+                        for ev in &frame.events {
+                            if let Some(hook_ev) = simulation::webhook::WebhookEvent::from_sim_event(tick, ev) {
+                                webhook_queue.push(hook_ev);
+                            }
+                        }
 
                         // Persist non-FeedingDrop events to DB
                         if !frame.events.is_empty() {
@@ -1292,28 +2271,7 @@ pub fn run() {
                             let db = db_state.lock().unwrap();
                             if let Some(ref conn) = *db {
                                 for ev in &frame.events {
-                                    let (etype, fish_id, species_id, desc) = match ev {
-                                        simulation::ecosystem::SimEvent::Birth { fish_id, genome_id, parent_a, parent_b } => {
-                                            ("birth", Some(*fish_id as i64), None::<i64>, format!("Fish #{} born (genome {}) from parents #{}, #{}", fish_id, genome_id, parent_a, parent_b))
-                                        }
-                                        simulation::ecosystem::SimEvent::Death { fish_id, genome_id, cause, .. } => {
-                                            ("death", Some(*fish_id as i64), None, format!("Fish #{} (genome {}) died: {:?}", fish_id, genome_id, cause))
-                                        }
-                                        simulation::ecosystem::SimEvent::Predation { predator_id, prey_id } => {
-                                            ("predation", Some(*prey_id as i64), None, format!("Fish #{} eaten by #{}", prey_id, predator_id))
-                                        }
-                                        simulation::ecosystem::SimEvent::NewSpecies { species_id } => {
-                                            ("new_species", None, Some(*species_id as i64), format!("New species #{} discovered", species_id))
-                                        }
-                                        simulation::ecosystem::SimEvent::Extinction { species_id } => {
-                                            ("extinction", None, Some(*species_id as i64), format!("Species #{} went extinct", species_id))
-                                        }
-                                        simulation::ecosystem::SimEvent::FeedingDrop { .. } => continue,
-                                    };
-                                    conn.execute(
-                                        "INSERT INTO events (tick, event_type, subject_fish_id, subject_species_id, description) VALUES (?1,?2,?3,?4,?5)",
-                                        rusqlite::params![tick as i64, etype, fish_id, species_id, desc],
-                                    ).ok();
+                                    simulation::persistence::log_event(conn, tick, ev).ok();
                                 }
                             }
                         }
@@ -1332,7 +2290,7 @@ pub fn run() {
                     if tick - last_achievement_tick >= 300 {
                         last_achievement_tick = tick;
                         let sim_state = app_handle.state::<Mutex<SimulationState>>();
-                        let sim = sim_state.lock().unwrap();
+                        let mut sim = sim_state.lock().unwrap();
                         let ach_state = app_handle.state::<Mutex<Vec<Achievement>>>();
                         let mut achs = ach_state.lock().unwrap();
 
@@ -1377,6 +2335,7 @@ pub fn run() {
 
                         // Persist + emit toasts for new achievements
                         if !newly_unlocked.is_empty() {
+                            sim.currency += newly_unlocked.len() as u32 * simulation::economy::ACHIEVEMENT_REWARD;
                             let db_state = app_handle.state::<Mutex<Option<rusqlite::Connection>>>();
                             let db = db_state.lock().unwrap();
                             if let Some(ref conn) = *db {
@@ -1392,6 +2351,7 @@ pub fn run() {
                             // Emit achievement events to frontend
                             for name in &newly_unlocked {
                                 let _ = app_handle.emit("achievement-unlocked", name.clone());
+                                webhook_queue.push(simulation::webhook::WebhookEvent::achievement(tick, name));
                             }
                         }
                     }
@@ -1402,15 +2362,60 @@ pub fn run() {
                         last_save_tick = tick;
                         let sim_state = app_handle.state::<Mutex<SimulationState>>();
                         let db_state = app_handle.state::<Mutex<Option<rusqlite::Connection>>>();
+                        let tank_path = resolve_tank_path(&app_handle.state::<Mutex<String>>().lock().unwrap());
                         let sim = sim_state.lock().unwrap();
-                        let db = db_state.lock().unwrap();
-                        if let Some(ref conn) = *db {
-                            if let Err(e) = persistence::save_state(
-                                conn, sim.tick, sim.ecosystem.water_quality,
+                        let mut db = db_state.lock().unwrap();
+                        let try_save = |conn: &rusqlite::Connection| -> Result<(), String> {
+                            // A deleted file keeps accepting writes through the open handle
+                            if !tank_path.exists() {
+                                return Err("Database file is missing".to_string());
+                            }
+                            persistence::save_state(
+                                conn, sim.tick, sim.ecosystem.water_quality, sim.currency,
                                 &sim.fish, &sim.genomes, &sim.ecosystem.species, &sim.ecosystem.eggs,
-                            ) {
-                                log::error!("Auto-save failed: {}", e);
+                            ).map_err(|e| e.to_string())?;
+                            persistence::save_conservation(conn, &sim.ecosystem.conservation).map_err(|e| e.to_string())
+                        };
+                        let mut result = db.as_ref().map(try_save);
+
+                        if let Some(Err(ref e)) = result {
+                            let reopen = save_health.record_failure();
+                            log::error!("Auto-save failed ({} in a row): {}", save_health.failures(), e);
+                            // Past a one-off hiccup: reopen the tank's DB (recreating it if it was deleted) and retry
+                            if reopen {
+                                let reopened = persistence::open_db(&tank_path)
+                                    .and_then(|c| persistence::init_schema(&c).map(|_| c));
+                                match reopened {
+                                    Ok(c) => {
+                                        result = Some(try_save(&c));
+                                        *db = Some(c);
+                                    }
+                                    Err(e) => log::error!("Reopening database failed: {}", e),
+                                }
+                            }
+                        }
+
+                        match result {
+                            Some(Ok(())) => {
+                                if save_health.record_success() {
+                                    let _ = app_handle.emit("db-recovered", ());
+                                }
+                                // Keep the snapshot table bounded on long runs
+                                let cfg = &sim.config;
+                                if cfg.snapshot_rollup_factor > 1 {
+                                    let cutoff = sim.tick.saturating_sub(cfg.snapshot_retention_ticks as u64);
+                                    let bucket = cfg.snapshot_interval as u64 * cfg.snapshot_rollup_factor as u64;
+                                    if let Some(ref conn) = *db {
+                                        if let Err(e) = persistence::rollup_snapshots(conn, cutoff, bucket) {
+                                            log::error!("Snapshot rollup failed: {}", e);
+                                        }
+                                    }
+                                }
                             }
+                            Some(Err(e)) if save_health.should_warn() => {
+                                let _ = app_handle.emit("db-error", format!("Your tank can't be saved: {}", e));
+                            }
+                            _ => {}
                         }
                     }
 
@@ -1477,6 +2482,20 @@ pub fn run() {
                         }
                     }
 
+                    // Webhook forwarding (async, throttled to one batched post per interval)
+                    let (webhook_url, webhook_interval) = {
+                        let sim = app_handle.state::<Mutex<SimulationState>>();
+                        let sim = sim.lock().unwrap();
+                        (sim.config.webhook_url.clone(), sim.config.webhook_interval_ticks)
+                    };
+                    if let Some(batch) = webhook_queue.take_due(tick, webhook_interval) {
+                        if !webhook_url.is_empty() {
+                            tokio::spawn(async move {
+                                simulation::webhook::post(&webhook_url, &batch).await;
+                            });
+                        }
+                    }
+
                     // Narration generation (shorter, more frequent than journal)
                     if should_narrate {
                         last_narration_tick = tick;
@@ -1505,6 +2524,9 @@ pub fn run() {
                     // Journal entry generation
                     if should_journal {
                         last_journal_tick = tick;
+                        if journal_occasion.is_some() {
+                            last_triggered_journal_tick = Some(tick);
+                        }
                         let sim_state = app_handle.state::<Mutex<SimulationState>>();
                         let sim = sim_state.lock().unwrap();
                         let url = sim.config.ollama_url.clone();
@@ -1519,17 +2541,41 @@ pub fn run() {
                         let current_tick = sim.tick;
                         drop(sim);
 
+                        // Mood is judged now, from the tank, not from whatever the LLM writes
+                        let (mood, goal) = {
+                            let db_state = app_handle.state::<Mutex<Option<rusqlite::Connection>>>();
+                            let db = db_state.lock().unwrap();
+                            let goal = db.as_ref()
+                                .and_then(|conn| persistence::load_setting(conn, persistence::TANK_GOAL_KEY).ok().flatten());
+                            let since = current_tick.saturating_sub(simulation::journal::MOOD_WINDOW_TICKS);
+                            let (before, recent_deaths) = match db.as_ref() {
+                                Some(conn) => (
+                                    persistence::population_at_or_before(conn, since).ok().flatten(),
+                                    persistence::death_counts(conn, since, current_tick)
+                                        .map(|c| c.iter().map(|(_, n)| n).sum())
+                                        .unwrap_or(0),
+                                ),
+                                None => (None, 0),
+                            };
+                            let mood = simulation::journal::JournalMood::assess(
+                                simulation::journal::TankReading { population: pop, water_quality: wq },
+                                before,
+                                recent_deaths,
+                            );
+                            (mood, goal)
+                        };
+
                         let app_h = app_handle.clone();
                         tokio::spawn(async move {
-                            if let Some(entry) = ollama::generate_journal_entry(&url, &model, current_tick, pop, wq, &species_summary).await {
+                            if let Some(entry) = ollama::generate_journal_entry(&url, &model, current_tick, pop, wq, &species_summary, goal.as_deref(), journal_occasion.as_deref()).await {
                                 let app_h2 = app_h.clone();
                                 let _ = tokio::task::spawn_blocking(move || {
                                     let db_state = app_h2.state::<Mutex<Option<rusqlite::Connection>>>();
                                     let db = db_state.lock().unwrap();
                                     if let Some(ref conn) = *db {
                                         conn.execute(
-                                            "INSERT INTO journal_entries (tick, entry_text) VALUES (?1, ?2)",
-                                            rusqlite::params![current_tick as i64, entry],
+                                            "INSERT INTO journal_entries (tick, entry_text, mood) VALUES (?1, ?2, ?3)",
+                                            rusqlite::params![current_tick as i64, entry, mood.as_str()],
                                         ).ok();
                                     }
                                 }).await;
@@ -1537,7 +2583,20 @@ pub fn run() {
                         });
                     }
 
+                    // Tray status for users running the tank minimized or in widget mode
+                    frames_since_tray_update += 1;
+                    if frames_since_tray_update >= TRAY_STATUS_INTERVAL {
+                        frames_since_tray_update = 0;
+                        let status = app_handle.state::<Mutex<SimulationState>>().lock().unwrap().tray_status();
+                        if status != last_tray_status {
+                            update_tray_status(&app_handle, &status);
+                            last_tray_status = status;
+                        }
+                    }
+
                     let elapsed = start.elapsed();
+                    app_handle.state::<Mutex<simulation::pacing::LoopStats>>().lock().unwrap()
+                        .record(step_time, elapsed, steps_taken);
                     if elapsed < tick_duration {
                         std::thread::sleep(tick_duration - elapsed);
                     }
@@ -1545,14 +2604,16 @@ pub fn run() {
             });
 
             // System tray
+            let status_i = MenuItem::with_id(app, "status", "Starting…", false, None::<&str>)?;
             let show_i = MenuItem::with_id(app, "show", "Show DeepTank", true, None::<&str>)?;
             let pause_i = MenuItem::with_id(app, "pause_toggle", "Pause", true, None::<&str>)?;
             let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_i, &pause_i, &quit_i])?;
+            let menu = Menu::with_items(app, &[&status_i, &show_i, &pause_i, &quit_i])?;
+            app.manage(TrayStatusItem(status_i));
 
             let tray_icon = app.default_window_icon().cloned()
                 .unwrap_or_else(|| tauri::image::Image::new(&[], 1, 1));
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(TRAY_ID)
                 .icon(tray_icon)
                 .tooltip("DeepTank")
                 .menu(&menu)
@@ -1585,45 +2646,102 @@ pub fn run() {
             resume,
             set_speed,
             feed,
+            predict_food_landing,
+            get_loop_diagnostics,
+            get_boids_diagnostics,
+            get_noise_seed,
+            set_noise_seed,
+            get_rng_checkpoint,
+            restore_rng_checkpoint,
             step_forward,
             select_fish,
             tap_glass,
             trigger_event,
             breed_fish,
+            introduce_predator,
+            clone_fish,
             get_breed_preview,
+            diff_genomes,
+            recommend_pairing,
+            inheritance_table,
             get_genome,
+            get_trait_info,
             get_all_genomes,
             get_species_list,
             get_species_history,
+            get_species_average_genome,
+            get_extinct_species_profile,
+            revive_species,
+            get_trait_histogram,
             get_fish_detail,
             name_fish,
+            name_fish_batch,
+            auto_name_species,
             toggle_favorite,
+            quarantine_fish,
+            release_fish,
+            protect_lineage,
+            unprotect_lineage,
             get_favorites,
             update_tank_size,
             get_snapshots,
             get_all_snapshots,
+            preview_snapshot_state,
             get_species_snapshots,
             get_events,
+            set_current,
+            get_grid_stats,
+            benchmark_step,
+            get_camera_suggestion,
+            get_environmental_outlook,
+            set_grid_cell_size,
+            get_death_stats,
+            get_survivorship_curve,
+            get_selection_report,
             get_journal_entries,
             get_config,
+            get_config_diff,
             update_config,
             add_decoration,
             remove_decoration,
             get_decorations,
             get_achievements,
+            get_currency,
+            get_shop_items,
+            purchase,
             get_lineage,
+            get_founder_analysis,
+            recompute_species,
             export_tank,
+            export_chronicle,
             import_tank,
+            import_tank_as,
+            export_state_json,
+            import_state_json,
             list_tanks,
             create_tank,
             switch_tank,
             delete_tank,
             get_active_tank,
+            compare_tanks,
+            get_tank_metadata,
+            validate_tank,
+            repair_tank,
+            set_tank_description,
+            set_tank_goal,
+            get_tank_goal,
+            list_presets,
+            apply_preset,
             get_scenarios,
+            get_daily_challenge,
             start_scenario,
             get_scenario_progress,
+            get_goal_eta,
+            get_dashboard,
+            get_effective_population_size,
             abandon_scenario,
             toggle_widget_mode,
+            capture_snapshot,
         ])
         .run(tauri::generate_context!())
         .expect("error while running DeepTank");
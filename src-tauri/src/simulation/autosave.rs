@@ -0,0 +1,85 @@
+/// Consecutive auto-save failures before the DB is reopened and the user warned
+pub const FAILURE_LIMIT: u32 = 2;
+
+/// Auto-save health for the sim loop: counts failures in a row, says when to
+/// reopen the database, and makes sure the user is warned once per outage
+#[derive(Debug, Clone, Default)]
+pub struct SaveHealth {
+    failures: u32,
+    warned: bool,
+}
+
+impl SaveHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Record a failed save. True once past a one-off hiccup, when the loop
+    /// should reopen the database and retry.
+    pub fn record_failure(&mut self) -> bool {
+        self.failures += 1;
+        self.failures >= FAILURE_LIMIT
+    }
+
+    /// Record a successful save. True if the user had been warned and should
+    /// now be told saving works again.
+    pub fn record_success(&mut self) -> bool {
+        self.failures = 0;
+        std::mem::take(&mut self.warned)
+    }
+
+    /// Whether a save that still failed after any retry should warn the user;
+    /// true at most once until a save succeeds
+    pub fn should_warn(&mut self) -> bool {
+        if self.failures >= FAILURE_LIMIT && !self.warned {
+            self.warned = true;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reopens_only_after_repeated_failures() {
+        let mut health = SaveHealth::new();
+        assert!(!health.record_failure(), "A single failure is a hiccup");
+        assert!(!health.should_warn());
+        assert!(health.record_failure());
+        assert_eq!(health.failures(), FAILURE_LIMIT);
+        assert!(health.record_failure(), "Keeps retrying while failures continue");
+    }
+
+    #[test]
+    fn success_resets_the_count() {
+        let mut health = SaveHealth::new();
+        health.record_failure();
+        assert!(!health.record_success(), "Nothing to report without a warning");
+        assert_eq!(health.failures(), 0);
+        assert!(!health.record_failure(), "The count starts over");
+    }
+
+    #[test]
+    fn warns_once_per_outage() {
+        let mut health = SaveHealth::new();
+        health.record_failure();
+        health.record_failure();
+        assert!(health.should_warn());
+        health.record_failure();
+        assert!(!health.should_warn(), "Already warned");
+        assert!(health.record_success(), "Recovery is announced after a warning");
+        assert!(!health.record_success());
+
+        health.record_failure();
+        health.record_failure();
+        assert!(health.should_warn(), "A new outage warns again");
+    }
+}
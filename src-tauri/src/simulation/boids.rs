@@ -1,20 +1,64 @@
-use crate::simulation::config::SimulationConfig;
+use crate::simulation::config::{BoundaryMode, SchoolingMode, SimulationConfig};
+use crate::simulation::ecosystem::{AlgaePatch, Species, HERBIVORE_MAX_AGGRESSION};
 use crate::simulation::fish::{BehaviorState, Fish};
 use crate::simulation::genome::{genome_distance, FishGenome};
 use noise::{NoiseFn, Perlin};
+use serde::Serialize;
+
+/// Occupied cells should hold about this many fish for efficient neighbor queries
+const ADAPTIVE_GRID_TARGET_PER_CELL: f32 = 4.0;
+/// Only rebuild when the ideal cell size differs from the current one by more than this fraction
+const ADAPTIVE_GRID_TOLERANCE: f32 = 0.25;
+/// Smallest cell size the grid is tuned or pinned to
+pub const MIN_CELL_SIZE: f32 = 5.0;
+/// Most cells a grid may allocate. Past this the cells grow instead, so a
+/// pathological tank size can't exhaust memory.
+pub const MAX_GRID_CELLS: usize = 1 << 16;
 
 pub struct SpatialGrid {
+    width: f32,
+    height: f32,
     cell_size: f32,
     cols: usize,
     rows: usize,
     cells: Vec<Vec<usize>>, // cell index -> list of fish indices
 }
 
+/// Occupancy summary of the spatial grid, for performance debugging
+#[derive(Debug, Clone, Serialize)]
+pub struct GridStats {
+    pub cell_size: f32,
+    pub cols: usize,
+    pub rows: usize,
+    pub total_cells: usize,
+    pub empty_cells: usize,
+    pub max_per_cell: usize,
+    pub avg_per_occupied_cell: f32,
+}
+
 impl SpatialGrid {
+    /// `cell_size` is a request: it is coarsened as needed to stay within
+    /// `MAX_GRID_CELLS` (check `cell_size()` to see what was used)
     pub fn new(width: f32, height: f32, cell_size: f32) -> Self {
-        let cols = (width / cell_size).ceil() as usize + 1;
-        let rows = (height / cell_size).ceil() as usize + 1;
+        let width = if width.is_finite() { width.max(0.0) } else { 0.0 };
+        let height = if height.is_finite() { height.max(0.0) } else { 0.0 };
+        let mut cell_size = if cell_size.is_finite() && cell_size > 0.0 { cell_size } else { 1.0 };
+        let dims = |c: f32| ((width as f64 / c as f64).ceil() + 1.0, (height as f64 / c as f64).ceil() + 1.0);
+        loop {
+            let (cols, rows) = dims(cell_size);
+            if cols * rows <= MAX_GRID_CELLS as f64 {
+                break;
+            }
+            // Jump most of the way at once, then creep up past rounding
+            let fit = ((width as f64 * height as f64) / MAX_GRID_CELLS as f64).sqrt()
+                .max(width.max(height) as f64 / MAX_GRID_CELLS as f64) as f32;
+            cell_size = fit.max(cell_size * 1.1);
+        }
+        let (cols, rows) = dims(cell_size);
+        let (cols, rows) = (cols as usize, rows as usize);
         Self {
+            width,
+            height,
             cell_size,
             cols,
             rows,
@@ -22,16 +66,51 @@ impl SpatialGrid {
         }
     }
 
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Occupancy as of the last `rebuild`
+    pub fn stats(&self) -> GridStats {
+        self.summarize(self.cells.iter().map(|c| c.len()))
+    }
+
+    /// Occupancy these fish would give the grid, leaving the grid itself untouched
+    pub fn stats_for(&self, fish: &[Fish]) -> GridStats {
+        let mut counts = vec![0usize; self.cells.len()];
+        for f in fish {
+            counts[self.cell_index(f)] += 1;
+        }
+        self.summarize(counts.into_iter())
+    }
+
+    fn summarize(&self, counts: impl Iterator<Item = usize>) -> GridStats {
+        let total_cells = self.cells.len();
+        let occupied: Vec<usize> = counts.filter(|&n| n > 0).collect();
+        let fish_count: usize = occupied.iter().sum();
+        GridStats {
+            cell_size: self.cell_size,
+            cols: self.cols,
+            rows: self.rows,
+            total_cells,
+            empty_cells: total_cells - occupied.len(),
+            max_per_cell: occupied.iter().copied().max().unwrap_or(0),
+            avg_per_occupied_cell: if occupied.is_empty() { 0.0 } else { fish_count as f32 / occupied.len() as f32 },
+        }
+    }
+
+    fn cell_index(&self, f: &Fish) -> usize {
+        let col = ((f.x.max(0.0) / self.cell_size) as usize).min(self.cols - 1);
+        let row = ((f.y.max(0.0) / self.cell_size) as usize).min(self.rows - 1);
+        row * self.cols + col
+    }
+
     pub fn rebuild(&mut self, fish: &[Fish]) {
         for cell in &mut self.cells {
             cell.clear();
         }
         for (i, f) in fish.iter().enumerate() {
-            let col = (f.x.max(0.0) / self.cell_size) as usize;
-            let row = (f.y.max(0.0) / self.cell_size) as usize;
-            let col = col.min(self.cols - 1);
-            let row = row.min(self.rows - 1);
-            let idx = row * self.cols + col;
+            let idx = self.cell_index(f);
             self.cells[idx].push(i);
         }
     }
@@ -51,21 +130,146 @@ impl SpatialGrid {
         }
         result
     }
+
+    /// Like `neighbors`, but also searches the mirrored positions across each
+    /// edge so queries near a seam see fish on the opposite side (toroidal tank)
+    pub fn neighbors_wrapped(&self, x: f32, y: f32, radius: f32) -> Vec<usize> {
+        let mut offsets_x = vec![0.0];
+        if x < radius { offsets_x.push(self.width); }
+        if x > self.width - radius { offsets_x.push(-self.width); }
+        let mut offsets_y = vec![0.0];
+        if y < radius { offsets_y.push(self.height); }
+        if y > self.height - radius { offsets_y.push(-self.height); }
+
+        let mut result = Vec::new();
+        for &ox in &offsets_x {
+            for &oy in &offsets_y {
+                result.extend(self.neighbors(x + ox, y + oy, radius));
+            }
+        }
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+}
+
+/// Cell size that brings occupied cells toward the target density, kept within
+/// half to double the cohesion radius so queries never scan too many or too few cells
+pub fn ideal_cell_size(stats: &GridStats, cohesion_radius: f32) -> f32 {
+    // A zero, negative or NaN radius would collapse the clamp range
+    let radius = cohesion_radius.max(MIN_CELL_SIZE);
+    if stats.max_per_cell == 0 {
+        return radius;
+    }
+    let scale = (ADAPTIVE_GRID_TARGET_PER_CELL / stats.avg_per_occupied_cell).sqrt();
+    (stats.cell_size * scale).clamp(radius * 0.5, radius * 2.0)
+}
+
+/// Average magnitude of each steering force across the tank, before the
+/// behavior speed multiplier, for tuning the boids weights
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ForceBreakdown {
+    pub fish_count: u32,
+    pub separation: f32,
+    pub alignment: f32,
+    pub cohesion: f32,
+    pub wander: f32,
+    pub boundary: f32,
+    pub obstacle: f32, // decoration avoidance and shelter seeking
+    pub hunger: f32,
+    pub territory: f32,
+    pub other: f32, // current, quarantine, guarding young, chasing and coordinated fleeing
+}
+
+impl ForceBreakdown {
+    fn averaged(mut self) -> Self {
+        let n = self.fish_count.max(1) as f32;
+        for v in [
+            &mut self.separation, &mut self.alignment, &mut self.cohesion, &mut self.wander,
+            &mut self.boundary, &mut self.obstacle, &mut self.hunger, &mut self.territory, &mut self.other,
+        ] {
+            *v /= n;
+        }
+        self
+    }
+}
+
+/// Length of the force added to (fx, fy) since `before`
+fn added(before: (f32, f32), fx: f32, fy: f32) -> f32 {
+    ((fx - before.0).powi(2) + (fy - before.1).powi(2)).sqrt()
+}
+
+/// How strongly a fish aligns and coheres with a neighbor (0 = ignores it).
+/// In species-only mode, other species are ignored outright instead of down-weighted.
+fn schooling_affinity(me: &FishGenome, other: Option<&FishGenome>, config: &SimulationConfig) -> f32 {
+    let species_only = config.schooling_mode == SchoolingMode::SpeciesOnly;
+    match other {
+        Some(other) => {
+            let gd = genome_distance(me, other, config.distance_weights());
+            if species_only && gd >= config.species_threshold {
+                0.0
+            } else {
+                (1.0 - gd / 10.0).clamp(0.0, 1.0)
+            }
+        }
+        None if species_only => 0.0,
+        None => 0.5,
+    }
+}
+
+/// (alignment, cohesion) multipliers for a fish's polarization trait. Schoolers
+/// (1) line up hard and bunch a little less; shoalers (0) gather without lining up.
+/// Polarization 0.5 or a strength of 0 leaves both at 1.
+fn schooling_style(polarization: f32, strength: f32) -> (f32, f32) {
+    let bias = strength * (2.0 * polarization - 1.0);
+    ((1.0 + bias).max(0.0), (1.0 - 0.5 * bias).max(0.0))
+}
+
+/// Shortest signed offset along one axis of a wrapping tank
+pub(crate) fn wrap_delta(d: f32, size: f32) -> f32 {
+    if d > size / 2.0 {
+        d - size
+    } else if d < -size / 2.0 {
+        d + size
+    } else {
+        d
+    }
 }
 
 pub struct BoidsEngine {
     pub perlin: Perlin,
     pub grid: SpatialGrid,
+    /// Genome id to living species id, refreshed by `set_species`
+    species_of: std::collections::HashMap<u32, u32>,
+    /// Algae patch centers herbivores forage toward, refreshed by `set_algae`
+    algae_positions: Vec<(f32, f32)>,
 }
 
 impl BoidsEngine {
-    pub fn new(config: &SimulationConfig) -> Self {
+    /// `noise_seed` fixes the tank's wander field: same seed, same wander paths
+    pub fn new(config: &SimulationConfig, noise_seed: u32) -> Self {
         Self {
-            perlin: Perlin::new(42),
+            perlin: Perlin::new(noise_seed),
             grid: SpatialGrid::new(config.tank_width, config.tank_height, config.cohesion_radius),
+            species_of: std::collections::HashMap::new(),
+            algae_positions: Vec::new(),
+        }
+    }
+
+    /// Record which living species each genome belongs to, for the same-species cohesion bonus
+    pub fn set_species(&mut self, species: &[Species]) {
+        self.species_of.clear();
+        for s in species.iter().filter(|s| s.extinct_at_tick.is_none()) {
+            self.species_of.extend(s.member_genome_ids.iter().map(|&g| (g, s.id)));
         }
     }
 
+    /// Record where algae is growing, so hungry grazers steer to it like food
+    pub fn set_algae(&mut self, algae: &[AlgaePatch]) {
+        self.algae_positions.clear();
+        self.algae_positions.extend(algae.iter().map(|a| (a.x, a.y)));
+    }
+
     pub fn update(
         &mut self,
         fish: &mut [Fish],
@@ -77,10 +281,22 @@ impl BoidsEngine {
     ) {
         self.grid.rebuild(fish);
 
+        // Periodically retune the cell size as the population grows or thins out
+        if config.adaptive_grid_enabled && config.adaptive_grid_interval > 0
+            && tick.is_multiple_of(config.adaptive_grid_interval as u64)
+        {
+            let current = self.grid.cell_size();
+            let ideal = ideal_cell_size(&self.grid.stats(), config.cohesion_radius);
+            if (ideal - current).abs() / current > ADAPTIVE_GRID_TOLERANCE {
+                self.grid = SpatialGrid::new(config.tank_width, config.tank_height, ideal);
+                self.grid.rebuild(fish);
+            }
+        }
+
         // Compute forces for all fish, then apply (avoids borrow issues)
         let forces: Vec<(f32, f32)> = (0..fish.len())
             .map(|i| {
-                self.compute_forces(i, fish, genomes, config, tick, food_positions, obstacles)
+                self.compute_forces(i, fish, genomes, config, tick, food_positions, obstacles, None)
             })
             .collect();
 
@@ -109,8 +325,8 @@ impl BoidsEngine {
             f.vx += applied_fx;
             f.vy += applied_fy;
 
-            // Clamp to max speed
-            let max_speed = config.base_max_speed * genome.speed;
+            // Clamp to max speed, never past the global cap
+            let max_speed = (config.base_max_speed * genome.speed * f.vigor(genome, config)).min(config.absolute_max_speed);
             let speed = (f.vx * f.vx + f.vy * f.vy).sqrt();
             if speed > max_speed {
                 let scale = max_speed / speed;
@@ -122,9 +338,17 @@ impl BoidsEngine {
             f.vx *= config.drag;
             f.vy *= config.drag;
 
-            // Update position and clamp to tank bounds
-            f.x = (f.x + f.vx).clamp(0.0, config.tank_width);
-            f.y = (f.y + f.vy).clamp(0.0, config.tank_height);
+            // Update position and clamp (or wrap) to tank bounds
+            match config.boundary_mode {
+                BoundaryMode::Clamp => {
+                    f.x = (f.x + f.vx).clamp(0.0, config.tank_width);
+                    f.y = (f.y + f.vy).clamp(0.0, config.tank_height);
+                }
+                BoundaryMode::Wrap => {
+                    f.x = (f.x + f.vx).rem_euclid(config.tank_width);
+                    f.y = (f.y + f.vy).rem_euclid(config.tank_height);
+                }
+            }
 
             // Update heading
             let spd = (f.vx * f.vx + f.vy * f.vy).sqrt();
@@ -138,6 +362,29 @@ impl BoidsEngine {
         }
     }
 
+    /// Steering forces every fish would feel this tick, without moving anyone
+    pub fn force_breakdown(
+        &self,
+        fish: &[Fish],
+        genomes: &std::collections::HashMap<u32, FishGenome>,
+        config: &SimulationConfig,
+        tick: u64,
+        food_positions: &[(f32, f32)],
+        obstacles: &[(f32, f32, f32)],
+    ) -> ForceBreakdown {
+        let mut total = ForceBreakdown::default();
+        for i in 0..fish.len() {
+            if genomes.contains_key(&fish[i].genome_id) {
+                self.compute_forces(i, fish, genomes, config, tick, food_positions, obstacles, Some(&mut total));
+                total.fish_count += 1;
+            }
+        }
+        total.averaged()
+    }
+
+    /// Net steering force on one fish. With `breakdown`, each component's
+    /// magnitude is also added to the matching field.
+    #[allow(clippy::too_many_arguments)]
     fn compute_forces(
         &self,
         fish_idx: usize,
@@ -147,12 +394,18 @@ impl BoidsEngine {
         tick: u64,
         food_positions: &[(f32, f32)],
         obstacles: &[(f32, f32, f32)],
+        mut breakdown: Option<&mut ForceBreakdown>,
     ) -> (f32, f32) {
         let me = &fish[fish_idx];
         let my_genome = match genomes.get(&me.genome_id) {
             Some(g) => g,
             None => return (0.0, 0.0),
         };
+        let mut record = |slot: fn(&mut ForceBreakdown) -> &mut f32, magnitude: f32| {
+            if let Some(b) = breakdown.as_deref_mut() {
+                *slot(b) += magnitude;
+            }
+        };
 
         let mut fx = 0.0_f32;
         let mut fy = 0.0_f32;
@@ -162,7 +415,14 @@ impl BoidsEngine {
         let speed_mult = me.behavior_speed_multiplier();
 
         // Get neighbors within cohesion radius (the largest)
-        let candidates = self.grid.neighbors(me.x, me.y, config.cohesion_radius);
+        let wrap = config.boundary_mode == BoundaryMode::Wrap;
+        let candidates = if wrap {
+            self.grid.neighbors_wrapped(me.x, me.y, config.cohesion_radius)
+        } else {
+            self.grid.neighbors(me.x, me.y, config.cohesion_radius)
+        };
+        // Offsets take the short way across the seam in wrap mode
+        let delta = |d: f32, size: f32| if wrap { wrap_delta(d, size) } else { d };
 
         let mut sep_x = 0.0_f32;
         let mut sep_y = 0.0_f32;
@@ -172,14 +432,16 @@ impl BoidsEngine {
         let mut coh_x = 0.0_f32;
         let mut coh_y = 0.0_f32;
         let mut coh_weight = 0.0_f32;
+        let mut school_size = 1_u32; // schoolmates within cohesion range, self included
+        let my_species = self.species_of.get(&me.genome_id);
 
         for &j in &candidates {
             if j == fish_idx {
                 continue;
             }
             let other = &fish[j];
-            let dx = me.x - other.x;
-            let dy = me.y - other.y;
+            let dx = delta(me.x - other.x, config.tank_width);
+            let dy = delta(me.y - other.y, config.tank_height);
             let dist_sq = dx * dx + dy * dy;
             let dist = dist_sq.sqrt();
 
@@ -188,12 +450,11 @@ impl BoidsEngine {
             }
 
             // Species affinity
-            let affinity = if let Some(other_genome) = genomes.get(&other.genome_id) {
-                let gd = genome_distance(my_genome, other_genome);
-                (1.0 - gd / 10.0).clamp(0.0, 1.0)
-            } else {
-                0.5
-            };
+            let affinity = schooling_affinity(my_genome, genomes.get(&other.genome_id), config);
+            let schoolmate = affinity > 0.0 || config.schooling_mode == SchoolingMode::Mixed;
+            // Established species hold together however diverse their members are
+            let same_species = my_species.is_some() && my_species == self.species_of.get(&other.genome_id);
+            let cohesion_affinity = if same_species { affinity + config.species_cohesion_bonus } else { affinity };
 
             // Separation
             if dist < config.separation_radius {
@@ -203,7 +464,7 @@ impl BoidsEngine {
             }
 
             // Alignment
-            if dist < config.alignment_radius {
+            if dist < config.alignment_radius && schoolmate {
                 let spd = (other.vx * other.vx + other.vy * other.vy).sqrt();
                 if spd > 0.01 {
                     align_x += (other.vx / spd) * affinity;
@@ -213,41 +474,59 @@ impl BoidsEngine {
             }
 
             // Cohesion
-            if dist < config.cohesion_radius {
-                coh_x += other.x * affinity;
-                coh_y += other.y * affinity;
-                coh_weight += affinity;
+            if dist < config.cohesion_radius && (schoolmate || same_species) {
+                // Use the neighbor's nearest image so the center stays local across a seam
+                coh_x += (me.x - dx) * cohesion_affinity;
+                coh_y += (me.y - dy) * cohesion_affinity;
+                coh_weight += cohesion_affinity;
+                school_size += 1;
             }
         }
 
+        // School size preference: crowded fish spread out, lonely ones pull in harder
+        let size_ratio = school_size as f32 / my_genome.ideal_school_size.max(1.0);
+        let crowding = config.school_size_strength * (size_ratio - 1.0).clamp(0.0, 2.0);
+        let loneliness = config.school_size_strength * (1.0 - size_ratio).max(0.0);
+        let (align_style, cohesion_style) = schooling_style(my_genome.polarization, config.schooling_style_strength);
+
         // Apply separation
-        let personal_space = 1.0 + my_genome.school_affinity.max(0.0) * 0.5;
+        let personal_space = (1.0 + my_genome.school_affinity.max(0.0) * 0.5) * (1.0 + crowding);
         fx += sep_x * config.separation_weight * personal_space;
         fy += sep_y * config.separation_weight * personal_space;
+        record(|b| &mut b.separation, added((0.0, 0.0), fx, fy));
 
         // Apply alignment (scaled by schooling behavior)
+        let before = (fx, fy);
         if align_count > 0 {
             let avg_x = align_x / align_count as f32;
             let avg_y = align_y / align_count as f32;
             let my_spd = (me.vx * me.vx + me.vy * me.vy).sqrt().max(0.01);
             let diff_x = avg_x - me.vx / my_spd;
             let diff_y = avg_y - me.vy / my_spd;
-            fx += diff_x * config.alignment_weight * my_genome.school_affinity * schooling_mult;
-            fy += diff_y * config.alignment_weight * my_genome.school_affinity * schooling_mult;
+            let steer = config.alignment_weight * my_genome.school_affinity * schooling_mult * align_style;
+            fx += diff_x * steer;
+            fy += diff_y * steer;
         }
 
+        record(|b| &mut b.alignment, added(before, fx, fy));
+
         // Apply cohesion (scaled by schooling behavior)
+        let before = (fx, fy);
         if coh_weight > 0.001 {
             let center_x = coh_x / coh_weight;
             let center_y = coh_y / coh_weight;
             let toward_x = center_x - me.x;
             let toward_y = center_y - me.y;
-            fx += toward_x * config.cohesion_weight * my_genome.school_affinity * schooling_mult * 0.01;
-            fy += toward_y * config.cohesion_weight * my_genome.school_affinity * schooling_mult * 0.01;
+            let pull = config.cohesion_weight * my_genome.school_affinity * schooling_mult * 0.01 * (1.0 + loneliness) * cohesion_style;
+            fx += toward_x * pull;
+            fy += toward_y * pull;
         }
 
-        // Boundary avoidance
-        let margin = config.boundary_margin;
+        record(|b| &mut b.cohesion, added(before, fx, fy));
+
+        // Boundary avoidance (none needed when edges wrap)
+        let before = (fx, fy);
+        let margin = if wrap { 0.0 } else { config.boundary_margin };
         if me.x < margin {
             let t = 1.0 - me.x / margin;
             fx += t * t * config.base_max_speed;
@@ -265,7 +544,11 @@ impl BoidsEngine {
             fy -= t * t * config.base_max_speed;
         }
 
+        record(|b| &mut b.boundary, added(before, fx, fy));
+
         // Obstacle avoidance (decorations)
+        let before = (fx, fy);
+        let mut nearest_cover: Option<(f32, f32, f32)> = None; // (dx, dy, gap to edge)
         for &(ox, oy, radius) in obstacles {
             let avoidance_radius = radius + config.boundary_margin * 0.5;
             let dx = me.x - ox;
@@ -279,9 +562,32 @@ impl BoidsEngine {
                 fx += (dx / dist) * force;
                 fy += (dy / dist) * force;
             }
+            let gap = dist_sq.sqrt() - radius;
+            if nearest_cover.is_none_or(|(_, _, g)| gap < g) {
+                nearest_cover = Some((dx, dy, gap));
+            }
+        }
+
+        // Shelter seeking: fleeing, stressed or timid fish head for the nearest
+        // decoration and settle just outside it
+        let seeks_cover = me.behavior == BehaviorState::Fleeing
+            || me.stress > 0.5
+            || my_genome.boldness < config.shelter_timid_boldness;
+        if config.shelter_enabled && seeks_cover {
+            if let Some((dx, dy, gap)) = nearest_cover {
+                if gap > config.shelter_radius * 0.5 {
+                    let dist = (dx * dx + dy * dy).sqrt();
+                    let pull = config.shelter_seek_strength * config.base_max_speed * (1.0 - my_genome.boldness);
+                    fx -= (dx / dist) * pull;
+                    fy -= (dy / dist) * pull;
+                }
+            }
         }
 
+        record(|b| &mut b.obstacle, added(before, fx, fy));
+
         // Wander force (Perlin noise)
+        let before = (fx, fy);
         let noise_val = self.perlin.get([
             me.x as f64 * 0.01 + tick as f64 * 0.01 * my_genome.curiosity as f64,
             me.y as f64 * 0.01 + (fish_idx as f64) * 100.0,
@@ -290,18 +596,29 @@ impl BoidsEngine {
         fx += wander_angle.cos() * config.wander_strength * my_genome.curiosity;
         fy += wander_angle.sin() * config.wander_strength * my_genome.curiosity;
 
+        record(|b| &mut b.wander, added(before, fx, fy));
+
         // Water current
+        let before = (fx, fy);
         if config.current_strength > 0.0 {
             fx += config.current_direction.cos() * config.current_strength;
             fy += config.current_direction.sin() * config.current_strength;
         }
 
-        // Hunger drive — steer toward nearest food
-        if me.hunger > 0.6 && !food_positions.is_empty() {
+        let mut other = added(before, fx, fy);
+
+        // Hunger drive — steer toward nearest food, or algae for grazers
+        let before = (fx, fy);
+        let grazing: &[(f32, f32)] = if my_genome.aggression < HERBIVORE_MAX_AGGRESSION {
+            &self.algae_positions
+        } else {
+            &[]
+        };
+        if me.hunger > config.forage_hunger_threshold && !(food_positions.is_empty() && grazing.is_empty()) {
             let mut nearest_dist = f32::MAX;
             let mut nearest_fx = 0.0_f32;
             let mut nearest_fy = 0.0_f32;
-            for &(food_x, food_y) in food_positions {
+            for &(food_x, food_y) in food_positions.iter().chain(grazing) {
                 let dx = food_x - me.x;
                 let dy = food_y - me.y;
                 let d = (dx * dx + dy * dy).sqrt();
@@ -318,7 +635,10 @@ impl BoidsEngine {
             }
         }
 
+        record(|b| &mut b.hunger, added(before, fx, fy));
+
         // Territory return force: steer back to territory center when outside
+        let before = (fx, fy);
         if let Some((tcx, tcy)) = me.territory_center {
             let dx = tcx - me.x;
             let dy = tcy - me.y;
@@ -332,12 +652,40 @@ impl BoidsEngine {
             }
         }
 
+        record(|b| &mut b.territory, added(before, fx, fy));
+
+        // Quarantine: hold isolated fish in the bottom-left corner
+        let before = (fx, fy);
+        if me.is_quarantined && config.quarantine_pin_to_corner {
+            let dx = config.boundary_margin - me.x;
+            let dy = config.tank_height - config.boundary_margin - me.y;
+            let dist = (dx * dx + dy * dy).sqrt();
+            if dist > 20.0 {
+                let urgency = ((dist - 20.0) / 40.0).min(2.0);
+                fx += (dx / dist) * urgency * config.base_max_speed;
+                fy += (dy / dist) * urgency * config.base_max_speed;
+            }
+        }
+
+        // Parental care: stay close to the egg or hatchling being guarded
+        if let Some((gx, gy)) = me.guarding {
+            let dx = delta(gx - me.x, config.tank_width);
+            let dy = delta(gy - me.y, config.tank_height);
+            let dist = (dx * dx + dy * dy).sqrt();
+            let leash = (config.nest_guard_radius * 0.5).max(1.0);
+            if dist > leash {
+                let urgency = ((dist - leash) / leash).min(2.0) * my_genome.parental_care;
+                fx += (dx / dist) * urgency * config.base_max_speed * 0.5;
+                fy += (dy / dist) * urgency * config.base_max_speed * 0.5;
+            }
+        }
+
         // Hunting: chase force toward target
         if me.behavior == BehaviorState::Hunting {
             if let Some(target_id) = me.hunting_target {
                 if let Some(target) = fish.iter().find(|f| f.id == target_id && f.is_alive) {
-                    let dx = target.x - me.x;
-                    let dy = target.y - me.y;
+                    let dx = delta(target.x - me.x, config.tank_width);
+                    let dy = delta(target.y - me.y, config.tank_height);
                     let dist = (dx * dx + dy * dy).sqrt().max(0.01);
                     let chase_strength = my_genome.speed * config.base_max_speed * 1.5;
                     fx += (dx / dist) * chase_strength;
@@ -378,6 +726,9 @@ impl BoidsEngine {
             }
         }
 
+        other += added(before, fx, fy);
+        record(|b| &mut b.other, other);
+
         // Apply speed multiplier from behavior state
         fx *= speed_mult;
         fy *= speed_mult;
@@ -446,12 +797,205 @@ mod tests {
         assert!(near.contains(&0), "Fish at origin should be found");
     }
 
+    #[test]
+    fn huge_tank_grid_is_capped() {
+        let mut rng = seeded_rng();
+        let mut grid = SpatialGrid::new(1_000_000.0, 1_000_000.0, 75.0);
+        let stats = grid.stats();
+        assert!(stats.total_cells <= MAX_GRID_CELLS);
+        assert!(grid.cell_size() > 75.0, "Cells grow to fit the cap");
+
+        let fish = vec![Fish::new(1, 500_000.0, 500_000.0, &mut rng), Fish::new(2, 500_010.0, 500_000.0, &mut rng)];
+        grid.rebuild(&fish);
+        assert_eq!(grid.neighbors(500_005.0, 500_000.0, 75.0).len(), 2);
+
+        // A long thin tank and nonsense sizes are bounded too
+        assert!(SpatialGrid::new(1.0e30, 10.0, 75.0).stats().total_cells <= MAX_GRID_CELLS);
+        assert!(SpatialGrid::new(f32::NAN, 800.0, 0.0).stats().total_cells <= MAX_GRID_CELLS);
+        // Ordinary tanks are untouched
+        assert_eq!(SpatialGrid::new(1200.0, 800.0, 75.0).cell_size(), 75.0);
+    }
+
+    #[test]
+    fn spatial_grid_stats_report_occupancy() {
+        let mut rng = seeded_rng();
+        let mut grid = SpatialGrid::new(1200.0, 800.0, 100.0);
+        let empty = grid.stats();
+        assert_eq!(empty.empty_cells, empty.total_cells);
+        assert_eq!(empty.max_per_cell, 0);
+        assert_eq!((empty.cols, empty.rows), (13, 9));
+        assert_eq!(empty.total_cells, 13 * 9);
+
+        // Three fish packed into one cell, one alone elsewhere
+        let fish = vec![
+            Fish::new(1, 10.0, 10.0, &mut rng),
+            Fish::new(2, 20.0, 20.0, &mut rng),
+            Fish::new(3, 30.0, 30.0, &mut rng),
+            Fish::new(4, 650.0, 450.0, &mut rng),
+        ];
+        grid.rebuild(&fish);
+        let stats = grid.stats();
+        assert_eq!(stats.max_per_cell, 3);
+        assert_eq!(stats.empty_cells, stats.total_cells - 2);
+        assert!((stats.avg_per_occupied_cell - 2.0).abs() < 1e-6);
+        assert_eq!(stats.cell_size, 100.0);
+    }
+
+    #[test]
+    fn ideal_cell_size_shrinks_for_dense_cells() {
+        let stats = GridStats {
+            cell_size: 75.0, cols: 17, rows: 12, total_cells: 204,
+            empty_cells: 200, max_per_cell: 40, avg_per_occupied_cell: 16.0,
+        };
+        // 16 per cell vs target 4 → halve the cell size
+        assert!((ideal_cell_size(&stats, 75.0) - 37.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn ideal_cell_size_is_clamped_and_defaults_when_empty() {
+        let sparse = GridStats {
+            cell_size: 75.0, cols: 17, rows: 12, total_cells: 204,
+            empty_cells: 203, max_per_cell: 1, avg_per_occupied_cell: 0.01,
+        };
+        assert_eq!(ideal_cell_size(&sparse, 75.0), 150.0);
+        let empty = GridStats { max_per_cell: 0, avg_per_occupied_cell: 0.0, ..sparse };
+        assert_eq!(ideal_cell_size(&empty, 75.0), 75.0);
+    }
+
+    fn packed_school(rng: &mut StdRng, gid: u32, count: usize) -> Vec<Fish> {
+        (0..count).map(|i| Fish::new(gid, 400.0 + (i % 5) as f32, 300.0 + (i / 5) as f32, rng)).collect()
+    }
+
+    #[test]
+    fn adaptive_grid_retunes_dense_school() {
+        let config = SimulationConfig::default();
+        let mut engine = BoidsEngine::new(&config, 42);
+        let mut rng = seeded_rng();
+        let genome = crate::simulation::genome::FishGenome::random(&mut rng);
+        let gid = genome.id;
+        let genomes = std::collections::HashMap::from([(gid, genome)]);
+        let mut fish = packed_school(&mut rng, gid, 40);
+
+        engine.update(&mut fish, &genomes, &config, 0, &[], &[]);
+        assert!(engine.grid.cell_size() < config.cohesion_radius, "Dense school should shrink cells");
+    }
+
+    #[test]
+    fn adaptive_grid_can_be_disabled() {
+        let config = SimulationConfig { adaptive_grid_enabled: false, ..SimulationConfig::default() };
+        let mut engine = BoidsEngine::new(&config, 42);
+        let mut rng = seeded_rng();
+        let genome = crate::simulation::genome::FishGenome::random(&mut rng);
+        let gid = genome.id;
+        let genomes = std::collections::HashMap::from([(gid, genome)]);
+        let mut fish = packed_school(&mut rng, gid, 40);
+
+        engine.update(&mut fish, &genomes, &config, 0, &[], &[]);
+        assert_eq!(engine.grid.cell_size(), config.cohesion_radius);
+    }
+
+    #[test]
+    fn stats_for_reads_positions_without_rebuilding() {
+        let mut rng = seeded_rng();
+        let mut fish = packed_school(&mut rng, 1, 8);
+        let mut grid = SpatialGrid::new(800.0, 600.0, 50.0);
+        grid.rebuild(&fish);
+        for (i, f) in fish.iter_mut().enumerate() {
+            f.x = 25.0 + 100.0 * i as f32;
+        }
+
+        let fresh = grid.stats_for(&fish);
+        assert_eq!((fresh.max_per_cell, fresh.total_cells - fresh.empty_cells), (1, 8));
+        assert_eq!(grid.stats().max_per_cell, 8, "The grid still holds the positions it was built from");
+        assert_eq!(grid.stats_for(&[]).empty_cells, grid.stats().total_cells);
+    }
+
+    #[test]
+    fn adaptive_grid_survives_a_zero_cohesion_radius() {
+        let config = SimulationConfig { cohesion_radius: 0.0, ..SimulationConfig::default() };
+        let mut engine = BoidsEngine::new(&config, 42);
+        let mut rng = seeded_rng();
+        let genome = crate::simulation::genome::FishGenome::random(&mut rng);
+        let gid = genome.id;
+        let genomes = std::collections::HashMap::from([(gid, genome)]);
+        let mut fish = packed_school(&mut rng, gid, 40);
+
+        engine.update(&mut fish, &genomes, &config, 0, &[], &[]);
+        let size = engine.grid.cell_size();
+        assert!(size.is_finite() && size >= MIN_CELL_SIZE * 0.5, "got {}", size);
+        assert_eq!(ideal_cell_size(&SpatialGrid::new(800.0, 600.0, 10.0).stats(), f32::NAN), MIN_CELL_SIZE);
+        assert_eq!(ideal_cell_size(&SpatialGrid::new(800.0, 600.0, 10.0).stats(), -20.0), MIN_CELL_SIZE);
+    }
+
     // --- BoidsEngine ---
 
+    #[test]
+    fn absolute_max_speed_caps_fast_genomes() {
+        let config = SimulationConfig { absolute_max_speed: 1.0, ..SimulationConfig::default() };
+        let mut engine = BoidsEngine::new(&config, 42);
+        let mut rng = seeded_rng();
+        let mut genome = crate::simulation::genome::FishGenome::random(&mut rng);
+        genome.speed = 2.0;
+        let gid = genome.id;
+        let genomes = std::collections::HashMap::from([(gid, genome)]);
+        let mut fish = vec![Fish::new(gid, 400.0, 300.0, &mut rng)];
+        (fish[0].vx, fish[0].vy) = (8.0, 0.0);
+
+        engine.update(&mut fish, &genomes, &config, 0, &[], &[]);
+        let speed = (fish[0].vx * fish[0].vx + fish[0].vy * fish[0].vy).sqrt();
+        assert!(speed <= config.absolute_max_speed + 1e-4, "speed = {}", speed);
+    }
+
+    #[test]
+    fn force_breakdown_reports_components_without_moving_fish() {
+        let config = SimulationConfig::default();
+        let mut engine = BoidsEngine::new(&config, 42);
+        let mut rng = seeded_rng();
+        let genome = crate::simulation::genome::FishGenome::random(&mut rng);
+        let gid = genome.id;
+        let genomes = std::collections::HashMap::from([(gid, genome)]);
+        let mut fish = vec![Fish::new(gid, 10.0, 300.0, &mut rng), Fish::new(gid, 18.0, 300.0, &mut rng)];
+        engine.update(&mut fish, &genomes, &config, 0, &[], &[]);
+        let positions: Vec<(f32, f32)> = fish.iter().map(|f| (f.x, f.y)).collect();
+
+        let b = engine.force_breakdown(&fish, &genomes, &config, 1, &[], &[]);
+        assert_eq!(b.fish_count, 2);
+        assert!(b.separation > 0.0, "close pair pushes apart");
+        assert!(b.boundary > 0.0, "both fish sit inside the left margin");
+        assert_eq!(b.hunger, 0.0, "no food to steer toward");
+        assert_eq!(fish.iter().map(|f| (f.x, f.y)).collect::<Vec<_>>(), positions);
+    }
+
+    #[test]
+    fn hungry_grazers_steer_toward_algae() {
+        let config = SimulationConfig::default();
+        let mut engine = BoidsEngine::new(&config, 42);
+        let mut rng = seeded_rng();
+        let mut grazer = crate::simulation::genome::FishGenome::random(&mut rng);
+        grazer.aggression = 0.1;
+        let mut hunter = crate::simulation::genome::FishGenome::random(&mut rng);
+        hunter.aggression = 0.9;
+        let (gid, hid) = (grazer.id, hunter.id);
+        let genomes = std::collections::HashMap::from([(gid, grazer), (hid, hunter)]);
+        let mut fish = vec![Fish::new(gid, 400.0, 300.0, &mut rng), Fish::new(hid, 400.0, 300.0, &mut rng)];
+        for f in &mut fish {
+            f.hunger = 0.9;
+        }
+        let patch = AlgaePatch { id: 1, x: 450.0, y: 300.0, size: 0.5 };
+
+        let unset = engine.force_breakdown(&fish[..1], &genomes, &config, 0, &[], &[]);
+        assert_eq!(unset.hunger, 0.0, "No algae recorded yet");
+        engine.set_algae(std::slice::from_ref(&patch));
+        let grazing = engine.force_breakdown(&fish[..1], &genomes, &config, 0, &[], &[]);
+        assert!(grazing.hunger > 0.0);
+        let hunting = engine.force_breakdown(&fish[1..], &genomes, &config, 0, &[], &[]);
+        assert_eq!(hunting.hunger, 0.0, "Carnivores ignore algae");
+    }
+
     #[test]
     fn boids_engine_creates_with_config() {
         let config = SimulationConfig::default();
-        let engine = BoidsEngine::new(&config);
+        let engine = BoidsEngine::new(&config, 42);
         // Should not panic, grid should be properly sized
         assert!(engine.grid.cols > 0);
         assert!(engine.grid.rows > 0);
@@ -460,7 +1004,7 @@ mod tests {
     #[test]
     fn boids_update_moves_fish() {
         let config = SimulationConfig::default();
-        let mut engine = BoidsEngine::new(&config);
+        let mut engine = BoidsEngine::new(&config, 42);
         let mut rng = seeded_rng();
         let genome = crate::simulation::genome::FishGenome::random(&mut rng);
         let mut genomes = std::collections::HashMap::new();
@@ -486,10 +1030,29 @@ mod tests {
         assert!(moved, "Fish should move from wander force");
     }
 
+    #[test]
+    fn noise_seed_sets_wander_paths() {
+        let config = SimulationConfig::default();
+        let run = |seed: u32| {
+            let mut rng = seeded_rng();
+            let genome = crate::simulation::genome::FishGenome::random(&mut rng);
+            let gid = genome.id;
+            let genomes = std::collections::HashMap::from([(gid, genome)]);
+            let mut fish = vec![Fish::new(gid, 400.0, 300.0, &mut rng)];
+            let mut engine = BoidsEngine::new(&config, seed);
+            for tick in 0..60 {
+                engine.update(&mut fish, &genomes, &config, tick, &[], &[]);
+            }
+            (fish[0].x, fish[0].y)
+        };
+        assert_eq!(run(7), run(7), "Same seed reproduces the path");
+        assert_ne!(run(7), run(8));
+    }
+
     #[test]
     fn boids_fish_stays_in_bounds() {
         let config = SimulationConfig::default();
-        let mut engine = BoidsEngine::new(&config);
+        let mut engine = BoidsEngine::new(&config, 42);
         let mut rng = seeded_rng();
         let genome = crate::simulation::genome::FishGenome::random(&mut rng);
         let mut genomes = std::collections::HashMap::new();
@@ -510,4 +1073,190 @@ mod tests {
         assert!(fish[0].x >= 0.0 && fish[0].x <= config.tank_width);
         assert!(fish[0].y >= 0.0 && fish[0].y <= config.tank_height);
     }
+
+    // --- Wrap-around boundary ---
+
+    #[test]
+    fn spatial_grid_wrapped_neighbors_cross_seam() {
+        let mut rng = seeded_rng();
+        let mut grid = SpatialGrid::new(800.0, 600.0, 50.0);
+        let fish = vec![
+            Fish::new(1, 5.0, 300.0, &mut rng),
+            Fish::new(2, 795.0, 300.0, &mut rng),
+            Fish::new(3, 795.0, 595.0, &mut rng),
+        ];
+        grid.rebuild(&fish);
+
+        let plain = grid.neighbors(5.0, 300.0, 30.0);
+        assert!(!plain.contains(&1), "Plain query should not see across the edge");
+
+        let wrapped = grid.neighbors_wrapped(5.0, 300.0, 30.0);
+        assert!(wrapped.contains(&0) && wrapped.contains(&1));
+
+        // Corner query reaches the diagonally opposite corner
+        let corner = grid.neighbors_wrapped(5.0, 5.0, 30.0);
+        assert!(corner.contains(&2));
+        let mut deduped = corner.clone();
+        deduped.dedup();
+        assert_eq!(corner, deduped, "Results should not repeat indices");
+    }
+
+    #[test]
+    fn wrap_delta_takes_short_way() {
+        assert_eq!(wrap_delta(790.0, 800.0), -10.0);
+        assert_eq!(wrap_delta(-790.0, 800.0), 10.0);
+        assert_eq!(wrap_delta(100.0, 800.0), 100.0);
+    }
+
+    #[test]
+    fn boids_wrap_mode_reenters_opposite_edge() {
+        let config = SimulationConfig {
+            boundary_mode: BoundaryMode::Wrap,
+            ..SimulationConfig::default()
+        };
+        let mut engine = BoidsEngine::new(&config, 42);
+        let mut rng = seeded_rng();
+        let genome = crate::simulation::genome::FishGenome::random(&mut rng);
+        let mut genomes = std::collections::HashMap::new();
+        let gid = genome.id;
+        genomes.insert(gid, genome);
+
+        let mut fish = vec![Fish::new(gid, 1.0, 300.0, &mut rng)];
+        fish[0].vx = -3.0;
+        fish[0].vy = 0.0;
+        engine.update(&mut fish, &genomes, &config, 0, &[], &[]);
+
+        assert!(fish[0].x > config.tank_width / 2.0, "Fish leaving the left edge should appear on the right, x = {}", fish[0].x);
+        assert!(fish[0].x >= 0.0 && fish[0].x < config.tank_width);
+    }
+
+    // --- Schooling mode ---
+
+    #[test]
+    fn species_only_ignores_other_species() {
+        let mut rng = seeded_rng();
+        let me = crate::simulation::genome::FishGenome::random(&mut rng);
+        let mut kin = me.clone();
+        kin.speed += 0.05;
+        let mut stranger = me.clone();
+        stranger.base_hue = (stranger.base_hue + 180.0) % 360.0;
+        stranger.body_length = if me.body_length > 1.2 { 0.6 } else { 2.0 };
+        stranger.speed = if me.speed > 1.2 { 0.5 } else { 2.0 };
+
+        let mixed = SimulationConfig::default();
+        assert!(genome_distance(&me, &stranger, mixed.distance_weights()) >= mixed.species_threshold, "Fixture should be another species");
+        assert!(schooling_affinity(&me, Some(&stranger), &mixed) > 0.0, "Mixed mode only down-weights");
+
+        let strict = SimulationConfig { schooling_mode: SchoolingMode::SpeciesOnly, ..mixed.clone() };
+        assert_eq!(schooling_affinity(&me, Some(&stranger), &strict), 0.0);
+        assert_eq!(schooling_affinity(&me, None, &strict), 0.0);
+        assert_eq!(schooling_affinity(&me, Some(&kin), &strict), schooling_affinity(&me, Some(&kin), &mixed));
+    }
+
+    #[test]
+    fn same_species_pulls_together_despite_genome_distance() {
+        let mut rng = seeded_rng();
+        let config = SimulationConfig::default();
+        let me = crate::simulation::genome::FishGenome::random(&mut rng);
+        let mut stranger = me.clone();
+        stranger.id = me.id + 1;
+        stranger.base_hue = (stranger.base_hue + 180.0) % 360.0;
+        stranger.body_length = if me.body_length > 1.2 { 0.6 } else { 2.0 };
+        stranger.speed = if me.speed > 1.2 { 0.5 } else { 2.0 };
+        let mut kin = me.clone();
+        kin.id = me.id + 2;
+        let mut genomes = std::collections::HashMap::new();
+        // Unclassified kin on the left, a distant species-mate on the right
+        let fish = vec![
+            Fish::new(me.id, 300.0, 300.0, &mut rng),
+            Fish::new(stranger.id, 350.0, 300.0, &mut rng),
+            Fish::new(kin.id, 250.0, 300.0, &mut rng),
+        ];
+        genomes.insert(me.id, me.clone());
+        genomes.insert(stranger.id, stranger.clone());
+        genomes.insert(kin.id, kin);
+
+        let mut engine = BoidsEngine::new(&config, 0);
+        engine.grid.rebuild(&fish);
+        let (apart, _) = engine.compute_forces(0, &fish, &genomes, &config, 0, &[], &[], None);
+
+        let species = Species {
+            id: 1, name: None, description: None, discovered_at_tick: 0, extinct_at_tick: None,
+            centroid_hue: 0.0, centroid_speed: 1.0, centroid_size: 1.0, centroid_pattern: String::new(),
+            member_count: 2, member_genome_ids: vec![me.id, stranger.id], distinct_marker: None, fossil: None,
+        };
+        engine.set_species(&[species]);
+        let (together, _) = engine.compute_forces(0, &fish, &genomes, &config, 0, &[], &[], None);
+        assert!(together > apart, "Same-species cohesion should pull harder toward the species-mate");
+    }
+
+    // --- School size preference ---
+
+    #[test]
+    fn school_size_preference_shifts_spacing() {
+        let mut rng = seeded_rng();
+        let mut genome = crate::simulation::genome::FishGenome::random(&mut rng);
+        genome.school_affinity = 1.0;
+        let gid = genome.id;
+        // Six fish: the subject plus one close and four looser neighbors, all to its right
+        let mut fish = vec![Fish::new(gid, 400.0, 300.0, &mut rng)];
+        for (x, y) in [(412.0, 300.0), (440.0, 290.0), (450.0, 310.0), (460.0, 300.0), (455.0, 280.0)] {
+            fish.push(Fish::new(gid, x, y, &mut rng));
+        }
+        let config = SimulationConfig::default();
+        let push_x = |ideal: f32| {
+            let genomes = std::collections::HashMap::from([(gid, crate::simulation::genome::FishGenome { ideal_school_size: ideal, ..genome.clone() })]);
+            let mut engine = BoidsEngine::new(&config, 42);
+            engine.grid.rebuild(&fish);
+            engine.compute_forces(0, &fish, &genomes, &config, 0, &[], &[], None).0
+        };
+        let content = push_x(6.0);
+        assert!(push_x(2.0) < content, "A crowded fish should be pushed away from the school");
+        assert!(push_x(24.0) > content, "A lonely fish should be pulled into the school");
+
+        let indifferent = SimulationConfig { school_size_strength: 0.0, ..SimulationConfig::default() };
+        let mut engine = BoidsEngine::new(&indifferent, 42);
+        engine.grid.rebuild(&fish);
+        let genomes = std::collections::HashMap::from([(gid, crate::simulation::genome::FishGenome { ideal_school_size: 2.0, ..genome.clone() })]);
+        assert_eq!(engine.compute_forces(0, &fish, &genomes, &indifferent, 0, &[], &[], None).0, content);
+    }
+
+    // --- Shoaling vs schooling ---
+
+    #[test]
+    fn polarization_trades_cohesion_for_alignment() {
+        let (school_align, school_coh) = schooling_style(1.0, 0.8);
+        let (shoal_align, shoal_coh) = schooling_style(0.0, 0.8);
+        assert!(school_align > 1.0 && school_coh < 1.0, "Schoolers should align harder and bunch less");
+        assert!(shoal_align < 1.0 && shoal_coh > 1.0, "Shoalers should gather without lining up");
+        assert_eq!(schooling_style(0.5, 0.8), (1.0, 1.0));
+        assert_eq!(schooling_style(0.0, 0.0), (1.0, 1.0));
+        assert_eq!(schooling_style(0.0, 1.0).0, 0.0);
+    }
+
+    // --- Shelter ---
+
+    #[test]
+    fn fleeing_fish_are_drawn_toward_cover() {
+        let mut rng = seeded_rng();
+        let mut genome = crate::simulation::genome::FishGenome::random(&mut rng);
+        genome.boldness = 0.5;
+        let gid = genome.id;
+        let genomes = std::collections::HashMap::from([(gid, genome)]);
+        let mut fish = vec![Fish::new(gid, 400.0, 300.0, &mut rng)];
+        fish[0].behavior = BehaviorState::Fleeing;
+        let rock = [(600.0, 300.0, 30.0)];
+
+        let pull_x = |config: &SimulationConfig, fish: &[Fish]| {
+            let engine = BoidsEngine::new(config, 42);
+            engine.compute_forces(0, fish, &genomes, config, 0, &[], &rock, None).0
+        };
+        let on = SimulationConfig::default();
+        let off = SimulationConfig { shelter_enabled: false, ..SimulationConfig::default() };
+        assert!(pull_x(&on, &fish) > pull_x(&off, &fish), "Fleeing fish should steer toward the rock");
+
+        // A calm, bold fish ignores cover
+        fish[0].behavior = BehaviorState::Swimming;
+        assert_eq!(pull_x(&on, &fish), pull_x(&off, &fish));
+    }
 }
@@ -0,0 +1,395 @@
+use super::config::SimulationConfig;
+use super::conservation::ConservationRegistry;
+use super::fish::Fish;
+use super::genome::{genome_distance, inheritance_cells, relatedness, sample_inherited_trait, FishGenome, InheritanceCell, Sex};
+use super::scenarios::{get_trait, trait_range};
+use rand::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Pairs scored per request; larger populations are randomly sampled down to this
+const MAX_PAIRS: usize = 150;
+/// Simulated offspring per pair
+const SAMPLES_PER_PAIR: usize = 24;
+/// Recommendations returned
+const TOP_PAIRS: usize = 5;
+
+/// Traits compared in the selection report, in the order deaths record them
+pub const SELECTION_TRAITS: [&str; 3] = ["speed", "body_length", "aggression"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairingRecommendation {
+    pub fish_a: u32,
+    pub fish_b: u32,
+    pub parent_a_value: f32,
+    pub parent_b_value: f32,
+    /// Mean trait value over the simulated offspring
+    pub expected: f32,
+    pub min: f32,
+    pub max: f32,
+    /// Share of simulated offspring past the population mean in the goal direction
+    pub chance_beyond_mean: f32,
+    pub relatedness: f32,
+    /// Offspring would take the inbreeding lifespan/fertility penalty
+    pub inbreeding_warning: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairingReport {
+    pub trait_name: String,
+    pub maximize: bool,
+    pub population_mean: f32,
+    pub eligible_pairs: usize,
+    pub pairs_considered: usize,
+    pub recommendations: Vec<PairingRecommendation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InheritanceTable {
+    pub trait_name: String,
+    pub parent_a_value: f32,
+    pub parent_b_value: f32,
+    pub relatedness: f32,
+    pub cross_species: bool,
+    /// Mean offspring value before clamping
+    pub expected: f32,
+    pub cells: Vec<InheritanceCell>,
+}
+
+/// Functional mutation rates (large, small) `force_breed` uses for this pair;
+/// crosses between species mutate more and protected lineages less
+fn pair_mutation_rates(
+    ga: &FishGenome,
+    gb: &FishGenome,
+    config: &SimulationConfig,
+    conservation: &ConservationRegistry,
+) -> (bool, f32, f32) {
+    let cross_species = genome_distance(ga, gb, config.distance_weights()) >= config.species_threshold;
+    let scale = conservation.mutation_scale(ga, gb, config);
+    if cross_species {
+        (true, config.mutation_rate_large * 2.0 * scale, config.mutation_rate_small * 1.5 * scale)
+    } else {
+        (false, config.mutation_rate_large * scale, config.mutation_rate_small * scale)
+    }
+}
+
+/// Whether `force_breed` would accept this fish as a parent
+fn can_breed(f: &Fish, g: &FishGenome, config: &SimulationConfig) -> bool {
+    f.is_alive && !f.is_juvenile && !f.is_quarantined && f.age_fraction(g, config.max_lifespan) >= g.maturity_age
+}
+
+/// Rank opposite-sex pairs of breedable fish by where their simulated offspring
+/// land on `trait_name`, highest first when maximizing and lowest first otherwise.
+/// Offspring are drawn with the same inheritance, mutation rates and inbreeding
+/// scaling as `force_breed`.
+pub fn recommend_pairings(
+    fish: &[Fish],
+    genomes: &HashMap<u32, FishGenome>,
+    config: &SimulationConfig,
+    conservation: &ConservationRegistry,
+    trait_name: &str,
+    maximize: bool,
+    rng: &mut impl Rng,
+) -> Result<PairingReport, String> {
+    let range = trait_range(trait_name).ok_or_else(|| format!("Unknown trait: {}", trait_name))?;
+
+    let living: Vec<(&Fish, &FishGenome)> = fish.iter()
+        .filter(|f| f.is_alive)
+        .filter_map(|f| genomes.get(&f.genome_id).map(|g| (f, g)))
+        .collect();
+    let population_mean = if living.is_empty() {
+        0.0
+    } else {
+        living.iter().map(|(_, g)| get_trait(g, trait_name)).sum::<f32>() / living.len() as f32
+    };
+
+    let (males, females): (Vec<_>, Vec<_>) = living.into_iter()
+        .filter(|(f, g)| can_breed(f, g, config))
+        .partition(|(_, g)| g.sex == Sex::Male);
+    let mut pairs: Vec<_> = males.iter()
+        .flat_map(|m| females.iter().map(move |f| (*m, *f)))
+        .collect();
+    let eligible_pairs = pairs.len();
+    if pairs.len() > MAX_PAIRS {
+        pairs.shuffle(rng);
+        pairs.truncate(MAX_PAIRS);
+    }
+
+    let direction = if maximize { 1.0 } else { -1.0 };
+    let mut recommendations: Vec<PairingRecommendation> = pairs.iter().map(|&((fa, ga), (fb, gb))| {
+        let (_, large_rate, small_rate) = pair_mutation_rates(ga, gb, config, conservation);
+        let related = relatedness(ga, gb, genomes, config.inbreeding_check_depth);
+        let (a, b) = (get_trait(ga, trait_name), get_trait(gb, trait_name));
+
+        let samples: Vec<f32> = (0..SAMPLES_PER_PAIR)
+            .map(|_| sample_inherited_trait(a, b, range, related, large_rate, small_rate, rng))
+            .collect();
+        let beyond = samples.iter().filter(|&&v| (v - population_mean) * direction > 0.0).count();
+        PairingRecommendation {
+            fish_a: fa.id,
+            fish_b: fb.id,
+            parent_a_value: a,
+            parent_b_value: b,
+            expected: samples.iter().sum::<f32>() / SAMPLES_PER_PAIR as f32,
+            min: samples.iter().copied().fold(f32::MAX, f32::min),
+            max: samples.iter().copied().fold(f32::MIN, f32::max),
+            chance_beyond_mean: beyond as f32 / SAMPLES_PER_PAIR as f32,
+            relatedness: related,
+            inbreeding_warning: related > 0.0,
+        }
+    }).collect();
+
+    recommendations.sort_by(|x, y| {
+        (y.expected * direction).partial_cmp(&(x.expected * direction)).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    recommendations.truncate(TOP_PAIRS);
+
+    Ok(PairingReport {
+        trait_name: trait_name.to_string(),
+        maximize,
+        population_mean,
+        eligible_pairs,
+        pairs_considered: pairs.len(),
+        recommendations,
+    })
+}
+
+/// Punnett-style table of every offspring outcome for one trait of a pair,
+/// with the rates and inbreeding scaling `force_breed` would use. `genomes`
+/// is only walked for shared ancestry; the only error is an unknown trait.
+pub fn inheritance_table(
+    ga: &FishGenome,
+    gb: &FishGenome,
+    genomes: &HashMap<u32, FishGenome>,
+    config: &SimulationConfig,
+    conservation: &ConservationRegistry,
+    trait_name: &str,
+) -> Result<InheritanceTable, String> {
+    let range = trait_range(trait_name).ok_or_else(|| format!("Unknown trait: {}", trait_name))?;
+    let (cross_species, large_rate, small_rate) = pair_mutation_rates(ga, gb, config, conservation);
+    let related = relatedness(ga, gb, genomes, config.inbreeding_check_depth);
+    let (a, b) = (get_trait(ga, trait_name), get_trait(gb, trait_name));
+    Ok(InheritanceTable {
+        trait_name: trait_name.to_string(),
+        parent_a_value: a,
+        parent_b_value: b,
+        relatedness: related,
+        cross_species,
+        expected: (a + b) / 2.0,
+        cells: inheritance_cells(a, b, range, related, large_rate, small_rate),
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraitSelection {
+    pub trait_name: &'static str,
+    pub reproduced_mean: f32,
+    pub failed_mean: f32,
+    /// Reproducers' mean minus non-reproducers' mean, in trait units
+    pub differential: f32,
+    /// Differential over the pooled standard deviation, comparable across traits
+    pub standardized: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectionReport {
+    pub reproduced_count: usize,
+    pub failed_count: usize,
+    /// Empty until both groups have members
+    pub traits: Vec<TraitSelection>,
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> f32 {
+    let (sum, n) = values.fold((0.0, 0), |(s, n), v| (s + v, n + 1));
+    if n == 0 { 0.0 } else { sum / n as f32 }
+}
+
+/// Traits of living fish that have bred, in `SELECTION_TRAITS` order. Uses the
+/// same rule deaths record: a fish counts once it has ever reproduced, and
+/// living fish that haven't yet are left out since they still might.
+pub fn living_selection_samples(fish: &[Fish], genomes: &HashMap<u32, FishGenome>) -> Vec<[f32; 3]> {
+    fish.iter()
+        .filter(|f| f.is_alive && f.last_reproduced_tick.is_some())
+        .filter_map(|f| genomes.get(&f.genome_id))
+        .map(|g| [g.speed, g.body_length, g.aggression])
+        .collect()
+}
+
+/// Selection differential per trait between fish that reproduced and fish
+/// that died without reproducing. Samples are in `SELECTION_TRAITS` order.
+pub fn selection_report(reproduced: &[[f32; 3]], failed: &[[f32; 3]]) -> SelectionReport {
+    let traits = if reproduced.is_empty() || failed.is_empty() {
+        Vec::new()
+    } else {
+        SELECTION_TRAITS.iter().enumerate().map(|(i, &trait_name)| {
+            let reproduced_mean = mean(reproduced.iter().map(|s| s[i]));
+            let failed_mean = mean(failed.iter().map(|s| s[i]));
+            let all = || reproduced.iter().chain(failed).map(|s| s[i]);
+            let overall = mean(all());
+            let sd = mean(all().map(|v| (v - overall).powi(2))).sqrt();
+            let differential = reproduced_mean - failed_mean;
+            TraitSelection {
+                trait_name,
+                reproduced_mean,
+                failed_mean,
+                differential,
+                standardized: if sd > 1e-6 { differential / sd } else { 0.0 },
+            }
+        }).collect()
+    };
+    SelectionReport { reproduced_count: reproduced.len(), failed_count: failed.len(), traits }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+
+    fn seeded_rng() -> StdRng {
+        StdRng::seed_from_u64(42)
+    }
+
+    /// A mature, breedable fish with the given sex and speed
+    fn adult(rng: &mut StdRng, genomes: &mut HashMap<u32, FishGenome>, sex: Sex, speed: f32) -> Fish {
+        let mut g = FishGenome::random(rng);
+        g.sex = sex;
+        g.speed = speed;
+        g.parent_a = None;
+        g.parent_b = None;
+        let mut f = Fish::new(g.id, 100.0, 100.0, rng);
+        f.age = (SimulationConfig::default().max_lifespan as f32 * g.lifespan_factor * g.maturity_age) as u32 + 1;
+        f.is_juvenile = false;
+        genomes.insert(g.id, g);
+        f
+    }
+
+    #[test]
+    fn fastest_pair_ranks_first_when_maximizing() {
+        let mut rng = seeded_rng();
+        let mut genomes = HashMap::new();
+        let fish = vec![
+            adult(&mut rng, &mut genomes, Sex::Male, 1.9),
+            adult(&mut rng, &mut genomes, Sex::Male, 0.6),
+            adult(&mut rng, &mut genomes, Sex::Female, 1.8),
+            adult(&mut rng, &mut genomes, Sex::Female, 0.7),
+        ];
+        let config = SimulationConfig::default();
+
+        let fast = recommend_pairings(&fish, &genomes, &config, &ConservationRegistry::new(), "speed", true, &mut rng).unwrap();
+        assert_eq!(fast.eligible_pairs, 4);
+        assert_eq!((fast.recommendations[0].fish_a, fast.recommendations[0].fish_b), (fish[0].id, fish[2].id));
+        assert!(fast.recommendations[0].expected > fast.population_mean);
+
+        let slow = recommend_pairings(&fish, &genomes, &config, &ConservationRegistry::new(), "speed", false, &mut rng).unwrap();
+        assert_eq!((slow.recommendations[0].fish_a, slow.recommendations[0].fish_b), (fish[1].id, fish[3].id));
+    }
+
+    #[test]
+    fn inheritance_table_covers_every_outcome() {
+        let mut rng = seeded_rng();
+        let mut genomes = HashMap::new();
+        let fish = [
+            adult(&mut rng, &mut genomes, Sex::Male, 0.8),
+            adult(&mut rng, &mut genomes, Sex::Female, 1.8),
+        ];
+        let config = SimulationConfig::default();
+
+        let (ga, gb) = (&genomes[&fish[0].genome_id], &genomes[&fish[1].genome_id]);
+        let table = inheritance_table(ga, gb, &genomes, &config, &ConservationRegistry::new(), "speed").unwrap();
+        assert_eq!(table.cells.len(), 9);
+        assert!((table.cells.iter().map(|c| c.probability).sum::<f32>() - 1.0).abs() < 1e-5);
+        assert!((table.expected - 1.3).abs() < 1e-5);
+        let blend = table.cells.iter().find(|c| c.source == "blend" && c.mutation == "none").unwrap();
+        assert!((blend.low - 1.1).abs() < 1e-5 && (blend.high - 1.5).abs() < 1e-5);
+        let copy_a = table.cells.iter().find(|c| c.source == "parent_a" && c.mutation == "none").unwrap();
+        let (_, large, small) = pair_mutation_rates(ga, gb, &config, &ConservationRegistry::new());
+        assert!((copy_a.probability - 0.3 * (1.0 - large - small)).abs() < 1e-5);
+
+        let mut conservation = ConservationRegistry::new();
+        conservation.protect(ga, &genomes);
+        let (_, protected_large, protected_small) = pair_mutation_rates(ga, gb, &config, &conservation);
+        assert!((protected_large - large * config.conservation_mutation_scale).abs() < 1e-6);
+        assert!((protected_small - small * config.conservation_mutation_scale).abs() < 1e-6);
+        let protected = inheritance_table(ga, gb, &genomes, &config, &conservation, "speed").unwrap();
+        let protected_copy = protected.cells.iter().find(|c| c.source == "parent_a" && c.mutation == "none").unwrap();
+        assert!(protected_copy.probability > copy_a.probability, "Protected lineages keep more parental copies");
+
+        assert!(inheritance_table(ga, gb, &genomes, &config, &ConservationRegistry::new(), "pattern").is_err());
+        assert!(inheritance_table(ga, gb, &genomes, &config, &ConservationRegistry::new(), "not_a_trait").is_err());
+    }
+
+    #[test]
+    fn siblings_carry_inbreeding_warning() {
+        let mut rng = seeded_rng();
+        let mut genomes = HashMap::new();
+        let mut fish = vec![
+            adult(&mut rng, &mut genomes, Sex::Male, 1.0),
+            adult(&mut rng, &mut genomes, Sex::Female, 1.0),
+        ];
+        // Give both the same two (pruned) parents
+        for f in &mut fish {
+            let g = genomes.get_mut(&f.genome_id).unwrap();
+            g.parent_a = Some(9001);
+            g.parent_b = Some(9002);
+        }
+        let report = recommend_pairings(&fish, &genomes, &SimulationConfig::default(), &ConservationRegistry::new(), "speed", true, &mut rng).unwrap();
+        let rec = &report.recommendations[0];
+        assert!(rec.inbreeding_warning);
+        assert!((rec.relatedness - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn juveniles_and_unknown_traits_are_excluded() {
+        let mut rng = seeded_rng();
+        let mut genomes = HashMap::new();
+        let mut fish = vec![
+            adult(&mut rng, &mut genomes, Sex::Male, 1.0),
+            adult(&mut rng, &mut genomes, Sex::Female, 1.0),
+        ];
+        fish[1].is_juvenile = true;
+        let config = SimulationConfig::default();
+        let report = recommend_pairings(&fish, &genomes, &config, &ConservationRegistry::new(), "speed", true, &mut rng).unwrap();
+        assert_eq!(report.eligible_pairs, 0);
+        assert!(report.recommendations.is_empty());
+        assert!(recommend_pairings(&fish, &genomes, &config, &ConservationRegistry::new(), "wingspan", true, &mut rng).is_err());
+    }
+
+    // --- Selection report ---
+
+    #[test]
+    fn fast_breeders_show_positive_speed_selection() {
+        let reproduced = [[1.8, 1.0, 0.5], [1.6, 1.2, 0.5]];
+        let failed = [[0.8, 1.0, 0.5], [1.0, 0.8, 0.5], [0.6, 1.0, 0.5]];
+        let report = selection_report(&reproduced, &failed);
+        assert_eq!((report.reproduced_count, report.failed_count), (2, 3));
+        let speed = &report.traits[0];
+        assert!((speed.differential - 0.9).abs() < 1e-5);
+        assert!(speed.standardized > 1.0);
+        assert!(report.traits[1].differential > 0.0);
+        assert_eq!(report.traits[2].standardized, 0.0, "No variation, no selection");
+    }
+
+    #[test]
+    fn living_breeders_count_however_long_ago_they_bred() {
+        let mut rng = seeded_rng();
+        let mut genomes = HashMap::new();
+        let mut fish = vec![
+            adult(&mut rng, &mut genomes, Sex::Male, 1.9),
+            adult(&mut rng, &mut genomes, Sex::Female, 0.7),
+            adult(&mut rng, &mut genomes, Sex::Female, 1.2),
+        ];
+        fish[0].last_reproduced_tick = Some(1);
+        fish[2].last_reproduced_tick = Some(50_000);
+        fish[2].is_alive = false;
+
+        let samples = living_selection_samples(&fish, &genomes);
+        assert_eq!(samples, vec![[1.9, genomes[&fish[0].genome_id].body_length, genomes[&fish[0].genome_id].aggression]]);
+        assert!(living_selection_samples(&fish[1..2], &genomes).is_empty(), "Not bred yet");
+        assert!(living_selection_samples(&fish[2..], &genomes).is_empty(), "Dead fish come from the events log");
+    }
+
+    #[test]
+    fn selection_needs_both_groups() {
+        assert!(selection_report(&[[1.0, 1.0, 0.5]], &[]).traits.is_empty());
+    }
+}
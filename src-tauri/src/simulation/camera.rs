@@ -0,0 +1,180 @@
+use super::config::SimulationConfig;
+use super::fish::{BehaviorState, Fish};
+use serde::Serialize;
+
+/// Margin added around the fish in a shot so they aren't framed edge to edge
+const FRAME_PADDING: f32 = 40.0;
+
+/// What the observation camera is looking at, most interesting first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShotKind {
+    Chase,
+    Courtship,
+    Hatchlings,
+    School,
+    Overview,
+}
+
+/// A region of the tank worth looking at: pan to (x, y) and zoom to fit `radius`
+#[derive(Debug, Clone, Serialize)]
+pub struct CameraSuggestion {
+    pub kind: ShotKind,
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+    pub fish_ids: Vec<u32>,
+}
+
+impl CameraSuggestion {
+    fn framing(kind: ShotKind, members: &[&Fish]) -> Self {
+        let n = members.len().max(1) as f32;
+        let x = members.iter().map(|f| f.x).sum::<f32>() / n;
+        let y = members.iter().map(|f| f.y).sum::<f32>() / n;
+        let spread = members.iter()
+            .map(|f| ((f.x - x).powi(2) + (f.y - y).powi(2)).sqrt())
+            .fold(0.0_f32, f32::max);
+        Self { kind, x, y, radius: spread + FRAME_PADDING, fish_ids: members.iter().map(|f| f.id).collect() }
+    }
+
+    fn overview(config: &SimulationConfig) -> Self {
+        Self {
+            kind: ShotKind::Overview,
+            x: config.tank_width / 2.0,
+            y: config.tank_height / 2.0,
+            radius: config.tank_width.max(config.tank_height) / 2.0,
+            fish_ids: Vec::new(),
+        }
+    }
+}
+
+/// Pick the most interesting action in the tank: a predator chasing prey, then
+/// a courting pair, then a cluster of hatchlings, then the densest school,
+/// falling back to the whole tank
+pub fn suggest(fish: &[Fish], config: &SimulationConfig) -> CameraSuggestion {
+    let find = |id: u32| fish.iter().find(|f| f.id == id && f.is_alive);
+    let active: Vec<&Fish> = fish.iter()
+        .filter(|f| f.is_alive && !f.is_quarantined && f.behavior != BehaviorState::Dying)
+        .collect();
+
+    let chase = active.iter()
+        .filter(|f| f.behavior == BehaviorState::Hunting)
+        .find_map(|&f| Some([f, find(f.hunting_target?)?]));
+    if let Some(pair) = chase {
+        return CameraSuggestion::framing(ShotKind::Chase, &pair);
+    }
+
+    let courtship = active.iter()
+        .filter(|f| f.behavior == BehaviorState::Courting)
+        .find_map(|&f| Some([f, find(f.courting_partner?)?]));
+    if let Some(pair) = courtship {
+        return CameraSuggestion::framing(ShotKind::Courtship, &pair);
+    }
+
+    let juveniles: Vec<&Fish> = active.iter().copied().filter(|f| f.is_juvenile).collect();
+    if let Some(cluster) = densest_cluster(&juveniles, config.camera_cluster_radius, 2) {
+        return CameraSuggestion::framing(ShotKind::Hatchlings, &cluster);
+    }
+
+    let adults: Vec<&Fish> = active.iter().copied().filter(|f| !f.is_juvenile).collect();
+    if let Some(cluster) = densest_cluster(&adults, config.camera_cluster_radius, config.camera_min_school as usize) {
+        return CameraSuggestion::framing(ShotKind::School, &cluster);
+    }
+
+    CameraSuggestion::overview(config)
+}
+
+/// The fish within `radius` of whichever fish has the most such neighbors,
+/// if that group reaches `min_size` (itself included)
+fn densest_cluster<'a>(fish: &[&'a Fish], radius: f32, min_size: usize) -> Option<Vec<&'a Fish>> {
+    let radius_sq = radius * radius;
+    let around = |c: &Fish| -> Vec<&'a Fish> {
+        fish.iter().copied()
+            .filter(|f| (f.x - c.x).powi(2) + (f.y - c.y).powi(2) <= radius_sq)
+            .collect()
+    };
+    fish.iter()
+        .map(|&c| around(c))
+        .max_by_key(|group| group.len())
+        .filter(|group| group.len() >= min_size.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn seeded_rng() -> StdRng {
+        StdRng::seed_from_u64(42)
+    }
+
+    fn fish_at(rng: &mut StdRng, x: f32, y: f32) -> Fish {
+        let mut f = Fish::new(1, x, y, rng);
+        f.x = x;
+        f.y = y;
+        f.is_juvenile = false;
+        f.behavior = BehaviorState::Swimming;
+        f
+    }
+
+    #[test]
+    fn empty_tank_shows_overview() {
+        let config = SimulationConfig::default();
+        let shot = suggest(&[], &config);
+        assert_eq!(shot.kind, ShotKind::Overview);
+        assert_eq!((shot.x, shot.y), (config.tank_width / 2.0, config.tank_height / 2.0));
+    }
+
+    #[test]
+    fn densest_school_beats_scattered_fish() {
+        let mut rng = seeded_rng();
+        let config = SimulationConfig::default();
+        let mut fish: Vec<Fish> = (0..5).map(|i| fish_at(&mut rng, 800.0 + i as f32 * 5.0, 200.0)).collect();
+        fish.push(fish_at(&mut rng, 100.0, 600.0));
+        fish.push(fish_at(&mut rng, 300.0, 400.0));
+
+        let shot = suggest(&fish, &config);
+        assert_eq!(shot.kind, ShotKind::School);
+        assert_eq!(shot.fish_ids.len(), 5);
+        assert!((shot.x - 810.0).abs() < 1e-3 && (shot.y - 200.0).abs() < 1e-3);
+        assert!(shot.radius >= FRAME_PADDING + 10.0);
+    }
+
+    #[test]
+    fn chase_outranks_courtship_and_schools() {
+        let mut rng = seeded_rng();
+        let config = SimulationConfig::default();
+        let mut fish: Vec<Fish> = (0..5).map(|i| fish_at(&mut rng, 800.0 + i as f32 * 5.0, 200.0)).collect();
+        let (a, b) = (fish[0].id, fish[1].id);
+        fish[0].behavior = BehaviorState::Courting;
+        fish[0].courting_partner = Some(b);
+        assert_eq!(suggest(&fish, &config).kind, ShotKind::Courtship);
+
+        let mut hunter = fish_at(&mut rng, 200.0, 300.0);
+        let prey = fish_at(&mut rng, 260.0, 300.0);
+        hunter.behavior = BehaviorState::Hunting;
+        hunter.hunting_target = Some(prey.id);
+        let ids = vec![hunter.id, prey.id];
+        fish.extend([hunter, prey]);
+
+        let shot = suggest(&fish, &config);
+        assert_eq!(shot.kind, ShotKind::Chase);
+        assert_eq!(shot.fish_ids, ids);
+        assert!((shot.x - 230.0).abs() < 1e-3);
+        assert!(!shot.fish_ids.contains(&a));
+    }
+
+    #[test]
+    fn hatchling_cluster_outranks_school() {
+        let mut rng = seeded_rng();
+        let config = SimulationConfig::default();
+        let mut fish: Vec<Fish> = (0..6).map(|i| fish_at(&mut rng, 800.0 + i as f32 * 5.0, 200.0)).collect();
+        for x in [300.0, 310.0] {
+            let mut fry = fish_at(&mut rng, x, 500.0);
+            fry.is_juvenile = true;
+            fish.push(fry);
+        }
+        assert_eq!(suggest(&fish, &config).kind, ShotKind::Hatchlings);
+    }
+}
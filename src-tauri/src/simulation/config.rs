@@ -1,5 +1,121 @@
+use crate::simulation::genome::{DistanceWeights, MutationRates, TraitLinkage};
+use crate::simulation::journal::JournalTrigger;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+/// What happens when a fish reaches the tank edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryMode {
+    Clamp,
+    Wrap, // toroidal: exit one edge, re-enter the opposite one
+}
+
+impl BoundaryMode {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "clamp" => Some(BoundaryMode::Clamp),
+            "wrap" => Some(BoundaryMode::Wrap),
+            _ => None,
+        }
+    }
+}
+
+/// Part of the day/night cycle when fish may spawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreedingSeason {
+    Always,
+    Day,      // full daylight
+    Night,    // full darkness; never arrives with the day/night cycle off
+    Twilight, // the dawn and dusk ramps
+}
+
+impl BreedingSeason {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "always" => Some(BreedingSeason::Always),
+            "day" => Some(BreedingSeason::Day),
+            "night" => Some(BreedingSeason::Night),
+            "twilight" => Some(BreedingSeason::Twilight),
+            _ => None,
+        }
+    }
+
+    /// Whether spawning is in season at this ambient light (see `SimulationConfig::light_level`)
+    pub fn allows(self, light_level: f32) -> bool {
+        match self {
+            BreedingSeason::Always => true,
+            BreedingSeason::Day => light_level >= 0.75,
+            BreedingSeason::Night => light_level <= 0.25,
+            BreedingSeason::Twilight => light_level > 0.25 && light_level < 0.75,
+        }
+    }
+}
+
+/// Outcome of a successful `SimulationConfig::set_value`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigUpdate {
+    Accepted,
+    Clamped, // value was out of range and stored at the nearest bound
+}
+
+fn number(value: &serde_json::Value) -> Result<f32, String> {
+    value.as_f64()
+        .map(|v| v as f32)
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| "Expected a number".to_string())
+}
+
+/// Bounds `margin` in from both ends of a tank axis, meeting in the middle
+/// when the axis is too short for the full margin
+fn inset(size: f32, margin: f32) -> (f32, f32) {
+    let size = size.max(0.0);
+    let m = margin.clamp(0.0, size / 2.0);
+    (m, size - m)
+}
+
+fn set_f32(field: &mut f32, value: &serde_json::Value, min: f32, max: f32) -> Result<ConfigUpdate, String> {
+    let v = number(value)?;
+    *field = v.clamp(min, max);
+    Ok(if *field == v { ConfigUpdate::Accepted } else { ConfigUpdate::Clamped })
+}
+
+fn set_u32(field: &mut u32, value: &serde_json::Value, min: u32, max: u32) -> Result<ConfigUpdate, String> {
+    let v = number(value)?.round();
+    *field = (v.clamp(min as f32, max as f32)) as u32;
+    Ok(if *field as f32 == v { ConfigUpdate::Accepted } else { ConfigUpdate::Clamped })
+}
+
+fn set_bool(field: &mut bool, value: &serde_json::Value) -> Result<ConfigUpdate, String> {
+    *field = value.as_bool().ok_or("Expected true or false")?;
+    Ok(ConfigUpdate::Accepted)
+}
+
+fn set_string(field: &mut String, value: &serde_json::Value) -> Result<ConfigUpdate, String> {
+    *field = value.as_str().ok_or("Expected a string")?.to_string();
+    Ok(ConfigUpdate::Accepted)
+}
+
+/// Which neighbors a fish schools with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchoolingMode {
+    Mixed,       // any neighbor, down-weighted by genetic distance
+    SpeciesOnly, // only neighbors within species_threshold
+}
+
+impl SchoolingMode {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "mixed" => Some(SchoolingMode::Mixed),
+            "species_only" => Some(SchoolingMode::SpeciesOnly),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimulationConfig {
     // Boids
@@ -9,35 +125,83 @@ pub struct SimulationConfig {
     pub separation_radius: f32,
     pub alignment_radius: f32,
     pub cohesion_radius: f32,
+    pub school_size_strength: f32, // how hard fish steer toward their ideal_school_size (0 ignores it)
+    pub schooling_style_strength: f32, // how far polarization shifts alignment vs cohesion (0 treats shoalers and schoolers alike)
+    pub species_cohesion_bonus: f32, // extra cohesion affinity between members of the same detected species
     pub base_max_speed: f32,
+    pub absolute_max_speed: f32, // hard cap on any fish's speed, whatever its genome
     pub max_force: f32,
     pub drag: f32,
     pub boundary_margin: f32,
     pub wander_strength: f32,
+    pub boundary_mode: BoundaryMode,
+    pub schooling_mode: SchoolingMode,
+    pub adaptive_grid_enabled: bool, // disable for runs that need a fixed grid layout; a manual cell size turns it off
+    pub adaptive_grid_interval: u32, // ticks between cell size re-evaluations
 
     // Ecosystem
     pub base_carrying_capacity: u32,
     pub hunger_rate: f32,
+    pub forage_hunger_threshold: f32, // hunger at which fish start seeking food
+    pub forage_stop_threshold: f32,   // hunger below which foraging fish go back to swimming
+    pub eat_hunger_threshold: f32,    // fish below this hunger ignore food they swim through
+    pub food_preference_enabled: bool, // herbivores favor flakes and carnivores live food
+    pub food_preference_bonus: f32, // extra nutrition fraction from a fish's preferred food
+    pub food_preference_penalty: f32, // nutrition fraction lost on a fish's disliked food
     pub food_decay_ticks: u32,
     pub fertility_scale: f32,
     pub reproduction_cooldown: u32,
-    pub mutation_rate_small: f32,
+    pub breeding_season: BreedingSeason, // phase of the day/night cycle when fish spawn
+    pub max_lifespan: u32, // ticks a fish with lifespan_factor 1.0 lives
+    pub senescence_onset: f32, // age fraction where speed, fertility and disease resistance start to decline
+    pub senescence_strength: f32, // fraction of each lost by the end of life
+    pub courtship_display_enabled: bool, // males must hold a display and females may reject them
+    pub courtship_display_radius: f32, // a displaying male that strays further than this fails
+    pub courtship_min_condition: f32, // females reject males whose mean health/energy is below this
+    pub courtship_choosiness: f32, // rejection chance for a male at the species threshold distance
+    pub mutation_rate_small: f32, // functional genes: behavior, lifecycle and body length
     pub mutation_rate_large: f32,
+    pub appearance_mutation_rate_small: f32, // cosmetic genes: color, fins, pattern
+    pub appearance_mutation_rate_large: f32,
+    pub trait_linkage: Vec<TraitLinkage>, // trait pairs that tend to come from the same parent
+    pub genetic_drift_rate: f32, // per-generation random walk on neutral traits, as a fraction of each trait's range
+    pub conservation_mutation_scale: f32, // mutation rate multiplier for protected lineages
     pub species_threshold: f32,
     pub species_min_members: u32,
+    pub speciation_appearance_weight: f32, // scales color/shape/pattern differences in genome distance
+    pub speciation_behavior_weight: f32,   // scales speed/aggression/schooling differences
     pub predation_size_ratio: f32,
+    pub predation_cooldown_ticks: u32, // satiation after a kill, shortened by remaining hunger
+    pub stomach_capacity: f32,         // prey body length a predator can hold per unit of its own body length
+    pub digestion_rate: f32,           // prey body length digested per tick
+    pub pack_radius: f32,              // same-species hunters on the same prey this close join the strike
+    pub pack_bonus_per_member: f32,    // strike odds added per extra pack member
+    pub pack_max_size: u32,            // hunters per strike, striker included; 1 = solitary
+    pub confusion_radius: f32,         // schoolmates this close to prey confuse the predator
+    pub confusion_max_reduction: f32,  // largest cut to a strike's odds from a dense school
+    pub confusion_half_saturation: f32, // proximity-weighted schoolmates giving half the max
     pub inbreeding_check_depth: u32,
+    pub min_viable_population: u32, // warn below this many fish (0 = off)
+    pub endangered_boost_enabled: bool,
+    pub endangered_fertility_multiplier: f32,
 
     // Water
     pub water_degradation_per_fish: f32,
     pub water_recovery_rate: f32,
     pub plant_recovery_bonus: f32,
+    pub water_resilience_enabled: bool, // bacterial bloom speeds recovery in critically bad water
+    pub water_resilience_threshold: f32, // quality below which the bloom kicks in
+    pub water_resilience_strength: f32, // extra recovery at zero quality, as a multiple of water_recovery_rate
 
     // Environment
     pub current_direction: f32,
     pub current_strength: f32,
     pub day_night_cycle: bool,
     pub day_night_speed: f32, // 0 = real-time clock, >0 = accelerated sim cycle
+    pub day_length_ticks: u32, // ticks per simulated 24h day at day_night_speed = 1
+    pub sunrise_hour: f32,
+    pub sunset_hour: f32,
+    pub twilight_hours: f32, // width of the dawn/dusk light ramp, centered on sunrise/sunset
     pub bubble_rate: f32,
     pub particle_density: f32,
     pub tank_width: f32,
@@ -51,11 +215,18 @@ pub struct SimulationConfig {
     // Persistence
     pub auto_save_interval: u32,
     pub snapshot_interval: u32,
+    pub snapshot_retention_ticks: u32, // snapshots newer than this keep full detail
+    pub snapshot_rollup_factor: u32,   // older snapshots keep 1 in N (1 = never roll up)
 
     // Ollama
     pub ollama_enabled: bool,
     pub ollama_url: String,
     pub ollama_model: String,
+    pub journal_triggers: Vec<JournalTrigger>, // conditions that prompt an immediate journal entry
+
+    // Webhook
+    pub webhook_url: String,         // empty disables forwarding
+    pub webhook_interval_ticks: u32, // minimum ticks between posts; events in between are batched
 
     // Audio
     pub master_volume: f32,
@@ -68,14 +239,26 @@ pub struct SimulationConfig {
     // Eggs & Juveniles
     pub egg_hatch_time: u32,
     pub juvenile_duration: u32,
+    pub spawn_protection_ticks: u32, // fresh hatchlings can't be targeted by predators for this long
+    pub hatch_shelter_radius: f32,   // hatchlings move to cover (or a parent) within this distance of the egg
+
+    // Parental care (scaled by the parents' parental_care trait)
+    pub parental_care_enabled: bool,
+    pub max_clutch_size: u32, // eggs per spawning for parents with no care; full care lays one
+    pub nest_guard_radius: f32, // eggs this close to a fully caring parent are safe from egg-eaters
+    pub escort_protection_ticks: u32, // extra hatchling protection while a fully caring parent lives
 
     // Environmental Events
     pub environmental_events_enabled: bool,
     pub event_frequency: f32,
+    pub max_concurrent_events: u32, // events that can overlap; 1 = one at a time
+    pub temperature_damage: f32, // health lost per tick per unit of temperature stress
 
     // Territory
     pub territory_enabled: bool,
     pub territory_claim_radius: f32,
+    pub territory_decay_rate: f32,     // claim strength lost per tick spent outside the territory
+    pub territory_abandon_health: f32, // holders below this health give up their territory
 
     // Disease
     pub disease_enabled: bool,
@@ -84,6 +267,60 @@ pub struct SimulationConfig {
     pub disease_duration: u32,
     pub disease_damage: f32,
     pub disease_spread_radius: f32,
+    pub immunity_gain: f32,  // share of the remaining gap to immunity_max closed by each recovery
+    pub immunity_max: f32,   // ceiling on acquired immunity
+    pub immunity_decay: f32, // acquired immunity lost per tick (0 = lifelong)
+    pub quarantine_pin_to_corner: bool, // steer quarantined fish into the bottom-left corner
+
+    // Crowding
+    pub crowding_enabled: bool,
+    pub crowding_radius: f32,
+    pub crowding_threshold: u32, // neighbors within radius before stress builds
+    pub crowding_stress_rate: f32, // stress per tick per neighbor over threshold
+    pub crowding_aggression_bonus: f32, // aggression added at full stress
+
+    // Fin-nipping
+    pub fin_nipping_enabled: bool,
+    pub fin_nip_aggression: f32, // both fish need at least this aggression to skirmish
+    pub fin_nip_radius: f32,
+    pub fin_nip_chance: f32, // per tick for each close, evenly matched pair
+    pub fin_nip_damage: f32, // health and energy each fish loses per skirmish
+    pub fin_nip_stress: f32, // stress each fish gains per skirmish
+
+    // Enrichment
+    pub enrichment_enabled: bool,
+    pub enrichment_radius: f32, // decorations within this distance count toward a fish's enrichment
+    pub enrichment_full_variety: u32, // distinct decoration kinds nearby for full enrichment
+    pub enrichment_interval: u32, // ticks between enrichment recomputes
+    pub boredom_health_drain: f32, // health lost per tick at full boredom
+    pub boredom_fertility_penalty: f32, // fraction of fertility lost at full boredom
+
+    // Shelter
+    pub shelter_enabled: bool,
+    pub shelter_radius: f32, // distance past a decoration's edge that still counts as cover
+    pub shelter_seek_strength: f32, // pull toward cover for fleeing, stressed or timid fish
+    pub shelter_timid_boldness: f32, // fish below this boldness seek cover even when calm
+    pub shelter_strike_reduction: f32, // cut to a strike's odds against sheltered prey
+    pub courtship_conspicuousness: f32, // courting prey: strike chance multiplier, and spotted from this much farther
+
+    // Algae
+    pub algae_enabled: bool,
+    pub algae_seed_chance: f32, // chance a decayed food particle seeds a patch
+    pub algae_growth_rate: f32, // size per tick in full light
+    pub algae_decay_rate: f32, // size lost per tick while too dark or too clean to grow
+    pub algae_min_light: f32, // no growth below this light level
+    pub algae_max_water_quality: f32, // clean water above this starves algae of nutrients
+    pub algae_water_degradation: f32, // water quality lost per tick per unit of patch size
+    pub max_algae_patches: u32,
+
+    // Carcasses
+    pub carcass_decay_enabled: bool, // dead fish leave a body that rots instead of vanishing
+    pub carcass_decay_ticks: u32,
+    pub carcass_water_degradation: f32, // water quality lost per tick per unit of body length
+
+    // Observation camera
+    pub camera_cluster_radius: f32, // fish this close together count as one school or brood
+    pub camera_min_school: u32,     // smallest group worth pointing the camera at
 }
 
 impl Default for SimulationConfig {
@@ -95,32 +332,80 @@ impl Default for SimulationConfig {
             separation_radius: 25.0,
             alignment_radius: 50.0,
             cohesion_radius: 75.0,
+            school_size_strength: 0.5,
+            schooling_style_strength: 0.8,
+            species_cohesion_bonus: 0.5,
             base_max_speed: 3.0,
+            absolute_max_speed: 5.0,
             max_force: 0.1,
             drag: 0.98,
             boundary_margin: 60.0,
             wander_strength: 0.3,
+            boundary_mode: BoundaryMode::Clamp,
+            schooling_mode: SchoolingMode::Mixed,
+            adaptive_grid_enabled: true,
+            adaptive_grid_interval: 150,
 
             base_carrying_capacity: 100,
             hunger_rate: 0.0005,
+            forage_hunger_threshold: 0.6,
+            forage_stop_threshold: 0.3,
+            eat_hunger_threshold: 0.2,
+            food_preference_enabled: true,
+            food_preference_bonus: 0.3,
+            food_preference_penalty: 0.3,
             food_decay_ticks: 300,
             fertility_scale: 0.05,
             reproduction_cooldown: 300,
+            breeding_season: BreedingSeason::Always,
+            max_lifespan: 20_000,
+            senescence_onset: 0.7,
+            senescence_strength: 0.5,
+            courtship_display_enabled: false,
+            courtship_display_radius: 60.0,
+            courtship_min_condition: 0.4,
+            courtship_choosiness: 0.5,
             mutation_rate_small: 0.10,
             mutation_rate_large: 0.02,
+            appearance_mutation_rate_small: 0.10,
+            appearance_mutation_rate_large: 0.02,
+            trait_linkage: Vec::new(),
+            genetic_drift_rate: 0.01,
+            conservation_mutation_scale: 0.25,
             species_threshold: 2.5,
             species_min_members: 3,
+            speciation_appearance_weight: 1.0,
+            speciation_behavior_weight: 1.0,
             predation_size_ratio: 0.6,
+            predation_cooldown_ticks: 300,
+            stomach_capacity: 1.5,
+            digestion_rate: 0.002,
+            confusion_radius: 40.0,
+            confusion_max_reduction: 0.85,
+            confusion_half_saturation: 1.5,
+            pack_radius: 50.0,
+            pack_bonus_per_member: 0.5,
+            pack_max_size: 4,
             inbreeding_check_depth: 2,
+            min_viable_population: 6,
+            endangered_boost_enabled: true,
+            endangered_fertility_multiplier: 1.5,
 
             water_degradation_per_fish: 0.00001,
             water_recovery_rate: 0.00005,
             plant_recovery_bonus: 0.00002,
+            water_resilience_enabled: true,
+            water_resilience_threshold: 0.2,
+            water_resilience_strength: 4.0,
 
             current_direction: 0.0,
             current_strength: 0.0,
             day_night_cycle: true,
             day_night_speed: 1.0,
+            day_length_ticks: 43_200, // 24 real minutes at 30Hz
+            sunrise_hour: 6.0,
+            sunset_hour: 20.0,
+            twilight_hours: 2.0,
             bubble_rate: 1.0,
             particle_density: 1.0,
             tank_width: 1200.0,
@@ -132,10 +417,16 @@ impl Default for SimulationConfig {
 
             auto_save_interval: 900,
             snapshot_interval: 300,
+            snapshot_retention_ticks: 10_000,
+            snapshot_rollup_factor: 10,
 
             ollama_enabled: true,
             ollama_url: "http://localhost:11434".to_string(),
             ollama_model: "llama3.2".to_string(),
+            journal_triggers: Vec::new(),
+
+            webhook_url: String::new(),
+            webhook_interval_ticks: 150, // 5 seconds at 30Hz
 
             master_volume: 0.3,
             ambient_enabled: true,
@@ -145,12 +436,23 @@ impl Default for SimulationConfig {
 
             egg_hatch_time: 180,      // 6 seconds at 30Hz
             juvenile_duration: 300,   // 10 seconds at 30Hz
+            spawn_protection_ticks: 150, // 5 seconds at 30Hz
+            hatch_shelter_radius: 150.0,
+
+            parental_care_enabled: true,
+            max_clutch_size: 3,
+            nest_guard_radius: 60.0,
+            escort_protection_ticks: 300, // 10 seconds at 30Hz
 
             environmental_events_enabled: true,
             event_frequency: 1.0,
+            max_concurrent_events: 1,
+            temperature_damage: 0.0005,
 
             territory_enabled: true,
             territory_claim_radius: 60.0,
+            territory_decay_rate: 1.0 / 600.0,
+            territory_abandon_health: 0.3,
 
             disease_enabled: false,
             disease_infection_chance: 0.3,
@@ -158,6 +460,357 @@ impl Default for SimulationConfig {
             disease_duration: 600,
             disease_damage: 0.0005,
             disease_spread_radius: 40.0,
+            immunity_gain: 0.5,
+            immunity_max: 0.9,
+            immunity_decay: 0.0,
+            quarantine_pin_to_corner: true,
+
+            crowding_enabled: true,
+            crowding_radius: 40.0,
+            crowding_threshold: 6,
+            crowding_stress_rate: 0.002,
+            crowding_aggression_bonus: 0.3,
+            fin_nipping_enabled: true,
+            fin_nip_aggression: 0.7,
+            fin_nip_radius: 15.0,
+            fin_nip_chance: 0.01,
+            fin_nip_damage: 0.03,
+            fin_nip_stress: 0.1,
+            enrichment_enabled: true,
+            enrichment_radius: 200.0,
+            enrichment_full_variety: 3,
+            enrichment_interval: 60,
+            boredom_health_drain: 0.00001,
+            boredom_fertility_penalty: 0.25,
+
+            shelter_enabled: true,
+            shelter_radius: 20.0,
+            shelter_seek_strength: 0.5,
+            shelter_timid_boldness: 0.25,
+            shelter_strike_reduction: 0.6,
+            courtship_conspicuousness: 1.5,
+
+            algae_enabled: true,
+            algae_seed_chance: 0.15,
+            algae_growth_rate: 0.0004,
+            algae_decay_rate: 0.0002,
+            algae_min_light: 0.3,
+            algae_max_water_quality: 0.9,
+            algae_water_degradation: 0.000002,
+            max_algae_patches: 20,
+
+            carcass_decay_enabled: false,
+            carcass_decay_ticks: 900,
+            carcass_water_degradation: 0.00005,
+
+            camera_cluster_radius: 60.0,
+            camera_min_school: 4,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Appearance/behavior weighting used when comparing genomes for speciation
+    pub fn appearance_mutation(&self) -> MutationRates {
+        MutationRates { large: self.appearance_mutation_rate_large, small: self.appearance_mutation_rate_small }
+    }
+
+    pub fn functional_mutation(&self) -> MutationRates {
+        MutationRates { large: self.mutation_rate_large, small: self.mutation_rate_small }
+    }
+
+    pub fn distance_weights(&self) -> DistanceWeights {
+        DistanceWeights {
+            appearance: self.speciation_appearance_weight,
+            behavior: self.speciation_behavior_weight,
+        }
+    }
+
+    /// Fields whose value differs from `SimulationConfig::default()`, keyed by
+    /// serialized name, each as `{ "current": .., "default": .. }`
+    pub fn diff_from_default(&self) -> serde_json::Map<String, serde_json::Value> {
+        let (Ok(serde_json::Value::Object(current)), Ok(serde_json::Value::Object(defaults))) =
+            (serde_json::to_value(self), serde_json::to_value(Self::default()))
+        else {
+            return serde_json::Map::new();
+        };
+        current
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let default = defaults.get(&key).cloned().unwrap_or(serde_json::Value::Null);
+                (value != default).then(|| (key, serde_json::json!({ "current": value, "default": default })))
+            })
+            .collect()
+    }
+
+    /// Set the water current from a strength and a direction in degrees
+    /// (0 flows right). Strength is clamped to the same bounds as `set_value`;
+    /// non-finite input is rejected without touching the config.
+    pub fn set_current(&mut self, strength: f32, direction_degrees: f32) -> Result<ConfigUpdate, String> {
+        if !strength.is_finite() || !direction_degrees.is_finite() {
+            return Err("Current strength and direction must be finite numbers".to_string());
+        }
+        let direction = direction_degrees.to_radians().rem_euclid(std::f32::consts::TAU);
+        let update = set_f32(&mut self.current_strength, &serde_json::json!(strength), 0.0, 5.0)?;
+        self.current_direction = direction;
+        Ok(update)
+    }
+
+    /// Set a single config field by its serialized name (used by `update_config`).
+    /// Out-of-range numbers are clamped into bounds; unknown keys and values of
+    /// the wrong type are rejected without touching the config. Setting one
+    /// forage threshold past the other drags the other along with it, so fish
+    /// never stop foraging above the hunger that started it.
+    pub fn set_value(&mut self, key: &str, value: serde_json::Value) -> Result<ConfigUpdate, String> {
+        let v = &value;
+        match key {
+            "separation_weight" => set_f32(&mut self.separation_weight, v, 0.0, 10.0),
+            "alignment_weight" => set_f32(&mut self.alignment_weight, v, 0.0, 10.0),
+            "cohesion_weight" => set_f32(&mut self.cohesion_weight, v, 0.0, 10.0),
+            "drag" => set_f32(&mut self.drag, v, 0.0, 1.0),
+            "absolute_max_speed" => set_f32(&mut self.absolute_max_speed, v, 0.5, 20.0),
+            "adaptive_grid_enabled" => set_bool(&mut self.adaptive_grid_enabled, v),
+            "adaptive_grid_interval" => set_u32(&mut self.adaptive_grid_interval, v, 1, 100_000),
+            "school_size_strength" => set_f32(&mut self.school_size_strength, v, 0.0, 5.0),
+            "schooling_style_strength" => set_f32(&mut self.schooling_style_strength, v, 0.0, 1.0),
+            "species_cohesion_bonus" => set_f32(&mut self.species_cohesion_bonus, v, 0.0, 2.0),
+            "wander_strength" => set_f32(&mut self.wander_strength, v, 0.0, 5.0),
+            "boundary_mode" => {
+                self.boundary_mode = v.as_str().and_then(BoundaryMode::from_str)
+                    .ok_or("Expected \"clamp\" or \"wrap\"")?;
+                Ok(ConfigUpdate::Accepted)
+            }
+            "schooling_mode" => {
+                self.schooling_mode = v.as_str().and_then(SchoolingMode::from_str)
+                    .ok_or("Expected \"mixed\" or \"species_only\"")?;
+                Ok(ConfigUpdate::Accepted)
+            }
+            "breeding_season" => {
+                self.breeding_season = v.as_str().and_then(BreedingSeason::from_str)
+                    .ok_or("Expected \"always\", \"day\", \"night\" or \"twilight\"")?;
+                Ok(ConfigUpdate::Accepted)
+            }
+            "hunger_rate" => set_f32(&mut self.hunger_rate, v, 0.0, 0.05),
+            "forage_hunger_threshold" => {
+                let update = set_f32(&mut self.forage_hunger_threshold, v, 0.0, 1.0)?;
+                let moved = self.forage_stop_threshold > self.forage_hunger_threshold;
+                if moved {
+                    self.forage_stop_threshold = self.forage_hunger_threshold;
+                }
+                Ok(if moved { ConfigUpdate::Clamped } else { update })
+            }
+            "forage_stop_threshold" => {
+                let update = set_f32(&mut self.forage_stop_threshold, v, 0.0, 1.0)?;
+                let moved = self.forage_hunger_threshold < self.forage_stop_threshold;
+                if moved {
+                    self.forage_hunger_threshold = self.forage_stop_threshold;
+                }
+                Ok(if moved { ConfigUpdate::Clamped } else { update })
+            }
+            "max_lifespan" => set_u32(&mut self.max_lifespan, v, 1_000, 1_000_000),
+            "senescence_onset" => set_f32(&mut self.senescence_onset, v, 0.0, 0.99),
+            "senescence_strength" => set_f32(&mut self.senescence_strength, v, 0.0, 1.0),
+            "eat_hunger_threshold" => set_f32(&mut self.eat_hunger_threshold, v, 0.0, 1.0),
+            "food_preference_enabled" => set_bool(&mut self.food_preference_enabled, v),
+            "food_preference_bonus" => set_f32(&mut self.food_preference_bonus, v, 0.0, 2.0),
+            "food_preference_penalty" => set_f32(&mut self.food_preference_penalty, v, 0.0, 1.0),
+            "courtship_display_enabled" => set_bool(&mut self.courtship_display_enabled, v),
+            "courtship_display_radius" => set_f32(&mut self.courtship_display_radius, v, 10.0, 300.0),
+            "courtship_min_condition" => set_f32(&mut self.courtship_min_condition, v, 0.0, 1.0),
+            "courtship_choosiness" => set_f32(&mut self.courtship_choosiness, v, 0.0, 1.0),
+            "mutation_rate_small" => set_f32(&mut self.mutation_rate_small, v, 0.0, 1.0),
+            "mutation_rate_large" => set_f32(&mut self.mutation_rate_large, v, 0.0, 1.0),
+            "appearance_mutation_rate_small" => set_f32(&mut self.appearance_mutation_rate_small, v, 0.0, 1.0),
+            "appearance_mutation_rate_large" => set_f32(&mut self.appearance_mutation_rate_large, v, 0.0, 1.0),
+            "genetic_drift_rate" => set_f32(&mut self.genetic_drift_rate, v, 0.0, 0.2),
+            "conservation_mutation_scale" => set_f32(&mut self.conservation_mutation_scale, v, 0.0, 1.0),
+            "species_threshold" => set_f32(&mut self.species_threshold, v, 0.1, 20.0),
+            "speciation_appearance_weight" => set_f32(&mut self.speciation_appearance_weight, v, 0.0, 10.0),
+            "speciation_behavior_weight" => set_f32(&mut self.speciation_behavior_weight, v, 0.0, 10.0),
+            "predation_cooldown_ticks" => set_u32(&mut self.predation_cooldown_ticks, v, 0, 100_000),
+            "stomach_capacity" => set_f32(&mut self.stomach_capacity, v, 0.1, 10.0),
+            "digestion_rate" => set_f32(&mut self.digestion_rate, v, 0.0, 0.1),
+            "spawn_protection_ticks" => set_u32(&mut self.spawn_protection_ticks, v, 0, 100_000),
+            "hatch_shelter_radius" => set_f32(&mut self.hatch_shelter_radius, v, 0.0, 1000.0),
+            "parental_care_enabled" => set_bool(&mut self.parental_care_enabled, v),
+            "max_clutch_size" => set_u32(&mut self.max_clutch_size, v, 1, 10),
+            "nest_guard_radius" => set_f32(&mut self.nest_guard_radius, v, 0.0, 500.0),
+            "escort_protection_ticks" => set_u32(&mut self.escort_protection_ticks, v, 0, 100_000),
+            "confusion_radius" => set_f32(&mut self.confusion_radius, v, 0.0, 200.0),
+            "confusion_max_reduction" => set_f32(&mut self.confusion_max_reduction, v, 0.0, 1.0),
+            "confusion_half_saturation" => set_f32(&mut self.confusion_half_saturation, v, 0.1, 50.0),
+            "pack_radius" => set_f32(&mut self.pack_radius, v, 0.0, 500.0),
+            "pack_bonus_per_member" => set_f32(&mut self.pack_bonus_per_member, v, 0.0, 5.0),
+            "pack_max_size" => set_u32(&mut self.pack_max_size, v, 1, 20),
+            "inbreeding_check_depth" => set_u32(&mut self.inbreeding_check_depth, v, 0, 6),
+            "day_night_cycle" => set_bool(&mut self.day_night_cycle, v),
+            "day_night_speed" => set_f32(&mut self.day_night_speed, v, 0.0, 100.0),
+            "day_length_ticks" => set_u32(&mut self.day_length_ticks, v, 1, 10_000_000),
+            // Hours and angles wrap around rather than clamp
+            "sunrise_hour" => {
+                self.sunrise_hour = number(v)?.rem_euclid(24.0);
+                Ok(ConfigUpdate::Accepted)
+            }
+            "sunset_hour" => {
+                self.sunset_hour = number(v)?.rem_euclid(24.0);
+                Ok(ConfigUpdate::Accepted)
+            }
+            "twilight_hours" => set_f32(&mut self.twilight_hours, v, 0.0, 12.0),
+            "bubble_rate" => set_f32(&mut self.bubble_rate, v, 0.0, 10.0),
+            "current_strength" => set_f32(&mut self.current_strength, v, 0.0, 5.0),
+            "current_direction" => {
+                self.current_direction = number(v)?.rem_euclid(std::f32::consts::TAU);
+                Ok(ConfigUpdate::Accepted)
+            }
+            "auto_feed_enabled" => set_bool(&mut self.auto_feed_enabled, v),
+            "auto_feed_interval" => set_u32(&mut self.auto_feed_interval, v, 1, 100_000),
+            "auto_feed_amount" => set_u32(&mut self.auto_feed_amount, v, 0, 100),
+            "snapshot_retention_ticks" => set_u32(&mut self.snapshot_retention_ticks, v, 0, 10_000_000),
+            "snapshot_rollup_factor" => set_u32(&mut self.snapshot_rollup_factor, v, 1, 1000),
+            "ollama_enabled" => set_bool(&mut self.ollama_enabled, v),
+            "ollama_url" => {
+                let url = v.as_str().ok_or("Expected a string")?;
+                // Basic URL validation: must start with http:// or https://
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err("URL must start with http:// or https://".into());
+                }
+                self.ollama_url = url.to_string();
+                Ok(ConfigUpdate::Accepted)
+            }
+            "ollama_model" => set_string(&mut self.ollama_model, v),
+            "webhook_url" => {
+                let url = v.as_str().ok_or("Expected a string")?;
+                if !(url.is_empty() || url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err("URL must be empty or start with http:// or https://".into());
+                }
+                self.webhook_url = url.to_string();
+                Ok(ConfigUpdate::Accepted)
+            }
+            "webhook_interval_ticks" => set_u32(&mut self.webhook_interval_ticks, v, 1, 100_000),
+            "trait_linkage" => {
+                let links: Vec<TraitLinkage> = serde_json::from_value(value).map_err(|e| e.to_string())?;
+                self.trait_linkage = links.into_iter().map(TraitLinkage::validated).collect::<Result<_, _>>()?;
+                Ok(ConfigUpdate::Accepted)
+            }
+            "journal_triggers" => {
+                self.journal_triggers = serde_json::from_value(value).map_err(|e| e.to_string())?;
+                Ok(ConfigUpdate::Accepted)
+            }
+            "master_volume" => set_f32(&mut self.master_volume, v, 0.0, 1.0),
+            "ambient_enabled" => set_bool(&mut self.ambient_enabled, v),
+            "event_sounds_enabled" => set_bool(&mut self.event_sounds_enabled, v),
+            "theme" => set_string(&mut self.theme, v),
+            "environmental_events_enabled" => set_bool(&mut self.environmental_events_enabled, v),
+            "event_frequency" => set_f32(&mut self.event_frequency, v, 0.0, 10.0),
+            "max_concurrent_events" => set_u32(&mut self.max_concurrent_events, v, 1, 5),
+            "temperature_damage" => set_f32(&mut self.temperature_damage, v, 0.0, 0.01),
+            "territory_enabled" => set_bool(&mut self.territory_enabled, v),
+            "territory_claim_radius" => set_f32(&mut self.territory_claim_radius, v, 10.0, 300.0),
+            "territory_decay_rate" => set_f32(&mut self.territory_decay_rate, v, 0.0, 1.0),
+            "territory_abandon_health" => set_f32(&mut self.territory_abandon_health, v, 0.0, 1.0),
+            "disease_enabled" => set_bool(&mut self.disease_enabled, v),
+            "disease_infection_chance" => set_f32(&mut self.disease_infection_chance, v, 0.0, 1.0),
+            "disease_spontaneous_chance" => set_f32(&mut self.disease_spontaneous_chance, v, 0.0, 0.01),
+            "disease_duration" => set_u32(&mut self.disease_duration, v, 1, 100_000),
+            "disease_damage" => set_f32(&mut self.disease_damage, v, 0.0, 0.1),
+            "disease_spread_radius" => set_f32(&mut self.disease_spread_radius, v, 0.0, 500.0),
+            "immunity_gain" => set_f32(&mut self.immunity_gain, v, 0.0, 1.0),
+            "immunity_max" => set_f32(&mut self.immunity_max, v, 0.0, 1.0),
+            "immunity_decay" => set_f32(&mut self.immunity_decay, v, 0.0, 0.01),
+            "quarantine_pin_to_corner" => set_bool(&mut self.quarantine_pin_to_corner, v),
+            "min_viable_population" => set_u32(&mut self.min_viable_population, v, 0, 1000),
+            "endangered_boost_enabled" => set_bool(&mut self.endangered_boost_enabled, v),
+            "endangered_fertility_multiplier" => set_f32(&mut self.endangered_fertility_multiplier, v, 1.0, 10.0),
+            "water_resilience_enabled" => set_bool(&mut self.water_resilience_enabled, v),
+            "water_resilience_threshold" => set_f32(&mut self.water_resilience_threshold, v, 0.0, 1.0),
+            "water_resilience_strength" => set_f32(&mut self.water_resilience_strength, v, 0.0, 20.0),
+            "crowding_enabled" => set_bool(&mut self.crowding_enabled, v),
+            "crowding_radius" => set_f32(&mut self.crowding_radius, v, 1.0, 500.0),
+            "crowding_threshold" => set_u32(&mut self.crowding_threshold, v, 0, 100),
+            "crowding_stress_rate" => set_f32(&mut self.crowding_stress_rate, v, 0.0, 1.0),
+            "crowding_aggression_bonus" => set_f32(&mut self.crowding_aggression_bonus, v, 0.0, 1.0),
+            "fin_nipping_enabled" => set_bool(&mut self.fin_nipping_enabled, v),
+            "fin_nip_aggression" => set_f32(&mut self.fin_nip_aggression, v, 0.0, 1.0),
+            "fin_nip_radius" => set_f32(&mut self.fin_nip_radius, v, 1.0, 200.0),
+            "fin_nip_chance" => set_f32(&mut self.fin_nip_chance, v, 0.0, 1.0),
+            "fin_nip_damage" => set_f32(&mut self.fin_nip_damage, v, 0.0, 0.5),
+            "fin_nip_stress" => set_f32(&mut self.fin_nip_stress, v, 0.0, 1.0),
+            "enrichment_enabled" => set_bool(&mut self.enrichment_enabled, v),
+            "enrichment_radius" => set_f32(&mut self.enrichment_radius, v, 10.0, 1000.0),
+            "enrichment_full_variety" => set_u32(&mut self.enrichment_full_variety, v, 1, 4),
+            "enrichment_interval" => set_u32(&mut self.enrichment_interval, v, 1, 1000),
+            "boredom_health_drain" => set_f32(&mut self.boredom_health_drain, v, 0.0, 0.001),
+            "boredom_fertility_penalty" => set_f32(&mut self.boredom_fertility_penalty, v, 0.0, 1.0),
+            "shelter_enabled" => set_bool(&mut self.shelter_enabled, v),
+            "shelter_radius" => set_f32(&mut self.shelter_radius, v, 0.0, 200.0),
+            "shelter_seek_strength" => set_f32(&mut self.shelter_seek_strength, v, 0.0, 5.0),
+            "shelter_timid_boldness" => set_f32(&mut self.shelter_timid_boldness, v, 0.0, 1.0),
+            "shelter_strike_reduction" => set_f32(&mut self.shelter_strike_reduction, v, 0.0, 1.0),
+            "courtship_conspicuousness" => set_f32(&mut self.courtship_conspicuousness, v, 1.0, 5.0),
+            "algae_enabled" => set_bool(&mut self.algae_enabled, v),
+            "algae_seed_chance" => set_f32(&mut self.algae_seed_chance, v, 0.0, 1.0),
+            "algae_growth_rate" => set_f32(&mut self.algae_growth_rate, v, 0.0, 0.1),
+            "algae_decay_rate" => set_f32(&mut self.algae_decay_rate, v, 0.0, 0.1),
+            "carcass_decay_enabled" => set_bool(&mut self.carcass_decay_enabled, v),
+            "carcass_decay_ticks" => set_u32(&mut self.carcass_decay_ticks, v, 1, 100_000),
+            "carcass_water_degradation" => set_f32(&mut self.carcass_water_degradation, v, 0.0, 0.01),
+            "camera_cluster_radius" => set_f32(&mut self.camera_cluster_radius, v, 10.0, 400.0),
+            "camera_min_school" => set_u32(&mut self.camera_min_school, v, 2, 100),
+            _ => Err(format!("Unknown config key: {}", key)),
+        }
+    }
+
+    /// Eggs laid per spawning by a pair with this mean parental care: up to
+    /// `max_clutch_size` with no care, down to one with full care
+    pub fn clutch_size(&self, care: f32) -> u32 {
+        if !self.parental_care_enabled {
+            return 1;
+        }
+        let extra = self.max_clutch_size.saturating_sub(1) as f32 * (1.0 - care.clamp(0.0, 1.0));
+        1 + extra.round() as u32
+    }
+
+    /// Clamp a point into the tank, `margin` clear of the walls where the tank
+    /// is wide enough; narrower tanks shrink the margin rather than push the
+    /// point outside
+    pub fn clamp_to_tank(&self, x: f32, y: f32, margin: f32) -> (f32, f32) {
+        let axis = |v: f32, size: f32| {
+            let (lo, hi) = inset(size, margin);
+            v.clamp(lo, hi)
+        };
+        (axis(x, self.tank_width), axis(y, self.tank_height))
+    }
+
+    /// Random spawn x inside the tank, with the same margin rule as `clamp_to_tank`
+    pub fn random_x(&self, rng: &mut impl Rng, margin: f32) -> f32 {
+        let (lo, hi) = inset(self.tank_width, margin);
+        rng.gen_range(lo..=hi)
+    }
+
+    /// Random spawn y inside the tank, with the same margin rule as `clamp_to_tank`
+    pub fn random_y(&self, rng: &mut impl Rng, margin: f32) -> f32 {
+        let (lo, hi) = inset(self.tank_height, margin);
+        rng.gen_range(lo..=hi)
+    }
+
+    /// Ambient light (0 = night, 1 = day) for an hour of the day, easing through dawn and dusk
+    pub fn light_level(&self, time_of_day: f32) -> f32 {
+        if !self.day_night_cycle {
+            return 1.0;
+        }
+        let half = (self.twilight_hours / 2.0).max(0.01);
+        let smoothstep = |t: f32| {
+            let t = t.clamp(0.0, 1.0);
+            t * t * (3.0 - 2.0 * t)
+        };
+        // Hours since dawn and dusk ramps began, wrapping past midnight
+        let since_dawn = (time_of_day - (self.sunrise_hour - half)).rem_euclid(24.0);
+        let since_dusk = (time_of_day - (self.sunset_hour - half)).rem_euclid(24.0);
+        let day_span = (self.sunset_hour - self.sunrise_hour).rem_euclid(24.0);
+        if since_dawn < day_span {
+            smoothstep(since_dawn / (2.0 * half))
+        } else {
+            1.0 - smoothstep(since_dusk / (2.0 * half))
         }
     }
 }
@@ -165,6 +818,8 @@ impl Default for SimulationConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn default_config_sane_values() {
@@ -214,6 +869,20 @@ mod tests {
         assert_eq!(c1.theme, c2.theme);
     }
 
+    #[test]
+    fn diff_from_default_lists_only_changed_fields() {
+        let mut c = SimulationConfig::default();
+        assert!(c.diff_from_default().is_empty());
+
+        c.base_max_speed += 1.0;
+        c.ollama_model = "other".to_string();
+        let diff = c.diff_from_default();
+        assert_eq!(diff.len(), 2);
+        assert_eq!(diff["ollama_model"]["current"], "other");
+        assert_eq!(diff["ollama_model"]["default"], SimulationConfig::default().ollama_model);
+        assert!(diff.contains_key("base_max_speed"));
+    }
+
     #[test]
     fn config_serialization_roundtrip() {
         let c = SimulationConfig::default();
@@ -222,5 +891,180 @@ mod tests {
         assert_eq!(c.base_max_speed, c2.base_max_speed);
         assert_eq!(c.base_carrying_capacity, c2.base_carrying_capacity);
         assert_eq!(c.ollama_url, c2.ollama_url);
+        assert_eq!(c.boundary_mode, c2.boundary_mode);
+    }
+
+    #[test]
+    fn boundary_mode_parses_serialized_form() {
+        // update_config accepts the same strings the config serializes to
+        for mode in [BoundaryMode::Clamp, BoundaryMode::Wrap] {
+            let s = serde_json::to_value(mode).unwrap();
+            assert_eq!(BoundaryMode::from_str(s.as_str().unwrap()), Some(mode));
+        }
+        assert_eq!(BoundaryMode::from_str("bounce"), None);
+    }
+
+    #[test]
+    fn light_level_day_night_and_twilight() {
+        let c = SimulationConfig::default();
+        assert_eq!(c.light_level(12.0), 1.0);
+        assert_eq!(c.light_level(2.0), 0.0);
+        assert_eq!(c.light_level(23.5), 0.0);
+        assert!((c.light_level(c.sunrise_hour) - 0.5).abs() < 1e-4);
+        assert!((c.light_level(c.sunset_hour) - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn light_level_ramps_smoothly() {
+        let c = SimulationConfig::default();
+        // Dawn brightens monotonically, dusk darkens monotonically
+        let mut prev = c.light_level(4.5);
+        for i in 1..=30 {
+            let l = c.light_level(4.5 + i as f32 * 0.1);
+            assert!(l >= prev);
+            prev = l;
+        }
+        let mut prev = c.light_level(18.5);
+        for i in 1..=30 {
+            let l = c.light_level(18.5 + i as f32 * 0.1);
+            assert!(l <= prev);
+            prev = l;
+        }
+    }
+
+    #[test]
+    fn light_level_constant_without_cycle() {
+        let c = SimulationConfig { day_night_cycle: false, ..SimulationConfig::default() };
+        assert_eq!(c.light_level(0.0), 1.0);
+    }
+
+    #[test]
+    fn clamp_to_tank_keeps_points_inside_tiny_tanks() {
+        let mut c = SimulationConfig::default();
+        assert_eq!(c.clamp_to_tank(-50.0, 5000.0, 10.0), (10.0, c.tank_height - 10.0));
+        c.tank_width = 15.0;
+        c.tank_height = 0.0;
+        assert_eq!(c.clamp_to_tank(-50.0, 30.0, 10.0), (7.5, 0.0), "Margin shrinks to the middle");
+        assert_eq!(c.clamp_to_tank(12.0, -3.0, 2.0), (12.0, 0.0));
+    }
+
+    #[test]
+    fn random_spawns_land_inside_the_tank() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut c = SimulationConfig::default();
+        for _ in 0..50 {
+            let x = c.random_x(&mut rng, 50.0);
+            assert!((50.0..=c.tank_width - 50.0).contains(&x));
+        }
+        c.tank_width = 30.0;
+        c.tank_height = 0.0;
+        for _ in 0..50 {
+            assert!((0.0..=30.0).contains(&c.random_x(&mut rng, 50.0)));
+        }
+        assert_eq!(c.random_y(&mut rng, 80.0), 0.0, "A flat tank has one place to spawn");
+    }
+
+    // --- set_value validation ---
+
+    #[test]
+    fn set_value_accepts_in_range() {
+        let mut c = SimulationConfig::default();
+        assert_eq!(c.set_value("drag", serde_json::json!(0.9)), Ok(ConfigUpdate::Accepted));
+        assert!((c.drag - 0.9).abs() < 1e-6);
+        assert_eq!(c.set_value("auto_feed_interval", serde_json::json!(300)), Ok(ConfigUpdate::Accepted));
+        assert_eq!(c.auto_feed_interval, 300);
+    }
+
+    #[test]
+    fn set_value_clamps_out_of_range() {
+        let mut c = SimulationConfig::default();
+        assert_eq!(c.set_value("drag", serde_json::json!(-5.0)), Ok(ConfigUpdate::Clamped));
+        assert_eq!(c.drag, 0.0);
+        assert_eq!(c.set_value("hunger_rate", serde_json::json!(1000.0)), Ok(ConfigUpdate::Clamped));
+        assert_eq!(c.hunger_rate, 0.05);
+        assert_eq!(c.set_value("separation_weight", serde_json::json!(-1.0)), Ok(ConfigUpdate::Clamped));
+        assert_eq!(c.separation_weight, 0.0);
+        assert_eq!(c.set_value("inbreeding_check_depth", serde_json::json!(40)), Ok(ConfigUpdate::Clamped));
+        assert_eq!(c.inbreeding_check_depth, 6);
+    }
+
+    #[test]
+    fn set_value_rejects_bad_input_unchanged() {
+        let mut c = SimulationConfig::default();
+        assert!(c.set_value("no_such_key", serde_json::json!(1.0)).is_err());
+        assert!(c.set_value("hunger_rate", serde_json::json!("fast")).is_err());
+        assert!(c.set_value("disease_enabled", serde_json::json!(1)).is_err());
+        assert!(c.set_value("ollama_url", serde_json::json!("ftp://x")).is_err());
+        let d = SimulationConfig::default();
+        assert_eq!(c.hunger_rate, d.hunger_rate);
+        assert_eq!(c.disease_enabled, d.disease_enabled);
+        assert_eq!(c.ollama_url, d.ollama_url);
+    }
+
+    #[test]
+    fn forage_thresholds_never_cross() {
+        let mut c = SimulationConfig::default();
+        assert_eq!(c.set_value("forage_stop_threshold", serde_json::json!(0.5)), Ok(ConfigUpdate::Accepted));
+        assert_eq!(c.set_value("forage_stop_threshold", serde_json::json!(0.8)), Ok(ConfigUpdate::Clamped));
+        assert_eq!((c.forage_hunger_threshold, c.forage_stop_threshold), (0.8, 0.8), "Start threshold raised to match");
+        assert_eq!(c.set_value("forage_hunger_threshold", serde_json::json!(0.2)), Ok(ConfigUpdate::Clamped));
+        assert_eq!((c.forage_hunger_threshold, c.forage_stop_threshold), (0.2, 0.2), "Stop threshold lowered to match");
+
+        // Applying a consistent pair in key order lands on exactly that pair
+        let mut c = SimulationConfig::default();
+        c.set_value("forage_hunger_threshold", serde_json::json!(0.25)).unwrap();
+        c.set_value("forage_stop_threshold", serde_json::json!(0.1)).unwrap();
+        assert_eq!((c.forage_hunger_threshold, c.forage_stop_threshold), (0.25, 0.1));
+    }
+
+    #[test]
+    fn webhook_url_accepts_blank_or_http() {
+        let mut c = SimulationConfig::default();
+        assert!(c.webhook_url.is_empty());
+        assert!(c.set_value("webhook_url", serde_json::json!("https://example.com/hook")).is_ok());
+        assert!(c.set_value("webhook_url", serde_json::json!("discord.com/hook")).is_err());
+        assert_eq!(c.webhook_url, "https://example.com/hook");
+        assert!(c.set_value("webhook_url", serde_json::json!("")).is_ok());
+        assert!(c.webhook_url.is_empty());
+    }
+
+    #[test]
+    fn trait_linkage_validates_names_and_clamps_strength() {
+        let mut c = SimulationConfig::default();
+        assert!(c.trait_linkage.is_empty());
+        let links = serde_json::json!([{ "traits": ["body_length", "metabolism"], "strength": 1.5 }]);
+        assert!(c.set_value("trait_linkage", links).is_ok());
+        assert_eq!(c.trait_linkage[0].strength, 1.0);
+        assert!(c.set_value("trait_linkage", serde_json::json!([{ "traits": ["pattern", "speed"], "strength": 0.5 }])).is_err());
+        assert!(c.set_value("trait_linkage", serde_json::json!([{ "traits": ["speed", "speed"], "strength": 0.5 }])).is_err());
+        assert_eq!(c.trait_linkage.len(), 1);
+    }
+
+    #[test]
+    fn set_current_converts_degrees_and_clamps_strength() {
+        let mut c = SimulationConfig::default();
+        assert_eq!(c.set_current(1.5, 90.0), Ok(ConfigUpdate::Accepted));
+        assert_eq!(c.current_strength, 1.5);
+        assert!((c.current_direction - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+
+        // Negative and past-a-full-turn directions wrap into 0..2π
+        assert_eq!(c.set_current(9.0, -90.0), Ok(ConfigUpdate::Clamped));
+        assert_eq!(c.current_strength, 5.0);
+        assert!((c.current_direction - 3.0 * std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+        assert_eq!(c.set_current(-1.0, 720.0), Ok(ConfigUpdate::Clamped));
+        assert_eq!(c.current_strength, 0.0);
+        assert!(c.current_direction.abs() < 1e-4);
+
+        assert!(c.set_current(f32::NAN, 0.0).is_err());
+        assert!(c.set_current(1.0, f32::INFINITY).is_err());
+        assert_eq!(c.current_strength, 0.0, "Rejected input leaves the current alone");
+    }
+
+    #[test]
+    fn clutch_size_shrinks_with_parental_care() {
+        let mut c = SimulationConfig::default();
+        assert_eq!((c.clutch_size(0.0), c.clutch_size(0.5), c.clutch_size(1.0)), (3, 2, 1));
+        c.parental_care_enabled = false;
+        assert_eq!(c.clutch_size(0.0), 1);
     }
 }
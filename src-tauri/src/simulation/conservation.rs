@@ -0,0 +1,197 @@
+use super::config::SimulationConfig;
+use super::genome::FishGenome;
+use std::collections::{HashMap, HashSet};
+
+/// Protected lineages: descendants of a registered founder mutate less and
+/// prefer mates that resemble the founder, to keep a favorite morph alive
+#[derive(Debug, Clone, Default)]
+pub struct ConservationRegistry {
+    /// Founder genome id -> copy of the founder, whose traits are preserved
+    founders: HashMap<u32, FishGenome>,
+    /// Genome id -> founder of the protected lineage it belongs to
+    members: HashMap<u32, u32>,
+}
+
+impl ConservationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a program for `founder`, enrolling it and every descendant whose
+    /// parent chain can still be traced through `genomes`
+    pub fn protect(&mut self, founder: &FishGenome, genomes: &HashMap<u32, FishGenome>) {
+        self.founders.insert(founder.id, founder.clone());
+        self.members.insert(founder.id, founder.id);
+        for &id in genomes.keys() {
+            if self.descends_from(id, founder.id, genomes) {
+                self.members.insert(id, founder.id);
+            }
+        }
+    }
+
+    /// End a program; returns false if the genome wasn't a protected founder
+    pub fn unprotect(&mut self, founder_id: u32) -> bool {
+        if self.founders.remove(&founder_id).is_none() {
+            return false;
+        }
+        self.members.retain(|_, f| *f != founder_id);
+        true
+    }
+
+    fn descends_from(&self, id: u32, founder_id: u32, genomes: &HashMap<u32, FishGenome>) -> bool {
+        let mut frontier = vec![id];
+        // Inbred pedigrees reach shared ancestors by many paths; visit each once
+        let mut seen = HashSet::new();
+        while let Some(cur) = frontier.pop() {
+            if !seen.insert(cur) {
+                continue;
+            }
+            if cur == founder_id || self.members.get(&cur) == Some(&founder_id) {
+                return true;
+            }
+            if let Some(g) = genomes.get(&cur) {
+                frontier.extend(g.parent_a.into_iter().chain(g.parent_b));
+            }
+        }
+        false
+    }
+
+    /// The founder whose traits this genome's lineage is preserving
+    pub fn founder_for(&self, genome_id: u32) -> Option<&FishGenome> {
+        self.members.get(&genome_id).and_then(|f| self.founders.get(f))
+    }
+
+    pub fn is_protected(&self, genome_id: u32) -> bool {
+        self.members.contains_key(&genome_id)
+    }
+
+    /// Mutation rate multiplier for offspring of this pair: reduced when either
+    /// parent is in a protected lineage
+    pub fn mutation_scale(&self, a: &FishGenome, b: &FishGenome, config: &SimulationConfig) -> f32 {
+        if self.is_protected(a.id) || self.is_protected(b.id) {
+            config.conservation_mutation_scale
+        } else {
+            1.0
+        }
+    }
+
+    /// Enroll a newborn if either parent is in a protected lineage (parent A wins ties)
+    pub fn register_child(&mut self, child: &FishGenome) {
+        let founder = [child.parent_a, child.parent_b].into_iter()
+            .flatten()
+            .find_map(|p| self.members.get(&p).copied());
+        if let Some(founder) = founder {
+            self.members.insert(child.id, founder);
+        }
+    }
+
+    /// Forget members whose genomes were pruned; founders stay until unprotected
+    pub fn prune(&mut self, genomes: &HashMap<u32, FishGenome>) {
+        let founders = &self.founders;
+        self.members.retain(|id, _| genomes.contains_key(id) || founders.contains_key(id));
+    }
+
+    pub fn founders(&self) -> impl Iterator<Item = &FishGenome> {
+        self.founders.values()
+    }
+
+    pub fn members(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.members.iter().map(|(&id, &f)| (id, f))
+    }
+
+    /// Rebuild from stored founders and (genome id, founder id) memberships
+    pub fn restore(founders: Vec<FishGenome>, members: Vec<(u32, u32)>) -> Self {
+        let founders: HashMap<u32, FishGenome> = founders.into_iter().map(|g| (g.id, g)).collect();
+        let members = members.into_iter().filter(|(_, f)| founders.contains_key(f)).collect();
+        Self { founders, members }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::genome::MutationRates;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn seeded_rng() -> StdRng {
+        StdRng::seed_from_u64(42)
+    }
+
+    fn child_of(rng: &mut StdRng, a: &FishGenome, b: &FishGenome) -> FishGenome {
+        FishGenome::inherit(a, b, rng, 0.0, MutationRates::NONE, MutationRates::NONE, &[])
+    }
+
+    #[test]
+    fn protecting_enrolls_existing_descendants() {
+        let mut rng = seeded_rng();
+        let founder = FishGenome::random(&mut rng);
+        let mate = FishGenome::random(&mut rng);
+        let stranger = FishGenome::random(&mut rng);
+        let kid = child_of(&mut rng, &mate, &founder);
+        let grandkid = child_of(&mut rng, &kid, &stranger);
+        let genomes: HashMap<u32, FishGenome> = [&founder, &mate, &stranger, &kid, &grandkid]
+            .into_iter()
+            .map(|g| (g.id, g.clone()))
+            .collect();
+
+        let mut reg = ConservationRegistry::new();
+        reg.protect(&founder, &genomes);
+        assert!(reg.is_protected(kid.id) && reg.is_protected(grandkid.id));
+        assert!(!reg.is_protected(mate.id) && !reg.is_protected(stranger.id));
+        assert_eq!(reg.founder_for(grandkid.id).map(|g| g.id), Some(founder.id));
+
+        assert!(reg.unprotect(founder.id));
+        assert!(!reg.is_protected(kid.id));
+        assert!(!reg.unprotect(founder.id));
+    }
+
+    #[test]
+    fn protection_reaches_through_inbred_pedigrees() {
+        let mut rng = seeded_rng();
+        let founder = FishGenome::random(&mut rng);
+        let stranger = FishGenome::random(&mut rng);
+        let (mut a, mut b) = (FishGenome::random(&mut rng), FishGenome::random(&mut rng));
+        let mut genomes: HashMap<u32, FishGenome> = [&founder, &stranger, &a, &b]
+            .into_iter()
+            .map(|g| (g.id, g.clone()))
+            .collect();
+        // Siblings mate for several generations, so every ancestor is reachable by many paths
+        for _ in 0..8 {
+            let (x, y) = (child_of(&mut rng, &a, &b), child_of(&mut rng, &a, &b));
+            genomes.insert(x.id, x.clone());
+            genomes.insert(y.id, y.clone());
+            (a, b) = (x, y);
+        }
+        let heir = child_of(&mut rng, &founder, &stranger);
+        // The founder's side is walked last, after the whole inbred line
+        let descendant = child_of(&mut rng, &heir, &a);
+        genomes.insert(heir.id, heir.clone());
+        genomes.insert(descendant.id, descendant.clone());
+
+        let mut reg = ConservationRegistry::new();
+        reg.protect(&founder, &genomes);
+        assert!(reg.is_protected(descendant.id));
+        assert!(reg.is_protected(heir.id));
+        assert!(!reg.is_protected(a.id) && !reg.is_protected(b.id), "The inbred line itself isn't the founder's");
+        assert!(!reg.is_protected(stranger.id));
+    }
+
+    #[test]
+    fn newborns_inherit_membership() {
+        let mut rng = seeded_rng();
+        let founder = FishGenome::random(&mut rng);
+        let mate = FishGenome::random(&mut rng);
+        let mut reg = ConservationRegistry::new();
+        reg.protect(&founder, &HashMap::new());
+
+        let kid = child_of(&mut rng, &mate, &founder);
+        reg.register_child(&kid);
+        assert!(reg.is_protected(kid.id));
+
+        // Pruned members drop out, the founder stays
+        reg.prune(&HashMap::new());
+        assert!(!reg.is_protected(kid.id));
+        assert!(reg.is_protected(founder.id));
+    }
+}
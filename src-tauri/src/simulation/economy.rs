@@ -0,0 +1,159 @@
+use crate::simulation::ecosystem::{DecorationType, FoodType, SimEvent};
+use serde::{Deserialize, Serialize};
+
+/// Currency awarded per fish born in the tank
+pub const BIRTH_REWARD: u32 = 2;
+/// Currency awarded per unlocked achievement
+pub const ACHIEVEMENT_REWARD: u32 = 50;
+/// Currency awarded when an environmental event ends with fish still alive
+pub const EVENT_SURVIVED_REWARD: u32 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShopItem {
+    LiveFoodPack,
+    PelletFeast,
+    Rock,
+    TallPlant,
+    ShortPlant,
+    Coral,
+}
+
+impl ShopItem {
+    pub fn all() -> [ShopItem; 6] {
+        [
+            ShopItem::LiveFoodPack,
+            ShopItem::PelletFeast,
+            ShopItem::Rock,
+            ShopItem::TallPlant,
+            ShopItem::ShortPlant,
+            ShopItem::Coral,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShopItem::LiveFoodPack => "live_food_pack",
+            ShopItem::PelletFeast => "pellet_feast",
+            ShopItem::Rock => "rock",
+            ShopItem::TallPlant => "tall_plant",
+            ShopItem::ShortPlant => "short_plant",
+            ShopItem::Coral => "coral",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "live_food_pack" => Some(ShopItem::LiveFoodPack),
+            "pellet_feast" => Some(ShopItem::PelletFeast),
+            "rock" => Some(ShopItem::Rock),
+            "tall_plant" => Some(ShopItem::TallPlant),
+            "short_plant" => Some(ShopItem::ShortPlant),
+            "coral" => Some(ShopItem::Coral),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ShopItem::LiveFoodPack => "Live Food Pack",
+            ShopItem::PelletFeast => "Pellet Feast",
+            ShopItem::Rock => "Rock",
+            ShopItem::TallPlant => "Tall Plant",
+            ShopItem::ShortPlant => "Short Plant",
+            ShopItem::Coral => "Coral",
+        }
+    }
+
+    pub fn price(&self) -> u32 {
+        match self {
+            ShopItem::LiveFoodPack => 15,
+            ShopItem::PelletFeast => 10,
+            ShopItem::Rock => 40,
+            ShopItem::ShortPlant => 30,
+            ShopItem::TallPlant => 50,
+            ShopItem::Coral => 80,
+        }
+    }
+
+    /// Food type and particle count for food items, None for decorations
+    pub fn food(&self) -> Option<(FoodType, u32)> {
+        match self {
+            ShopItem::LiveFoodPack => Some((FoodType::LiveFood, 6)),
+            ShopItem::PelletFeast => Some((FoodType::Pellet, 12)),
+            _ => None,
+        }
+    }
+
+    /// Decoration type for decoration items, None for food
+    pub fn decoration(&self) -> Option<DecorationType> {
+        match self {
+            ShopItem::Rock => Some(DecorationType::Rock),
+            ShopItem::TallPlant => Some(DecorationType::TallPlant),
+            ShopItem::ShortPlant => Some(DecorationType::ShortPlant),
+            ShopItem::Coral => Some(DecorationType::Coral),
+            _ => None,
+        }
+    }
+}
+
+/// Currency earned from a tick's worth of simulation events
+pub fn event_rewards(events: &[SimEvent]) -> u32 {
+    events.iter()
+        .filter(|e| matches!(e, SimEvent::Birth { .. }))
+        .count() as u32 * BIRTH_REWARD
+}
+
+/// Deduct the item's price, or explain why the purchase can't be afforded
+pub fn spend(currency: &mut u32, item: ShopItem) -> Result<(), String> {
+    let price = item.price();
+    if *currency < price {
+        return Err(format!("Not enough currency: {} costs {}, have {}", item.display_name(), price, currency));
+    }
+    *currency -= price;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shop_item_str_roundtrip() {
+        for item in ShopItem::all() {
+            assert_eq!(ShopItem::from_str(item.as_str()), Some(item));
+        }
+        assert_eq!(ShopItem::from_str("yacht"), None);
+    }
+
+    #[test]
+    fn every_item_is_food_or_decoration() {
+        for item in ShopItem::all() {
+            assert!(item.food().is_some() != item.decoration().is_some(), "{} must be exactly one kind", item.as_str());
+            assert!(item.price() > 0);
+        }
+    }
+
+    #[test]
+    fn births_earn_currency() {
+        let events = vec![
+            SimEvent::Birth { fish_id: 1, genome_id: 1, parent_a: 2, parent_b: 3 },
+            SimEvent::Birth { fish_id: 4, genome_id: 4, parent_a: 2, parent_b: 3 },
+            SimEvent::FeedingDrop { x: 0.0, y: 0.0 },
+        ];
+        assert_eq!(event_rewards(&events), 2 * BIRTH_REWARD);
+    }
+
+    #[test]
+    fn spend_deducts_price() {
+        let mut currency = 100;
+        spend(&mut currency, ShopItem::Rock).unwrap();
+        assert_eq!(currency, 100 - ShopItem::Rock.price());
+    }
+
+    #[test]
+    fn spend_rejects_insufficient_funds() {
+        let mut currency = 5;
+        assert!(spend(&mut currency, ShopItem::Coral).is_err());
+        assert_eq!(currency, 5, "Failed purchase must not change balance");
+    }
+}
@@ -1,14 +1,14 @@
+use crate::simulation::boids::SpatialGrid;
 use crate::simulation::config::SimulationConfig;
+use crate::simulation::conservation::ConservationRegistry;
 use crate::simulation::fish::{BehaviorState, Fish};
-use crate::simulation::genome::{genome_distance, FishGenome, Sex};
+use crate::simulation::genome::{genome_distance, relatedness, FishGenome, Sex};
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 
-pub const BASE_LIFESPAN: u32 = 20_000;
-
 // ─── Food ───
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FoodType {
     Flake,
     Pellet,
@@ -41,6 +41,58 @@ impl FoodType {
     }
 }
 
+/// Food leaning that follows from aggression: grazers favor flakes and
+/// hunters live food, while everyone takes pellets as they come
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Diet {
+    Herbivore,
+    Omnivore,
+    Carnivore,
+}
+
+impl Diet {
+    pub fn of(genome: &FishGenome) -> Self {
+        if genome.aggression < HERBIVORE_MAX_AGGRESSION {
+            Diet::Herbivore
+        } else if genome.aggression > 0.6 {
+            Diet::Carnivore
+        } else {
+            Diet::Omnivore
+        }
+    }
+
+    pub fn preferred_food(self) -> Option<FoodType> {
+        match self {
+            Diet::Herbivore => Some(FoodType::Flake),
+            Diet::Omnivore => None,
+            Diet::Carnivore => Some(FoodType::LiveFood),
+        }
+    }
+
+    pub fn disliked_food(self) -> Option<FoodType> {
+        match self {
+            Diet::Herbivore => Some(FoodType::LiveFood),
+            Diet::Omnivore => None,
+            Diet::Carnivore => Some(FoodType::Flake),
+        }
+    }
+
+    /// Nutrition of `food` for a fish with this diet
+    pub fn nutrition(self, food: FoodType, config: &SimulationConfig) -> f32 {
+        let base = food.nutrition();
+        if !config.food_preference_enabled {
+            base
+        } else if self.preferred_food() == Some(food) {
+            base * (1.0 + config.food_preference_bonus)
+        } else if self.disliked_food() == Some(food) {
+            base * (1.0 - config.food_preference_penalty)
+        } else {
+            base
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FoodParticle {
     pub x: f32,
@@ -95,6 +147,26 @@ impl FoodParticle {
     pub fn is_expired(&self, config: &SimulationConfig) -> bool {
         self.age >= config.food_decay_ticks
     }
+
+    /// Where food dropped at (x, y) on `tick` comes to rest: the floor position
+    /// once it settles, or where it is when it decays mid-water. Live food never
+    /// settles, so this returns the drop point clamped into its wander bounds.
+    pub fn predict_landing(x: f32, y: f32, food_type: FoodType, config: &SimulationConfig, tick: u64) -> (f32, f32) {
+        // Same surface clamp as drop_food
+        let mut food = Self::new_typed(x, y.clamp(5.0, 50.0), food_type);
+        if matches!(food.food_type, FoodType::LiveFood) {
+            return (
+                food.x.clamp(10.0, config.tank_width - 10.0),
+                food.y.clamp(10.0, config.tank_height - 40.0),
+            );
+        }
+        let mut t = tick;
+        while !food.on_floor && !food.is_expired(config) {
+            food.update(config, t);
+            t += 1;
+        }
+        (food.x, food.y)
+    }
 }
 
 // ─── Eggs ───
@@ -125,14 +197,17 @@ pub struct Egg {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SimEvent {
     Birth { fish_id: u32, genome_id: u32, parent_a: u32, parent_b: u32 },
-    Death { fish_id: u32, genome_id: u32, cause: DeathCause, custom_name: Option<String>, is_favorite: bool },
+    Death { fish_id: u32, genome_id: u32, cause: DeathCause, custom_name: Option<String>, is_favorite: bool, age: u32, age_fraction: f32, speed: Option<f32>, body_length: Option<f32>, aggression: Option<f32>, reproduced: bool },
     FeedingDrop { x: f32, y: f32 },
     Predation { predator_id: u32, prey_id: u32 },
     NewSpecies { species_id: u32 },
     Extinction { species_id: u32 },
+    PopulationWarning { population: u32, minimum: u32 },
+    PredatorIntroduced { fish_id: u32, genome_id: u32 },
+    SpeciesRevived { species_id: u32, count: u32 },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DeathCause {
     OldAge,
     Starvation,
@@ -140,6 +215,21 @@ pub enum DeathCause {
     Predation,
 }
 
+impl DeathCause {
+    pub fn all() -> [DeathCause; 4] {
+        [DeathCause::OldAge, DeathCause::Starvation, DeathCause::PoorWater, DeathCause::Predation]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeathCause::OldAge => "old_age",
+            DeathCause::Starvation => "starvation",
+            DeathCause::PoorWater => "poor_water",
+            DeathCause::Predation => "predation",
+        }
+    }
+}
+
 // ─── Species ───
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,8 +245,20 @@ pub struct Species {
     pub centroid_pattern: String,
     pub member_count: u32,
     pub member_genome_ids: Vec<u32>,
+    /// Shape token from `SPECIES_MARKERS`, so the renderer can tell
+    /// co-existing species apart without relying on hue
+    #[serde(default)]
+    pub distinct_marker: Option<String>,
+    /// Average genome of the last members, captured at extinction
+    #[serde(default, skip_serializing)]
+    pub fossil: Option<FishGenome>,
 }
 
+/// Non-color cues for telling species apart, most distinguishable first
+pub const SPECIES_MARKERS: &[&str] = &[
+    "circle", "triangle", "square", "star", "cross", "diamond", "ring", "bar",
+];
+
 // ─── Decorations ───
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,6 +267,7 @@ pub enum DecorationType {
     TallPlant,
     ShortPlant,
     Coral,
+    Feeder, // auto-feeder drops food here instead of at random
 }
 
 impl DecorationType {
@@ -178,15 +281,22 @@ impl DecorationType {
             DecorationType::TallPlant => 12.0,
             DecorationType::ShortPlant => 8.0,
             DecorationType::Coral => 18.0,
+            DecorationType::Feeder => 10.0,
         }
     }
 
+    /// Whether the decoration counts toward enrichment; feeders are equipment, not scenery
+    pub fn is_enriching(&self) -> bool {
+        !matches!(self, DecorationType::Feeder)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             DecorationType::Rock => "rock",
             DecorationType::TallPlant => "tall_plant",
             DecorationType::ShortPlant => "short_plant",
             DecorationType::Coral => "coral",
+            DecorationType::Feeder => "feeder",
         }
     }
 
@@ -195,6 +305,7 @@ impl DecorationType {
             "tall_plant" => DecorationType::TallPlant,
             "short_plant" => DecorationType::ShortPlant,
             "coral" => DecorationType::Coral,
+            "feeder" => DecorationType::Feeder,
             _ => DecorationType::Rock,
         }
     }
@@ -210,6 +321,46 @@ pub struct Decoration {
     pub flip_x: bool,
 }
 
+// ─── Algae ───
+
+/// Fish below this aggression graze algae
+pub const HERBIVORE_MAX_AGGRESSION: f32 = 0.35;
+/// Patch size removed per bite
+const ALGAE_BITE: f32 = 0.1;
+/// Hunger relieved per bite
+const ALGAE_NUTRITION: f32 = 0.15;
+/// Fin-nipping never takes a fish's health below this
+const FIN_NIP_HEALTH_FLOOR: f32 = 0.1;
+/// Horizontal gap between eggs of one clutch
+const CLUTCH_SPACING: f32 = 8.0;
+/// Ticks a pair courts before it can spawn; female choice is made once this display has run
+pub const COURTSHIP_DISPLAY_TICKS: u32 = 90;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlgaePatch {
+    pub id: u32,
+    pub x: f32,
+    pub y: f32,
+    pub size: f32, // 0-1
+}
+
+impl AlgaePatch {
+    pub fn radius(&self) -> f32 {
+        6.0 + self.size * 24.0
+    }
+
+    /// Grow when lit and fed by nutrient-rich water, otherwise die back;
+    /// returns true if it grew
+    pub fn grow(&mut self, config: &SimulationConfig, light_level: f32, water_quality: f32) -> bool {
+        if light_level < config.algae_min_light || water_quality > config.algae_max_water_quality {
+            self.size = (self.size - config.algae_decay_rate).max(0.0);
+            return false;
+        }
+        self.size = (self.size + config.algae_growth_rate * light_level).min(1.0);
+        true
+    }
+}
+
 // ─── Bubble ───
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -243,6 +394,33 @@ impl Bubble {
 
 // ─── Ecosystem Manager ───
 
+/// Body left by a fish that died of anything but predation, when carcass decay is on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Carcass {
+    pub id: u32, // the dead fish's id
+    pub x: f32,
+    pub y: f32,
+    pub heading: f32,
+    pub body_length: f32,
+    pub hue: f32,
+    pub remaining_ticks: u32,
+}
+
+impl Carcass {
+    /// Sink toward the floor and rot by one tick
+    pub fn update(&mut self, floor_y: f32) {
+        if self.y < floor_y {
+            self.y = (self.y + 0.3).min(floor_y);
+        }
+        self.remaining_ticks = self.remaining_ticks.saturating_sub(1);
+    }
+
+    /// 0 when fresh, 1 when about to disappear
+    pub fn decay(&self, config: &SimulationConfig) -> f32 {
+        1.0 - self.remaining_ticks as f32 / config.carcass_decay_ticks.max(1) as f32
+    }
+}
+
 pub struct EcosystemManager {
     pub food: Vec<FoodParticle>,
     pub bubbles: Vec<Bubble>,
@@ -252,10 +430,20 @@ pub struct EcosystemManager {
     pub events: Vec<SimEvent>,
     pub plant_count: u32,
     pub decorations: Vec<Decoration>,
+    pub algae: Vec<AlgaePatch>,
+    pub carcasses: Vec<Carcass>,
+    pub conservation: ConservationRegistry,
     next_species_id: u32,
     next_decoration_id: u32,
     last_speciation_tick: u64,
     auto_feed_timer: u32,
+    next_algae_id: u32,
+}
+
+impl Default for EcosystemManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EcosystemManager {
@@ -269,10 +457,14 @@ impl EcosystemManager {
             events: Vec::new(),
             plant_count: 0,
             decorations: Vec::new(),
+            algae: Vec::new(),
+            carcasses: Vec::new(),
+            conservation: ConservationRegistry::new(),
             next_species_id: 1,
             next_decoration_id: 1,
             last_speciation_tick: 0,
             auto_feed_timer: 0,
+            next_algae_id: 1,
         }
     }
 
@@ -305,6 +497,23 @@ impl EcosystemManager {
         removed
     }
 
+    /// Enrichment at a point: distinct decoration kinds within
+    /// `enrichment_radius`, as a fraction of `enrichment_full_variety`
+    pub fn enrichment_at(&self, x: f32, y: f32, config: &SimulationConfig) -> f32 {
+        let radius_sq = config.enrichment_radius * config.enrichment_radius;
+        let mut kinds: Vec<&'static str> = Vec::new();
+        for d in &self.decorations {
+            let (dx, dy) = (d.x - x, d.y - y);
+            if d.decoration_type.is_enriching() && dx * dx + dy * dy < radius_sq {
+                let kind = d.decoration_type.as_str();
+                if !kinds.contains(&kind) {
+                    kinds.push(kind);
+                }
+            }
+        }
+        (kinds.len() as f32 / config.enrichment_full_variety.max(1) as f32).min(1.0)
+    }
+
     pub fn obstacle_positions(&self) -> Vec<(f32, f32, f32)> {
         self.decorations.iter()
             .map(|d| (d.x, d.y, d.decoration_type.obstacle_radius() * d.scale))
@@ -323,6 +532,21 @@ impl EcosystemManager {
         self.last_speciation_tick = tick;
     }
 
+    /// Run a speciation pass now rather than on the 300-tick schedule, which restarts
+    /// from this tick. New-species/extinction events go out with the next update.
+    /// Returns the number of living species.
+    pub fn recompute_species(
+        &mut self,
+        fish: &[Fish],
+        genomes: &std::collections::HashMap<u32, FishGenome>,
+        config: &SimulationConfig,
+        tick: u64,
+    ) -> u32 {
+        self.detect_species(fish, genomes, config, tick);
+        self.last_speciation_tick = tick;
+        self.species.iter().filter(|s| s.extinct_at_tick.is_none()).count() as u32
+    }
+
     pub fn drop_food(&mut self, x: f32, y: f32) {
         self.food.push(FoodParticle::new(x, y.max(5.0).min(50.0)));
         self.events.push(SimEvent::FeedingDrop { x, y });
@@ -373,22 +597,17 @@ impl EcosystemManager {
         config: &SimulationConfig,
         tick: u64,
         rng: &mut impl Rng,
-        time_of_day: f32,
+        light_level: f32,
+        fertility_scale: f32,
         event_system: &crate::simulation::events::EventSystem,
+        grid: &SpatialGrid,
     ) -> Vec<SimEvent> {
         // Drain inter-tick events (e.g. FeedingDrop from user clicks) instead of clearing
         let mut carried_events: Vec<SimEvent> = self.events.drain(..).collect();
 
         // Auto-feeder
         if config.auto_feed_enabled {
-            self.auto_feed_timer += 1;
-            if self.auto_feed_timer >= config.auto_feed_interval {
-                self.auto_feed_timer = 0;
-                for _ in 0..config.auto_feed_amount {
-                    let x = rng.gen_range(50.0..config.tank_width - 50.0);
-                    self.food.push(FoodParticle::new(x, 5.0));
-                }
-            }
+            self.run_auto_feeder(config, rng);
         }
 
         // Update food
@@ -408,22 +627,33 @@ impl EcosystemManager {
         self.bubbles.retain(|b| !b.is_popped());
 
         // Feeding - fish eat nearby food
-        self.process_feeding(fish, config);
+        self.process_feeding(fish, genomes, config);
 
         // Predation
         self.process_predation(fish, genomes, config, tick, rng);
 
         // Behavior updates
-        self.update_fish_behavior(fish, genomes, config, tick, time_of_day);
+        self.update_fish_behavior(fish, genomes, config, tick, light_level, grid);
+
+        // Fin-nipping — non-lethal skirmishes between crowded aggressive fish
+        if config.fin_nipping_enabled {
+            Self::process_fin_nipping(fish, genomes, config, rng, grid);
+        }
 
         // Reproduction (creates eggs, not fish directly)
-        self.process_reproduction(fish, genomes, config, tick, rng);
+        if config.courtship_display_enabled {
+            Self::check_courtship_displays(fish, config);
+        }
+        self.process_reproduction(fish, genomes, config, tick, rng, light_level, fertility_scale);
 
         // Hatch eggs → juvenile fish
         self.process_eggs(fish, genomes, config, rng);
 
+        // Caring parents pick the egg or hatchling to stay near
+        self.update_guarding(fish, genomes, config);
+
         // Egg predation — aggressive large fish eat nearby eggs
-        self.process_egg_predation(fish, genomes);
+        self.process_egg_predation(fish, genomes, config);
 
         // Territory claiming & defense
         if config.territory_enabled {
@@ -437,20 +667,63 @@ impl EcosystemManager {
 
         // Remove expired food
         let wq = &mut self.water_quality;
+        let mut decayed: Vec<f32> = Vec::new();
         self.food.retain(|f| {
             if f.is_expired(config) {
                 // Decayed food degrades water
                 *wq = (*wq - 0.001).max(0.0);
+                decayed.push(f.x);
                 false
             } else {
                 true
             }
         });
 
+        // Algae: decayed food seeds patches, which grow and are grazed
+        if config.algae_enabled {
+            for x in decayed {
+                self.seed_algae(x, config, rng);
+            }
+            self.update_algae(config, light_level);
+            self.process_grazing(fish, genomes);
+        }
+
+        // Carcasses sink and rot, fouling the water until gone
+        self.update_carcasses(config);
+
         // Remove dead fish
+        self.remove_dead(fish, genomes, config);
+
+        // Speciation detection (every 300 ticks)
+        if tick - self.last_speciation_tick >= 300 && fish.len() >= 3 {
+            self.detect_species(fish, genomes, config, tick);
+            self.last_speciation_tick = tick;
+        }
+
+        carried_events.extend(self.events.drain(..));
+        carried_events
+    }
+
+    /// Drop dead fish, leaving a carcass and a Death event for each. Traits
+    /// are None when the fish's genome is already gone.
+    fn remove_dead(&mut self, fish: &mut Vec<Fish>, genomes: &std::collections::HashMap<u32, FishGenome>, config: &SimulationConfig) {
         let events = &mut self.events;
+        let carcasses = &mut self.carcasses;
         fish.retain(|f| {
             if !f.is_alive {
+                let genome = genomes.get(&f.genome_id);
+                // Eaten fish leave nothing behind
+                if config.carcass_decay_enabled && !f.killed_by_predator {
+                    carcasses.push(Carcass {
+                        id: f.id,
+                        x: f.x,
+                        y: f.y,
+                        heading: f.heading,
+                        body_length: genome.map_or(1.0, |g| g.body_length),
+                        hue: genome.map_or(0.0, |g| g.base_hue),
+                        remaining_ticks: config.carcass_decay_ticks,
+                    });
+                }
                 events.push(SimEvent::Death {
                     fish_id: f.id,
                     genome_id: f.genome_id,
@@ -465,21 +738,18 @@ impl EcosystemManager {
                     },
                     custom_name: f.custom_name.clone(),
                     is_favorite: f.is_favorite,
+                    age: f.age,
+                    age_fraction: genome.map(|g| f.age_fraction(g, config.max_lifespan)).unwrap_or(1.0),
+                    speed: genome.map(|g| g.speed),
+                    body_length: genome.map(|g| g.body_length),
+                    aggression: genome.map(|g| g.aggression),
+                    reproduced: f.last_reproduced_tick.is_some(),
                 });
                 false
             } else {
                 true
             }
         });
-
-        // Speciation detection (every 300 ticks)
-        if tick - self.last_speciation_tick >= 300 && fish.len() >= 3 {
-            self.detect_species(fish, genomes, config, tick);
-            self.last_speciation_tick = tick;
-        }
-
-        carried_events.extend(self.events.drain(..));
-        carried_events
     }
 
     fn update_water_quality(&mut self, fish_count: usize, config: &SimulationConfig) {
@@ -489,12 +759,84 @@ impl EcosystemManager {
         let food_degradation = self.food.len() as f32 * 0.0001;
         // Recovery
         let recovery = config.water_recovery_rate + self.plant_count as f32 * config.plant_recovery_bonus;
+        // Emergency bacterial bloom, ramping up linearly as quality falls below the threshold
+        let bloom = if config.water_resilience_enabled && self.water_quality < config.water_resilience_threshold {
+            let severity = 1.0 - self.water_quality / config.water_resilience_threshold;
+            config.water_recovery_rate * config.water_resilience_strength * severity
+        } else {
+            0.0
+        };
 
-        self.water_quality = (self.water_quality - fish_degradation - food_degradation + recovery)
+        self.water_quality = (self.water_quality - fish_degradation - food_degradation + recovery + bloom)
             .clamp(0.0, 1.0);
     }
 
-    fn process_feeding(&mut self, fish: &mut [Fish], _config: &SimulationConfig) {
+    fn seed_algae(&mut self, x: f32, config: &SimulationConfig, rng: &mut impl Rng) {
+        if self.algae.len() >= config.max_algae_patches as usize || rng.gen::<f32>() >= config.algae_seed_chance {
+            return;
+        }
+        self.algae.push(AlgaePatch {
+            id: self.next_algae_id,
+            x: x.clamp(0.0, config.tank_width),
+            y: config.tank_height - 30.0, // food floor
+            size: 0.1,
+        });
+        self.next_algae_id += 1;
+    }
+
+    fn update_algae(&mut self, config: &SimulationConfig, light_level: f32) {
+        let water_quality = self.water_quality;
+        let mut total_size = 0.0;
+        for patch in &mut self.algae {
+            patch.grow(config, light_level, water_quality);
+            total_size += patch.size;
+        }
+        self.algae.retain(|a| a.size > 0.0);
+        self.water_quality = (self.water_quality - total_size * config.algae_water_degradation).max(0.0);
+    }
+
+    fn update_carcasses(&mut self, config: &SimulationConfig) {
+        if !config.carcass_decay_enabled {
+            self.carcasses.clear();
+            return;
+        }
+        let floor_y = config.tank_height - 30.0;
+        let mut load = 0.0;
+        for c in &mut self.carcasses {
+            c.update(floor_y);
+            load += c.body_length;
+        }
+        self.carcasses.retain(|c| c.remaining_ticks > 0);
+        self.water_quality = (self.water_quality - load * config.carcass_water_degradation).max(0.0);
+    }
+
+    /// Hungry herbivores take a bite from any algae patch they are touching
+    fn process_grazing(&mut self, fish: &mut [Fish], genomes: &std::collections::HashMap<u32, FishGenome>) {
+        if self.algae.is_empty() {
+            return;
+        }
+        for f in fish.iter_mut() {
+            if !f.is_alive || f.hunger < 0.2
+                || matches!(f.behavior, BehaviorState::Dying | BehaviorState::Fleeing | BehaviorState::Hunting)
+            {
+                continue;
+            }
+            let herbivore = genomes.get(&f.genome_id).is_some_and(|g| g.aggression < HERBIVORE_MAX_AGGRESSION);
+            if !herbivore {
+                continue;
+            }
+            if let Some(patch) = self.algae.iter_mut().find(|a| {
+                let (dx, dy) = (f.x - a.x, f.y - a.y);
+                a.size > 0.0 && dx * dx + dy * dy < a.radius() * a.radius()
+            }) {
+                patch.size -= ALGAE_BITE;
+                f.hunger = (f.hunger - ALGAE_NUTRITION).max(0.0);
+            }
+        }
+        self.algae.retain(|a| a.size > 0.0);
+    }
+
+    fn process_feeding(&mut self, fish: &mut [Fish], genomes: &std::collections::HashMap<u32, FishGenome>, config: &SimulationConfig) {
         let eating_radius_sq = 8.0 * 8.0;
 
         let mut eaten_food = std::collections::HashSet::new();
@@ -503,7 +845,7 @@ impl EcosystemManager {
             if !f.is_alive || (f.behavior != BehaviorState::Foraging && f.behavior != BehaviorState::Swimming) {
                 continue;
             }
-            if f.hunger < 0.2 {
+            if f.hunger < config.eat_hunger_threshold {
                 continue;
             }
             for (food_idx, food) in self.food.iter().enumerate() {
@@ -511,7 +853,9 @@ impl EcosystemManager {
                 let dy = f.y - food.y;
                 if dx * dx + dy * dy < eating_radius_sq && !eaten_food.contains(&food_idx) {
                     eaten_food.insert(food_idx);
-                    nutrition_map.push((fi, food.food_type.nutrition()));
+                    let nutrition = genomes.get(&f.genome_id)
+                        .map_or(food.food_type.nutrition(), |g| Diet::of(g).nutrition(food.food_type, config));
+                    nutrition_map.push((fi, nutrition));
                     f.eat();
                     break;
                 }
@@ -522,7 +866,8 @@ impl EcosystemManager {
         for (fi, nutrition) in &nutrition_map {
             let f = &mut fish[*fi];
             // eat() reduces hunger by 0.3; adjust based on nutrition
-            // pellet=0.3 is baseline; flake=0.2 less satisfying, live=0.5 more
+            // pellet=0.3 is baseline; flake=0.2 less satisfying, live=0.5 more,
+            // each scaled up or down by the fish's diet preference
             let adjustment = nutrition - 0.3;
             f.hunger = (f.hunger - adjustment).clamp(0.0, 1.0);
         }
@@ -548,6 +893,7 @@ impl EcosystemManager {
         let strike_radius = 12.0;
         let strike_radius_sq = strike_radius * strike_radius;
         let max_chase_ticks: u32 = 150;
+        let cover = if config.shelter_enabled { self.obstacle_positions() } else { Vec::new() };
 
         // Snapshot for read-only queries while mutating
         let snap: Vec<(u32, f32, f32, u32, bool, BehaviorState, Option<u32>)> = fish
@@ -556,43 +902,58 @@ impl EcosystemManager {
             .collect();
 
         let mut kills: std::collections::HashSet<usize> = std::collections::HashSet::new();
-        let mut fed_predators: Vec<(usize, f32)> = Vec::new(); // (idx, hunger_reduction)
+        let mut fed_predators: Vec<(usize, f32, f32)> = Vec::new(); // (idx, hunger_reduction, prey length eaten)
 
         for i in 0..fish.len() {
             let (fid, fx, fy, gid, alive, beh, _htarget) = snap[i];
-            if !alive || kills.contains(&i) { continue; }
+            if !alive || kills.contains(&i) || fish[i].is_quarantined { continue; }
 
             let genome = match genomes.get(&gid) {
                 Some(g) => g,
                 None => continue,
             };
+            // Crowding stress makes fish more aggressive than their genes alone
+            let aggression = if config.crowding_enabled {
+                genome.aggression + fish[i].stress * config.crowding_aggression_bonus
+            } else {
+                genome.aggression
+            };
 
             // === Phase 1: Target acquisition ===
             // Predators (aggression > 0.6) that are Swimming/Foraging/Satiated can start hunting
-            if genome.aggression > 0.6
+            if aggression > 0.6
+                && fish[i].hunt_cooldown == 0
                 && beh != BehaviorState::Hunting
                 && beh != BehaviorState::Fleeing
                 && beh != BehaviorState::Resting
                 && beh != BehaviorState::Dying
                 && beh != BehaviorState::Courting
             {
-                // Scan for prey
+                // Scan for prey that still fits in the stomach
+                let room = genome.stomach_capacity(config.stomach_capacity) - fish[i].stomach_fullness;
                 let mut best_prey: Option<(usize, f32)> = None;
                 for j in 0..fish.len() {
                     if j == i || kills.contains(&j) { continue; }
                     let (_, px, py, pgid, palive, pbeh, _) = snap[j];
-                    if !palive || pbeh == BehaviorState::Dying { continue; }
+                    if !palive || pbeh == BehaviorState::Dying || fish[j].is_quarantined || fish[j].spawn_protection > 0 { continue; }
                     let prey_genome = match genomes.get(&pgid) {
                         Some(g) => g,
                         None => continue,
                     };
                     // Must be smaller
-                    if prey_genome.body_length >= genome.body_length * config.predation_size_ratio {
+                    if prey_genome.body_length >= genome.max_prey_length(config.predation_size_ratio) {
+                        continue;
+                    }
+                    if prey_genome.body_length > room {
                         continue;
                     }
                     let dx = fx - px;
                     let dy = fy - py;
-                    let dist_sq = dx * dx + dy * dy;
+                    let mut dist_sq = dx * dx + dy * dy;
+                    // A courtship display draws the eye from farther off
+                    if pbeh == BehaviorState::Courting {
+                        dist_sq /= config.courtship_conspicuousness * config.courtship_conspicuousness;
+                    }
                     if dist_sq < scan_radius_sq {
                         if best_prey.is_none() || dist_sq < best_prey.unwrap().1 {
                             best_prey = Some((j, dist_sq));
@@ -601,7 +962,7 @@ impl EcosystemManager {
                 }
                 // Start hunting if prey found and hunger is relevant or aggression is high
                 if let Some((prey_idx, _)) = best_prey {
-                    if genome.aggression > 0.8 || fish[i].hunger > 0.3 {
+                    if aggression > 0.8 || fish[i].hunger > 0.3 {
                         fish[i].behavior = BehaviorState::Hunting;
                         fish[i].hunting_target = Some(snap[prey_idx].0);
                         fish[i].hunting_timer = 0;
@@ -626,7 +987,7 @@ impl EcosystemManager {
 
                 // Find target
                 let target_idx = snap.iter().position(|s| s.0 == target_id);
-                let target_alive = target_idx.map(|ti| fish[ti].is_alive && fish[ti].health > 0.0 && !kills.contains(&ti)).unwrap_or(false);
+                let target_alive = target_idx.map(|ti| fish[ti].is_alive && fish[ti].health > 0.0 && !kills.contains(&ti) && !fish[ti].is_quarantined && fish[ti].spawn_protection == 0).unwrap_or(false);
 
                 if !target_alive || fish[i].hunting_timer >= max_chase_ticks {
                     // Give up
@@ -644,8 +1005,10 @@ impl EcosystemManager {
 
                 if dist_sq < strike_radius_sq {
                     // === Strike roll ===
-                    // Pack hunting: count same-species hunters targeting the same prey within 50px
-                    let mut pack_count = 0_u32;
+                    // Pack hunting: same-species hunters targeting the same prey nearby,
+                    // closest first, capped so a swarm can't stack up absurd odds
+                    let pack_radius_sq = config.pack_radius * config.pack_radius;
+                    let mut pack: Vec<(usize, f32)> = Vec::new();
                     for k in 0..fish.len() {
                         if k == i { continue; }
                         let (_, kx, ky, kgid, kalive, kbeh, ktarget) = snap[k];
@@ -653,31 +1016,51 @@ impl EcosystemManager {
                         if ktarget != Some(target_id) { continue; }
                         let dkx = fx - kx;
                         let dky = fy - ky;
-                        if dkx * dkx + dky * dky < 50.0 * 50.0 {
+                        let dist_sq = dkx * dkx + dky * dky;
+                        if dist_sq < pack_radius_sq {
                             if let Some(kg) = genomes.get(&kgid) {
-                                if genome_distance(genome, kg) < config.species_threshold {
-                                    pack_count += 1;
+                                if genome_distance(genome, kg, config.distance_weights()) < config.species_threshold {
+                                    pack.push((k, dist_sq));
                                 }
                             }
                         }
                     }
-
-                    // Safety in numbers for prey
-                    let mut prey_allies = 0_u32;
+                    pack.sort_by(|a, b| a.1.total_cmp(&b.1));
+                    pack.truncate(config.pack_max_size.saturating_sub(1) as usize);
+                    let pack_count = pack.len() as u32;
+
+                    // Safety in numbers: nearby schoolmates of the prey confuse the predator,
+                    // closer ones more so
+                    let prey_genome = genomes.get(&snap[ti].3);
+                    let confusion_radius_sq = config.confusion_radius * config.confusion_radius;
+                    let mut school_weight = 0.0_f32;
                     for k in 0..fish.len() {
-                        if k == ti || !snap[k].4 { continue; }
+                        if k == ti || k == i || !snap[k].4 { continue; }
                         let dkx = tx - snap[k].1;
                         let dky = ty - snap[k].2;
-                        if dkx * dkx + dky * dky < config.separation_radius * config.separation_radius {
-                            prey_allies += 1;
+                        let dist_sq = dkx * dkx + dky * dky;
+                        if dist_sq >= confusion_radius_sq { continue; }
+                        let same_species = match (prey_genome, genomes.get(&snap[k].3)) {
+                            (Some(pg), Some(kg)) => genome_distance(pg, kg, config.distance_weights()) < config.species_threshold,
+                            _ => false,
+                        };
+                        if same_species {
+                            school_weight += 1.0 - dist_sq.sqrt() / config.confusion_radius;
                         }
                     }
                     // Allies reduce attack chance but pack hunting can overcome
-                    let ally_penalty = if prey_allies >= 3 { 0.3 } else { 1.0 };
+                    let ally_penalty = confusion_multiplier(school_weight, config);
 
-                    // Pack bonus: 1.5x per extra hunter
-                    let pack_bonus = 1.0 + pack_count as f32 * 0.5;
-                    let attack_chance = genome.aggression * 0.15 * pack_bonus * ally_penalty;
+                    let pack_bonus = 1.0 + pack_count as f32 * config.pack_bonus_per_member;
+                    // Prey tucked in beside a decoration is harder to strike
+                    let cover_penalty = if is_sheltered(tx, ty, &cover, config.shelter_radius) {
+                        1.0 - config.shelter_strike_reduction
+                    } else {
+                        1.0
+                    };
+                    // Displaying prey is too busy showing off to dodge
+                    let display_bonus = if snap[ti].5 == BehaviorState::Courting { config.courtship_conspicuousness } else { 1.0 };
+                    let attack_chance = aggression * 0.15 * pack_bonus * ally_penalty * cover_penalty * display_bonus;
 
                     if rng.gen::<f32>() < attack_chance {
                         kills.insert(ti);
@@ -688,22 +1071,11 @@ impl EcosystemManager {
 
                         // Share meal among pack (partial hunger reduction per member)
                         let share = 0.5 / (1.0 + pack_count as f32);
-                        fed_predators.push((i, share));
+                        let portion = prey_genome.map_or(1.0, |g| g.body_length) / (1.0 + pack_count as f32);
+                        fed_predators.push((i, share, portion));
                         // Feed pack members too
-                        for k in 0..fish.len() {
-                            if k == i { continue; }
-                            let (_, kx, ky, kgid, kalive, kbeh, ktarget) = snap[k];
-                            if !kalive || kbeh != BehaviorState::Hunting { continue; }
-                            if ktarget != Some(target_id) { continue; }
-                            let dkx = fx - kx;
-                            let dky = fy - ky;
-                            if dkx * dkx + dky * dky < 50.0 * 50.0 {
-                                if let Some(kg) = genomes.get(&kgid) {
-                                    if genome_distance(genome, kg) < config.species_threshold {
-                                        fed_predators.push((k, share));
-                                    }
-                                }
-                            }
+                        for &(k, _) in &pack {
+                            fed_predators.push((k, share, portion));
                         }
 
                         // Reset hunting state
@@ -723,12 +1095,14 @@ impl EcosystemManager {
             fish[idx].killed_by_predator = true;
         }
         // Apply feeding to predators
-        for &(idx, hunger_reduction) in &fed_predators {
+        for &(idx, hunger_reduction, portion) in &fed_predators {
             fish[idx].hunger = (fish[idx].hunger - hunger_reduction).max(0.0);
+            fish[idx].stomach_fullness += portion;
             fish[idx].energy = (fish[idx].energy + 0.15).min(1.0);
             fish[idx].behavior = BehaviorState::Swimming;
             fish[idx].hunting_target = None;
             fish[idx].hunting_timer = 0;
+            fish[idx].start_hunt_cooldown(config);
         }
     }
 
@@ -738,15 +1112,55 @@ impl EcosystemManager {
         genomes: &std::collections::HashMap<u32, FishGenome>,
         config: &SimulationConfig,
         tick: u64,
-        time_of_day: f32,
+        light_level: f32,
+        grid: &SpatialGrid,
     ) {
+        // Crowding stress from local density (grid indices match fish order this tick)
+        if config.crowding_enabled {
+            let radius_sq = config.crowding_radius * config.crowding_radius;
+            let crowding: Vec<u32> = (0..fish.len())
+                .map(|i| {
+                    grid.neighbors(fish[i].x, fish[i].y, config.crowding_radius)
+                        .into_iter()
+                        .filter(|&j| j != i)
+                        .filter_map(|j| fish.get(j))
+                        .filter(|n| {
+                            let dx = n.x - fish[i].x;
+                            let dy = n.y - fish[i].y;
+                            n.is_alive && dx * dx + dy * dy < radius_sq
+                        })
+                        .count() as u32
+                })
+                .collect();
+            for (f, &count) in fish.iter_mut().zip(&crowding) {
+                if count > config.crowding_threshold {
+                    let excess = (count - config.crowding_threshold) as f32;
+                    f.stress = (f.stress + config.crowding_stress_rate * excess).min(1.0);
+                }
+            }
+        }
+
+        // Boredom in barren surroundings: a slow health drain (and lower
+        // fertility at breeding). Kept apart from stress, which would feed
+        // crowding aggression and turn a wellness nudge into predation.
+        if config.enrichment_enabled {
+            if tick.is_multiple_of(config.enrichment_interval.max(1) as u64) {
+                for f in fish.iter_mut() {
+                    f.enrichment = self.enrichment_at(f.x, f.y, config);
+                }
+            }
+            for f in fish.iter_mut().filter(|f| f.is_alive) {
+                f.health -= config.boredom_health_drain * f.boredom(config);
+            }
+        }
+
         // Pre-compute predator positions (include hunting fish as threats)
         let predator_info: Vec<(f32, f32, f32, u32)> = fish
             .iter()
             .filter_map(|f| {
                 let g = genomes.get(&f.genome_id)?;
-                if (g.aggression > 0.6 || f.behavior == BehaviorState::Hunting) && f.is_alive {
-                    Some((f.x, f.y, g.body_length, f.id))
+                if (g.aggression > 0.6 || f.behavior == BehaviorState::Hunting) && f.is_alive && !f.is_quarantined {
+                    Some((f.x, f.y, g.max_prey_length(config.predation_size_ratio), f.id))
                 } else {
                     None
                 }
@@ -759,7 +1173,7 @@ impl EcosystemManager {
             .enumerate()
             .filter_map(|(i, f)| {
                 let g = genomes.get(&f.genome_id)?;
-                if f.is_alive && f.can_reproduce(g, tick, config, BASE_LIFESPAN, self.water_quality) {
+                if f.is_alive && !f.is_quarantined && f.can_reproduce(g, tick, config, self.water_quality, light_level) {
                     Some((i, f.x, f.y, f.id, f.genome_id, g.sex, g.body_length))
                 } else {
                     None
@@ -781,11 +1195,12 @@ impl EcosystemManager {
             // Check for nearby predators
             let danger_radius = 80.0 * (1.0 - genome.boldness * 0.5);
             let danger_radius_sq = danger_radius * danger_radius;
-            let has_predator = predator_info.iter().any(|&(px, py, pred_size, pid)| {
+            // Quarantined fish are isolated: nothing hunts them and they can't court
+            let has_predator = !fish[i].is_quarantined && predator_info.iter().any(|&(px, py, max_prey_length, pid)| {
                 if pid == fish[i].id {
                     return false;
                 }
-                if genome.body_length >= pred_size * config.predation_size_ratio {
+                if genome.body_length >= max_prey_length {
                     return false;
                 }
                 let dx = fish[i].x - px;
@@ -794,8 +1209,8 @@ impl EcosystemManager {
             });
 
             // Check for nearby compatible mate
-            let has_mate = if fish[i].behavior == BehaviorState::Satiated {
-                mate_info.iter().find_map(|&(_, mx, my, mid, mgid, msex, _)| {
+            let has_mate = if fish[i].behavior == BehaviorState::Satiated && !fish[i].is_quarantined {
+                let mut candidates = mate_info.iter().filter_map(|&(_, mx, my, mid, mgid, msex, _)| {
                     if mid == fish[i].id || msex == genome.sex {
                         return None;
                     }
@@ -804,13 +1219,17 @@ impl EcosystemManager {
                     if dx * dx + dy * dy > mating_radius_sq {
                         return None;
                     }
-                    if let Some(mg) = genomes.get(&mgid) {
-                        if genome_distance(genome, mg) < config.species_threshold {
-                            return Some(mid);
-                        }
-                    }
-                    None
-                })
+                    let mg = genomes.get(&mgid)?;
+                    (genome_distance(genome, mg, config.distance_weights()) < config.species_threshold).then_some((mid, mg))
+                });
+                match self.conservation.founder_for(genome.id) {
+                    // Protected lineages court the mate closest to their founder
+                    Some(founder) => candidates
+                        .map(|(mid, mg)| (mid, genome_distance(founder, mg, config.distance_weights())))
+                        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                        .map(|(mid, _)| mid),
+                    None => candidates.next().map(|(mid, _)| mid),
+                }
             } else {
                 None
             };
@@ -821,13 +1240,76 @@ impl EcosystemManager {
                 tick,
                 has_predator,
                 has_mate,
-                BASE_LIFESPAN,
+                config.max_lifespan,
                 self.water_quality,
-                time_of_day,
+                light_level,
             );
         }
     }
 
+    /// Offspring in a protected lineage mutate at a reduced rate
+    fn conservation_mutation_scale(&self, a: &FishGenome, b: &FishGenome, config: &SimulationConfig) -> f32 {
+        self.conservation.mutation_scale(a, b, config)
+    }
+
+    /// Count down to the next feeding and drop food: at the feeders if any are
+    /// placed, otherwise at random along the surface
+    fn run_auto_feeder(&mut self, config: &SimulationConfig, rng: &mut impl Rng) {
+        self.auto_feed_timer += 1;
+        if self.auto_feed_timer >= config.auto_feed_interval {
+            self.auto_feed_timer = 0;
+            let feeders: Vec<(f32, f32)> = self.decorations.iter()
+                .filter(|d| matches!(d.decoration_type, DecorationType::Feeder))
+                .map(|d| (d.x, d.y))
+                .collect();
+            if feeders.is_empty() {
+                for _ in 0..config.auto_feed_amount {
+                    let x = rng.gen_range(50.0..config.tank_width - 50.0);
+                    self.food.push(FoodParticle::new(x, 5.0));
+                }
+            } else {
+                // Feeders split the amount; the first ones get any remainder
+                let n = feeders.len() as u32;
+                for (k, &(fx, fy)) in feeders.iter().enumerate() {
+                    let share = config.auto_feed_amount / n + u32::from((k as u32) < config.auto_feed_amount % n);
+                    for _ in 0..share {
+                        let x = (fx + rng.gen_range(-8.0..8.0)).clamp(0.0, config.tank_width);
+                        self.food.push(FoodParticle::new(x, fy.max(5.0)));
+                    }
+                }
+            }
+        }
+    }
+
+    /// A courting fish must stay near a calm partner for the whole display;
+    /// pairs that drift apart or get scattered go back to swimming
+    fn check_courtship_displays(fish: &mut [Fish], config: &SimulationConfig) {
+        let radius_sq = config.courtship_display_radius * config.courtship_display_radius;
+        for i in 0..fish.len() {
+            if fish[i].behavior != BehaviorState::Courting {
+                continue;
+            }
+            let (x, y) = (fish[i].x, fish[i].y);
+            let holding = fish[i].courting_partner
+                .and_then(|pid| fish.iter().find(|p| p.id == pid))
+                .is_some_and(|p| {
+                    let dx = x - p.x;
+                    let dy = y - p.y;
+                    p.is_alive
+                        && !matches!(p.behavior, BehaviorState::Fleeing | BehaviorState::Dying)
+                        && dx * dx + dy * dy <= radius_sq
+                });
+            if !holding {
+                fish[i].behavior = BehaviorState::Swimming;
+                fish[i].courting_partner = None;
+                fish[i].courting_timer = 0;
+            }
+        }
+    }
+
+    /// `fertility_scale` stands in for `config.fertility_scale`, with any
+    /// endangered-population boost already applied
+    #[allow(clippy::too_many_arguments)]
     fn process_reproduction(
         &mut self,
         fish: &mut Vec<Fish>,
@@ -835,7 +1317,13 @@ impl EcosystemManager {
         config: &SimulationConfig,
         tick: u64,
         rng: &mut impl Rng,
+        light_level: f32,
+        fertility_scale: f32,
     ) {
+        // Courtships still running when the season ends come to nothing
+        if !config.breeding_season.allows(light_level) {
+            return;
+        }
         let effective_capacity = (config.base_carrying_capacity as f32 * self.water_quality) as usize;
         if fish.len() >= effective_capacity {
             return;
@@ -845,14 +1333,14 @@ impl EcosystemManager {
         let mut reproduced: Vec<u32> = Vec::new();
 
         for i in 0..fish.len() {
-            if fish[i].behavior != BehaviorState::Courting || fish[i].courting_timer < 90 {
+            if fish[i].behavior != BehaviorState::Courting || fish[i].courting_timer < COURTSHIP_DISPLAY_TICKS {
                 continue;
             }
             let partner_id = match fish[i].courting_partner {
                 Some(id) => id,
                 None => continue,
             };
-            if reproduced.contains(&fish[i].id) || reproduced.contains(&partner_id) {
+            if reproduced.contains(&fish[i].id) || reproduced.contains(&partner_id) || fish[i].is_quarantined {
                 continue;
             }
 
@@ -860,6 +1348,9 @@ impl EcosystemManager {
                 Some(idx) => idx,
                 None => continue,
             };
+            if fish[partner_idx].is_quarantined {
+                continue;
+            }
 
             let genome_a = match genomes.get(&fish[i].genome_id) {
                 Some(g) => g.clone(),
@@ -870,22 +1361,52 @@ impl EcosystemManager {
                 None => continue,
             };
 
+            // Female choice, made once per courtship on the first tick after the
+            // display that the pair gets this far, and shared with the partner
+            // so neither side rolls it again
+            if config.courtship_display_enabled {
+                let mutual = fish[partner_idx].courting_partner == Some(fish[i].id);
+                let decided = fish[i].courtship_accepted
+                    .or(if mutual { fish[partner_idx].courtship_accepted } else { None });
+                let accepted = decided.unwrap_or_else(|| {
+                    let (female, male, male_idx) = if genome_a.sex == Sex::Female {
+                        (&genome_a, &genome_b, partner_idx)
+                    } else {
+                        (&genome_b, &genome_a, i)
+                    };
+                    female_accepts(female, male, &fish[male_idx], config, rng)
+                });
+                fish[i].courtship_accepted = Some(accepted);
+                if mutual {
+                    fish[partner_idx].courtship_accepted = Some(accepted);
+                }
+                if !accepted {
+                    for idx in [i, partner_idx] {
+                        fish[idx].behavior = BehaviorState::Swimming;
+                        fish[idx].courting_partner = None;
+                        fish[idx].courting_timer = 0;
+                        fish[idx].courtship_accepted = None;
+                    }
+                    continue;
+                }
+            }
+
             // Fertility roll
-            let fertility_avg = (genome_a.fertility + genome_b.fertility) / 2.0;
-            if rng.gen::<f32>() > fertility_avg * config.fertility_scale {
+            let wellness = |f: &Fish, g: &FishGenome| {
+                f.vigor(g, config) * (1.0 - config.boredom_fertility_penalty * f.boredom(config))
+            };
+            let fertility_avg = (genome_a.fertility * wellness(&fish[i], &genome_a)
+                + genome_b.fertility * wellness(&fish[partner_idx], &genome_b)) / 2.0;
+            if rng.gen::<f32>() > fertility_avg * fertility_scale {
                 continue;
             }
 
-            // Inbreeding check (share a parent = inbred for simplicity)
-            let inbred = genome_a.parent_a.is_some()
-                && (genome_a.parent_a == genome_b.parent_a
-                    || genome_a.parent_a == genome_b.parent_b
-                    || genome_a.parent_b == genome_b.parent_a
-                    || genome_a.parent_b == genome_b.parent_b);
+            // Inbreeding check over shared ancestors within the configured depth
+            let related = relatedness(&genome_a, &genome_b, genomes, config.inbreeding_check_depth);
 
-            let child_genome = FishGenome::inherit(&genome_a, &genome_b, rng, inbred, config.mutation_rate_large, config.mutation_rate_small);
+            let scale = self.conservation_mutation_scale(&genome_a, &genome_b, config);
 
-            // Spawn egg at parents' midpoint, snapped near sand floor or nearest decoration
+            // Spawn eggs at parents' midpoint, snapped near sand floor or nearest decoration
             let mid_x = (fish[i].x + fish[partner_idx].x) / 2.0;
             let mut egg_y = config.tank_height - 40.0; // default: sand floor
             // Try to find nearest decoration for egg placement
@@ -900,27 +1421,44 @@ impl EcosystemManager {
                 }
             }
 
-            let egg = Egg {
-                id: next_egg_id(),
-                genome_id: child_genome.id,
-                x: mid_x,
-                y: egg_y,
-                age: 0,
-                parent_a_genome: genome_a.id,
-                parent_b_genome: genome_b.id,
-            };
+            // Careless pairs scatter a clutch; careful ones lay a single egg
+            let clutch = config.clutch_size((genome_a.parental_care + genome_b.parental_care) / 2.0);
+            for k in 0..clutch {
+                if k > 0 && fish.len() + self.eggs.len() + new_eggs.len() >= effective_capacity {
+                    break;
+                }
+                let mut child_genome = FishGenome::inherit(
+                    &genome_a, &genome_b, rng, related,
+                    config.appearance_mutation().scaled(scale, scale), config.functional_mutation().scaled(scale, scale),
+                    &config.trait_linkage,
+                );
+                child_genome.drift(config.genetic_drift_rate * scale, rng);
+                self.conservation.register_child(&child_genome);
+
+                let spread = (k as f32 - (clutch - 1) as f32 / 2.0) * CLUTCH_SPACING;
+                let egg = Egg {
+                    id: next_egg_id(),
+                    genome_id: child_genome.id,
+                    x: (mid_x + spread).clamp(0.0, config.tank_width),
+                    y: egg_y,
+                    age: 0,
+                    parent_a_genome: genome_a.id,
+                    parent_b_genome: genome_b.id,
+                };
+                new_eggs.push((egg, child_genome));
+            }
 
             reproduced.push(fish[i].id);
             reproduced.push(partner_id);
             fish[i].last_reproduced_tick = Some(tick);
             fish[partner_idx].last_reproduced_tick = Some(tick);
+            fish[i].offspring_count += 1;
+            fish[partner_idx].offspring_count += 1;
             fish[i].behavior = BehaviorState::Swimming;
             fish[i].courting_partner = None;
             fish[partner_idx].behavior = BehaviorState::Swimming;
             fish[partner_idx].courting_partner = None;
 
-            new_eggs.push((egg, child_genome));
-
             if fish.len() + self.eggs.len() + new_eggs.len() >= effective_capacity {
                 break;
             }
@@ -951,12 +1489,16 @@ impl EcosystemManager {
         // Hatch in reverse order for safe removal
         hatched_indices.sort_unstable();
         hatched_indices.reverse();
+        let cover = self.obstacle_positions();
         for idx in hatched_indices {
             let egg = self.eggs.swap_remove(idx);
             if genomes.contains_key(&egg.genome_id) {
-                let mut child = Fish::new(egg.genome_id, egg.x, egg.y, rng);
+                let (x, y) = Self::hatch_position(&egg, fish, &cover, config);
+                let escort = Self::escort_ticks(&egg, fish, genomes, config);
+                let mut child = Fish::new(egg.genome_id, x, y, rng);
                 child.is_juvenile = true;
                 child.juvenile_timer = 0;
+                child.spawn_protection = config.spawn_protection_ticks + escort;
                 self.events.push(SimEvent::Birth {
                     fish_id: child.id,
                     genome_id: egg.genome_id,
@@ -968,10 +1510,89 @@ impl EcosystemManager {
         }
     }
 
+    /// Extra protection a hatchling gets from its most caring living parent,
+    /// which escorts it while it finds its fins
+    fn escort_ticks(egg: &Egg, fish: &[Fish], genomes: &std::collections::HashMap<u32, FishGenome>, config: &SimulationConfig) -> u32 {
+        if !config.parental_care_enabled {
+            return 0;
+        }
+        let care = fish.iter()
+            .filter(|f| f.is_alive && (f.genome_id == egg.parent_a_genome || f.genome_id == egg.parent_b_genome))
+            .filter_map(|f| genomes.get(&f.genome_id))
+            .map(|g| g.parental_care)
+            .fold(0.0_f32, f32::max);
+        (config.escort_protection_ticks as f32 * care).round() as u32
+    }
+
+    /// Point each caring parent stays near: its closest egg, else its closest
+    /// hatchling still under spawn protection. Everyone else guards nothing.
+    fn update_guarding(&self, fish: &mut [Fish], genomes: &std::collections::HashMap<u32, FishGenome>, config: &SimulationConfig) {
+        // (parent genome ids, x, y) of everything that can be guarded
+        let mut wards: Vec<([u32; 2], f32, f32)> = self.eggs.iter()
+            .map(|e| ([e.parent_a_genome, e.parent_b_genome], e.x, e.y))
+            .collect();
+        wards.extend(fish.iter()
+            .filter(|f| f.is_alive && f.is_juvenile && f.spawn_protection > 0)
+            .filter_map(|f| {
+                let g = genomes.get(&f.genome_id)?;
+                Some(([g.parent_a?, g.parent_b?], f.x, f.y))
+            }));
+
+        // Eggs come first in `wards` and are preferred over hatchlings
+        let egg_count = self.eggs.len();
+        for f in fish.iter_mut() {
+            f.guarding = None;
+            if !config.parental_care_enabled || !f.is_alive || f.is_quarantined || wards.is_empty() {
+                continue;
+            }
+            if !genomes.get(&f.genome_id).is_some_and(|g| g.parental_care > 0.0) {
+                continue;
+            }
+            let (gid, x, y) = (f.genome_id, f.x, f.y);
+            let nearest = |range: std::ops::Range<usize>| wards[range].iter()
+                .filter(|w| w.0.contains(&gid))
+                .map(|w| (w.1, w.2))
+                .min_by(|a, b| {
+                    let da = (a.0 - x).powi(2) + (a.1 - y).powi(2);
+                    let db = (b.0 - x).powi(2) + (b.1 - y).powi(2);
+                    da.total_cmp(&db)
+                });
+            f.guarding = nearest(0..egg_count).or_else(|| nearest(egg_count..wards.len()));
+        }
+    }
+
+    /// Where a hatchling emerges: tucked in beside the nearest decoration within
+    /// `hatch_shelter_radius`, else beside the nearest living parent in that
+    /// range, else where the egg lay
+    fn hatch_position(egg: &Egg, fish: &[Fish], cover: &[(f32, f32, f32)], config: &SimulationConfig) -> (f32, f32) {
+        let reach = config.hatch_shelter_radius;
+        let gap = |x: f32, y: f32, r: f32| ((egg.x - x).powi(2) + (egg.y - y).powi(2)).sqrt() - r;
+        let shelter = cover.iter()
+            .map(|&(x, y, r)| (x, y, r, gap(x, y, r)))
+            .filter(|c| c.3 <= reach)
+            .min_by(|a, b| a.3.total_cmp(&b.3));
+        if let Some((x, y, r, _)) = shelter {
+            let (dx, dy) = (egg.x - x, egg.y - y);
+            let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+            let offset = r + config.shelter_radius * 0.5;
+            return config.clamp_to_tank(x + dx / dist * offset, y + dy / dist * offset, 10.0);
+        }
+        let parent = fish.iter()
+            .filter(|f| f.is_alive && (f.genome_id == egg.parent_a_genome || f.genome_id == egg.parent_b_genome))
+            .map(|f| (f.x, f.y, gap(f.x, f.y, 0.0)))
+            .filter(|p| p.2 <= reach)
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        match parent {
+            Some((x, y, _)) => (x, y),
+            None => (egg.x, egg.y),
+        }
+    }
+
     fn process_egg_predation(
         &mut self,
         fish: &[Fish],
         genomes: &std::collections::HashMap<u32, FishGenome>,
+        config: &SimulationConfig,
     ) {
         if self.eggs.is_empty() { return; }
 
@@ -984,9 +1605,22 @@ impl EcosystemManager {
             })
             .collect();
 
+        // Caring parents fend egg-eaters off their own eggs within a care-scaled radius
+        let guards: Vec<(u32, f32, f32, f32)> = if config.parental_care_enabled {
+            fish.iter()
+                .filter(|f| f.is_alive && f.behavior != BehaviorState::Dying && !f.is_quarantined)
+                .filter_map(|f| {
+                    let reach = config.nest_guard_radius * genomes.get(&f.genome_id)?.parental_care;
+                    (reach > 0.0).then_some((f.genome_id, f.x, f.y, reach))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         // Aggressive large fish eat nearby eggs
         let predator_ids: Vec<(f32, f32)> = fish.iter()
-            .filter(|f| f.is_alive && f.behavior != BehaviorState::Dying)
+            .filter(|f| f.is_alive && f.behavior != BehaviorState::Dying && !f.is_quarantined)
             .filter_map(|f| {
                 let g = genomes.get(&f.genome_id)?;
                 if g.aggression > 0.7 && g.body_length > 1.2 {
@@ -1004,13 +1638,17 @@ impl EcosystemManager {
                 let dy = egg.y - cy;
                 dx * dx + dy * dy < r * r
             });
+            let guarded = guards.iter().any(|&(gid, gx, gy, reach)| {
+                (gid == egg.parent_a_genome || gid == egg.parent_b_genome)
+                    && (egg.x - gx).powi(2) + (egg.y - gy).powi(2) < reach * reach
+            });
 
             for &(px, py) in &predator_ids {
                 let dx = egg.x - px;
                 let dy = egg.y - py;
                 if dx * dx + dy * dy < 20.0 * 20.0 {
                     // Eggs in territory have 50% chance of surviving (territory defense)
-                    if in_territory { return true; } // guarded — safe from this predator
+                    if in_territory || guarded { return true; } // guarded — safe from this predator
                     return false; // eaten
                 }
             }
@@ -1018,14 +1656,69 @@ impl EcosystemManager {
         });
     }
 
+    /// Evenly matched aggressive fish that crowd each other occasionally
+    /// skirmish: both are stressed and lose a little health and energy, but
+    /// neither is killed. Grid indices match fish order this tick.
+    fn process_fin_nipping(
+        fish: &mut [Fish],
+        genomes: &std::collections::HashMap<u32, FishGenome>,
+        config: &SimulationConfig,
+        rng: &mut impl Rng,
+        grid: &SpatialGrid,
+    ) {
+        let willing = |f: &Fish, g: &FishGenome| {
+            // Crowding stress makes fish more aggressive than their genes alone
+            let aggression = if config.crowding_enabled {
+                g.aggression + f.stress * config.crowding_aggression_bonus
+            } else {
+                g.aggression
+            };
+            f.is_alive
+                && f.behavior != BehaviorState::Dying
+                && !f.is_juvenile
+                && !f.is_quarantined
+                && f.health > FIN_NIP_HEALTH_FLOOR
+                && aggression >= config.fin_nip_aggression
+        };
+        let radius_sq = config.fin_nip_radius * config.fin_nip_radius;
+
+        for i in 0..fish.len() {
+            let Some(a) = genomes.get(&fish[i].genome_id) else { continue };
+            if !willing(&fish[i], a) { continue; }
+            for j in grid.neighbors(fish[i].x, fish[i].y, config.fin_nip_radius) {
+                if j <= i || j >= fish.len() { continue; }
+                let Some(b) = genomes.get(&fish[j].genome_id) else { continue };
+                if !willing(&fish[j], b) { continue; }
+                // Only fish too close in size for either to eat the other
+                if b.body_length < a.max_prey_length(config.predation_size_ratio)
+                    || a.body_length < b.max_prey_length(config.predation_size_ratio)
+                {
+                    continue;
+                }
+                let dx = fish[j].x - fish[i].x;
+                let dy = fish[j].y - fish[i].y;
+                if dx * dx + dy * dy >= radius_sq || rng.gen::<f32>() >= config.fin_nip_chance {
+                    continue;
+                }
+                for k in [i, j] {
+                    let f = &mut fish[k];
+                    f.health = (f.health - config.fin_nip_damage).max(FIN_NIP_HEALTH_FLOOR);
+                    f.energy = (f.energy - config.fin_nip_damage).max(0.0);
+                    f.stress = (f.stress + config.fin_nip_stress).min(1.0);
+                }
+            }
+        }
+    }
+
     fn process_territories(
         fish: &mut [Fish],
         genomes: &std::collections::HashMap<u32, FishGenome>,
         config: &SimulationConfig,
     ) {
         // Territorial fish: low school_affinity (<0.3) + moderate aggression (>0.4)
-        // They claim a territory around a point and defend it.
-        // Territory is claimed once and kept until death.
+        // They claim a territory around a point and defend it. Claim strength fades
+        // while the holder is away and recovers (twice as fast) once it returns;
+        // territories are abandoned at zero strength or when the holder is too weak.
 
         // Collect existing territory centers for intruder checks
         let territory_snap: Vec<(u32, Option<(f32, f32)>, f32, u32)> = fish
@@ -1047,15 +1740,34 @@ impl EcosystemManager {
                 // Not territorial — clear any claimed territory
                 fish[i].territory_center = None;
                 fish[i].territory_radius = 0.0;
+                fish[i].territory_strength = 0.0;
                 continue;
             }
 
-            // Claim territory if not yet claimed
-            if fish[i].territory_center.is_none() && !fish[i].is_juvenile {
+            // Decay or reinforce an existing claim, abandoning it if it lapses
+            if let Some((cx, cy)) = fish[i].territory_center {
+                let dx = fish[i].x - cx;
+                let dy = fish[i].y - cy;
+                let inside = dx * dx + dy * dy <= fish[i].territory_radius * fish[i].territory_radius;
+                fish[i].territory_strength = if inside {
+                    (fish[i].territory_strength + config.territory_decay_rate * 2.0).min(1.0)
+                } else {
+                    fish[i].territory_strength - config.territory_decay_rate
+                };
+                if fish[i].territory_strength <= 0.0 || fish[i].health < config.territory_abandon_health {
+                    fish[i].territory_center = None;
+                    fish[i].territory_radius = 0.0;
+                    fish[i].territory_strength = 0.0;
+                }
+            }
+
+            // Claim territory if not yet claimed; a lapsed holder re-claims where it now is
+            if fish[i].territory_center.is_none() && !fish[i].is_juvenile && fish[i].health >= config.territory_abandon_health {
                 // Claim at current position
                 let radius = config.territory_claim_radius * genome.body_length;
                 fish[i].territory_center = Some((fish[i].x, fish[i].y));
                 fish[i].territory_radius = radius;
+                fish[i].territory_strength = 1.0;
             }
 
             // Intruder detection: if fish has territory, check for intruders of different species
@@ -1073,7 +1785,7 @@ impl EcosystemManager {
                     };
 
                     // Only react to different species
-                    if genome_distance(genome, other_genome) < config.species_threshold {
+                    if genome_distance(genome, other_genome, config.distance_weights()) < config.species_threshold {
                         continue;
                     }
 
@@ -1082,6 +1794,7 @@ impl EcosystemManager {
                     if dx * dx + dy * dy < radius_sq {
                         // Intruder detected — chase them if aggressive enough, else posture
                         if genome.aggression > 0.7
+                            && fish[i].hunt_cooldown == 0
                             && fish[i].behavior != BehaviorState::Hunting
                             && fish[i].behavior != BehaviorState::Fleeing
                         {
@@ -1123,7 +1836,7 @@ impl EcosystemManager {
 
         for i in 0..n {
             for j in (i + 1)..n {
-                let d = genome_distance(living[i], living[j]);
+                let d = genome_distance(living[i], living[j], config.distance_weights());
                 if d < config.species_threshold {
                     // Union
                     let ci = find_root(&cluster, i);
@@ -1202,6 +1915,8 @@ impl EcosystemManager {
                     centroid_pattern: pattern_str,
                     member_count: members.len() as u32,
                     member_genome_ids: members.iter().map(|&i| living[i].id).collect(),
+                    distinct_marker: None,
+                    fossil: None,
                 });
                 self.events.push(SimEvent::NewSpecies { species_id });
                 matched_species.push(species_id);
@@ -1212,6 +1927,8 @@ impl EcosystemManager {
         for sp in &mut self.species {
             if sp.extinct_at_tick.is_none() && !matched_species.contains(&sp.id) {
                 sp.extinct_at_tick = Some(tick);
+                let last: Vec<&FishGenome> = sp.member_genome_ids.iter().filter_map(|id| genomes.get(id)).collect();
+                sp.fossil = FishGenome::average(&last);
                 self.events.push(SimEvent::Extinction { species_id: sp.id });
             }
         }
@@ -1223,9 +1940,38 @@ impl EcosystemManager {
                 None => true,
             }
         });
+
+        self.assign_species_markers();
     }
 
-    fn process_disease(
+    /// Give every living species a marker no other living species has while
+    /// markers last, then the least-shared one. Species keep their marker for
+    /// life; older species win when two collide (e.g. after loading a save).
+    pub fn assign_species_markers(&mut self) {
+        let mut usage = vec![0u32; SPECIES_MARKERS.len()];
+        let mut order: Vec<usize> = (0..self.species.len())
+            .filter(|&i| self.species[i].extinct_at_tick.is_none())
+            .collect();
+        order.sort_by_key(|&i| self.species[i].id);
+
+        let mut unassigned = Vec::new();
+        for &i in &order {
+            let kept = self.species[i].distinct_marker.as_deref()
+                .and_then(|m| SPECIES_MARKERS.iter().position(|&k| k == m))
+                .filter(|&k| usage[k] == 0);
+            match kept {
+                Some(k) => usage[k] += 1,
+                None => unassigned.push(i),
+            }
+        }
+        for i in unassigned {
+            let k = (0..SPECIES_MARKERS.len()).min_by_key(|&k| usage[k]).unwrap_or(0);
+            usage[k] += 1;
+            self.species[i].distinct_marker = Some(SPECIES_MARKERS[k].to_string());
+        }
+    }
+
+    fn process_disease(
         &mut self,
         fish: &mut [Fish],
         genomes: &std::collections::HashMap<u32, FishGenome>,
@@ -1239,24 +1985,24 @@ impl EcosystemManager {
             if !f.is_alive || f.is_infected || f.recovery_timer > 0 {
                 continue;
             }
-            let resistance = genomes.get(&f.genome_id).map(|g| g.disease_resistance).unwrap_or(0.5);
+            let resistance = genomes.get(&f.genome_id).map(|g| f.disease_resistance(g, config)).unwrap_or(0.5);
             if rng.gen::<f32>() < config.disease_spontaneous_chance * (1.0 - resistance) {
                 f.is_infected = true;
                 f.infection_timer = 0;
             }
         }
 
-        // Spreading: infected fish infect nearby fish
+        // Spreading: infected fish infect nearby fish (quarantined fish neither spread nor catch it)
         let infected_positions: Vec<(f32, f32)> = fish.iter()
-            .filter(|f| f.is_alive && f.is_infected)
+            .filter(|f| f.is_alive && f.is_infected && !f.is_quarantined)
             .map(|f| (f.x, f.y))
             .collect();
 
         for f in fish.iter_mut() {
-            if !f.is_alive || f.is_infected || f.recovery_timer > 0 {
+            if !f.is_alive || f.is_infected || f.recovery_timer > 0 || f.is_quarantined {
                 continue;
             }
-            let resistance = genomes.get(&f.genome_id).map(|g| g.disease_resistance).unwrap_or(0.5);
+            let resistance = genomes.get(&f.genome_id).map(|g| f.disease_resistance(g, config)).unwrap_or(0.5);
             for &(ix, iy) in &infected_positions {
                 let dx = f.x - ix;
                 let dy = f.y - iy;
@@ -1275,6 +2021,7 @@ impl EcosystemManager {
             if !f.is_alive {
                 continue;
             }
+            f.acquired_immunity = (f.acquired_immunity - config.immunity_decay).max(0.0);
             if f.is_infected {
                 f.infection_timer += 1;
                 f.health -= config.disease_damage;
@@ -1284,6 +2031,7 @@ impl EcosystemManager {
                     f.is_infected = false;
                     f.infection_timer = 0;
                     f.recovery_timer = config.disease_duration / 2; // temporary immunity
+                    f.acquire_immunity(config);
                 }
             } else if f.recovery_timer > 0 {
                 f.recovery_timer -= 1;
@@ -1335,8 +2083,8 @@ impl EcosystemManager {
             return Err("Must be opposite sex".to_string());
         }
 
-        let age_frac_a = fish[a_idx].age_fraction(&genome_a, BASE_LIFESPAN);
-        let age_frac_b = fish[b_idx].age_fraction(&genome_b, BASE_LIFESPAN);
+        let age_frac_a = fish[a_idx].age_fraction(&genome_a, config.max_lifespan);
+        let age_frac_b = fish[b_idx].age_fraction(&genome_b, config.max_lifespan);
         if age_frac_a < genome_a.maturity_age || age_frac_b < genome_b.maturity_age {
             return Err("Both fish must be mature".to_string());
         }
@@ -1345,17 +2093,20 @@ impl EcosystemManager {
         }
 
         // Cross-species: higher mutation rate
-        let cross_species = genome_distance(&genome_a, &genome_b) >= config.species_threshold;
-        let large_rate = if cross_species { config.mutation_rate_large * 2.0 } else { config.mutation_rate_large };
-        let small_rate = if cross_species { config.mutation_rate_small * 1.5 } else { config.mutation_rate_small };
+        let cross_species = genome_distance(&genome_a, &genome_b, config.distance_weights()) >= config.species_threshold;
+        let (large, small) = if cross_species { (2.0, 1.5) } else { (1.0, 1.0) };
 
-        let inbred = genome_a.parent_a.is_some()
-            && (genome_a.parent_a == genome_b.parent_a
-                || genome_a.parent_a == genome_b.parent_b
-                || genome_a.parent_b == genome_b.parent_a
-                || genome_a.parent_b == genome_b.parent_b);
+        let related = relatedness(&genome_a, &genome_b, genomes, config.inbreeding_check_depth);
 
-        let child_genome = FishGenome::inherit(&genome_a, &genome_b, rng, inbred, large_rate, small_rate);
+        let scale = self.conservation_mutation_scale(&genome_a, &genome_b, config);
+        let (large, small) = (large * scale, small * scale);
+        let mut child_genome = FishGenome::inherit(
+            &genome_a, &genome_b, rng, related,
+            config.appearance_mutation().scaled(large, small), config.functional_mutation().scaled(large, small),
+            &config.trait_linkage,
+        );
+        child_genome.drift(config.genetic_drift_rate * scale, rng);
+        self.conservation.register_child(&child_genome);
 
         let mid_x = (fish[a_idx].x + fish[b_idx].x) / 2.0;
         let mut egg_y = config.tank_height - 40.0;
@@ -1383,6 +2134,8 @@ impl EcosystemManager {
 
         fish[a_idx].last_reproduced_tick = Some(tick);
         fish[b_idx].last_reproduced_tick = Some(tick);
+        fish[a_idx].offspring_count += 1;
+        fish[b_idx].offspring_count += 1;
 
         genomes.insert(child_genome.id, child_genome);
         self.eggs.push(egg);
@@ -1395,6 +2148,33 @@ impl EcosystemManager {
     }
 }
 
+/// Strike-odds multiplier for prey surrounded by `school_weight` proximity-weighted
+/// schoolmates: 1.0 for a lone fish, easing toward 1 - confusion_max_reduction
+fn confusion_multiplier(school_weight: f32, config: &SimulationConfig) -> f32 {
+    let saturation = school_weight / (school_weight + config.confusion_half_saturation);
+    1.0 - config.confusion_max_reduction * saturation
+}
+
+/// Whether a female accepts a displaying male: he must be in good enough
+/// condition, and the less he resembles her the likelier she turns him down
+fn female_accepts(female: &FishGenome, male: &FishGenome, male_fish: &Fish, config: &SimulationConfig, rng: &mut impl Rng) -> bool {
+    let condition = (male_fish.health + male_fish.energy) / 2.0;
+    if condition < config.courtship_min_condition {
+        return false;
+    }
+    let dissimilarity = genome_distance(female, male, config.distance_weights()) / config.species_threshold;
+    rng.gen::<f32>() >= config.courtship_choosiness * dissimilarity
+}
+
+/// Whether a fish at (x, y) is within `shelter_radius` of a decoration's edge
+fn is_sheltered(x: f32, y: f32, obstacles: &[(f32, f32, f32)], shelter_radius: f32) -> bool {
+    obstacles.iter().any(|&(ox, oy, radius)| {
+        let dx = x - ox;
+        let dy = y - oy;
+        (dx * dx + dy * dy).sqrt() - radius <= shelter_radius
+    })
+}
+
 fn find_root(cluster: &[usize], mut i: usize) -> usize {
     while cluster[i] != i {
         i = cluster[i];
@@ -1472,6 +2252,37 @@ mod tests {
         assert!(food.is_expired(&config));
     }
 
+    #[test]
+    fn predicted_landing_matches_simulated_pellet() {
+        let config = SimulationConfig { food_decay_ticks: 5000, ..SimulationConfig::default() };
+        let (px, py) = FoodParticle::predict_landing(300.0, 20.0, FoodType::Pellet, &config, 40);
+
+        let mut eco = EcosystemManager::new();
+        eco.drop_food(300.0, 20.0);
+        let mut tick = 40;
+        while !eco.food[0].on_floor {
+            eco.food[0].update(&config, tick);
+            tick += 1;
+        }
+        assert_eq!((px, py), (eco.food[0].x, eco.food[0].y));
+        assert!((py - (config.tank_height - 30.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn predicted_landing_stops_at_decay() {
+        let config = SimulationConfig::default();
+        let (_, y) = FoodParticle::predict_landing(300.0, 5.0, FoodType::Flake, &config, 0);
+        // 0.1 per tick for food_decay_ticks ticks, well short of the floor
+        assert!((y - (5.0 + 0.1 * config.food_decay_ticks as f32)).abs() < 0.1);
+    }
+
+    #[test]
+    fn live_food_landing_is_wander_bound() {
+        let config = SimulationConfig::default();
+        let (x, y) = FoodParticle::predict_landing(-50.0, 0.0, FoodType::LiveFood, &config, 0);
+        assert_eq!((x, y), (10.0, 10.0));
+    }
+
     #[test]
     fn live_food_never_settles() {
         let config = SimulationConfig::default();
@@ -1518,6 +2329,7 @@ mod tests {
             DecorationType::TallPlant,
             DecorationType::ShortPlant,
             DecorationType::Coral,
+            DecorationType::Feeder,
         ];
         for dt in &types {
             let s = dt.as_str();
@@ -1562,6 +2374,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn feeders_split_auto_feed_at_their_positions() {
+        let config = SimulationConfig { auto_feed_interval: 1, auto_feed_amount: 5, ..SimulationConfig::default() };
+        let mut rng = seeded_rng();
+        let mut eco = EcosystemManager::new();
+        eco.run_auto_feeder(&config, &mut rng);
+        assert_eq!(eco.food.len(), 5, "Without feeders food still drops at random");
+
+        eco.food.clear();
+        eco.add_decoration(DecorationType::Feeder, 200.0, 700.0, 1.0, false);
+        eco.add_decoration(DecorationType::Feeder, 900.0, 700.0, 1.0, false);
+        eco.run_auto_feeder(&config, &mut rng);
+        let near = |x: f32| eco.food.iter().filter(|f| (f.x - x).abs() <= 8.0 && f.y == 700.0).count();
+        assert_eq!((near(200.0), near(900.0)), (3, 2));
+    }
+
     #[test]
     fn add_remove_decoration() {
         let mut eco = EcosystemManager::new();
@@ -1613,6 +2441,971 @@ mod tests {
         assert!(eco.water_quality > 0.5, "Plants should help water recovery");
     }
 
+    #[test]
+    fn bacterial_bloom_speeds_recovery_only_in_critical_water() {
+        let mut config = SimulationConfig::default();
+        let recovered = |config: &SimulationConfig, quality: f32| {
+            let mut eco = EcosystemManager::new();
+            eco.water_quality = quality;
+            eco.update_water_quality(0, config);
+            eco.water_quality - quality
+        };
+        let base = config.water_recovery_rate;
+        assert!((recovered(&config, 0.5) - base).abs() < 1e-7);
+        assert!((recovered(&config, 0.0) - base * (1.0 + config.water_resilience_strength)).abs() < 1e-7);
+        assert!(recovered(&config, 0.05) > recovered(&config, 0.15));
+
+        config.water_resilience_enabled = false;
+        assert!((recovered(&config, 0.0) - base).abs() < 1e-7);
+    }
+
+    // --- Crowding ---
+
+    fn crowd(count: usize, spacing: f32) -> (Vec<Fish>, std::collections::HashMap<u32, FishGenome>) {
+        let mut rng = seeded_rng();
+        let mut fish = Vec::new();
+        let mut genomes = std::collections::HashMap::new();
+        for i in 0..count {
+            let g = FishGenome::random(&mut rng);
+            let f = Fish::new(g.id, 400.0 + i as f32 * spacing, 400.0, &mut rng);
+            genomes.insert(g.id, g);
+            fish.push(f);
+        }
+        (fish, genomes)
+    }
+
+    fn run_behavior(fish: &mut [Fish], genomes: &std::collections::HashMap<u32, FishGenome>, config: &SimulationConfig) {
+        let mut eco = EcosystemManager::new();
+        let mut grid = SpatialGrid::new(config.tank_width, config.tank_height, config.cohesion_radius);
+        grid.rebuild(fish);
+        eco.update_fish_behavior(fish, genomes, config, 1, 1.0, &grid);
+    }
+
+    #[test]
+    fn dense_crowd_builds_stress() {
+        let config = SimulationConfig::default();
+        let (mut fish, genomes) = crowd(12, 2.0);
+        run_behavior(&mut fish, &genomes, &config);
+        assert!(fish.iter().all(|f| f.stress > 0.0), "Packed fish should be stressed");
+    }
+
+    #[test]
+    fn sparse_fish_stay_calm() {
+        let config = SimulationConfig::default();
+        let (mut fish, genomes) = crowd(12, 100.0);
+        run_behavior(&mut fish, &genomes, &config);
+        assert!(fish.iter().all(|f| f.stress == 0.0), "Spread-out fish should not gain stress");
+    }
+
+    #[test]
+    fn crowding_disabled_adds_no_stress() {
+        let config = SimulationConfig { crowding_enabled: false, ..SimulationConfig::default() };
+        let (mut fish, genomes) = crowd(12, 2.0);
+        run_behavior(&mut fish, &genomes, &config);
+        assert!(fish.iter().all(|f| f.stress == 0.0));
+    }
+
+    #[test]
+    fn crowd_at_threshold_stays_calm() {
+        let config = SimulationConfig::default();
+        // Exactly threshold neighbors each (threshold + 1 fish) is tolerated
+        let (mut fish, genomes) = crowd(config.crowding_threshold as usize + 1, 2.0);
+        run_behavior(&mut fish, &genomes, &config);
+        assert!(fish.iter().all(|f| f.stress == 0.0));
+    }
+
+    // --- Fin-nipping ---
+
+    #[test]
+    fn evenly_matched_aggressive_fish_skirmish_without_dying() {
+        let config = SimulationConfig { fin_nip_chance: 1.0, ..SimulationConfig::default() };
+        let (mut fish, mut genomes) = crowd(2, 5.0);
+        for g in genomes.values_mut() {
+            g.aggression = 0.9;
+            g.body_length = 1.0;
+        }
+        for f in &mut fish {
+            f.is_juvenile = false;
+            f.health = 0.12;
+        }
+        let mut grid = SpatialGrid::new(config.tank_width, config.tank_height, config.cohesion_radius);
+        grid.rebuild(&fish);
+        let mut rng = seeded_rng();
+        EcosystemManager::process_fin_nipping(&mut fish, &genomes, &config, &mut rng, &grid);
+        for f in &fish {
+            assert_eq!(f.health, FIN_NIP_HEALTH_FLOOR, "nipped but not killed");
+            assert!(f.stress >= config.fin_nip_stress);
+            assert!(f.is_alive);
+        }
+
+        // A size mismatch is predation's business, not a skirmish
+        let (mut fish, mut genomes) = crowd(2, 5.0);
+        for (k, g) in genomes.values_mut().enumerate() {
+            g.aggression = 0.9;
+            g.body_length = if k == 0 { 0.5 } else { 1.5 };
+        }
+        for f in &mut fish {
+            f.is_juvenile = false;
+        }
+        grid.rebuild(&fish);
+        EcosystemManager::process_fin_nipping(&mut fish, &genomes, &config, &mut rng, &grid);
+        assert!(fish.iter().all(|f| f.stress == 0.0));
+    }
+
+    // --- Enrichment ---
+
+    #[test]
+    fn enrichment_counts_distinct_nearby_decoration_kinds() {
+        let config = SimulationConfig::default();
+        let mut eco = EcosystemManager::new();
+        assert_eq!(eco.enrichment_at(100.0, 100.0, &config), 0.0);
+
+        eco.add_decoration(DecorationType::Rock, 120.0, 100.0, 1.0, false);
+        eco.add_decoration(DecorationType::Rock, 140.0, 100.0, 1.0, false);
+        eco.add_decoration(DecorationType::Feeder, 100.0, 120.0, 1.0, false);
+        let one_kind = eco.enrichment_at(100.0, 100.0, &config);
+        assert!((one_kind - 1.0 / config.enrichment_full_variety as f32).abs() < 1e-6,
+            "Repeats and feeders add nothing");
+
+        eco.add_decoration(DecorationType::Coral, 100.0, 80.0, 1.0, false);
+        eco.add_decoration(DecorationType::TallPlant, 90.0, 100.0, 1.0, false);
+        eco.add_decoration(DecorationType::ShortPlant, 110.0, 100.0, 1.0, false);
+        assert_eq!(eco.enrichment_at(100.0, 100.0, &config), 1.0);
+        assert_eq!(eco.enrichment_at(100.0 + config.enrichment_radius * 2.0, 100.0, &config), 0.0);
+    }
+
+    #[test]
+    fn bored_fish_slowly_lose_health() {
+        let config = SimulationConfig { crowding_enabled: false, ..SimulationConfig::default() };
+        let (mut fish, genomes) = crowd(2, 200.0);
+        let mut eco = EcosystemManager::new();
+        let grid = SpatialGrid::new(config.tank_width, config.tank_height, config.cohesion_radius);
+        for tick in 0..2000 {
+            eco.update_fish_behavior(&mut fish, &genomes, &config, tick, 1.0, &grid);
+        }
+        for f in &fish {
+            assert_eq!(f.enrichment, 0.0);
+            assert_eq!(f.stress, 0.0, "Boredom doesn't feed crowding stress");
+            assert!(f.health < 1.0 && f.health > 0.9, "Drain is slow");
+        }
+
+        let relaxed = SimulationConfig { enrichment_enabled: false, ..config };
+        let (mut fish, genomes) = crowd(2, 200.0);
+        eco.update_fish_behavior(&mut fish, &genomes, &relaxed, 0, 1.0, &grid);
+        assert!(fish.iter().all(|f| f.health == 1.0 && f.boredom(&relaxed) == 0.0));
+    }
+
+    // --- Algae ---
+
+    #[test]
+    fn algae_grows_only_in_light_and_dirty_water() {
+        let config = SimulationConfig::default();
+        let mut patch = AlgaePatch { id: 1, x: 100.0, y: 100.0, size: 0.1 };
+        assert!(!patch.grow(&config, 0.0, 0.5), "No growth at night");
+        assert!(!patch.grow(&config, 1.0, 1.0), "No growth in clean water");
+        let before = patch.size;
+        assert!(patch.grow(&config, 1.0, 0.5));
+        assert!(patch.size > before);
+    }
+
+    #[test]
+    fn algae_dies_back_in_the_dark_and_in_clean_water() {
+        let config = SimulationConfig::default();
+        let mut eco = EcosystemManager::new();
+        eco.water_quality = 1.0;
+        eco.algae.push(AlgaePatch { id: 1, x: 0.0, y: 0.0, size: 0.5 });
+        eco.algae.push(AlgaePatch { id: 2, x: 0.0, y: 0.0, size: config.algae_decay_rate / 2.0 });
+        eco.update_algae(&config, 1.0);
+        assert!((eco.algae[0].size - (0.5 - config.algae_decay_rate)).abs() < 1e-6, "Clean water starves it");
+        assert_eq!(eco.algae.len(), 1, "Patches that die back completely are removed");
+
+        eco.water_quality = 0.5;
+        eco.update_algae(&config, 0.0);
+        assert!(eco.algae[0].size < 0.5 - config.algae_decay_rate, "Darkness starves it too");
+    }
+
+    #[test]
+    fn decayed_food_can_seed_algae() {
+        let config = SimulationConfig { algae_seed_chance: 1.0, ..SimulationConfig::default() };
+        let mut eco = EcosystemManager::new();
+        let mut rng = seeded_rng();
+        eco.seed_algae(200.0, &config, &mut rng);
+        assert_eq!(eco.algae.len(), 1);
+        assert!((eco.algae[0].y - (config.tank_height - 30.0)).abs() < 0.01);
+
+        let capped = SimulationConfig { max_algae_patches: 1, ..config };
+        eco.seed_algae(300.0, &capped, &mut rng);
+        assert_eq!(eco.algae.len(), 1, "Patch cap respected");
+    }
+
+    #[test]
+    fn algae_degrades_water() {
+        let config = SimulationConfig::default();
+        let mut eco = EcosystemManager::new();
+        eco.water_quality = 0.5;
+        eco.algae.push(AlgaePatch { id: 1, x: 0.0, y: 0.0, size: 1.0 });
+        eco.update_algae(&config, 1.0);
+        assert!(eco.water_quality < 0.5);
+    }
+
+    #[test]
+    fn herbivores_graze_algae_carnivores_do_not() {
+        let mut rng = seeded_rng();
+        let mut genomes = std::collections::HashMap::new();
+        let mut grazer_genome = FishGenome::random(&mut rng);
+        grazer_genome.aggression = 0.1;
+        let mut hunter_genome = FishGenome::random(&mut rng);
+        hunter_genome.aggression = 0.9;
+        let mut fish = vec![
+            Fish::new(grazer_genome.id, 100.0, 100.0, &mut rng),
+            Fish::new(hunter_genome.id, 100.0, 100.0, &mut rng),
+        ];
+        for f in &mut fish { f.hunger = 0.8; }
+        genomes.insert(grazer_genome.id, grazer_genome);
+        genomes.insert(hunter_genome.id, hunter_genome);
+
+        let mut eco = EcosystemManager::new();
+        eco.algae.push(AlgaePatch { id: 1, x: 100.0, y: 100.0, size: 0.15 });
+        eco.process_grazing(&mut fish, &genomes);
+        assert!(fish[0].hunger < 0.8, "Herbivore should eat");
+        assert_eq!(fish[1].hunger, 0.8, "Carnivore ignores algae");
+        assert!((eco.algae[0].size - 0.05).abs() < 1e-6);
+
+        // Second bite finishes the patch
+        eco.process_grazing(&mut fish, &genomes);
+        assert!(eco.algae.is_empty());
+    }
+
+    // --- Diet ---
+
+    #[test]
+    fn diet_follows_aggression() {
+        let mut g = FishGenome::random(&mut seeded_rng());
+        g.aggression = 0.1;
+        assert_eq!(Diet::of(&g), Diet::Herbivore);
+        g.aggression = 0.5;
+        assert_eq!(Diet::of(&g), Diet::Omnivore);
+        g.aggression = 0.9;
+        assert_eq!(Diet::of(&g), Diet::Carnivore);
+    }
+
+    #[test]
+    fn preferred_food_satisfies_more() {
+        let mut config = SimulationConfig::default();
+        let herbivore = Diet::Herbivore;
+        assert!(herbivore.nutrition(FoodType::Flake, &config) > FoodType::Flake.nutrition());
+        assert!(herbivore.nutrition(FoodType::LiveFood, &config) < FoodType::LiveFood.nutrition());
+        assert_eq!(herbivore.nutrition(FoodType::Pellet, &config), FoodType::Pellet.nutrition());
+        assert_eq!(Diet::Omnivore.nutrition(FoodType::LiveFood, &config), FoodType::LiveFood.nutrition());
+
+        config.food_preference_enabled = false;
+        assert_eq!(Diet::Carnivore.nutrition(FoodType::Flake, &config), FoodType::Flake.nutrition());
+    }
+
+    #[test]
+    fn feeding_applies_diet_preference() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let mut grazer = FishGenome::random(&mut rng);
+        grazer.aggression = 0.1;
+        let mut hunter = FishGenome::random(&mut rng);
+        hunter.aggression = 0.9;
+        let mut fish = vec![
+            Fish::new(grazer.id, 100.0, 100.0, &mut rng),
+            Fish::new(hunter.id, 300.0, 100.0, &mut rng),
+        ];
+        for f in &mut fish {
+            f.hunger = 1.0;
+            f.behavior = BehaviorState::Foraging;
+        }
+        let genomes = std::collections::HashMap::from([(grazer.id, grazer), (hunter.id, hunter)]);
+
+        let mut eco = EcosystemManager::new();
+        eco.food.push(FoodParticle::new_typed(100.0, 100.0, FoodType::Flake));
+        eco.food.push(FoodParticle::new_typed(300.0, 100.0, FoodType::Flake));
+        eco.process_feeding(&mut fish, &genomes, &config);
+        assert!(eco.food.is_empty());
+        assert!(fish[0].hunger < fish[1].hunger, "Flakes fill a grazer more than a hunter");
+    }
+
+    #[test]
+    fn fish_below_eat_threshold_swim_past_food() {
+        let config = SimulationConfig { eat_hunger_threshold: 0.5, ..SimulationConfig::default() };
+        let mut rng = seeded_rng();
+        let genome = FishGenome::random(&mut rng);
+        let mut fish = vec![Fish::new(genome.id, 100.0, 100.0, &mut rng)];
+        fish[0].behavior = BehaviorState::Swimming;
+        fish[0].hunger = 0.4;
+        let genomes = std::collections::HashMap::from([(genome.id, genome)]);
+
+        let mut eco = EcosystemManager::new();
+        eco.food.push(FoodParticle::new(100.0, 100.0));
+        eco.process_feeding(&mut fish, &genomes, &config);
+        assert_eq!(eco.food.len(), 1, "Not hungry enough to eat");
+        assert_eq!(fish[0].hunger, 0.4);
+
+        fish[0].hunger = 0.6;
+        eco.process_feeding(&mut fish, &genomes, &config);
+        assert!(eco.food.is_empty());
+        assert!(fish[0].hunger < 0.6);
+    }
+
+    // --- Predator confusion ---
+
+    #[test]
+    fn confusion_grows_smoothly_with_school() {
+        let config = SimulationConfig::default();
+        assert_eq!(confusion_multiplier(0.0, &config), 1.0, "Lone fish are fully exposed");
+        let half = confusion_multiplier(config.confusion_half_saturation, &config);
+        assert!((half - (1.0 - config.confusion_max_reduction * 0.5)).abs() < 1e-6);
+        let mut prev = 1.0;
+        for w in 1..20 {
+            let m = confusion_multiplier(w as f32 * 0.5, &config);
+            assert!(m < prev, "Denser schools protect more");
+            assert!(m > 1.0 - config.confusion_max_reduction);
+            prev = m;
+        }
+    }
+
+    // --- Shelter ---
+
+    #[test]
+    fn shelter_extends_past_decoration_edge() {
+        let rock = [(100.0, 100.0, 30.0)];
+        assert!(is_sheltered(100.0, 140.0, &rock, 20.0), "10px past the edge");
+        assert!(!is_sheltered(100.0, 160.0, &rock, 20.0), "30px past the edge");
+        assert!(!is_sheltered(100.0, 100.0, &[], 20.0), "Bare tank offers no cover");
+    }
+
+    // --- Speciation ---
+
+    #[test]
+    fn recompute_species_runs_off_schedule() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let base = FishGenome::random(&mut rng);
+        let mut genomes = std::collections::HashMap::new();
+        let mut fish = Vec::new();
+        for i in 0..4 {
+            let mut g = base.clone();
+            g.id = 1000 + i;
+            fish.push(Fish::new(g.id, 100.0, 100.0, &mut rng));
+            genomes.insert(g.id, g);
+        }
+
+        let mut eco = EcosystemManager::new();
+        assert_eq!(eco.recompute_species(&fish, &genomes, &config, 50), 1);
+        assert_eq!(eco.last_speciation_tick, 50, "Schedule restarts from the forced pass");
+        assert!(eco.events.iter().any(|e| matches!(e, SimEvent::NewSpecies { .. })));
+        assert!(eco.species[0].distinct_marker.is_some());
+    }
+
+    #[test]
+    fn extinct_species_keep_a_fossil_genome() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let base = FishGenome::random(&mut rng);
+        let mut genomes = std::collections::HashMap::new();
+        let mut fish = Vec::new();
+        for i in 0..4 {
+            let mut g = base.clone();
+            g.id = 1000 + i;
+            fish.push(Fish::new(g.id, 100.0, 100.0, &mut rng));
+            genomes.insert(g.id, g);
+        }
+
+        let mut eco = EcosystemManager::new();
+        eco.recompute_species(&fish, &genomes, &config, 50);
+        assert!(eco.species[0].fossil.is_none());
+
+        // The founders die out and an unrelated group moves in; the dead
+        // fish's genomes are still on record
+        let mut newcomer = FishGenome::random(&mut rng);
+        newcomer.base_hue = (base.base_hue + 180.0) % 360.0;
+        newcomer.body_length = if base.body_length > 1.3 { 0.6 } else { 2.0 };
+        let mut others = Vec::new();
+        for i in 0..4 {
+            let mut g = newcomer.clone();
+            g.id = 2000 + i;
+            others.push(Fish::new(g.id, 100.0, 100.0, &mut rng));
+            genomes.insert(g.id, g);
+        }
+        eco.recompute_species(&others, &genomes, &config, 100);
+        let fossil = eco.species[0].fossil.as_ref().expect("fossil captured at extinction");
+        assert_eq!(eco.species[0].extinct_at_tick, Some(100));
+        assert!((fossil.speed - base.speed).abs() < 1e-5);
+    }
+
+    fn bare_species(id: u32, marker: Option<&str>) -> Species {
+        Species {
+            id, name: None, description: None, discovered_at_tick: 0, extinct_at_tick: None,
+            centroid_hue: 0.0, centroid_speed: 1.0, centroid_size: 1.0, centroid_pattern: String::new(),
+            member_count: 3, member_genome_ids: Vec::new(),
+            distinct_marker: marker.map(str::to_string), fossil: None,
+        }
+    }
+
+    #[test]
+    fn species_markers_are_distinct_and_stable() {
+        let mut eco = EcosystemManager::new();
+        eco.species = vec![
+            bare_species(1, Some("star")),
+            bare_species(2, Some("star")),
+            bare_species(3, None),
+        ];
+        eco.species.push(Species { extinct_at_tick: Some(5), ..bare_species(4, Some("circle")) });
+        eco.assign_species_markers();
+
+        assert_eq!(eco.species[0].distinct_marker.as_deref(), Some("star"), "Oldest keeps its marker");
+        let living: std::collections::HashSet<_> = eco.species[..3].iter()
+            .map(|s| s.distinct_marker.clone().unwrap())
+            .collect();
+        assert_eq!(living.len(), 3, "Living species get different markers");
+        assert_eq!(eco.species[3].distinct_marker.as_deref(), Some("circle"), "Extinct species untouched");
+
+        let before: Vec<_> = eco.species.iter().map(|s| s.distinct_marker.clone()).collect();
+        eco.assign_species_markers();
+        let after: Vec<_> = eco.species.iter().map(|s| s.distinct_marker.clone()).collect();
+        assert_eq!(before, after);
+
+        // More species than markers: every marker is used before any repeats
+        eco.species = (1..=SPECIES_MARKERS.len() as u32 + 1).map(|id| bare_species(id, None)).collect();
+        eco.assign_species_markers();
+        let used: std::collections::HashSet<_> = eco.species.iter().map(|s| s.distinct_marker.clone()).collect();
+        assert_eq!(used.len(), SPECIES_MARKERS.len());
+    }
+
+    // --- Parental care ---
+
+    #[test]
+    fn caring_parents_guard_their_own_eggs() {
+        let mut rng = seeded_rng();
+        let (mut egg_eater, mut parent) = pair_genomes(&mut rng);
+        egg_eater.parental_care = 0.0;
+        parent.parental_care = 1.0;
+        let fish = vec![
+            Fish::new(egg_eater.id, 100.0, 500.0, &mut rng),
+            Fish::new(parent.id, 130.0, 500.0, &mut rng),
+        ];
+        let genomes = std::collections::HashMap::from([(egg_eater.id, egg_eater), (parent.id, parent.clone())]);
+        let egg = |id: u32, parent_genome: u32| Egg {
+            id, genome_id: 0, x: 100.0, y: 505.0, age: 0, parent_a_genome: parent_genome, parent_b_genome: 0,
+        };
+
+        let mut eco = EcosystemManager::new();
+        eco.eggs = vec![egg(1, parent.id), egg(2, 9999)];
+        eco.process_egg_predation(&fish, &genomes, &SimulationConfig::default());
+        assert_eq!(eco.eggs.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1], "Only the guarded egg survives");
+
+        let careless = SimulationConfig { parental_care_enabled: false, ..SimulationConfig::default() };
+        eco.eggs = vec![egg(1, parent.id)];
+        eco.process_egg_predation(&fish, &genomes, &careless);
+        assert!(eco.eggs.is_empty());
+    }
+
+    #[test]
+    fn caring_parents_stay_with_their_clutch() {
+        let mut rng = seeded_rng();
+        let mut parent = FishGenome::random(&mut rng);
+        parent.parental_care = 0.8;
+        let mut drifter = FishGenome::random(&mut rng);
+        drifter.parental_care = 0.0;
+        let mut fish = vec![
+            Fish::new(parent.id, 300.0, 300.0, &mut rng),
+            Fish::new(drifter.id, 300.0, 300.0, &mut rng),
+        ];
+        let genomes = std::collections::HashMap::from([(parent.id, parent.clone()), (drifter.id, drifter.clone())]);
+        let mut eco = EcosystemManager::new();
+        eco.eggs = vec![
+            Egg { id: 1, genome_id: 0, x: 500.0, y: 550.0, age: 0, parent_a_genome: parent.id, parent_b_genome: drifter.id },
+            Egg { id: 2, genome_id: 0, x: 320.0, y: 550.0, age: 0, parent_a_genome: parent.id, parent_b_genome: drifter.id },
+        ];
+        eco.update_guarding(&mut fish, &genomes, &SimulationConfig::default());
+        assert_eq!(fish[0].guarding, Some((320.0, 550.0)), "Guards the nearest of its eggs");
+        assert_eq!(fish[1].guarding, None, "No care, no guarding");
+
+        let config = SimulationConfig::default();
+        let careful = fish[0].clone();
+        assert_eq!(EcosystemManager::escort_ticks(&eco.eggs[0], &fish, &genomes, &config), (config.escort_protection_ticks as f32 * 0.8).round() as u32);
+        fish.retain(|f| f.id != careful.id);
+        assert_eq!(EcosystemManager::escort_ticks(&eco.eggs[0], &fish, &genomes, &config), 0, "Orphans get no escort");
+    }
+
+    // --- Quarantine ---
+
+    fn pair_genomes(rng: &mut StdRng) -> (FishGenome, FishGenome) {
+        let mut big = FishGenome::random(rng);
+        big.aggression = 0.95;
+        big.body_length = 2.0;
+        let mut small = FishGenome::random(rng);
+        small.aggression = 0.1;
+        small.body_length = 0.5;
+        (big, small)
+    }
+
+    #[test]
+    fn death_events_carry_age_and_traits() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let (known, _) = pair_genomes(&mut rng);
+        let mut fish = vec![
+            Fish::new(known.id, 100.0, 100.0, &mut rng),
+            Fish::new(known.id, 140.0, 100.0, &mut rng),
+            Fish::new(424_242, 180.0, 100.0, &mut rng),
+        ];
+        fish[0].age = 500;
+        fish[0].is_alive = false;
+        fish[2].is_alive = false;
+        let genomes = std::collections::HashMap::from([(known.id, known.clone())]);
+
+        let mut eco = EcosystemManager::new();
+        eco.remove_dead(&mut fish, &genomes, &config);
+        assert_eq!(fish.len(), 1, "Only the living fish remains");
+        let deaths: Vec<_> = eco.events.iter().filter_map(|e| match e {
+            SimEvent::Death { age, speed, body_length, aggression, .. } => Some((*age, *speed, *body_length, *aggression)),
+            _ => None,
+        }).collect();
+        assert_eq!(deaths[0], (500, Some(known.speed), Some(known.body_length), Some(known.aggression)));
+        // A fish whose genome is gone records no traits rather than zeros
+        assert_eq!((deaths[1].1, deaths[1].2, deaths[1].3), (None, None, None));
+    }
+
+    #[test]
+    fn quarantined_fish_are_not_hunted() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let (big, small) = pair_genomes(&mut rng);
+        let mut fish = vec![
+            Fish::new(big.id, 100.0, 100.0, &mut rng),
+            Fish::new(small.id, 140.0, 100.0, &mut rng),
+        ];
+        fish[1].quarantine();
+        let mut genomes = std::collections::HashMap::new();
+        genomes.insert(big.id, big);
+        genomes.insert(small.id, small);
+
+        let mut eco = EcosystemManager::new();
+        eco.process_predation(&mut fish, &genomes, &config, 1, &mut rng);
+        assert!(fish[0].hunting_target.is_none(), "Quarantined prey is off limits");
+
+        fish[1].release();
+        eco.process_predation(&mut fish, &genomes, &config, 2, &mut rng);
+        assert_eq!(fish[0].hunting_target, Some(fish[1].id));
+    }
+
+    #[test]
+    fn predator_waits_out_hunt_cooldown() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let (big, small) = pair_genomes(&mut rng);
+        let mut fish = vec![
+            Fish::new(big.id, 100.0, 100.0, &mut rng),
+            Fish::new(small.id, 140.0, 100.0, &mut rng),
+        ];
+        fish[0].hunt_cooldown = 10;
+        let mut genomes = std::collections::HashMap::new();
+        genomes.insert(big.id, big);
+        genomes.insert(small.id, small);
+
+        let mut eco = EcosystemManager::new();
+        eco.process_predation(&mut fish, &genomes, &config, 1, &mut rng);
+        assert!(fish[0].hunting_target.is_none(), "Satiated predator doesn't start a hunt");
+
+        fish[0].hunt_cooldown = 0;
+        eco.process_predation(&mut fish, &genomes, &config, 2, &mut rng);
+        assert_eq!(fish[0].hunting_target, Some(fish[1].id));
+    }
+
+    #[test]
+    fn full_predator_digests_before_hunting_again() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let (big, small) = pair_genomes(&mut rng);
+        let capacity = big.stomach_capacity(config.stomach_capacity);
+        let mut fish = vec![
+            Fish::new(big.id, 100.0, 100.0, &mut rng),
+            Fish::new(small.id, 105.0, 100.0, &mut rng),
+        ];
+        let mut genomes = std::collections::HashMap::new();
+        genomes.insert(big.id, big);
+        genomes.insert(small.id, small);
+
+        let mut eco = EcosystemManager::new();
+        for tick in 0..500 {
+            eco.process_predation(&mut fish, &genomes, &config, tick, &mut rng);
+            if !fish[1].is_alive || fish[1].killed_by_predator { break; }
+        }
+        assert!(fish[1].killed_by_predator, "Predator beside its prey should land a strike");
+        assert!((fish[0].stomach_fullness - 0.5).abs() < 1e-5, "A kill fills the stomach by the prey's length");
+
+        // Stuffed to the limit, fresh prey is ignored even off cooldown
+        let prey = Fish::new(fish[1].genome_id, 140.0, 100.0, &mut rng);
+        fish[1] = prey;
+        fish[0].hunt_cooldown = 0;
+        fish[0].behavior = BehaviorState::Swimming;
+        fish[0].stomach_fullness = capacity - 0.2;
+        eco.process_predation(&mut fish, &genomes, &config, 600, &mut rng);
+        assert!(fish[0].hunting_target.is_none(), "A full predator doesn't start a hunt");
+
+        fish[0].stomach_fullness = capacity - 0.6;
+        eco.process_predation(&mut fish, &genomes, &config, 601, &mut rng);
+        assert_eq!(fish[0].hunting_target, Some(fish[1].id));
+    }
+
+    /// Predators fed by the first kill among `hunters` big fish closing on one prey
+    fn fed_hunters(hunters: usize, config: &SimulationConfig) -> usize {
+        let mut rng = seeded_rng();
+        let (big, small) = pair_genomes(&mut rng);
+        let mut fish: Vec<Fish> = (0..hunters)
+            .map(|k| Fish::new(big.id, 100.0 + k as f32 * 4.0, 100.0, &mut rng))
+            .collect();
+        fish.push(Fish::new(small.id, 106.0, 104.0, &mut rng));
+        let prey_id = fish[hunters].id;
+        for f in &mut fish[..hunters] {
+            f.behavior = BehaviorState::Hunting;
+            f.hunting_target = Some(prey_id);
+        }
+        let mut genomes = std::collections::HashMap::new();
+        genomes.insert(big.id, big);
+        genomes.insert(small.id, small);
+
+        let mut eco = EcosystemManager::new();
+        for tick in 0..500 {
+            eco.process_predation(&mut fish, &genomes, config, tick, &mut rng);
+            if fish[hunters].killed_by_predator { break; }
+        }
+        assert!(fish[hunters].killed_by_predator);
+        fish[..hunters].iter().filter(|f| f.hunt_cooldown > 0).count()
+    }
+
+    #[test]
+    fn pack_size_is_capped() {
+        let config = SimulationConfig::default();
+        assert_eq!(fed_hunters(4, &config), 4, "A full pack shares the kill");
+
+        let capped = SimulationConfig { pack_max_size: 2, ..SimulationConfig::default() };
+        assert_eq!(fed_hunters(4, &capped), 2);
+
+        let solitary = SimulationConfig { pack_max_size: 1, ..SimulationConfig::default() };
+        assert_eq!(fed_hunters(4, &solitary), 1, "Lone hunters don't share");
+    }
+
+    #[test]
+    fn courting_prey_draws_predators() {
+        let hunt = |conspicuousness: f32| {
+            let config = SimulationConfig { courtship_conspicuousness: conspicuousness, ..SimulationConfig::default() };
+            let mut rng = seeded_rng();
+            let (big, small) = pair_genomes(&mut rng);
+            let mut fish = vec![
+                Fish::new(big.id, 100.0, 100.0, &mut rng),
+                Fish::new(small.id, 145.0, 100.0, &mut rng),
+                Fish::new(small.id, 100.0, 160.0, &mut rng),
+            ];
+            fish[2].behavior = BehaviorState::Courting;
+            let mut genomes = std::collections::HashMap::new();
+            genomes.insert(big.id, big);
+            genomes.insert(small.id, small);
+
+            let mut eco = EcosystemManager::new();
+            eco.process_predation(&mut fish, &genomes, &config, 1, &mut rng);
+            (fish[0].hunting_target, fish[1].id, fish[2].id)
+        };
+        let (target, near, _) = hunt(1.0);
+        assert_eq!(target, Some(near), "Without the display the nearest prey is chosen");
+        let (target, _, courting) = hunt(1.5);
+        assert_eq!(target, Some(courting), "A display outweighs a little extra distance");
+    }
+
+    #[test]
+    fn fresh_hatchlings_are_not_hunted() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let (big, small) = pair_genomes(&mut rng);
+        let mut fish = vec![
+            Fish::new(big.id, 100.0, 100.0, &mut rng),
+            Fish::new(small.id, 140.0, 100.0, &mut rng),
+        ];
+        fish[1].spawn_protection = config.spawn_protection_ticks;
+        let mut genomes = std::collections::HashMap::new();
+        genomes.insert(big.id, big);
+        genomes.insert(small.id, small);
+
+        let mut eco = EcosystemManager::new();
+        eco.process_predation(&mut fish, &genomes, &config, 1, &mut rng);
+        assert!(fish[0].hunting_target.is_none(), "Protected hatchling is off limits");
+
+        fish[1].spawn_protection = 0;
+        eco.process_predation(&mut fish, &genomes, &config, 2, &mut rng);
+        assert_eq!(fish[0].hunting_target, Some(fish[1].id));
+    }
+
+    #[test]
+    fn hatchlings_emerge_beside_cover_or_parents() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let egg = Egg { id: 1, genome_id: 9, x: 300.0, y: 500.0, age: 0, parent_a_genome: 5, parent_b_genome: 6 };
+        let parent = Fish::new(5, 380.0, 480.0, &mut rng);
+
+        // Nothing in range: hatch on the spot
+        assert_eq!(EcosystemManager::hatch_position(&egg, &[], &[], &config), (300.0, 500.0));
+        // A parent nearby: hatch beside it
+        assert_eq!(EcosystemManager::hatch_position(&egg, std::slice::from_ref(&parent), &[], &config), (380.0, 480.0));
+        // Cover beats the parent: hatch just outside the rock on the egg's side
+        let rock = [(200.0, 500.0, 25.0)];
+        let (x, y) = EcosystemManager::hatch_position(&egg, &[parent], &rock, &config);
+        assert!((x - (200.0 + 25.0 + config.shelter_radius * 0.5)).abs() < 1e-3 && (y - 500.0).abs() < 1e-3);
+        assert!(is_sheltered(x, y, &rock, config.shelter_radius));
+    }
+
+    #[test]
+    fn hatchlings_stay_inside_a_tiny_tank() {
+        let config = SimulationConfig { tank_width: 15.0, tank_height: 8.0, ..SimulationConfig::default() };
+        let egg = Egg { id: 1, genome_id: 9, x: 6.0, y: 4.0, age: 0, parent_a_genome: 5, parent_b_genome: 6 };
+        let rock = [(2.0, 4.0, 3.0)];
+        let (x, y) = EcosystemManager::hatch_position(&egg, &[], &rock, &config);
+        assert!((0.0..=15.0).contains(&x) && (0.0..=8.0).contains(&y), "hatched at ({}, {})", x, y);
+        assert_eq!((x, y), (7.5, 4.0), "Pushed off the rock as far as the tank allows");
+    }
+
+    // --- Territory ---
+
+    fn territorial_fish(rng: &mut StdRng) -> (Fish, std::collections::HashMap<u32, FishGenome>) {
+        let mut g = FishGenome::random(rng);
+        g.school_affinity = 0.1;
+        g.aggression = 0.5;
+        let mut f = Fish::new(g.id, 100.0, 100.0, rng);
+        f.is_juvenile = false;
+        (f, std::collections::HashMap::from([(g.id, g)]))
+    }
+
+    #[test]
+    fn territory_fades_while_holder_is_away() {
+        let config = SimulationConfig { territory_decay_rate: 0.1, ..SimulationConfig::default() };
+        let mut rng = seeded_rng();
+        let (f, genomes) = territorial_fish(&mut rng);
+        let mut fish = vec![f];
+
+        EcosystemManager::process_territories(&mut fish, &genomes, &config);
+        assert_eq!(fish[0].territory_center, Some((100.0, 100.0)));
+        assert_eq!(fish[0].territory_strength, 1.0);
+
+        fish[0].x = 600.0;
+        for _ in 0..5 {
+            EcosystemManager::process_territories(&mut fish, &genomes, &config);
+        }
+        assert!((fish[0].territory_strength - 0.5).abs() < 1e-4);
+        assert_eq!(fish[0].territory_center, Some((100.0, 100.0)), "Still held while fading");
+
+        // Returning restores the claim
+        fish[0].x = 100.0;
+        EcosystemManager::process_territories(&mut fish, &genomes, &config);
+        assert!((fish[0].territory_strength - 0.7).abs() < 1e-4);
+
+        fish[0].x = 600.0;
+        for _ in 0..8 {
+            EcosystemManager::process_territories(&mut fish, &genomes, &config);
+        }
+        assert_eq!(fish[0].territory_center, Some((600.0, 100.0)), "Lapsed territory re-claimed at the new spot");
+        assert_eq!(fish[0].territory_strength, 1.0);
+    }
+
+    #[test]
+    fn weak_holder_abandons_territory() {
+        let config = SimulationConfig::default();
+        let mut rng = seeded_rng();
+        let (f, genomes) = territorial_fish(&mut rng);
+        let mut fish = vec![f];
+        EcosystemManager::process_territories(&mut fish, &genomes, &config);
+        assert!(fish[0].territory_center.is_some());
+
+        fish[0].health = config.territory_abandon_health - 0.05;
+        EcosystemManager::process_territories(&mut fish, &genomes, &config);
+        assert!(fish[0].territory_center.is_none());
+        assert_eq!(fish[0].territory_strength, 0.0);
+    }
+
+    #[test]
+    fn disease_skips_quarantined_fish() {
+        let config = SimulationConfig {
+            disease_infection_chance: 1.0,
+            disease_spontaneous_chance: 0.0,
+            disease_duration: 100_000,
+            disease_damage: 0.0,
+            ..SimulationConfig::default()
+        };
+        let mut rng = seeded_rng();
+        let mut genomes = std::collections::HashMap::new();
+        let mut fish = Vec::new();
+        for _ in 0..3 {
+            let mut g = FishGenome::random(&mut rng);
+            g.disease_resistance = 0.0;
+            fish.push(Fish::new(g.id, 100.0, 100.0, &mut rng));
+            genomes.insert(g.id, g);
+        }
+        fish[0].is_infected = true;
+        fish[1].quarantine();
+
+        let mut eco = EcosystemManager::new();
+        for _ in 0..2000 {
+            eco.process_disease(&mut fish, &genomes, &config, &mut rng);
+        }
+        assert!(!fish[1].is_infected, "Quarantined fish can't catch it");
+        assert!(fish[2].is_infected, "Exposed fish should catch it");
+    }
+
+    #[test]
+    fn survivors_build_lasting_immunity() {
+        let config = SimulationConfig {
+            disease_enabled: true,
+            disease_damage: 0.0,
+            disease_duration: 10,
+            ..SimulationConfig::default()
+        };
+        let mut rng = seeded_rng();
+        let mut g = FishGenome::random(&mut rng);
+        g.disease_resistance = 0.0;
+        let mut fish = vec![Fish::new(g.id, 100.0, 100.0, &mut rng)];
+        let mut genomes = std::collections::HashMap::new();
+        genomes.insert(g.id, g);
+        let naive = fish[0].disease_resistance(&genomes[&fish[0].genome_id], &config);
+
+        let mut eco = EcosystemManager::new();
+        fish[0].is_infected = true;
+        for _ in 0..config.disease_duration {
+            eco.process_disease(&mut fish, &genomes, &config, &mut rng);
+        }
+        assert!(!fish[0].is_infected);
+        assert!((fish[0].acquired_immunity - config.immunity_gain * config.immunity_max).abs() < 1e-6);
+
+        // Repeated recoveries approach but never pass the ceiling
+        for _ in 0..20 {
+            fish[0].acquire_immunity(&config);
+        }
+        assert!(fish[0].acquired_immunity <= config.immunity_max + 1e-6);
+        assert!(fish[0].disease_resistance(&genomes[&fish[0].genome_id], &config) > naive);
+
+        let fading = SimulationConfig { immunity_decay: 0.01, ..config };
+        fish[0].recovery_timer = 0;
+        for _ in 0..100 {
+            eco.process_disease(&mut fish, &genomes, &fading, &mut rng);
+        }
+        assert!(fish[0].acquired_immunity < config.immunity_max, "Immunity wanes when decay is set");
+    }
+
+    // --- Courtship display ---
+
+    #[test]
+    fn females_reject_males_in_poor_condition() {
+        let config = SimulationConfig { courtship_choosiness: 0.0, ..SimulationConfig::default() };
+        let mut rng = seeded_rng();
+        let female = FishGenome { sex: Sex::Female, ..FishGenome::random(&mut rng) };
+        let male_genome = FishGenome { sex: Sex::Male, ..female.clone() };
+        let mut male = Fish::new(male_genome.id, 100.0, 100.0, &mut rng);
+        male.health = 1.0;
+        male.energy = 1.0;
+        assert!(female_accepts(&female, &male_genome, &male, &config, &mut rng));
+        male.health = 0.2;
+        male.energy = 0.3;
+        assert!(!female_accepts(&female, &male_genome, &male, &config, &mut rng));
+    }
+
+    #[test]
+    fn choosy_females_prefer_lookalikes() {
+        let config = SimulationConfig { courtship_choosiness: 1.0, ..SimulationConfig::default() };
+        let mut rng = seeded_rng();
+        let female = FishGenome { sex: Sex::Female, ..FishGenome::random(&mut rng) };
+        let twin = FishGenome { sex: Sex::Male, ..female.clone() };
+        let stranger = FishGenome { sex: Sex::Male, ..FishGenome::random(&mut rng) };
+        let male = Fish::new(twin.id, 100.0, 100.0, &mut rng);
+        let accepted = |g: &FishGenome, rng: &mut StdRng| (0..200).filter(|_| female_accepts(&female, g, &male, &config, rng)).count();
+        assert_eq!(accepted(&twin, &mut rng), 200, "Identical males are never turned down");
+        assert!(accepted(&stranger, &mut rng) < 200);
+    }
+
+    #[test]
+    fn display_fails_when_pair_drifts_apart() {
+        let config = SimulationConfig { courtship_display_enabled: true, ..SimulationConfig::default() };
+        let mut rng = seeded_rng();
+        let mut fish = vec![Fish::new(1, 100.0, 100.0, &mut rng), Fish::new(2, 120.0, 100.0, &mut rng)];
+        for (me, partner) in [(0, 1), (1, 0)] {
+            fish[me].behavior = BehaviorState::Courting;
+            fish[me].courting_partner = Some(fish[partner].id);
+            fish[me].courting_timer = 40;
+        }
+        EcosystemManager::check_courtship_displays(&mut fish, &config);
+        assert!(fish.iter().all(|f| f.behavior == BehaviorState::Courting), "Close pair keeps displaying");
+
+        fish[1].x = 100.0 + config.courtship_display_radius * 2.0;
+        EcosystemManager::check_courtship_displays(&mut fish, &config);
+        assert!(fish.iter().all(|f| f.behavior == BehaviorState::Swimming && f.courting_partner.is_none()));
+    }
+
+    fn courting_pair(rng: &mut StdRng, timer: u32) -> (Vec<Fish>, std::collections::HashMap<u32, FishGenome>) {
+        let female = FishGenome { sex: Sex::Female, ..FishGenome::random(rng) };
+        let male = FishGenome { sex: Sex::Male, ..FishGenome::random(rng) };
+        let mut fish = vec![Fish::new(female.id, 100.0, 100.0, rng), Fish::new(male.id, 110.0, 100.0, rng)];
+        for (me, partner) in [(0, 1), (1, 0)] {
+            fish[me].behavior = BehaviorState::Courting;
+            fish[me].courting_partner = Some(fish[partner].id);
+            fish[me].courting_timer = timer;
+        }
+        let genomes = std::collections::HashMap::from([(female.id, female), (male.id, male)]);
+        (fish, genomes)
+    }
+
+    #[test]
+    fn female_choice_runs_even_when_the_display_tick_was_missed() {
+        // Males in no state to be accepted
+        let config = SimulationConfig { courtship_display_enabled: true, courtship_min_condition: 2.0, ..SimulationConfig::default() };
+        let mut rng = seeded_rng();
+        let (mut fish, mut genomes) = courting_pair(&mut rng, COURTSHIP_DISPLAY_TICKS + 1);
+        let mut eco = EcosystemManager::new();
+        eco.process_reproduction(&mut fish, &mut genomes, &config, 1, &mut rng, 1.0, 1.0);
+        assert!(eco.eggs.is_empty());
+        assert!(fish.iter().all(|f| f.behavior == BehaviorState::Swimming && f.courtship_accepted.is_none()));
+
+        // Out of season the pair waits, undecided
+        let (mut fish, mut genomes) = courting_pair(&mut rng, COURTSHIP_DISPLAY_TICKS);
+        let season = SimulationConfig { breeding_season: crate::simulation::config::BreedingSeason::Day, ..config.clone() };
+        eco.process_reproduction(&mut fish, &mut genomes, &season, 1, &mut rng, 0.0, 1.0);
+        assert!(fish.iter().all(|f| f.behavior == BehaviorState::Courting && f.courtship_accepted.is_none()));
+    }
+
+    #[test]
+    fn female_choice_is_made_once_per_courtship() {
+        let config = SimulationConfig { courtship_display_enabled: true, courtship_min_condition: 2.0, ..SimulationConfig::default() };
+        let mut rng = seeded_rng();
+        let (mut fish, mut genomes) = courting_pair(&mut rng, COURTSHIP_DISPLAY_TICKS);
+        fish[0].courtship_accepted = Some(true);
+        fish[1].courtship_accepted = Some(true);
+        let mut eco = EcosystemManager::new();
+        // Zero fertility: the roll fails, so both fish are visited still courting
+        eco.process_reproduction(&mut fish, &mut genomes, &config, 1, &mut rng, 1.0, 0.0);
+        assert!(fish.iter().all(|f| f.behavior == BehaviorState::Courting), "Accepted pair isn't re-judged");
+        assert!(fish.iter().all(|f| f.courtship_accepted == Some(true)));
+
+        for tick in 2..50 {
+            if !eco.eggs.is_empty() {
+                break;
+            }
+            eco.process_reproduction(&mut fish, &mut genomes, &config, tick, &mut rng, 1.0, 1.0);
+        }
+        assert!(!eco.eggs.is_empty(), "The earlier acceptance still stands");
+    }
+
+    // --- Conservation ---
+
+    #[test]
+    fn protected_lineages_mutate_less() {
+        let mut rng = seeded_rng();
+        let config = SimulationConfig::default();
+        let (a, b) = pair_genomes(&mut rng);
+        let mut eco = EcosystemManager::new();
+        assert_eq!(eco.conservation_mutation_scale(&a, &b, &config), 1.0);
+        eco.conservation.protect(&b, &std::collections::HashMap::new());
+        assert_eq!(eco.conservation_mutation_scale(&a, &b, &config), config.conservation_mutation_scale);
+    }
+
     // --- find_root (union-find) ---
 
     #[test]
@@ -1,6 +1,9 @@
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Quiet ticks after the last event ends before a random one can roll
+const COOLDOWN_TICKS: u32 = 3000;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EnvironmentalEvent {
     AlgaeBloom,
@@ -11,6 +14,14 @@ pub enum EnvironmentalEvent {
 }
 
 impl EnvironmentalEvent {
+    pub const ALL: [Self; 5] = [
+        Self::AlgaeBloom,
+        Self::ColdSnap,
+        Self::Heatwave,
+        Self::CurrentSurge,
+        Self::PlanktonBloom,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::AlgaeBloom => "algae_bloom",
@@ -52,116 +63,169 @@ impl EnvironmentalEvent {
         }
     }
 
-    fn random(rng: &mut impl Rng) -> Self {
-        match rng.gen_range(0..5) {
-            0 => Self::AlgaeBloom,
-            1 => Self::ColdSnap,
-            2 => Self::Heatwave,
-            3 => Self::CurrentSurge,
-            _ => Self::PlanktonBloom,
+    /// What the event does to the tank, for forecasts and alerts
+    pub fn advice(&self) -> &'static str {
+        match self {
+            Self::AlgaeBloom => "Water quality will drop; plants and lighter feeding help",
+            Self::ColdSnap => "Fish slow down and burn less food; cold-sensitive fish take damage",
+            Self::Heatwave => "Hunger, aggression and energy drain rise; heat-sensitive fish take damage",
+            Self::CurrentSurge => "A strong current pushes weaker swimmers around",
+            Self::PlanktonBloom => "Free food drifts in while the water clouds slightly",
         }
     }
+
+    fn random(rng: &mut impl Rng) -> Self {
+        Self::ALL[rng.gen_range(0..Self::ALL.len())]
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventSystem {
-    pub active_event: Option<(EnvironmentalEvent, u32)>, // (type, remaining_ticks)
+    pub active_events: Vec<(EnvironmentalEvent, u32)>, // (type, remaining_ticks), oldest first
     pub cooldown: u32,
 }
 
+impl Default for EventSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EventSystem {
     pub fn new() -> Self {
         Self {
-            active_event: None,
-            cooldown: 3000,
+            active_events: Vec::new(),
+            cooldown: COOLDOWN_TICKS,
         }
     }
 
-    pub fn update(&mut self, event_frequency: f32, rng: &mut impl Rng) {
-        // Tick active event
-        if let Some((_, ref mut remaining)) = self.active_event {
-            if *remaining > 0 {
-                *remaining -= 1;
-            } else {
-                self.active_event = None;
-                self.cooldown = 3000;
+    /// `max_concurrent` caps how many random events can overlap; at 1 a new
+    /// event only rolls once the last has ended and the cooldown has passed.
+    /// Returns the events that ran their course this tick.
+    pub fn update(&mut self, event_frequency: f32, max_concurrent: u32, rng: &mut impl Rng) -> Vec<EnvironmentalEvent> {
+        // Tick active events; one that has counted down to 0 clears on the next tick
+        let had_events = !self.active_events.is_empty();
+        let mut ended = Vec::new();
+        self.active_events.retain(|&(e, remaining)| {
+            if remaining == 0 {
+                ended.push(e);
             }
+            remaining > 0
+        });
+        for (_, remaining) in &mut self.active_events {
+            *remaining -= 1;
+        }
+        if had_events && self.active_events.is_empty() {
+            self.cooldown = COOLDOWN_TICKS;
         }
 
         // Cooldown
-        if self.active_event.is_none() && self.cooldown > 0 {
+        if self.active_events.is_empty() && self.cooldown > 0 {
             self.cooldown -= 1;
         }
 
         // Random event trigger
-        if self.active_event.is_none() && self.cooldown == 0 {
-            if rng.gen::<f32>() < event_frequency * 0.0001 {
+        if self.active_events.len() < max_concurrent as usize && self.cooldown == 0 {
+            if rng.gen::<f32>() < Self::trigger_chance(event_frequency) {
                 let event = EnvironmentalEvent::random(rng);
-                let duration = event.duration();
-                self.active_event = Some((event, duration));
+                if !self.is_active(event) {
+                    self.active_events.push((event, event.duration()));
+                }
             }
         }
+        ended
     }
 
-    pub fn trigger(&mut self, event: EnvironmentalEvent) {
-        let duration = event.duration();
-        self.active_event = Some((event, duration));
+    /// Chance per eligible tick that a random event rolls (it is dropped if
+    /// the rolled type is already running)
+    pub fn trigger_chance(event_frequency: f32) -> f32 {
+        (event_frequency * 0.0001).clamp(0.0, 1.0)
     }
 
-    pub fn active_event_name(&self) -> Option<&'static str> {
-        self.active_event.as_ref().map(|(e, _)| e.as_str())
+    /// Ticks until `update` can roll a random event again, assuming nothing
+    /// is triggered by hand in the meantime; None when the cap is 0
+    pub fn ticks_until_eligible(&self, max_concurrent: u32) -> Option<u64> {
+        let cap = max_concurrent as usize;
+        if cap == 0 {
+            return None;
+        }
+        let mut remaining: Vec<u64> = self.active_events.iter().map(|&(_, r)| r as u64).collect();
+        remaining.sort_unstable();
+        if remaining.is_empty() {
+            return Some(self.cooldown as u64);
+        }
+        if remaining.len() < cap && self.cooldown == 0 {
+            return Some(0);
+        }
+        // Enough events must end to drop below the cap; once the last one
+        // ends the cooldown restarts
+        // An event with r ticks remaining clears on update r + 1
+        let must_end = (remaining.len() + 1).saturating_sub(cap);
+        Some(if must_end < remaining.len() && self.cooldown == 0 {
+            remaining[must_end - 1] + 1
+        } else {
+            remaining[remaining.len() - 1] + COOLDOWN_TICKS as u64
+        })
     }
 
-    // Event effect modifiers
+    /// Start `event`, restarting it if already running. At the cap the
+    /// oldest event makes way, so with a cap of 1 it replaces the current one.
+    pub fn trigger(&mut self, event: EnvironmentalEvent, max_concurrent: u32) {
+        self.active_events.retain(|&(e, _)| e != event);
+        while !self.active_events.is_empty() && self.active_events.len() >= max_concurrent.max(1) as usize {
+            self.active_events.remove(0);
+        }
+        self.active_events.push((event, event.duration()));
+    }
+
+    pub fn is_active(&self, event: EnvironmentalEvent) -> bool {
+        self.active_events.iter().any(|&(e, _)| e == event)
+    }
+
+    pub fn active_event_names(&self) -> Vec<&'static str> {
+        self.active_events.iter().map(|(e, _)| e.as_str()).collect()
+    }
+
+    // Event effect modifiers; overlapping events compound
     pub fn metabolism_multiplier(&self) -> f32 {
-        match self.active_event {
-            Some((EnvironmentalEvent::ColdSnap, _)) => 0.6,
-            Some((EnvironmentalEvent::Heatwave, _)) => 1.5,
+        self.active_events.iter().map(|(e, _)| match e {
+            EnvironmentalEvent::ColdSnap => 0.6,
+            EnvironmentalEvent::Heatwave => 1.5,
             _ => 1.0,
-        }
+        }).product()
     }
 
     pub fn speed_multiplier(&self) -> f32 {
-        match self.active_event {
-            Some((EnvironmentalEvent::ColdSnap, _)) => 0.7,
-            _ => 1.0,
-        }
+        if self.is_active(EnvironmentalEvent::ColdSnap) { 0.7 } else { 1.0 }
     }
 
     pub fn aggression_bonus(&self) -> f32 {
-        match self.active_event {
-            Some((EnvironmentalEvent::Heatwave, _)) => 0.2,
-            _ => 0.0,
-        }
+        if self.is_active(EnvironmentalEvent::Heatwave) { 0.2 } else { 0.0 }
     }
 
     pub fn current_strength_override(&self) -> Option<f32> {
-        match self.active_event {
-            Some((EnvironmentalEvent::CurrentSurge, _)) => Some(0.5),
-            _ => None,
-        }
+        if self.is_active(EnvironmentalEvent::CurrentSurge) { Some(0.5) } else { None }
     }
 
     pub fn extra_water_degradation(&self) -> f32 {
-        match self.active_event {
-            Some((EnvironmentalEvent::AlgaeBloom, _)) => 0.001,
-            Some((EnvironmentalEvent::PlanktonBloom, _)) => 0.0005,
+        self.active_events.iter().map(|(e, _)| match e {
+            EnvironmentalEvent::AlgaeBloom => 0.001,
+            EnvironmentalEvent::PlanktonBloom => 0.0005,
             _ => 0.0,
-        }
+        }).sum()
     }
 
     pub fn should_spawn_free_food(&self, tick: u64) -> bool {
-        match self.active_event {
-            Some((EnvironmentalEvent::PlanktonBloom, _)) => tick % 30 == 0,
-            _ => false,
-        }
+        self.is_active(EnvironmentalEvent::PlanktonBloom) && tick % 30 == 0
+    }
+
+    /// How far the water temperature is from normal: 1.0 during a cold snap or heatwave
+    pub fn temperature_exposure(&self) -> f32 {
+        if self.is_active(EnvironmentalEvent::ColdSnap) || self.is_active(EnvironmentalEvent::Heatwave) { 1.0 } else { 0.0 }
     }
 
     pub fn energy_drain_multiplier(&self) -> f32 {
-        match self.active_event {
-            Some((EnvironmentalEvent::Heatwave, _)) => 2.0,
-            _ => 1.0,
-        }
+        if self.is_active(EnvironmentalEvent::Heatwave) { 2.0 } else { 1.0 }
     }
 }
 
@@ -178,16 +242,16 @@ mod tests {
     #[test]
     fn event_system_starts_on_cooldown() {
         let es = EventSystem::new();
-        assert!(es.active_event.is_none());
+        assert!(es.active_events.is_empty());
         assert_eq!(es.cooldown, 3000);
     }
 
     #[test]
     fn trigger_activates_event() {
         let mut es = EventSystem::new();
-        es.trigger(EnvironmentalEvent::AlgaeBloom);
-        assert!(es.active_event.is_some());
-        let (event, remaining) = es.active_event.unwrap();
+        es.trigger(EnvironmentalEvent::AlgaeBloom, 1);
+        assert_eq!(es.active_events.len(), 1);
+        let (event, remaining) = es.active_events[0];
         assert_eq!(event, EnvironmentalEvent::AlgaeBloom);
         assert_eq!(remaining, 600); // AlgaeBloom duration
     }
@@ -196,14 +260,15 @@ mod tests {
     fn event_expires_after_duration() {
         let mut es = EventSystem::new();
         let mut rng = seeded_rng();
-        es.trigger(EnvironmentalEvent::CurrentSurge); // 300 ticks
+        es.trigger(EnvironmentalEvent::CurrentSurge, 1); // 300 ticks
 
         // Duration ticks down each update; at remaining=0 it clears on the next tick
-        for _ in 0..301 {
-            es.update(0.0, &mut rng); // frequency=0 to prevent random new events
+        for _ in 0..300 {
+            assert!(es.update(0.0, 1, &mut rng).is_empty()); // frequency=0 to prevent random new events
         }
-        // Should have expired
-        assert!(es.active_event.is_none());
+        assert_eq!(es.active_events, vec![(EnvironmentalEvent::CurrentSurge, 0)]);
+        assert_eq!(es.update(0.0, 1, &mut rng), vec![EnvironmentalEvent::CurrentSurge]);
+        assert!(es.active_events.is_empty());
     }
 
     #[test]
@@ -212,7 +277,7 @@ mod tests {
         let mut rng = seeded_rng();
         es.cooldown = 5;
         for _ in 0..5 {
-            es.update(0.0, &mut rng); // frequency=0 so no random trigger
+            es.update(0.0, 1, &mut rng); // frequency=0 so no random trigger
         }
         assert_eq!(es.cooldown, 0);
     }
@@ -222,19 +287,29 @@ mod tests {
         let mut es = EventSystem::new();
         assert!((es.metabolism_multiplier() - 1.0).abs() < 0.01);
 
-        es.trigger(EnvironmentalEvent::ColdSnap);
+        es.trigger(EnvironmentalEvent::ColdSnap, 1);
         assert!((es.metabolism_multiplier() - 0.6).abs() < 0.01);
 
-        es.trigger(EnvironmentalEvent::Heatwave);
+        es.trigger(EnvironmentalEvent::Heatwave, 1);
         assert!((es.metabolism_multiplier() - 1.5).abs() < 0.01);
     }
 
+    #[test]
+    fn temperature_exposure_during_climate_events() {
+        let mut es = EventSystem::new();
+        assert_eq!(es.temperature_exposure(), 0.0);
+        es.trigger(EnvironmentalEvent::ColdSnap, 1);
+        assert_eq!(es.temperature_exposure(), 1.0);
+        es.trigger(EnvironmentalEvent::CurrentSurge, 1);
+        assert_eq!(es.temperature_exposure(), 0.0);
+    }
+
     #[test]
     fn current_strength_override() {
         let mut es = EventSystem::new();
         assert!(es.current_strength_override().is_none());
 
-        es.trigger(EnvironmentalEvent::CurrentSurge);
+        es.trigger(EnvironmentalEvent::CurrentSurge, 1);
         assert!((es.current_strength_override().unwrap() - 0.5).abs() < 0.01);
     }
 
@@ -243,17 +318,17 @@ mod tests {
         let mut es = EventSystem::new();
         assert!((es.extra_water_degradation() - 0.0).abs() < 0.001);
 
-        es.trigger(EnvironmentalEvent::AlgaeBloom);
+        es.trigger(EnvironmentalEvent::AlgaeBloom, 1);
         assert!((es.extra_water_degradation() - 0.001).abs() < 0.0001);
 
-        es.trigger(EnvironmentalEvent::PlanktonBloom);
+        es.trigger(EnvironmentalEvent::PlanktonBloom, 1);
         assert!((es.extra_water_degradation() - 0.0005).abs() < 0.0001);
     }
 
     #[test]
     fn plankton_bloom_spawns_food() {
         let mut es = EventSystem::new();
-        es.trigger(EnvironmentalEvent::PlanktonBloom);
+        es.trigger(EnvironmentalEvent::PlanktonBloom, 1);
         assert!(es.should_spawn_free_food(30));
         assert!(es.should_spawn_free_food(60));
         assert!(!es.should_spawn_free_food(31));
@@ -264,10 +339,70 @@ mod tests {
         let mut es = EventSystem::new();
         assert!((es.energy_drain_multiplier() - 1.0).abs() < 0.01);
 
-        es.trigger(EnvironmentalEvent::Heatwave);
+        es.trigger(EnvironmentalEvent::Heatwave, 1);
         assert!((es.energy_drain_multiplier() - 2.0).abs() < 0.01);
     }
 
+    #[test]
+    fn concurrent_events_compound() {
+        let mut es = EventSystem::new();
+        es.trigger(EnvironmentalEvent::ColdSnap, 3);
+        es.trigger(EnvironmentalEvent::Heatwave, 3);
+        es.trigger(EnvironmentalEvent::CurrentSurge, 3);
+        assert_eq!(es.active_event_names(), vec!["cold_snap", "heatwave", "current_surge"]);
+        assert!((es.metabolism_multiplier() - 0.9).abs() < 0.01);
+        assert!((es.speed_multiplier() - 0.7).abs() < 0.01);
+        assert!(es.current_strength_override().is_some());
+
+        // At the cap the oldest event gives way; re-triggering restarts in place
+        es.trigger(EnvironmentalEvent::AlgaeBloom, 3);
+        assert!(!es.is_active(EnvironmentalEvent::ColdSnap));
+        es.trigger(EnvironmentalEvent::Heatwave, 3);
+        assert_eq!(es.active_events.len(), 3);
+    }
+
+    #[test]
+    fn single_event_cap_replaces() {
+        let mut es = EventSystem::new();
+        es.trigger(EnvironmentalEvent::ColdSnap, 1);
+        es.trigger(EnvironmentalEvent::PlanktonBloom, 1);
+        assert_eq!(es.active_event_names(), vec!["plankton_bloom"]);
+    }
+
+    #[test]
+    fn events_expire_independently() {
+        let mut es = EventSystem::new();
+        let mut rng = seeded_rng();
+        es.trigger(EnvironmentalEvent::CurrentSurge, 2); // 300 ticks
+        es.trigger(EnvironmentalEvent::Heatwave, 2); // 400 ticks
+        for _ in 0..350 {
+            es.update(0.0, 2, &mut rng);
+        }
+        assert_eq!(es.active_event_names(), vec!["heatwave"]);
+        es.cooldown = 0;
+        for _ in 0..51 {
+            es.update(0.0, 2, &mut rng);
+        }
+        assert!(es.active_events.is_empty());
+        assert!(es.cooldown > 2900, "Cooldown restarts once the last event ends");
+    }
+
+    #[test]
+    fn eligibility_waits_for_cooldown_and_cap() {
+        let mut es = EventSystem::new();
+        assert_eq!(es.ticks_until_eligible(1), Some(3000));
+        assert_eq!(es.ticks_until_eligible(0), None);
+
+        es.cooldown = 0;
+        assert_eq!(es.ticks_until_eligible(2), Some(0));
+        es.active_events = vec![(EnvironmentalEvent::ColdSnap, 100), (EnvironmentalEvent::Heatwave, 40)];
+        // Under a cap of 2 the shorter event ending frees a slot
+        assert_eq!(es.ticks_until_eligible(2), Some(41));
+        assert_eq!(es.ticks_until_eligible(3), Some(0));
+        // At a cap of 1 both must end, then the cooldown restarts
+        assert_eq!(es.ticks_until_eligible(1), Some(3100));
+    }
+
     #[test]
     fn event_durations() {
         assert_eq!(EnvironmentalEvent::AlgaeBloom.duration(), 600);
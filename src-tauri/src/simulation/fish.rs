@@ -15,6 +15,41 @@ pub enum BehaviorState {
     Dying,
 }
 
+/// Age fraction past which a fish is an elder: health decays and it can no longer breed
+pub const ELDER_AGE_FRACTION: f32 = 0.85;
+
+/// How far into decline a fish of this age is: 0 before `senescence_onset`,
+/// rising linearly to `senescence_strength` at the end of its life
+pub fn senescence(age_fraction: f32, config: &SimulationConfig) -> f32 {
+    if age_fraction <= config.senescence_onset {
+        return 0.0;
+    }
+    let span = (1.0 - config.senescence_onset).max(0.01);
+    ((age_fraction - config.senescence_onset) / span).min(1.0) * config.senescence_strength
+}
+
+/// Longest custom name a fish can carry, in characters
+pub const MAX_NAME_LEN: usize = 20;
+
+/// Coarse life stage for display. Unhatched eggs are tracked separately as `Egg`s,
+/// so every `Fish` is at least a juvenile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LifeStage {
+    Juvenile,
+    Adult,
+    Elder,
+}
+
+impl LifeStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Juvenile => "juvenile",
+            Self::Adult => "adult",
+            Self::Elder => "elder",
+        }
+    }
+}
+
 impl BehaviorState {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -28,6 +63,20 @@ impl BehaviorState {
             Self::Dying => "dying",
         }
     }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "swimming" => Some(Self::Swimming),
+            "foraging" => Some(Self::Foraging),
+            "fleeing" => Some(Self::Fleeing),
+            "satiated" => Some(Self::Satiated),
+            "courting" => Some(Self::Courting),
+            "resting" => Some(Self::Resting),
+            "hunting" => Some(Self::Hunting),
+            "dying" => Some(Self::Dying),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +96,8 @@ pub struct Fish {
     pub behavior: BehaviorState,
     pub meals_eaten: u32,
     pub last_reproduced_tick: Option<u64>,
+    #[serde(default)]
+    pub offspring_count: u32, // successful spawnings, for reproductive variance
     pub is_alive: bool,
 
     // Internal state not sent to frontend every frame
@@ -55,6 +106,9 @@ pub struct Fish {
     pub satiated_timer: u32,
     pub courting_partner: Option<u32>,
     pub courting_timer: u32,
+    /// Female choice for the current courtship, once its display has run
+    #[serde(default)]
+    pub courtship_accepted: Option<bool>,
     pub dying_timer: u32,
     pub starvation_ticks: u32,
     pub fleeing_from: Option<u32>,
@@ -63,6 +117,8 @@ pub struct Fish {
     // Juvenile stage
     pub is_juvenile: bool,
     pub juvenile_timer: u32,
+    #[serde(default)]
+    pub spawn_protection: u32, // ticks left during which predators ignore this hatchling
 
     // Stress (from glass taps)
     pub stress: f32,
@@ -71,10 +127,22 @@ pub struct Fish {
     // Hunting (predation overhaul)
     pub hunting_target: Option<u32>,  // target fish id
     pub hunting_timer: u32,
+    pub hunt_cooldown: u32,  // ticks until a new hunt can start after a kill
+    #[serde(default)]
+    pub stomach_fullness: f32, // prey body length eaten and not yet digested
+
+    // Enrichment (decoration variety nearby, refreshed periodically)
+    #[serde(default)]
+    pub enrichment: f32, // 0 = barren surroundings, 1 = fully varied
 
     // Territory
     pub territory_center: Option<(f32, f32)>,
     pub territory_radius: f32,
+    pub territory_strength: f32, // 1 when freshly claimed, abandoned at 0
+
+    // Parental care
+    #[serde(default)]
+    pub guarding: Option<(f32, f32)>, // own egg or hatchling this parent is staying near
 
     // Naming & favorites
     pub custom_name: Option<String>,
@@ -84,6 +152,11 @@ pub struct Fish {
     pub is_infected: bool,
     pub infection_timer: u32,
     pub recovery_timer: u32,
+    #[serde(default)]
+    pub acquired_immunity: f32, // lasting resistance built up by surviving infections
+
+    // Quarantine: isolated from disease, predation and breeding
+    pub is_quarantined: bool,
 }
 
 static NEXT_FISH_ID: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(1);
@@ -114,29 +187,39 @@ impl Fish {
             behavior: BehaviorState::Swimming,
             meals_eaten: 0,
             last_reproduced_tick: None,
+            offspring_count: 0,
             is_alive: true,
             prev_force_x: 0.0,
             prev_force_y: 0.0,
             satiated_timer: 0,
             courting_partner: None,
             courting_timer: 0,
+            courtship_accepted: None,
             dying_timer: 0,
             starvation_ticks: 0,
             fleeing_from: None,
             killed_by_predator: false,
             is_juvenile: false,
             juvenile_timer: 0,
+            spawn_protection: 0,
             stress: 0.0,
             tap_flee_timer: 0,
             hunting_target: None,
             hunting_timer: 0,
+            hunt_cooldown: 0,
+            stomach_fullness: 0.0,
+            enrichment: 1.0,
             territory_center: None,
             territory_radius: 0.0,
+            territory_strength: 0.0,
+            guarding: None,
             custom_name: None,
             is_favorite: false,
             is_infected: false,
             infection_timer: 0,
             recovery_timer: 0,
+            acquired_immunity: 0.0,
+            is_quarantined: false,
         }
     }
 
@@ -146,6 +229,49 @@ impl Fish {
         self.age as f32 / max_age as f32
     }
 
+    /// Ticks until this fish reaches its nominal lifespan (0 once past it)
+    pub fn remaining_lifespan(&self, genome: &FishGenome, base_lifespan: u32) -> u32 {
+        let max_age = (base_lifespan as f32 * genome.lifespan_factor) as u32;
+        max_age.saturating_sub(self.age)
+    }
+
+    /// Multiplier on speed, fertility and disease resistance that falls as the fish senesces
+    pub fn vigor(&self, genome: &FishGenome, config: &SimulationConfig) -> f32 {
+        1.0 - senescence(self.age_fraction(genome, config.max_lifespan), config)
+    }
+
+    /// Chance-reducing resistance to infection: the genome's, weakened by
+    /// age, combined with immunity acquired from past infections
+    pub fn disease_resistance(&self, genome: &FishGenome, config: &SimulationConfig) -> f32 {
+        let innate = genome.disease_resistance * self.vigor(genome, config);
+        1.0 - (1.0 - innate) * (1.0 - self.acquired_immunity)
+    }
+
+    /// Surviving an infection closes part of the gap to `immunity_max`
+    pub fn acquire_immunity(&mut self, config: &SimulationConfig) {
+        let gap = (config.immunity_max - self.acquired_immunity).max(0.0);
+        self.acquired_immunity += config.immunity_gain * gap;
+    }
+
+    /// How under-stimulated the fish is, 0 when enrichment is off or fully met
+    pub fn boredom(&self, config: &SimulationConfig) -> f32 {
+        if config.enrichment_enabled {
+            (1.0 - self.enrichment).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn life_stage(&self, age_fraction: f32) -> LifeStage {
+        if self.is_juvenile {
+            LifeStage::Juvenile
+        } else if age_fraction > ELDER_AGE_FRACTION {
+            LifeStage::Elder
+        } else {
+            LifeStage::Adult
+        }
+    }
+
     pub fn behavior_schooling_multiplier(&self) -> f32 {
         match self.behavior {
             BehaviorState::Foraging => 0.3,
@@ -178,7 +304,7 @@ impl Fish {
         has_nearby_mate: Option<u32>,
         base_lifespan: u32,
         water_quality: f32,
-        time_of_day: f32,
+        light_level: f32,
     ) {
         let age_frac = self.age_fraction(genome, base_lifespan);
 
@@ -208,9 +334,10 @@ impl Fish {
             self.health -= 0.001;
         }
 
-        // Elder health degradation
-        if age_frac > 0.85 {
-            self.health -= 0.00005 * (1.0 + (1.0 - water_quality));
+        // Senescent health degradation, steepening with age
+        let decline = senescence(age_frac, config);
+        if decline > 0.0 {
+            self.health -= 0.0001 * decline * (1.0 + (1.0 - water_quality));
         }
 
         // Juvenile growth
@@ -236,6 +363,12 @@ impl Fish {
             }
         }
 
+        if self.hunt_cooldown > 0 {
+            self.hunt_cooldown -= 1;
+        }
+        self.stomach_fullness = (self.stomach_fullness - config.digestion_rate).max(0.0);
+        self.spawn_protection = self.spawn_protection.saturating_sub(1);
+
         // Starvation tracking
         if self.hunger >= 1.0 {
             self.starvation_ticks += 1;
@@ -268,17 +401,17 @@ impl Fish {
                 // Only override: dying check above can interrupt hunting.
             }
             BehaviorState::Swimming => {
-                let is_night = time_of_day >= 21.0 || time_of_day < 5.0;
+                let darkness = 1.0 - light_level;
                 let is_nocturnal = genome.boldness > 0.7;
                 if has_nearby_predator {
                     self.behavior = BehaviorState::Fleeing;
-                } else if self.hunger > 0.6 {
+                } else if self.hunger > config.forage_hunger_threshold {
                     self.behavior = BehaviorState::Foraging;
                 } else if self.energy < 0.2 {
                     self.behavior = BehaviorState::Resting;
-                } else if is_night && !is_nocturnal && config.day_night_cycle {
-                    // Non-nocturnal fish rest at night (40% chance per tick when swimming)
-                    if self.age % 5 == 0 { // ~40% effective per second at 30Hz
+                } else if darkness > 0.0 && !is_nocturnal && config.day_night_cycle {
+                    // Non-nocturnal fish settle down as light fades: up to 1 in 5 ticks in full dark
+                    if ((self.age % 100) as f32) < darkness * 20.0 {
                         self.behavior = BehaviorState::Resting;
                     }
                 }
@@ -286,7 +419,7 @@ impl Fish {
             BehaviorState::Foraging => {
                 if has_nearby_predator {
                     self.behavior = BehaviorState::Fleeing;
-                } else if self.hunger < 0.3 {
+                } else if self.hunger < config.forage_stop_threshold {
                     self.behavior = BehaviorState::Swimming;
                 }
             }
@@ -304,6 +437,7 @@ impl Fish {
                         self.behavior = BehaviorState::Courting;
                         self.courting_partner = Some(mate_id);
                         self.courting_timer = 0;
+                        self.courtship_accepted = None;
                     } else {
                         self.behavior = BehaviorState::Swimming;
                     }
@@ -337,6 +471,11 @@ impl Fish {
         }
     }
 
+    /// Start post-kill satiation; the hungrier the fish after its meal, the sooner it hunts again
+    pub fn start_hunt_cooldown(&mut self, config: &SimulationConfig) {
+        self.hunt_cooldown = (config.predation_cooldown_ticks as f32 * (1.0 - self.hunger).clamp(0.0, 1.0)).round() as u32;
+    }
+
     /// Called when this fish eats food
     pub fn eat(&mut self) {
         self.hunger = (self.hunger - 0.3).max(0.0);
@@ -346,24 +485,52 @@ impl Fish {
         self.satiated_timer = 0;
     }
 
-    /// Check if this fish can reproduce
+    /// Isolate this fish, abandoning any hunt or courtship in progress
+    pub fn quarantine(&mut self) {
+        self.is_quarantined = true;
+        self.hunting_target = None;
+        self.hunting_timer = 0;
+        self.courting_partner = None;
+        self.courting_timer = 0;
+        if matches!(self.behavior, BehaviorState::Hunting | BehaviorState::Courting | BehaviorState::Fleeing) {
+            self.behavior = BehaviorState::Swimming;
+        }
+        self.fleeing_from = None;
+    }
+
+    /// Return a quarantined fish to the tank
+    pub fn release(&mut self) {
+        self.is_quarantined = false;
+    }
+
+    /// Set the display name, trimmed and capped at MAX_NAME_LEN characters; blank clears it
+    pub fn set_name(&mut self, name: &str) {
+        let trimmed = name.trim();
+        self.custom_name = if trimmed.is_empty() { None } else { Some(trimmed.chars().take(MAX_NAME_LEN).collect()) };
+    }
+
+    /// Check if this fish can reproduce. Outside `config.breeding_season`
+    /// (judged by `light_level`) no fish can.
     pub fn can_reproduce(
         &self,
         genome: &FishGenome,
         tick: u64,
         config: &SimulationConfig,
-        base_lifespan: u32,
         water_quality: f32,
+        light_level: f32,
     ) -> bool {
-        let age_frac = self.age_fraction(genome, base_lifespan);
+        let age_frac = self.age_fraction(genome, config.max_lifespan);
+        // Senescent fish take longer to recover between spawns
+        let cooldown = config.reproduction_cooldown as f32 / (1.0 - senescence(age_frac, config)).max(0.1);
         self.is_alive
             && !self.is_juvenile
             && self.hunger < 0.4
             && age_frac > genome.maturity_age
-            && age_frac < 0.85
+            && age_frac < ELDER_AGE_FRACTION
             && water_quality > 0.4
+            && config.breeding_season.allows(light_level)
             && self.last_reproduced_tick
-                .map(|t| tick - t > config.reproduction_cooldown as u64)
+                .map(|t| (tick - t) as f32 > cooldown)
                 .unwrap_or(true)
     }
 }
@@ -372,7 +539,7 @@ impl Fish {
 mod tests {
     use super::*;
     use crate::simulation::genome::FishGenome;
-    use crate::simulation::config::SimulationConfig;
+    use crate::simulation::config::{BreedingSeason, SimulationConfig};
     use rand::SeedableRng;
     use rand::rngs::StdRng;
 
@@ -503,7 +670,7 @@ mod tests {
         f.is_alive = true;
         f.is_juvenile = false;
 
-        assert!(f.can_reproduce(&genome, 1000, &config, 20_000, 0.8));
+        assert!(f.can_reproduce(&genome, 1000, &config, 0.8, 1.0));
     }
 
     #[test]
@@ -517,7 +684,7 @@ mod tests {
         let mut f = Fish::new(genome.id, 0.0, 0.0, &mut rng);
         f.age = 8000;
         f.hunger = 0.5; // above 0.4 threshold
-        assert!(!f.can_reproduce(&genome, 1000, &config, 20_000, 0.8));
+        assert!(!f.can_reproduce(&genome, 1000, &config, 0.8, 1.0));
     }
 
     #[test]
@@ -532,7 +699,7 @@ mod tests {
         f.age = 8000;
         f.hunger = 0.2;
         f.is_juvenile = true;
-        assert!(!f.can_reproduce(&genome, 1000, &config, 20_000, 0.8));
+        assert!(!f.can_reproduce(&genome, 1000, &config, 0.8, 1.0));
     }
 
     #[test]
@@ -546,7 +713,85 @@ mod tests {
         let mut f = Fish::new(genome.id, 0.0, 0.0, &mut rng);
         f.age = 8000;
         f.hunger = 0.2;
-        assert!(!f.can_reproduce(&genome, 1000, &config, 20_000, 0.3)); // water < 0.4
+        assert!(!f.can_reproduce(&genome, 1000, &config, 0.3, 1.0)); // water < 0.4
+    }
+
+    #[test]
+    fn senescent_fish_need_longer_between_spawns() {
+        let mut rng = seeded_rng();
+        let mut genome = test_genome();
+        genome.maturity_age = 0.3;
+        genome.lifespan_factor = 1.0;
+        let config = SimulationConfig::default();
+
+        let mut f = Fish::new(genome.id, 0.0, 0.0, &mut rng);
+        f.hunger = 0.2;
+        f.is_juvenile = false;
+        f.last_reproduced_tick = Some(1000);
+        let tick = 1000 + config.reproduction_cooldown as u64 + 50;
+
+        f.age = 10_000; // prime adult, no decline yet
+        assert!(f.can_reproduce(&genome, tick, &config, 0.8, 1.0));
+        f.age = 16_000; // age_frac 0.8, a third of the way into decline
+        assert!(!f.can_reproduce(&genome, tick, &config, 0.8, 1.0));
+        assert!(f.can_reproduce(&genome, tick + 100, &config, 0.8, 1.0));
+    }
+
+    #[test]
+    fn breeding_season_gates_reproduction() {
+        let mut rng = seeded_rng();
+        let mut genome = test_genome();
+        genome.maturity_age = 0.3;
+        genome.lifespan_factor = 1.0;
+        let mut config = SimulationConfig::default();
+
+        let mut f = Fish::new(genome.id, 0.0, 0.0, &mut rng);
+        f.hunger = 0.2;
+        f.is_juvenile = false;
+        f.age = 10_000;
+        assert!(f.can_reproduce(&genome, 1000, &config, 0.8, 0.0));
+
+        config.breeding_season = BreedingSeason::Night;
+        assert!(f.can_reproduce(&genome, 1000, &config, 0.8, 0.0));
+        assert!(!f.can_reproduce(&genome, 1000, &config, 0.8, 1.0));
+        config.breeding_season = BreedingSeason::Twilight;
+        assert!(f.can_reproduce(&genome, 1000, &config, 0.8, 0.5));
+        assert!(!f.can_reproduce(&genome, 1000, &config, 0.8, 1.0));
+    }
+
+    #[test]
+    fn senescence_curve_and_vigor() {
+        let mut rng = seeded_rng();
+        let mut genome = test_genome();
+        genome.lifespan_factor = 1.0;
+        let config = SimulationConfig::default();
+
+        assert_eq!(senescence(0.5, &config), 0.0);
+        assert_eq!(senescence(config.senescence_onset, &config), 0.0);
+        assert!((senescence(0.85, &config) - 0.25).abs() < 1e-4);
+        assert!((senescence(1.0, &config) - config.senescence_strength).abs() < 1e-4);
+        assert!((senescence(1.5, &config) - config.senescence_strength).abs() < 1e-4);
+
+        let mut f = Fish::new(genome.id, 0.0, 0.0, &mut rng);
+        f.age = 10_000;
+        assert_eq!(f.vigor(&genome, &config), 1.0);
+        f.age = 19_000;
+        assert!(f.vigor(&genome, &config) < 0.6);
+
+        let long_lived = SimulationConfig { max_lifespan: 40_000, ..SimulationConfig::default() };
+        assert_eq!(f.vigor(&genome, &long_lived), 1.0);
+    }
+
+    #[test]
+    fn life_stage_from_juvenile_flag_and_age() {
+        let mut rng = seeded_rng();
+        let mut f = Fish::new(1, 0.0, 0.0, &mut rng);
+        assert_eq!(f.life_stage(0.1), LifeStage::Adult);
+        assert_eq!(f.life_stage(ELDER_AGE_FRACTION), LifeStage::Adult);
+        assert_eq!(f.life_stage(0.9), LifeStage::Elder);
+        f.is_juvenile = true;
+        assert_eq!(f.life_stage(0.9), LifeStage::Juvenile, "Juvenile flag takes precedence");
+        assert_eq!(LifeStage::Elder.as_str(), "elder");
     }
 
     #[test]
@@ -570,9 +815,106 @@ mod tests {
         f.health = 0.0; // trigger dying
 
         for tick in 0..200 {
-            f.update_behavior(&genome, &config, tick, false, None, 20_000, 1.0, 12.0);
+            f.update_behavior(&genome, &config, tick, false, None, 20_000, 1.0, 1.0);
             if !f.is_alive { break; }
         }
         assert!(!f.is_alive, "Fish should die within 200 ticks of health=0");
     }
+
+    #[test]
+    fn forage_thresholds_come_from_config() {
+        let mut rng = seeded_rng();
+        let genome = test_genome();
+        let config = SimulationConfig { forage_hunger_threshold: 0.4, forage_stop_threshold: 0.1, ..SimulationConfig::default() };
+        let mut f = Fish::new(genome.id, 400.0, 400.0, &mut rng);
+        f.behavior = BehaviorState::Swimming;
+        f.hunger = 0.5;
+        f.update_behavior(&genome, &config, 1, false, None, 20_000, 1.0, 1.0);
+        assert_eq!(f.behavior, BehaviorState::Foraging);
+        f.hunger = 0.2;
+        f.update_behavior(&genome, &config, 2, false, None, 20_000, 1.0, 1.0);
+        assert_eq!(f.behavior, BehaviorState::Foraging, "Still above the stop threshold");
+        f.hunger = 0.05;
+        f.update_behavior(&genome, &config, 3, false, None, 20_000, 1.0, 1.0);
+        assert_eq!(f.behavior, BehaviorState::Swimming);
+    }
+
+    fn rest_count(light_level: f32) -> u32 {
+        let mut rng = seeded_rng();
+        let mut genome = test_genome();
+        genome.boldness = 0.3; // not nocturnal
+        let config = SimulationConfig::default();
+        let mut f = Fish::new(genome.id, 400.0, 400.0, &mut rng);
+        let mut rests = 0;
+        for tick in 0..100 {
+            f.behavior = BehaviorState::Swimming;
+            f.hunger = 0.0;
+            f.energy = 1.0;
+            f.update_behavior(&genome, &config, tick, false, None, 20_000, 1.0, light_level);
+            if f.behavior == BehaviorState::Resting { rests += 1; }
+        }
+        rests
+    }
+
+    #[test]
+    fn resting_scales_with_darkness() {
+        let day = rest_count(1.0);
+        let dusk = rest_count(0.5);
+        let night = rest_count(0.0);
+        assert_eq!(day, 0, "No night-resting in full daylight");
+        assert!(dusk > 0 && dusk < night, "Twilight should be between day and night: {} vs {}", dusk, night);
+        assert_eq!(night, 20);
+    }
+
+    // --- Quarantine ---
+
+    #[test]
+    fn quarantine_drops_hunt_and_courtship() {
+        let mut rng = seeded_rng();
+        let genome = test_genome();
+        let mut f = Fish::new(genome.id, 100.0, 100.0, &mut rng);
+        f.behavior = BehaviorState::Hunting;
+        f.hunting_target = Some(7);
+        f.courting_partner = Some(9);
+
+        f.quarantine();
+        assert!(f.is_quarantined);
+        assert_eq!(f.behavior, BehaviorState::Swimming);
+        assert!(f.hunting_target.is_none());
+        assert!(f.courting_partner.is_none());
+
+        f.release();
+        assert!(!f.is_quarantined);
+    }
+
+    // --- Naming ---
+
+    #[test]
+    fn set_name_trims_and_caps_length() {
+        let mut rng = seeded_rng();
+        let mut f = Fish::new(1, 100.0, 100.0, &mut rng);
+        f.set_name("  Bubbles  ");
+        assert_eq!(f.custom_name.as_deref(), Some("Bubbles"));
+        f.set_name("A very long name that keeps going");
+        assert_eq!(f.custom_name.as_ref().map(|n| n.chars().count()), Some(MAX_NAME_LEN));
+        f.set_name("   ");
+        assert!(f.custom_name.is_none());
+    }
+
+    // --- Hunt cooldown ---
+
+    #[test]
+    fn hunt_cooldown_scales_inversely_with_hunger() {
+        let mut rng = seeded_rng();
+        let config = SimulationConfig { predation_cooldown_ticks: 200, ..SimulationConfig::default() };
+        let mut f = Fish::new(1, 100.0, 100.0, &mut rng);
+
+        f.hunger = 0.0;
+        f.start_hunt_cooldown(&config);
+        assert_eq!(f.hunt_cooldown, 200);
+
+        f.hunger = 0.75;
+        f.start_hunt_cooldown(&config);
+        assert_eq!(f.hunt_cooldown, 50);
+    }
 }
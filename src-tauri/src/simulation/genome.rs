@@ -1,6 +1,7 @@
 use rand::prelude::*;
 use rand_distr::Normal;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Sex {
@@ -73,7 +74,13 @@ pub struct FishGenome {
     // Behavior
     pub speed: f32,
     pub aggression: f32,
+    #[serde(default = "default_gape_size")]
+    pub gape_size: f32, // multiplier on the largest prey a predator can take relative to its body
     pub school_affinity: f32,
+    #[serde(default = "default_ideal_school_size")]
+    pub ideal_school_size: f32, // preferred shoal size: crowded above it, lonely below it
+    #[serde(default = "default_polarization")]
+    pub polarization: f32, // 0 shoals (loose, unaligned), 1 schools (tight, polarized)
     pub curiosity: f32,
     pub boldness: f32,
 
@@ -82,7 +89,134 @@ pub struct FishGenome {
     pub fertility: f32,
     pub lifespan_factor: f32,
     pub maturity_age: f32,
+    #[serde(default = "default_disease_resistance")]
     pub disease_resistance: f32,
+    #[serde(default = "default_temp_tolerance")]
+    pub temp_tolerance: f32, // how wide a temperature band the fish thrives in (0 narrow, 1 anything)
+    #[serde(default = "default_parental_care")]
+    pub parental_care: f32, // 0 broadcast spawner (many unguarded eggs), 1 guards a single egg and escorts the fry
+}
+
+/// Genome format written by this build. When adding a trait, bump this, list
+/// the trait in `ADDED_TRAITS` and give its field a `#[serde(default)]`, so
+/// older saves and shared JSON keep loading
+pub const GENOME_VERSION: u32 = 7;
+
+/// A trait that joined the genome after the first format
+#[derive(Debug, Clone, Copy)]
+pub struct AddedTrait {
+    pub name: &'static str, // field name, also the genomes table column
+    pub since_version: u32,
+    pub default: f32, // value assumed for genomes written before the trait existed
+}
+
+pub const DEFAULT_DISEASE_RESISTANCE: f32 = 0.5;
+pub const DEFAULT_GAPE_SIZE: f32 = 1.0;
+pub const DEFAULT_TEMP_TOLERANCE: f32 = 0.5;
+pub const DEFAULT_IDEAL_SCHOOL_SIZE: f32 = 8.0;
+pub const DEFAULT_POLARIZATION: f32 = 0.5;
+pub const DEFAULT_PARENTAL_CARE: f32 = 0.5;
+
+pub const ADDED_TRAITS: &[AddedTrait] = &[
+    AddedTrait { name: "disease_resistance", since_version: 2, default: DEFAULT_DISEASE_RESISTANCE },
+    AddedTrait { name: "gape_size", since_version: 3, default: DEFAULT_GAPE_SIZE },
+    AddedTrait { name: "temp_tolerance", since_version: 4, default: DEFAULT_TEMP_TOLERANCE },
+    AddedTrait { name: "ideal_school_size", since_version: 5, default: DEFAULT_IDEAL_SCHOOL_SIZE },
+    AddedTrait { name: "polarization", since_version: 6, default: DEFAULT_POLARIZATION },
+    AddedTrait { name: "parental_care", since_version: 7, default: DEFAULT_PARENTAL_CARE },
+];
+
+/// What a genome trait does in the simulation, for player-facing tooltips.
+/// Keep `effects` in step with the code that reads the trait.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TraitInfo {
+    pub name: &'static str,
+    pub category: &'static str,       // appearance, behavior or lifecycle
+    pub range: Option<[f32; 2]>,      // None for categorical traits
+    pub effects: &'static str,
+}
+
+pub const TRAIT_INFO: &[TraitInfo] = &[
+    TraitInfo { name: "sex", category: "lifecycle", range: None,
+        effects: "Male or female. Breeding needs one of each; with courtship displays on, males display and females choose." },
+    TraitInfo { name: "base_hue", category: "appearance", range: Some([0.0, 360.0]),
+        effects: "Body color on the color wheel. No effect on survival, but weighs heavily in species distance and drifts each generation." },
+    TraitInfo { name: "saturation", category: "appearance", range: Some([0.3, 1.0]),
+        effects: "Color intensity. Counts toward species distance and drifts each generation." },
+    TraitInfo { name: "lightness", category: "appearance", range: Some([0.3, 0.7]),
+        effects: "Color brightness. Appearance only; drifts each generation." },
+    TraitInfo { name: "body_length", category: "appearance", range: Some([0.6, 2.0]),
+        effects: "Overall size. Decides which fish it can eat and which can eat it, and scales stomach capacity and territory radius. Large aggressive fish also eat eggs. Counts toward species distance." },
+    TraitInfo { name: "body_width", category: "appearance", range: Some([0.5, 1.5]),
+        effects: "Body depth. Counts toward species distance." },
+    TraitInfo { name: "tail_size", category: "appearance", range: Some([0.5, 2.0]),
+        effects: "Tail fin size. Appearance only." },
+    TraitInfo { name: "dorsal_fin_size", category: "appearance", range: Some([0.3, 1.5]),
+        effects: "Dorsal fin size. Appearance only; drifts each generation." },
+    TraitInfo { name: "pectoral_fin_size", category: "appearance", range: Some([0.3, 1.5]),
+        effects: "Pectoral fin size. Appearance only; drifts each generation." },
+    TraitInfo { name: "pattern", category: "appearance", range: None,
+        effects: "Solid, striped, spotted, gradient or bicolor markings. A different pattern type adds heavily to species distance." },
+    TraitInfo { name: "pattern_intensity", category: "appearance", range: Some([0.0, 1.0]),
+        effects: "How strongly the pattern shows. Counts toward species distance and drifts each generation." },
+    TraitInfo { name: "pattern_color_offset", category: "appearance", range: Some([0.0, 180.0]),
+        effects: "Hue shift of the pattern from the body color. Appearance only; drifts each generation." },
+    TraitInfo { name: "eye_size", category: "appearance", range: Some([0.5, 1.5]),
+        effects: "Eye size. Appearance only; drifts each generation." },
+    TraitInfo { name: "speed", category: "behavior", range: Some([0.5, 2.0]),
+        effects: "Top swimming speed, and how hard it chases prey or flees. Counts toward species distance." },
+    TraitInfo { name: "aggression", category: "behavior", range: Some([0.0, 1.0]),
+        effects: "Above 0.6 a fish hunts smaller fish, striking more often the higher it is. Above 0.8 it hunts even when fed. Above 0.4 with low schooling it holds a territory, chasing intruders above 0.7. Below 0.35 it grazes algae. Crowding stress raises it temporarily. Counts toward species distance." },
+    TraitInfo { name: "gape_size", category: "behavior", range: Some([0.7, 1.3]),
+        effects: "Multiplier on the largest prey a predator can take relative to its own body." },
+    TraitInfo { name: "school_affinity", category: "behavior", range: Some([0.0, 1.0]),
+        effects: "Strength of alignment and cohesion with schoolmates, plus extra personal space. Above 0.6 a fleeing fish coordinates its escape with the school. Below 0.3 an aggressive fish may hold a territory. Counts toward species distance." },
+    TraitInfo { name: "ideal_school_size", category: "behavior", range: Some([1.0, 30.0]),
+        effects: "Preferred shoal size. In larger schools the fish spreads out; in smaller ones it pulls in harder." },
+    TraitInfo { name: "polarization", category: "behavior", range: Some([0.0, 1.0]),
+        effects: "Shoaling versus schooling. Low values gather loosely without lining up; high values swim aligned in a tight, polarized school." },
+    TraitInfo { name: "curiosity", category: "behavior", range: Some([0.0, 1.0]),
+        effects: "How far and how restlessly it wanders when nothing else steers it." },
+    TraitInfo { name: "boldness", category: "behavior", range: Some([0.0, 1.0]),
+        effects: "Bold fish react to predators at a shorter range and stay active at night above 0.7. Timid fish hide by decorations." },
+    TraitInfo { name: "metabolism", category: "lifecycle", range: Some([0.5, 2.0]),
+        effects: "Scales how fast hunger rises and how much energy swimming costs." },
+    TraitInfo { name: "fertility", category: "lifecycle", range: Some([0.3, 1.0]),
+        effects: "Chance that a courting pair spawns, averaged over both parents. Falls with senescence and inbreeding." },
+    TraitInfo { name: "lifespan_factor", category: "lifecycle", range: Some([0.5, 2.0]),
+        effects: "Multiplier on the tank's maximum lifespan. Inbreeding lowers it." },
+    TraitInfo { name: "maturity_age", category: "lifecycle", range: Some([0.3, 0.7]),
+        effects: "Fraction of its lifespan before it can breed." },
+    TraitInfo { name: "disease_resistance", category: "lifecycle", range: Some([0.0, 1.0]),
+        effects: "Cuts the chance of falling ill, whether spontaneously or from infected neighbors. Weakens with senescence. Counts toward species distance." },
+    TraitInfo { name: "temp_tolerance", category: "lifecycle", range: Some([0.0, 1.0]),
+        effects: "Protection from heatwaves and cold snaps. During either event a fish loses health every tick in proportion to 1 minus this value, so at 1.0 it takes no damage. Counts toward species distance." },
+    TraitInfo { name: "parental_care", category: "lifecycle", range: Some([0.0, 1.0]),
+        effects: "Low-care parents scatter several eggs and leave them. High-care parents lay fewer, stay near their eggs to fend off egg-eaters, and escort the hatchlings, which stay safe from predators for longer." },
+];
+
+fn default_disease_resistance() -> f32 {
+    DEFAULT_DISEASE_RESISTANCE
+}
+
+fn default_gape_size() -> f32 {
+    DEFAULT_GAPE_SIZE
+}
+
+fn default_temp_tolerance() -> f32 {
+    DEFAULT_TEMP_TOLERANCE
+}
+
+fn default_ideal_school_size() -> f32 {
+    DEFAULT_IDEAL_SCHOOL_SIZE
+}
+
+fn default_polarization() -> f32 {
+    DEFAULT_POLARIZATION
+}
+
+fn default_parental_care() -> f32 {
+    DEFAULT_PARENTAL_CARE
 }
 
 static NEXT_GENOME_ID: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(1);
@@ -96,6 +230,28 @@ pub fn set_genome_id_counter(val: u32) {
 }
 
 impl FishGenome {
+    /// JSON tagged with the genome format version, for storing or sharing
+    /// genomes outside the genomes table
+    pub fn to_versioned_json(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        value["genome_version"] = GENOME_VERSION.into();
+        value
+    }
+
+    /// Parse genome JSON from any build: untagged JSON counts as version 1,
+    /// traits it predates take their defaults, and fields added by newer
+    /// builds are ignored
+    pub fn from_versioned_json(mut value: serde_json::Value) -> Result<Self, String> {
+        let version = value.get("genome_version").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("genome_version");
+            for t in ADDED_TRAITS.iter().filter(|t| t.since_version > version) {
+                obj.entry(t.name).or_insert_with(|| t.default.into());
+            }
+        }
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
     pub fn random(rng: &mut impl Rng) -> Self {
         Self {
             id: next_genome_id(),
@@ -119,7 +275,10 @@ impl FishGenome {
 
             speed: rng.gen_range(0.5..2.0),
             aggression: rng.gen_range(0.2..0.5), // moderate for initial pop
+            gape_size: rng.gen_range(0.8..1.2),
             school_affinity: rng.gen_range(0.0..1.0),
+            ideal_school_size: rng.gen_range(3.0..15.0),
+            polarization: rng.gen_range(0.0..1.0),
             curiosity: rng.gen_range(0.0..1.0),
             boldness: rng.gen_range(0.0..1.0),
 
@@ -128,6 +287,8 @@ impl FishGenome {
             lifespan_factor: rng.gen_range(0.5..2.0),
             maturity_age: rng.gen_range(0.3..0.7),
             disease_resistance: rng.gen_range(0.2..0.8),
+            temp_tolerance: rng.gen_range(0.2..0.6),
+            parental_care: rng.gen_range(0.0..1.0),
         }
     }
 
@@ -153,8 +314,54 @@ impl FishGenome {
         genome
     }
 
-    pub fn inherit(parent_a: &FishGenome, parent_b: &FishGenome, rng: &mut impl Rng, inbred: bool, rate_large: f32, rate_small: f32) -> Self {
-        let mutation_scale = if inbred { 1.5 } else { 1.0 };
+    /// Body length below which another fish counts as prey for this one
+    pub fn max_prey_length(&self, predation_size_ratio: f32) -> f32 {
+        self.body_length * predation_size_ratio * self.gape_size
+    }
+
+    /// Total prey body length this fish can hold before it has to digest
+    pub fn stomach_capacity(&self, stomach_capacity: f32) -> f32 {
+        self.body_length * stomach_capacity
+    }
+
+    /// How far a temperature excursion of `exposure` (0 = normal, 1 = cold snap or
+    /// heatwave) reaches beyond this fish's tolerance band
+    pub fn temperature_stress(&self, exposure: f32) -> f32 {
+        (exposure - self.temp_tolerance).max(0.0)
+    }
+
+    /// Large, fast, aggressive template for deliberately introduced predators
+    pub fn apex_predator(rng: &mut impl Rng) -> Self {
+        let mut genome = Self::random(rng);
+        genome.base_hue = rng.gen_range(0.0..20.0);
+        genome.saturation = rng.gen_range(0.7..0.9);
+        genome.lightness = rng.gen_range(0.3..0.4);
+        genome.body_length = rng.gen_range(1.8..2.0);
+        genome.body_width = rng.gen_range(1.2..1.5);
+        genome.tail_size = rng.gen_range(1.5..2.0);
+        genome.dorsal_fin_size = rng.gen_range(1.2..1.5);
+        genome.pattern = PatternGene::Striped { angle: rng.gen_range(60.0..120.0) };
+        genome.pattern_intensity = rng.gen_range(0.6..0.9);
+        genome.speed = rng.gen_range(1.7..2.0);
+        genome.aggression = rng.gen_range(0.85..0.95);
+        genome.gape_size = rng.gen_range(1.15..1.3);
+        genome.school_affinity = rng.gen_range(0.0..0.2);
+        genome.ideal_school_size = rng.gen_range(1.0..3.0);
+        genome.boldness = rng.gen_range(0.8..1.0);
+        // Moderate metabolism so invaders don't starve before they hunt
+        genome.metabolism = rng.gen_range(0.8..1.2);
+        genome
+    }
+
+    /// `relatedness` is the parents' coefficient of relationship (see `relatedness`);
+    /// inbreeding penalties scale with it, at full strength for half-siblings.
+    /// Purely cosmetic genes mutate at `appearance` rates and everything that
+    /// affects survival (including body length) at `functional` rates.
+    /// Traits paired in `linkage` tend to come from the same parent.
+    pub fn inherit(parent_a: &FishGenome, parent_b: &FishGenome, rng: &mut impl Rng, relatedness: f32, appearance: MutationRates, functional: MutationRates, linkage: &[TraitLinkage]) -> Self {
+        let mut links = Linker::new(linkage);
+        let severity = inbreeding_severity(relatedness);
+        let mutation_scale = 1.0 + 0.5 * severity;
         let gen = parent_a.generation.max(parent_b.generation) + 1;
 
         let mut child = Self {
@@ -164,49 +371,472 @@ impl FishGenome {
             parent_b: Some(parent_b.id),
             sex: if rng.gen_bool(0.5) { Sex::Male } else { Sex::Female },
 
-            base_hue: inherit_hue(parent_a.base_hue, parent_b.base_hue, rng, mutation_scale, rate_large, rate_small),
-            saturation: inherit_trait(parent_a.saturation, parent_b.saturation, 0.3, 1.0, rng, mutation_scale, rate_large, rate_small),
-            lightness: inherit_trait(parent_a.lightness, parent_b.lightness, 0.3, 0.7, rng, mutation_scale, rate_large, rate_small),
-            body_length: inherit_trait(parent_a.body_length, parent_b.body_length, 0.6, 2.0, rng, mutation_scale, rate_large, rate_small),
-            body_width: inherit_trait(parent_a.body_width, parent_b.body_width, 0.5, 1.5, rng, mutation_scale, rate_large, rate_small),
-            tail_size: inherit_trait(parent_a.tail_size, parent_b.tail_size, 0.5, 2.0, rng, mutation_scale, rate_large, rate_small),
-            dorsal_fin_size: inherit_trait(parent_a.dorsal_fin_size, parent_b.dorsal_fin_size, 0.3, 1.5, rng, mutation_scale, rate_large, rate_small),
-            pectoral_fin_size: inherit_trait(parent_a.pectoral_fin_size, parent_b.pectoral_fin_size, 0.3, 1.5, rng, mutation_scale, rate_large, rate_small),
-            pattern: inherit_pattern(&parent_a.pattern, &parent_b.pattern, rng),
-            pattern_intensity: inherit_trait(parent_a.pattern_intensity, parent_b.pattern_intensity, 0.0, 1.0, rng, mutation_scale, rate_large, rate_small),
-            pattern_color_offset: inherit_trait(parent_a.pattern_color_offset, parent_b.pattern_color_offset, 0.0, 180.0, rng, mutation_scale, rate_large, rate_small),
-            eye_size: inherit_trait(parent_a.eye_size, parent_b.eye_size, 0.5, 1.5, rng, mutation_scale, rate_large, rate_small),
-
-            speed: inherit_trait(parent_a.speed, parent_b.speed, 0.5, 2.0, rng, mutation_scale, rate_large, rate_small),
-            aggression: inherit_trait(parent_a.aggression, parent_b.aggression, 0.0, 1.0, rng, mutation_scale, rate_large, rate_small),
-            school_affinity: inherit_trait(parent_a.school_affinity, parent_b.school_affinity, 0.0, 1.0, rng, mutation_scale, rate_large, rate_small),
-            curiosity: inherit_trait(parent_a.curiosity, parent_b.curiosity, 0.0, 1.0, rng, mutation_scale, rate_large, rate_small),
-            boldness: inherit_trait(parent_a.boldness, parent_b.boldness, 0.0, 1.0, rng, mutation_scale, rate_large, rate_small),
-
-            metabolism: inherit_trait(parent_a.metabolism, parent_b.metabolism, 0.5, 2.0, rng, mutation_scale, rate_large, rate_small),
-            fertility: inherit_trait(parent_a.fertility, parent_b.fertility, 0.3, 1.0, rng, mutation_scale, rate_large, rate_small),
-            lifespan_factor: inherit_trait(parent_a.lifespan_factor, parent_b.lifespan_factor, 0.5, 2.0, rng, mutation_scale, rate_large, rate_small),
-            maturity_age: inherit_trait(parent_a.maturity_age, parent_b.maturity_age, 0.3, 0.7, rng, mutation_scale, rate_large, rate_small),
-            disease_resistance: inherit_trait(parent_a.disease_resistance, parent_b.disease_resistance, 0.0, 1.0, rng, mutation_scale, rate_large, rate_small),
+            base_hue: inherit_hue(links.source("base_hue", rng), parent_a.base_hue, parent_b.base_hue, rng, mutation_scale, appearance.large, appearance.small),
+            saturation: inherit_trait(links.source("saturation", rng), parent_a.saturation, parent_b.saturation, 0.3, 1.0, rng, mutation_scale, appearance.large, appearance.small),
+            lightness: inherit_trait(links.source("lightness", rng), parent_a.lightness, parent_b.lightness, 0.3, 0.7, rng, mutation_scale, appearance.large, appearance.small),
+            body_length: inherit_trait(links.source("body_length", rng), parent_a.body_length, parent_b.body_length, 0.6, 2.0, rng, mutation_scale, functional.large, functional.small),
+            body_width: inherit_trait(links.source("body_width", rng), parent_a.body_width, parent_b.body_width, 0.5, 1.5, rng, mutation_scale, appearance.large, appearance.small),
+            tail_size: inherit_trait(links.source("tail_size", rng), parent_a.tail_size, parent_b.tail_size, 0.5, 2.0, rng, mutation_scale, appearance.large, appearance.small),
+            dorsal_fin_size: inherit_trait(links.source("dorsal_fin_size", rng), parent_a.dorsal_fin_size, parent_b.dorsal_fin_size, 0.3, 1.5, rng, mutation_scale, appearance.large, appearance.small),
+            pectoral_fin_size: inherit_trait(links.source("pectoral_fin_size", rng), parent_a.pectoral_fin_size, parent_b.pectoral_fin_size, 0.3, 1.5, rng, mutation_scale, appearance.large, appearance.small),
+            pattern: inherit_pattern(&parent_a.pattern, &parent_b.pattern, rng, appearance),
+            pattern_intensity: inherit_trait(links.source("pattern_intensity", rng), parent_a.pattern_intensity, parent_b.pattern_intensity, 0.0, 1.0, rng, mutation_scale, appearance.large, appearance.small),
+            pattern_color_offset: inherit_trait(links.source("pattern_color_offset", rng), parent_a.pattern_color_offset, parent_b.pattern_color_offset, 0.0, 180.0, rng, mutation_scale, appearance.large, appearance.small),
+            eye_size: inherit_trait(links.source("eye_size", rng), parent_a.eye_size, parent_b.eye_size, 0.5, 1.5, rng, mutation_scale, appearance.large, appearance.small),
+
+            speed: inherit_trait(links.source("speed", rng), parent_a.speed, parent_b.speed, 0.5, 2.0, rng, mutation_scale, functional.large, functional.small),
+            aggression: inherit_trait(links.source("aggression", rng), parent_a.aggression, parent_b.aggression, 0.0, 1.0, rng, mutation_scale, functional.large, functional.small),
+            gape_size: inherit_trait(links.source("gape_size", rng), parent_a.gape_size, parent_b.gape_size, 0.7, 1.3, rng, mutation_scale, functional.large, functional.small),
+            school_affinity: inherit_trait(links.source("school_affinity", rng), parent_a.school_affinity, parent_b.school_affinity, 0.0, 1.0, rng, mutation_scale, functional.large, functional.small),
+            ideal_school_size: inherit_trait(links.source("ideal_school_size", rng), parent_a.ideal_school_size, parent_b.ideal_school_size, 1.0, 30.0, rng, mutation_scale, functional.large, functional.small),
+            polarization: inherit_trait(links.source("polarization", rng), parent_a.polarization, parent_b.polarization, 0.0, 1.0, rng, mutation_scale, functional.large, functional.small),
+            curiosity: inherit_trait(links.source("curiosity", rng), parent_a.curiosity, parent_b.curiosity, 0.0, 1.0, rng, mutation_scale, functional.large, functional.small),
+            boldness: inherit_trait(links.source("boldness", rng), parent_a.boldness, parent_b.boldness, 0.0, 1.0, rng, mutation_scale, functional.large, functional.small),
+
+            metabolism: inherit_trait(links.source("metabolism", rng), parent_a.metabolism, parent_b.metabolism, 0.5, 2.0, rng, mutation_scale, functional.large, functional.small),
+            fertility: inherit_trait(links.source("fertility", rng), parent_a.fertility, parent_b.fertility, 0.3, 1.0, rng, mutation_scale, functional.large, functional.small),
+            lifespan_factor: inherit_trait(links.source("lifespan_factor", rng), parent_a.lifespan_factor, parent_b.lifespan_factor, 0.5, 2.0, rng, mutation_scale, functional.large, functional.small),
+            maturity_age: inherit_trait(links.source("maturity_age", rng), parent_a.maturity_age, parent_b.maturity_age, 0.3, 0.7, rng, mutation_scale, functional.large, functional.small),
+            disease_resistance: inherit_trait(links.source("disease_resistance", rng), parent_a.disease_resistance, parent_b.disease_resistance, 0.0, 1.0, rng, mutation_scale, functional.large, functional.small),
+            temp_tolerance: inherit_trait(links.source("temp_tolerance", rng), parent_a.temp_tolerance, parent_b.temp_tolerance, 0.0, 1.0, rng, mutation_scale, functional.large, functional.small),
+            parental_care: inherit_trait(links.source("parental_care", rng), parent_a.parental_care, parent_b.parental_care, 0.0, 1.0, rng, mutation_scale, functional.large, functional.small),
         };
 
         // Inbreeding penalties
-        if inbred {
-            child.lifespan_factor *= 0.85;
-            child.fertility *= 0.90;
+        if severity > 0.0 {
+            child.lifespan_factor *= 1.0 - 0.15 * severity;
+            child.fertility *= 1.0 - 0.10 * severity;
         }
 
         child
     }
+
+    /// Neutral drift: every listed trait takes a small gaussian step of
+    /// `magnitude` times its range, regardless of mutation rolls. These traits
+    /// have no fitness effect, so any divergence they build up is pure drift.
+    pub fn drift(&mut self, magnitude: f32, rng: &mut impl Rng) {
+        if magnitude <= 0.0 { return; }
+        let mut walk = |range: f32| {
+            let normal = Normal::new(0.0_f64, (magnitude * range) as f64).unwrap_or(Normal::new(0.0, 1.0).unwrap());
+            normal.sample(rng) as f32
+        };
+        self.base_hue = (self.base_hue + walk(360.0)).rem_euclid(360.0);
+        let mut step = |value: &mut f32, min: f32, max: f32| {
+            *value = (*value + walk(max - min)).clamp(min, max);
+        };
+        step(&mut self.saturation, 0.3, 1.0);
+        step(&mut self.lightness, 0.3, 0.7);
+        step(&mut self.dorsal_fin_size, 0.3, 1.5);
+        step(&mut self.pectoral_fin_size, 0.3, 1.5);
+        step(&mut self.pattern_intensity, 0.0, 1.0);
+        step(&mut self.pattern_color_offset, 0.0, 180.0);
+        step(&mut self.eye_size, 0.5, 1.5);
+    }
+
+    /// Synthetic "type specimen" averaging a group of genomes. Hue and gradient
+    /// direction use a circular mean, sex and pattern type use plurality.
+    /// The result has id 0 and is never persisted.
+    pub fn average(members: &[&FishGenome]) -> Option<Self> {
+        if members.is_empty() { return None; }
+        let n = members.len() as f32;
+        let mean = |f: fn(&FishGenome) -> f32| members.iter().map(|g| f(g)).sum::<f32>() / n;
+
+        let males = members.iter().filter(|g| g.sex == Sex::Male).count();
+        let sex = if males * 2 >= members.len() { Sex::Male } else { Sex::Female };
+
+        let mut type_counts = [0usize; 5];
+        for g in members {
+            type_counts[g.pattern.type_index() as usize] += 1;
+        }
+        // Ties go to the lower type index for determinism
+        let plurality = (0..5).rev().max_by_key(|&i| type_counts[i]).unwrap_or(0) as u8;
+        let of_type: Vec<&PatternGene> = members.iter()
+            .map(|g| &g.pattern)
+            .filter(|p| p.type_index() == plurality)
+            .collect();
+        let k = of_type.len().max(1) as f32;
+        let pattern = match plurality {
+            1 => PatternGene::Striped {
+                angle: of_type.iter().map(|p| if let PatternGene::Striped { angle } = p { *angle } else { 0.0 }).sum::<f32>() / k,
+            },
+            2 => PatternGene::Spotted {
+                density: of_type.iter().map(|p| if let PatternGene::Spotted { density } = p { *density } else { 0.0 }).sum::<f32>() / k,
+            },
+            3 => PatternGene::Gradient {
+                direction: circular_mean(of_type.iter().map(|p| if let PatternGene::Gradient { direction } = p { *direction } else { 0.0 })),
+            },
+            4 => PatternGene::Bicolor {
+                split: of_type.iter().map(|p| if let PatternGene::Bicolor { split } = p { *split } else { 0.0 }).sum::<f32>() / k,
+            },
+            _ => PatternGene::Solid,
+        };
+
+        Some(Self {
+            id: 0,
+            generation: (mean(|g| g.generation as f32)).round() as u32,
+            parent_a: None,
+            parent_b: None,
+            sex,
+
+            base_hue: circular_mean(members.iter().map(|g| g.base_hue)),
+            saturation: mean(|g| g.saturation),
+            lightness: mean(|g| g.lightness),
+            body_length: mean(|g| g.body_length),
+            body_width: mean(|g| g.body_width),
+            tail_size: mean(|g| g.tail_size),
+            dorsal_fin_size: mean(|g| g.dorsal_fin_size),
+            pectoral_fin_size: mean(|g| g.pectoral_fin_size),
+            pattern,
+            pattern_intensity: mean(|g| g.pattern_intensity),
+            pattern_color_offset: mean(|g| g.pattern_color_offset),
+            eye_size: mean(|g| g.eye_size),
+
+            speed: mean(|g| g.speed),
+            aggression: mean(|g| g.aggression),
+            gape_size: mean(|g| g.gape_size),
+            school_affinity: mean(|g| g.school_affinity),
+            ideal_school_size: mean(|g| g.ideal_school_size),
+            polarization: mean(|g| g.polarization),
+            curiosity: mean(|g| g.curiosity),
+            boldness: mean(|g| g.boldness),
+
+            metabolism: mean(|g| g.metabolism),
+            fertility: mean(|g| g.fertility),
+            lifespan_factor: mean(|g| g.lifespan_factor),
+            maturity_age: mean(|g| g.maturity_age),
+            disease_resistance: mean(|g| g.disease_resistance),
+            temp_tolerance: mean(|g| g.temp_tolerance),
+            parental_care: mean(|g| g.parental_care),
+        })
+    }
 }
 
-fn inherit_trait(a: f32, b: f32, min: f32, max: f32, rng: &mut impl Rng, mutation_scale: f32, rate_large: f32, rate_small: f32) -> f32 {
-    // Inheritance: dominant (60%) or blended (40%)
-    let base = if rng.gen_bool(0.6) {
-        if rng.gen_bool(0.5) { a } else { b }
-    } else {
-        let w: f32 = rng.gen_range(0.3..0.7);
-        a * w + b * (1.0 - w)
+/// Chance a trait is copied whole from one parent rather than blended
+const DOMINANT_CHANCE: f64 = 0.6;
+/// Blended traits take this range of weight on parent a
+const BLEND_WEIGHT: (f32, f32) = (0.3, 0.7);
+/// Mutation step standard deviations, as fractions of the trait's range
+const LARGE_MUTATION_SIGMA: f32 = 0.2;
+const SMALL_MUTATION_SIGMA: f32 = 0.05;
+
+/// Relatedness at which inbreeding penalties apply at their base strength
+pub const HALF_SIBLING_RELATEDNESS: f32 = 0.25;
+
+fn inbreeding_severity(relatedness: f32) -> f32 {
+    (relatedness / HALF_SIBLING_RELATEDNESS).clamp(0.0, 2.0)
+}
+
+/// One draw of a single numeric trait exactly as `inherit` would produce it,
+/// without minting a genome id. `range` is the trait's valid (min, max).
+pub fn sample_inherited_trait(a: f32, b: f32, range: (f32, f32), relatedness: f32, rate_large: f32, rate_small: f32, rng: &mut impl Rng) -> f32 {
+    let mutation_scale = 1.0 + 0.5 * inbreeding_severity(relatedness);
+    inherit_trait(Source::draw(rng), a, b, range.0, range.1, rng, mutation_scale, rate_large, rate_small)
+}
+
+/// One cell of the inheritance table for a numeric trait: where the child's
+/// copy comes from crossed with how it mutates
+#[derive(Debug, Clone, Serialize)]
+pub struct InheritanceCell {
+    pub source: &'static str,   // parent_a, parent_b or blend
+    pub mutation: &'static str, // none, small or large
+    pub probability: f32,
+    pub low: f32,  // value before mutation; blends spread evenly over low..high
+    pub high: f32,
+    pub sigma: f32, // standard deviation of the mutation step, 0 for none
+}
+
+/// Every outcome `sample_inherited_trait` can draw, with its probability.
+/// Rows are parent a, parent b and blend; columns no, small and large
+/// mutation. Results are clamped to `range` after mutation.
+pub fn inheritance_cells(a: f32, b: f32, range: (f32, f32), relatedness: f32, rate_large: f32, rate_small: f32) -> Vec<InheritanceCell> {
+    let mutation_scale = 1.0 + 0.5 * inbreeding_severity(relatedness);
+    let p_large = (rate_large * mutation_scale).clamp(0.0, 1.0);
+    let p_small = ((rate_large + rate_small) * mutation_scale).clamp(0.0, 1.0) - p_large;
+    let span = range.1 - range.0;
+    let mutations = [
+        ("none", 1.0 - p_large - p_small, 0.0),
+        ("small", p_small, SMALL_MUTATION_SIGMA * span),
+        ("large", p_large, LARGE_MUTATION_SIGMA * span),
+    ];
+    let dominant = DOMINANT_CHANCE as f32 / 2.0;
+    let blend_ends = [a * BLEND_WEIGHT.0 + b * (1.0 - BLEND_WEIGHT.0), a * BLEND_WEIGHT.1 + b * (1.0 - BLEND_WEIGHT.1)];
+    let sources = [
+        ("parent_a", dominant, a, a),
+        ("parent_b", dominant, b, b),
+        ("blend", 1.0 - DOMINANT_CHANCE as f32, blend_ends[0].min(blend_ends[1]), blend_ends[0].max(blend_ends[1])),
+    ];
+    sources.iter().flat_map(|&(source, p_source, low, high)| {
+        mutations.iter().map(move |&(mutation, p_mutation, sigma)| InheritanceCell {
+            source, mutation, probability: p_source * p_mutation, low, high, sigma,
+        })
+    }).collect()
+}
+
+/// Ancestor ids of `genome` up to `depth` generations back, mapped to the fewest
+/// generations separating them. The genome itself is included at distance 0.
+/// Ancestors pruned from `genomes` end their branch of the walk.
+fn ancestor_distances(genome: &FishGenome, genomes: &HashMap<u32, FishGenome>, depth: u32) -> HashMap<u32, u32> {
+    let mut found = HashMap::from([(genome.id, 0)]);
+    let mut frontier = vec![(genome.parent_a, genome.parent_b)];
+    for distance in 1..=depth {
+        let mut next = Vec::new();
+        for id in frontier.into_iter().flat_map(|(a, b)| [a, b]).flatten() {
+            if found.contains_key(&id) {
+                continue;
+            }
+            found.insert(id, distance);
+            if let Some(g) = genomes.get(&id) {
+                next.push((g.parent_a, g.parent_b));
+            }
+        }
+        frontier = next;
+    }
+    found
+}
+
+/// Coefficient of relationship between two genomes from ancestors shared within
+/// `depth` generations: 0.5 for full siblings or parent/child, 0.25 for half
+/// siblings, 0.125 for first cousins. Shared ancestors that are only reached
+/// through a closer shared ancestor are not counted again. Depth 0 disables the check.
+pub fn relatedness(a: &FishGenome, b: &FishGenome, genomes: &HashMap<u32, FishGenome>, depth: u32) -> f32 {
+    if depth == 0 || a.id == b.id {
+        return 0.0;
+    }
+    let ancestors_a = ancestor_distances(a, genomes, depth);
+    let ancestors_b = ancestor_distances(b, genomes, depth);
+    let shared: Vec<(u32, u32)> = ancestors_a.iter()
+        .filter_map(|(id, da)| ancestors_b.get(id).map(|db| (*id, da + db)))
+        .collect();
+
+    // Skip ancestors that are a parent of another shared ancestor
+    let is_behind_closer = |id: u32| shared.iter().any(|(other, _)| {
+        genomes.get(other).is_some_and(|g| g.parent_a == Some(id) || g.parent_b == Some(id))
+    });
+    let r: f32 = shared.iter()
+        .filter(|(id, _)| !is_behind_closer(*id))
+        .map(|(_, steps)| 0.5_f32.powi(*steps as i32))
+        .sum();
+    r.min(1.0)
+}
+
+/// Parent links by genome id, as stored in the genomes table
+pub type ParentLinks = HashMap<u32, (Option<u32>, Option<u32>)>;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FounderShare {
+    pub founder_id: u32,
+    pub share: f64, // fraction of the living gene pool descending from this founder
+    pub living_descendants: u32,
+}
+
+/// How the living gene pool divides among the founders it descends from
+#[derive(Debug, Clone, Serialize)]
+pub struct FounderAnalysis {
+    pub living_count: u32,
+    pub founders: Vec<FounderShare>, // largest share first
+    pub lost_lineages: Vec<u32>,     // founders with no living descendants
+    pub untraced_share: f64,         // ancestry lost to genomes missing from the links
+}
+
+/// Trace each living genome back to its founders (genomes with no parents).
+/// Each parent passes on half of a genome, or all of it when only one is recorded.
+pub fn founder_analysis(living: &[u32], links: &ParentLinks) -> FounderAnalysis {
+    // None = untraceable ancestor
+    fn contributions(id: u32, links: &ParentLinks, memo: &mut HashMap<u32, HashMap<Option<u32>, f64>>) -> HashMap<Option<u32>, f64> {
+        if let Some(c) = memo.get(&id) {
+            return c.clone();
+        }
+        let result = match links.get(&id) {
+            None => HashMap::from([(None, 1.0)]),
+            Some((None, None)) => HashMap::from([(Some(id), 1.0)]),
+            Some(&(a, b)) => {
+                let parents: Vec<u32> = [a, b].into_iter().flatten().collect();
+                let weight = 1.0 / parents.len() as f64;
+                let mut merged = HashMap::new();
+                for p in parents {
+                    for (founder, share) in contributions(p, links, memo) {
+                        *merged.entry(founder).or_insert(0.0) += share * weight;
+                    }
+                }
+                merged
+            }
+        };
+        memo.insert(id, result.clone());
+        result
+    }
+
+    let mut memo = HashMap::new();
+    let mut shares: HashMap<Option<u32>, f64> = HashMap::new();
+    let mut descendants: HashMap<u32, u32> = HashMap::new();
+    for &gid in living {
+        for (founder, share) in contributions(gid, links, &mut memo) {
+            *shares.entry(founder).or_insert(0.0) += share / living.len() as f64;
+            if let Some(f) = founder {
+                *descendants.entry(f).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut founders: Vec<FounderShare> = shares.iter()
+        .filter_map(|(founder, &share)| founder.map(|id| FounderShare {
+            founder_id: id,
+            share,
+            living_descendants: descendants.get(&id).copied().unwrap_or(0),
+        }))
+        .collect();
+    founders.sort_by(|a, b| b.share.total_cmp(&a.share).then(a.founder_id.cmp(&b.founder_id)));
+
+    let mut lost_lineages: Vec<u32> = links.iter()
+        .filter(|(id, parents)| **parents == (None, None) && !descendants.contains_key(id))
+        .map(|(id, _)| *id)
+        .collect();
+    lost_lineages.sort_unstable();
+
+    FounderAnalysis {
+        living_count: living.len() as u32,
+        founders,
+        lost_lineages,
+        untraced_share: shares.get(&None).copied().unwrap_or(0.0),
+    }
+}
+
+/// Earliest ancestor of each genome within `links`, following parent_a where it is
+/// present and parent_b otherwise, so each branch of a family tree keeps one root
+pub fn lineage_roots(links: &ParentLinks) -> HashMap<u32, u32> {
+    links.keys().map(|&id| {
+        let mut cur = id;
+        // Bounded walk in case of a malformed cycle
+        for _ in 0..links.len() {
+            let (a, b) = links[&cur];
+            match a.filter(|p| links.contains_key(p)).or(b.filter(|p| links.contains_key(p))) {
+                Some(parent) => cur = parent,
+                None => break,
+            }
+        }
+        (id, cur)
+    }).collect()
+}
+
+/// Stable CSS color for a lineage root: golden-angle hue steps keep
+/// consecutive ids far apart on the color wheel
+pub fn lineage_color(root_id: u32) -> String {
+    let hue = (root_id as f64 * 137.508).rem_euclid(360.0);
+    format!("hsl({:.0}, 65%, 55%)", hue)
+}
+
+/// Mean of angles in degrees on the 0-360 circle
+pub fn circular_mean(angles: impl Iterator<Item = f32>) -> f32 {
+    let (mut sin_sum, mut cos_sum) = (0.0_f32, 0.0_f32);
+    for a in angles {
+        let rad = a.to_radians();
+        sin_sum += rad.sin();
+        cos_sum += rad.cos();
+    }
+    sin_sum.atan2(cos_sum).to_degrees().rem_euclid(360.0)
+}
+
+/// Per-gene chances of a large jump and of a small nudge at inheritance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MutationRates {
+    pub large: f32,
+    pub small: f32,
+}
+
+impl MutationRates {
+    /// Offspring genes are exact copies or blends of their parents'
+    pub const NONE: Self = Self { large: 0.0, small: 0.0 };
+
+    pub fn scaled(self, large: f32, small: f32) -> Self {
+        Self { large: self.large * large, small: self.small * small }
+    }
+}
+
+/// A pair of numeric traits inherited together: with probability `strength`
+/// the second one to be inherited copies the first one's parent choice.
+/// Trait names are `FishGenome` field names as listed in `TRAIT_INFO`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraitLinkage {
+    pub traits: [String; 2],
+    pub strength: f32,
+}
+
+impl TraitLinkage {
+    fn partner_of(&self, name: &str) -> Option<&str> {
+        match &self.traits {
+            [a, b] if a == name => Some(b),
+            [a, b] if b == name => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Rejects unknown or categorical traits and clamps `strength` to 0..1
+    pub fn validated(mut self) -> Result<Self, String> {
+        for name in &self.traits {
+            if !TRAIT_INFO.iter().any(|t| t.name == name && t.range.is_some()) {
+                return Err(format!("Cannot link trait '{}'", name));
+            }
+        }
+        if self.traits[0] == self.traits[1] {
+            return Err("A trait cannot be linked to itself".into());
+        }
+        self.strength = self.strength.clamp(0.0, 1.0);
+        Ok(self)
+    }
+}
+
+/// Where a child's copy of one trait comes from, before mutation
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Source {
+    ParentA,
+    ParentB,
+    Blend(f32), // weight on parent a
+}
+
+impl Source {
+    /// Dominant (60%) or blended (40%)
+    fn draw(rng: &mut impl Rng) -> Self {
+        if rng.gen_bool(DOMINANT_CHANCE) {
+            if rng.gen_bool(0.5) { Source::ParentA } else { Source::ParentB }
+        } else {
+            Source::Blend(rng.gen_range(BLEND_WEIGHT.0..BLEND_WEIGHT.1))
+        }
+    }
+}
+
+/// Remembers each trait's `Source` during one `inherit` so linked traits can share it
+struct Linker<'a> {
+    linkage: &'a [TraitLinkage],
+    chosen: Vec<(&'static str, Source)>,
+}
+
+impl<'a> Linker<'a> {
+    fn new(linkage: &'a [TraitLinkage]) -> Self {
+        Self { linkage, chosen: Vec::new() }
+    }
+
+    fn source(&mut self, name: &'static str, rng: &mut impl Rng) -> Source {
+        let linked = self.linkage.iter().find_map(|link| {
+            let partner = link.partner_of(name)?;
+            let (_, source) = self.chosen.iter().find(|(n, _)| *n == partner)?;
+            Some((*source, link.strength))
+        });
+        let source = match linked {
+            Some((source, strength)) if rng.gen::<f32>() < strength => source,
+            _ => Source::draw(rng),
+        };
+        if !self.linkage.is_empty() {
+            self.chosen.push((name, source));
+        }
+        source
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn inherit_trait(source: Source, a: f32, b: f32, min: f32, max: f32, rng: &mut impl Rng, mutation_scale: f32, rate_large: f32, rate_small: f32) -> f32 {
+    let base = match source {
+        Source::ParentA => a,
+        Source::ParentB => b,
+        Source::Blend(w) => a * w + b * (1.0 - w),
     };
 
     // Mutation
@@ -217,12 +847,12 @@ fn inherit_trait(a: f32, b: f32, min: f32, max: f32, rng: &mut impl Rng, mutatio
     let roll: f32 = rng.gen();
     let mutated = if roll < rate_large * mutation_scale {
         // Large mutation
-        let sigma = (0.2 * range) as f64;
+        let sigma = (LARGE_MUTATION_SIGMA * range) as f64;
         let normal = Normal::new(0.0_f64, sigma).unwrap_or(Normal::new(0.0, 1.0).unwrap());
         base + normal.sample(rng) as f32
     } else if roll < (rate_large + rate_small) * mutation_scale {
         // Small mutation
-        let sigma = (0.05 * range) as f64;
+        let sigma = (SMALL_MUTATION_SIGMA * range) as f64;
         let normal = Normal::new(0.0_f64, sigma).unwrap_or(Normal::new(0.0, 1.0).unwrap());
         base + normal.sample(rng) as f32
     } else {
@@ -233,17 +863,16 @@ fn inherit_trait(a: f32, b: f32, min: f32, max: f32, rng: &mut impl Rng, mutatio
 }
 
 /// Circular hue inheritance (wraps around 0/360 boundary correctly)
-fn inherit_hue(a: f32, b: f32, rng: &mut impl Rng, mutation_scale: f32, rate_large: f32, rate_small: f32) -> f32 {
+fn inherit_hue(source: Source, a: f32, b: f32, rng: &mut impl Rng, mutation_scale: f32, rate_large: f32, rate_small: f32) -> f32 {
     // Use shortest arc on color wheel
     let mut diff = b - a;
     if diff > 180.0 { diff -= 360.0; }
     if diff < -180.0 { diff += 360.0; }
 
-    let base = if rng.gen_bool(0.6) {
-        if rng.gen_bool(0.5) { a } else { b }
-    } else {
-        let w: f32 = rng.gen_range(0.3..0.7);
-        a + diff * w
+    let base = match source {
+        Source::ParentA => a,
+        Source::ParentB => b,
+        Source::Blend(w) => a + diff * w,
     };
 
     // Mutation
@@ -259,12 +888,15 @@ fn inherit_hue(a: f32, b: f32, rng: &mut impl Rng, mutation_scale: f32, rate_lar
     mutated.rem_euclid(360.0)
 }
 
-fn inherit_pattern(a: &PatternGene, b: &PatternGene, rng: &mut impl Rng) -> PatternGene {
+/// Pattern inheritance. At the default appearance rates a tenth of offspring
+/// get a new pattern type and a further fifth a tweaked one.
+fn inherit_pattern(a: &PatternGene, b: &PatternGene, rng: &mut impl Rng, rates: MutationRates) -> PatternGene {
+    let new_type = rates.large * 5.0;
     let roll: f32 = rng.gen();
-    if roll < 0.10 {
+    if roll < new_type {
         // Completely new pattern type
         PatternGene::random(rng)
-    } else if roll < 0.30 {
+    } else if roll < new_type + rates.small * 2.0 {
         // Inherit one parent's pattern but mutate sub-params
         let base = if rng.gen_bool(0.5) { a } else { b };
         match base {
@@ -289,24 +921,131 @@ fn inherit_pattern(a: &PatternGene, b: &PatternGene, rng: &mut impl Rng) -> Patt
 }
 
 /// Genome distance for species affinity and reproduction compatibility
-pub fn genome_distance(a: &FishGenome, b: &FishGenome) -> f32 {
-    let mut d = 0.0_f32;
+/// Multipliers on the appearance and behavior halves of `genome_distance`.
+/// The default (1, 1) keeps appearance dominant; raising `behavior` lets
+/// look-alike fish that act differently split into separate species.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceWeights {
+    pub appearance: f32,
+    pub behavior: f32,
+}
 
+impl Default for DistanceWeights {
+    fn default() -> Self {
+        Self { appearance: 1.0, behavior: 1.0 }
+    }
+}
+
+pub fn genome_distance(a: &FishGenome, b: &FishGenome, weights: DistanceWeights) -> f32 {
     // Appearance traits (weighted higher)
-    d += hue_distance(a.base_hue, b.base_hue) / 180.0 * 3.0;
-    d += (a.saturation - b.saturation).abs() * 1.5;
-    d += (a.body_length - b.body_length).abs() / 1.4 * 2.0;
-    d += (a.body_width - b.body_width).abs() * 1.0;
-    d += pattern_distance(&a.pattern, &b.pattern) * 2.5;
-    d += (a.pattern_intensity - b.pattern_intensity).abs() * 1.0;
+    let mut appearance = 0.0_f32;
+    appearance += hue_distance(a.base_hue, b.base_hue) / 180.0 * 3.0;
+    appearance += (a.saturation - b.saturation).abs() * 1.5;
+    appearance += (a.body_length - b.body_length).abs() / 1.4 * 2.0;
+    appearance += (a.body_width - b.body_width).abs() * 1.0;
+    appearance += pattern_distance(&a.pattern, &b.pattern) * 2.5;
+    appearance += (a.pattern_intensity - b.pattern_intensity).abs() * 1.0;
 
     // Behavior traits (weighted lower)
-    d += (a.speed - b.speed).abs() / 1.5 * 0.5;
-    d += (a.aggression - b.aggression).abs() * 0.5;
-    d += (a.school_affinity - b.school_affinity).abs() * 0.5;
-    d += (a.disease_resistance - b.disease_resistance).abs() * 0.3;
+    let mut behavior = 0.0_f32;
+    behavior += (a.speed - b.speed).abs() / 1.5 * 0.5;
+    behavior += (a.aggression - b.aggression).abs() * 0.5;
+    behavior += (a.school_affinity - b.school_affinity).abs() * 0.5;
+    behavior += (a.disease_resistance - b.disease_resistance).abs() * 0.3;
+    behavior += (a.temp_tolerance - b.temp_tolerance).abs() * 0.3;
 
-    d
+    appearance * weights.appearance + behavior * weights.behavior
+}
+
+/// One trait side by side; `diff` is always non-negative
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TraitDiff {
+    pub a: f32,
+    pub b: f32,
+    pub diff: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SexDiff {
+    pub a: Sex,
+    pub b: Sex,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternDiff {
+    pub a: PatternGene,
+    pub b: PatternGene,
+    pub same_type: bool,
+}
+
+/// Trait-by-trait comparison of two genomes, plus whether they'd count as one species
+#[derive(Debug, Clone, Serialize)]
+pub struct GenomeDiff {
+    pub genome_a_id: u32,
+    pub genome_b_id: u32,
+    pub traits: BTreeMap<&'static str, TraitDiff>,
+    pub sex: SexDiff,
+    pub pattern: PatternDiff,
+    pub genome_distance: f32,
+    pub species_threshold: f32,
+    pub same_species: bool,
+}
+
+type TraitGetter = fn(&FishGenome) -> f32;
+
+/// Numeric traits compared by `diff_genomes`; base_hue is handled separately
+const DIFFED_TRAITS: &[(&str, TraitGetter)] = &[
+    ("saturation", |g| g.saturation),
+    ("lightness", |g| g.lightness),
+    ("body_length", |g| g.body_length),
+    ("body_width", |g| g.body_width),
+    ("tail_size", |g| g.tail_size),
+    ("dorsal_fin_size", |g| g.dorsal_fin_size),
+    ("pectoral_fin_size", |g| g.pectoral_fin_size),
+    ("pattern_intensity", |g| g.pattern_intensity),
+    ("pattern_color_offset", |g| g.pattern_color_offset),
+    ("eye_size", |g| g.eye_size),
+    ("speed", |g| g.speed),
+    ("aggression", |g| g.aggression),
+    ("gape_size", |g| g.gape_size),
+    ("school_affinity", |g| g.school_affinity),
+    ("ideal_school_size", |g| g.ideal_school_size),
+    ("polarization", |g| g.polarization),
+    ("curiosity", |g| g.curiosity),
+    ("boldness", |g| g.boldness),
+    ("metabolism", |g| g.metabolism),
+    ("fertility", |g| g.fertility),
+    ("lifespan_factor", |g| g.lifespan_factor),
+    ("maturity_age", |g| g.maturity_age),
+    ("disease_resistance", |g| g.disease_resistance),
+    ("temp_tolerance", |g| g.temp_tolerance),
+    ("parental_care", |g| g.parental_care),
+    ("generation", |g| g.generation as f32),
+];
+
+pub fn diff_genomes(a: &FishGenome, b: &FishGenome, weights: DistanceWeights, species_threshold: f32) -> GenomeDiff {
+    let mut traits = BTreeMap::new();
+    // Hue difference is the shortest way around the color wheel
+    traits.insert("base_hue", TraitDiff { a: a.base_hue, b: b.base_hue, diff: hue_distance(a.base_hue, b.base_hue) });
+    for &(name, get) in DIFFED_TRAITS {
+        let (va, vb) = (get(a), get(b));
+        traits.insert(name, TraitDiff { a: va, b: vb, diff: (va - vb).abs() });
+    }
+    let distance = genome_distance(a, b, weights);
+    GenomeDiff {
+        genome_a_id: a.id,
+        genome_b_id: b.id,
+        traits,
+        sex: SexDiff { a: a.sex, b: b.sex },
+        pattern: PatternDiff {
+            a: a.pattern.clone(),
+            b: b.pattern.clone(),
+            same_type: a.pattern.type_index() == b.pattern.type_index(),
+        },
+        genome_distance: distance,
+        species_threshold,
+        same_species: distance < species_threshold,
+    }
 }
 
 fn hue_distance(a: f32, b: f32) -> f32 {
@@ -342,6 +1081,8 @@ mod tests {
     use rand::SeedableRng;
     use rand::rngs::StdRng;
 
+    const DEFAULT_RATES: MutationRates = MutationRates { large: 0.02, small: 0.10 };
+
     fn seeded_rng() -> StdRng {
         StdRng::seed_from_u64(42)
     }
@@ -417,6 +1158,109 @@ mod tests {
         assert_ne!(g1.id, g2.id);
     }
 
+    #[test]
+    fn apex_predator_can_hunt_typical_fish() {
+        let mut rng = seeded_rng();
+        for _ in 0..20 {
+            let g = FishGenome::apex_predator(&mut rng);
+            assert!(g.aggression > 0.8, "Above the hunting threshold regardless of hunger");
+            assert!(g.body_length >= 1.8);
+            assert!(g.speed >= 1.7);
+            assert!(matches!(g.pattern, PatternGene::Striped { .. }));
+        }
+    }
+
+    #[test]
+    fn gape_size_scales_prey_limit() {
+        let mut rng = seeded_rng();
+        let mut g = FishGenome::random(&mut rng);
+        g.body_length = 2.0;
+        g.gape_size = 1.0;
+        assert!((g.max_prey_length(0.6) - 1.2).abs() < 1e-6);
+        g.gape_size = 1.25;
+        assert!((g.max_prey_length(0.6) - 1.5).abs() < 1e-6, "Wide gape takes larger prey");
+    }
+
+    #[test]
+    fn trait_info_covers_every_genome_trait() {
+        let mut rng = seeded_rng();
+        let value = serde_json::to_value(FishGenome::random(&mut rng)).unwrap();
+        let mut fields: Vec<&str> = value.as_object().unwrap().keys()
+            .map(|k| k.as_str())
+            .filter(|k| !matches!(*k, "id" | "generation" | "parent_a" | "parent_b"))
+            .collect();
+        let mut listed: Vec<&str> = TRAIT_INFO.iter().map(|t| t.name).collect();
+        fields.sort_unstable();
+        listed.sort_unstable();
+        assert_eq!(fields, listed);
+        for t in TRAIT_INFO {
+            if let Some([min, max]) = t.range {
+                let v = value[t.name].as_f64().unwrap() as f32;
+                assert!(min < max && v >= min && v <= max, "{} = {} outside {:?}", t.name, v, t.range);
+            }
+        }
+    }
+
+    #[test]
+    fn gape_size_defaults_for_older_genomes() {
+        let mut rng = seeded_rng();
+        let g = FishGenome::random(&mut rng);
+        let mut v = serde_json::to_value(&g).unwrap();
+        v.as_object_mut().unwrap().remove("gape_size");
+        let restored: FishGenome = serde_json::from_value(v).unwrap();
+        assert_eq!(restored.gape_size, 1.0);
+    }
+
+    #[test]
+    fn added_traits_default_when_missing() {
+        assert_eq!(ADDED_TRAITS.last().map(|t| t.since_version), Some(GENOME_VERSION));
+        let mut rng = seeded_rng();
+        let g = FishGenome::random(&mut rng);
+        for t in ADDED_TRAITS {
+            let mut v = serde_json::to_value(&g).unwrap();
+            v.as_object_mut().unwrap().remove(t.name);
+            let restored: FishGenome = serde_json::from_value(v).unwrap_or_else(|e| panic!("{} has no serde default: {}", t.name, e));
+            assert_eq!(serde_json::to_value(&restored).unwrap()[t.name], serde_json::json!(t.default));
+        }
+    }
+
+    #[test]
+    fn versioned_json_roundtrips_across_versions() {
+        let mut rng = seeded_rng();
+        let g = FishGenome::random(&mut rng);
+        let v = g.to_versioned_json();
+        assert_eq!(v["genome_version"], GENOME_VERSION);
+        let restored = FishGenome::from_versioned_json(v.clone()).unwrap();
+        assert_eq!(restored.temp_tolerance, g.temp_tolerance);
+
+        // A version 1 genome from before any added trait
+        let mut old = v.clone();
+        for t in ADDED_TRAITS {
+            old.as_object_mut().unwrap().remove(t.name);
+        }
+        old.as_object_mut().unwrap().remove("genome_version");
+        let restored = FishGenome::from_versioned_json(old).unwrap();
+        assert_eq!(restored.disease_resistance, DEFAULT_DISEASE_RESISTANCE);
+        assert_eq!(restored.gape_size, DEFAULT_GAPE_SIZE);
+
+        // A newer build's genome with a trait this one doesn't know
+        let mut newer = v;
+        newer["genome_version"] = serde_json::json!(GENOME_VERSION + 1);
+        newer["bioluminescence"] = serde_json::json!(0.7);
+        assert_eq!(FishGenome::from_versioned_json(newer).unwrap().id, g.id);
+    }
+
+    #[test]
+    fn temperature_stress_beyond_tolerance() {
+        let mut rng = seeded_rng();
+        let mut g = FishGenome::random(&mut rng);
+        g.temp_tolerance = 0.3;
+        assert_eq!(g.temperature_stress(0.0), 0.0, "No stress at normal temperature");
+        assert!((g.temperature_stress(1.0) - 0.7).abs() < 1e-6);
+        g.temp_tolerance = 1.0;
+        assert_eq!(g.temperature_stress(1.0), 0.0, "Fully tolerant fish shrug it off");
+    }
+
     // --- Inheritance ---
 
     #[test]
@@ -424,7 +1268,7 @@ mod tests {
         let mut rng = seeded_rng();
         let parent_a = FishGenome::random(&mut rng);
         let parent_b = FishGenome::random(&mut rng);
-        let child = FishGenome::inherit(&parent_a, &parent_b, &mut rng, false, 0.02, 0.10);
+        let child = FishGenome::inherit(&parent_a, &parent_b, &mut rng, 0.0, DEFAULT_RATES, DEFAULT_RATES, &[]);
 
         assert_eq!(child.generation, parent_a.generation.max(parent_b.generation) + 1);
         assert_eq!(child.parent_a, Some(parent_a.id));
@@ -437,6 +1281,81 @@ mod tests {
         assert!(child.aggression >= 0.0 && child.aggression <= 1.0);
     }
 
+    #[test]
+    fn appearance_and_functional_rates_are_independent() {
+        let mut rng = seeded_rng();
+        let parent = FishGenome::random(&mut rng);
+        let twin = parent.clone();
+        let always = MutationRates { large: 1.0, small: 0.0 };
+
+        // Identical parents only pass on their own values unless a gene mutates
+        let mut functional_changed = false;
+        for _ in 0..20 {
+            let child = FishGenome::inherit(&parent, &twin, &mut rng, 0.0, MutationRates::NONE, always, &[]);
+            for (c, p) in [(child.base_hue, parent.base_hue), (child.saturation, parent.saturation), (child.tail_size, parent.tail_size), (child.eye_size, parent.eye_size)] {
+                assert!((c - p).abs() < 1e-4);
+            }
+            assert_eq!(format!("{:?}", child.pattern), format!("{:?}", parent.pattern));
+            functional_changed |= (child.speed - parent.speed).abs() > 1e-3 || (child.aggression - parent.aggression).abs() > 1e-3;
+        }
+        assert!(functional_changed);
+
+        let mut appearance_changed = false;
+        for _ in 0..20 {
+            let child = FishGenome::inherit(&parent, &twin, &mut rng, 0.0, always, MutationRates::NONE, &[]);
+            for (c, p) in [(child.speed, parent.speed), (child.aggression, parent.aggression), (child.metabolism, parent.metabolism), (child.body_length, parent.body_length)] {
+                assert!((c - p).abs() < 1e-4);
+            }
+            appearance_changed |= (child.base_hue - parent.base_hue).abs() > 1e-3 || (child.saturation - parent.saturation).abs() > 1e-3;
+        }
+        assert!(appearance_changed);
+    }
+
+    #[test]
+    fn linked_traits_share_parent_choice() {
+        let mut rng = seeded_rng();
+        let mut parent_a = FishGenome::random(&mut rng);
+        let mut parent_b = parent_a.clone();
+        (parent_a.body_length, parent_a.metabolism) = (0.6, 0.5);
+        (parent_b.body_length, parent_b.metabolism) = (2.0, 2.0);
+        // Where each trait landed between the parents: 1.0 = parent a, 0.0 = parent b
+        let weights = |child: &FishGenome| ((child.body_length - 2.0) / (0.6 - 2.0), (child.metabolism - 2.0) / (0.5 - 2.0));
+
+        let linkage = [TraitLinkage { traits: ["body_length".into(), "metabolism".into()], strength: 1.0 }];
+        for _ in 0..100 {
+            let child = FishGenome::inherit(&parent_a, &parent_b, &mut rng, 0.0, MutationRates::NONE, MutationRates::NONE, &linkage);
+            let (length, metabolism) = weights(&child);
+            assert!((length - metabolism).abs() < 1e-4);
+        }
+
+        let unlinked = (0..100)
+            .map(|_| weights(&FishGenome::inherit(&parent_a, &parent_b, &mut rng, 0.0, MutationRates::NONE, MutationRates::NONE, &[])))
+            .filter(|(length, metabolism)| (length - metabolism).abs() > 1e-4)
+            .count();
+        assert!(unlinked > 30);
+    }
+
+    #[test]
+    fn drift_moves_only_neutral_traits() {
+        let mut rng = seeded_rng();
+        let original = FishGenome::random(&mut rng);
+
+        let mut still = original.clone();
+        still.drift(0.0, &mut rng);
+        assert_eq!(still.saturation, original.saturation);
+        assert_eq!(still.base_hue, original.base_hue);
+
+        let mut g = original.clone();
+        for _ in 0..200 {
+            g.drift(0.05, &mut rng);
+        }
+        assert!((g.saturation - original.saturation).abs() > 1e-4 || (g.eye_size - original.eye_size).abs() > 1e-4);
+        assert!(g.base_hue >= 0.0 && g.base_hue < 360.0);
+        assert!(g.saturation >= 0.3 && g.saturation <= 1.0);
+        assert!(g.pattern_color_offset >= 0.0 && g.pattern_color_offset <= 180.0);
+        assert_eq!((g.speed, g.aggression, g.metabolism, g.body_length), (original.speed, original.aggression, original.metabolism, original.body_length));
+    }
+
     #[test]
     fn inbreeding_reduces_fitness() {
         let mut rng = seeded_rng();
@@ -448,8 +1367,8 @@ mod tests {
         let mut normal_lifespan_sum = 0.0_f64;
         let trials = 500;
         for _ in 0..trials {
-            let inbred = FishGenome::inherit(&parent_a, &parent_b, &mut rng, true, 0.02, 0.10);
-            let normal = FishGenome::inherit(&parent_a, &parent_b, &mut rng, false, 0.02, 0.10);
+            let inbred = FishGenome::inherit(&parent_a, &parent_b, &mut rng, HALF_SIBLING_RELATEDNESS, DEFAULT_RATES, DEFAULT_RATES, &[]);
+            let normal = FishGenome::inherit(&parent_a, &parent_b, &mut rng, 0.0, DEFAULT_RATES, DEFAULT_RATES, &[]);
             inbred_lifespan_sum += inbred.lifespan_factor as f64;
             normal_lifespan_sum += normal.lifespan_factor as f64;
         }
@@ -462,6 +1381,125 @@ mod tests {
         );
     }
 
+    // --- Relatedness ---
+
+    /// Founders a, b; children c (a×b), d (a×b), e (a×b); grandchildren
+    /// f (c×x) and g (d×y) are first cousins; x and y are unrelated mates.
+    fn family(rng: &mut StdRng) -> HashMap<u32, FishGenome> {
+        let mut genomes = HashMap::new();
+        let mut add = |id: u32, parents: Option<(u32, u32)>, rng: &mut StdRng| {
+            let mut g = FishGenome::random(rng);
+            g.id = id;
+            g.parent_a = parents.map(|p| p.0);
+            g.parent_b = parents.map(|p| p.1);
+            genomes.insert(id, g);
+        };
+        for id in [1, 2, 100, 101] { add(id, None, rng); }
+        add(3, Some((1, 2)), rng);
+        add(4, Some((1, 2)), rng);
+        add(5, Some((1, 100)), rng);
+        add(6, Some((3, 100)), rng);
+        add(7, Some((4, 101)), rng);
+        genomes
+    }
+
+    #[test]
+    fn relatedness_of_close_relatives() {
+        let mut rng = seeded_rng();
+        let g = family(&mut rng);
+        let r = |a: u32, b: u32| relatedness(&g[&a], &g[&b], &g, 2);
+        assert!((r(3, 4) - 0.5).abs() < 1e-6, "Full siblings");
+        assert!((r(1, 3) - 0.5).abs() < 1e-6, "Parent and child");
+        assert!((r(3, 5) - 0.25).abs() < 1e-6, "Half siblings");
+        assert!((r(6, 7) - 0.125).abs() < 1e-6, "First cousins");
+        assert_eq!(r(100, 101), 0.0, "Unrelated founders");
+    }
+
+    #[test]
+    fn relatedness_limited_by_depth() {
+        let mut rng = seeded_rng();
+        let g = family(&mut rng);
+        // Cousins share only grandparents, two generations back on each side
+        assert_eq!(relatedness(&g[&6], &g[&7], &g, 1), 0.0);
+        assert!((relatedness(&g[&3], &g[&4], &g, 1) - 0.5).abs() < 1e-6);
+        assert_eq!(relatedness(&g[&3], &g[&4], &g, 0), 0.0, "Depth 0 disables the check");
+    }
+
+    #[test]
+    fn inbreeding_penalty_scales_with_relatedness() {
+        let mut rng = seeded_rng();
+        let parent_a = FishGenome::random(&mut rng);
+        let parent_b = FishGenome::random(&mut rng);
+        let trials = 500;
+        let avg_lifespan = |r: f32, rng: &mut StdRng| {
+            (0..trials).map(|_| FishGenome::inherit(&parent_a, &parent_b, rng, r, DEFAULT_RATES, DEFAULT_RATES, &[]).lifespan_factor as f64).sum::<f64>() / trials as f64
+        };
+        let cousins = avg_lifespan(0.125, &mut rng);
+        let siblings = avg_lifespan(0.5, &mut rng);
+        assert!(siblings < cousins, "Sibling offspring {:.3} should fare worse than cousin offspring {:.3}", siblings, cousins);
+    }
+
+    // --- Founder analysis ---
+
+    #[test]
+    fn founder_shares_halve_each_generation() {
+        // 1 x 2 -> 10; 10 x 3 -> 20; founder 4 left no descendants
+        let links: ParentLinks = HashMap::from([
+            (1, (None, None)), (2, (None, None)), (3, (None, None)), (4, (None, None)),
+            (10, (Some(1), Some(2))),
+            (20, (Some(10), Some(3))),
+        ]);
+        let a = founder_analysis(&[20, 3], &links);
+        assert_eq!(a.living_count, 2);
+        let share = |id: u32| a.founders.iter().find(|f| f.founder_id == id).map(|f| f.share).unwrap_or(0.0);
+        // Fish 20 is 1/4 founder 1, 1/4 founder 2, 1/2 founder 3; fish 3 is all founder 3
+        assert!((share(3) - 0.75).abs() < 1e-9);
+        assert!((share(1) - 0.125).abs() < 1e-9);
+        assert_eq!(a.founders[0].founder_id, 3);
+        assert_eq!(a.founders[0].living_descendants, 2);
+        assert_eq!(a.lost_lineages, vec![4]);
+        assert_eq!(a.untraced_share, 0.0);
+    }
+
+    #[test]
+    fn founder_analysis_reports_untraced_ancestry() {
+        // Parent 99 was never recorded
+        let links: ParentLinks = HashMap::from([
+            (1, (None, None)),
+            (10, (Some(1), Some(99))),
+        ]);
+        let a = founder_analysis(&[10], &links);
+        assert!((a.untraced_share - 0.5).abs() < 1e-9);
+        assert!((a.founders[0].share - 0.5).abs() < 1e-9);
+        let total: f64 = a.founders.iter().map(|f| f.share).sum::<f64>() + a.untraced_share;
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    // --- Lineage coloring ---
+
+    #[test]
+    fn lineage_roots_follow_each_branch() {
+        // 30's parents are 20 (from 10 x 11) and 21 (from 12 x missing 99)
+        let links: ParentLinks = HashMap::from([
+            (10, (None, None)), (11, (None, None)), (12, (None, None)),
+            (20, (Some(10), Some(11))),
+            (21, (Some(99), Some(12))),
+            (30, (Some(20), Some(21))),
+        ]);
+        let roots = lineage_roots(&links);
+        assert_eq!(roots[&30], 10);
+        assert_eq!(roots[&20], 10);
+        assert_eq!(roots[&21], 12, "Falls back to parent_b when parent_a isn't in the set");
+        assert_eq!(roots[&11], 11);
+    }
+
+    #[test]
+    fn lineage_color_is_stable_and_distinct() {
+        assert_eq!(lineage_color(7), lineage_color(7));
+        assert_ne!(lineage_color(7), lineage_color(8));
+        assert!(lineage_color(1).starts_with("hsl("));
+    }
+
     // --- Hue distance ---
 
     #[test]
@@ -479,7 +1517,7 @@ mod tests {
     fn genome_distance_self_is_zero() {
         let mut rng = seeded_rng();
         let g = FishGenome::random(&mut rng);
-        assert!((genome_distance(&g, &g) - 0.0).abs() < 0.001);
+        assert!((genome_distance(&g, &g, DistanceWeights::default()) - 0.0).abs() < 0.001);
     }
 
     #[test]
@@ -487,8 +1525,8 @@ mod tests {
         let mut rng = seeded_rng();
         let a = FishGenome::random(&mut rng);
         let b = FishGenome::random(&mut rng);
-        let d1 = genome_distance(&a, &b);
-        let d2 = genome_distance(&b, &a);
+        let d1 = genome_distance(&a, &b, DistanceWeights::default());
+        let d2 = genome_distance(&b, &a, DistanceWeights::default());
         assert!((d1 - d2).abs() < 0.001, "Distance should be symmetric: {} vs {}", d1, d2);
     }
 
@@ -498,10 +1536,65 @@ mod tests {
         for _ in 0..100 {
             let a = FishGenome::random(&mut rng);
             let b = FishGenome::random(&mut rng);
-            assert!(genome_distance(&a, &b) >= 0.0);
+            assert!(genome_distance(&a, &b, DistanceWeights::default()) >= 0.0);
         }
     }
 
+    #[test]
+    fn behavior_weight_separates_lookalikes() {
+        let mut rng = seeded_rng();
+        let a = FishGenome::random(&mut rng);
+        let mut b = a.clone();
+        b.speed = (a.speed + 1.0).min(2.0);
+        b.aggression = 1.0 - a.aggression;
+        b.school_affinity = 1.0 - a.school_affinity;
+
+        let base = genome_distance(&a, &b, DistanceWeights::default());
+        let behavioral = genome_distance(&a, &b, DistanceWeights { appearance: 1.0, behavior: 5.0 });
+        assert!((behavioral - base * 5.0).abs() < 1e-4, "Look-alikes differ only in behavior");
+        let ignored = genome_distance(&a, &b, DistanceWeights { appearance: 1.0, behavior: 0.0 });
+        assert!(ignored.abs() < 1e-6);
+    }
+
+    // --- Genome diff ---
+
+    #[test]
+    fn diff_of_identical_genomes_is_all_zero() {
+        let mut rng = seeded_rng();
+        let g = FishGenome::random(&mut rng);
+        let diff = diff_genomes(&g, &g, DistanceWeights::default(), 1.0);
+        assert_eq!(diff.traits.len(), DIFFED_TRAITS.len() + 1);
+        assert!(diff.traits.values().all(|t| t.diff == 0.0));
+        assert!(diff.pattern.same_type);
+        assert!(diff.same_species);
+    }
+
+    #[test]
+    fn diff_takes_hue_the_short_way_round() {
+        let mut rng = seeded_rng();
+        let mut a = FishGenome::random(&mut rng);
+        let mut b = a.clone();
+        a.base_hue = 350.0;
+        b.base_hue = 10.0;
+        let hue = diff_genomes(&a, &b, DistanceWeights::default(), 1.0).traits["base_hue"];
+        assert_eq!((hue.a, hue.b), (350.0, 10.0));
+        assert!((hue.diff - 20.0).abs() < 1e-4, "Not 340: {}", hue.diff);
+        b.speed = a.speed + 0.5;
+        assert!((diff_genomes(&a, &b, DistanceWeights::default(), 1.0).traits["speed"].diff - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn diff_same_species_is_strictly_below_threshold() {
+        let mut rng = seeded_rng();
+        let a = FishGenome::random(&mut rng);
+        let b = FishGenome::random(&mut rng);
+        let distance = genome_distance(&a, &b, DistanceWeights::default());
+        assert!(distance > 0.0);
+        assert!(!diff_genomes(&a, &b, DistanceWeights::default(), distance).same_species, "At the threshold they split");
+        assert!(diff_genomes(&a, &b, DistanceWeights::default(), distance + 0.001).same_species);
+        assert!(!diff_genomes(&a, &b, DistanceWeights::default(), 0.0).same_species);
+    }
+
     // --- Pattern distance ---
 
     #[test]
@@ -525,8 +1618,53 @@ mod tests {
     fn inherit_hue_stays_in_range() {
         let mut rng = seeded_rng();
         for _ in 0..1000 {
-            let h = inherit_hue(350.0, 10.0, &mut rng, 1.0, 0.02, 0.10);
+            let h = inherit_hue(Source::draw(&mut rng), 350.0, 10.0, &mut rng, 1.0, 0.02, 0.10);
             assert!(h >= 0.0 && h < 360.0, "Hue out of range: {}", h);
         }
     }
+
+    // --- Average genome ---
+
+    #[test]
+    fn average_of_none_is_none() {
+        assert!(FishGenome::average(&[]).is_none());
+    }
+
+    #[test]
+    fn average_of_one_matches_it() {
+        let mut rng = seeded_rng();
+        let g = FishGenome::random(&mut rng);
+        let avg = FishGenome::average(&[&g]).unwrap();
+        assert_eq!(avg.id, 0, "Average genome is synthetic");
+        assert!((avg.base_hue - g.base_hue).abs() < 0.01);
+        assert!((avg.speed - g.speed).abs() < 0.001);
+        assert_eq!(avg.pattern.type_index(), g.pattern.type_index());
+        assert_eq!(avg.sex, g.sex);
+    }
+
+    #[test]
+    fn average_hue_wraps_around_red() {
+        let mut rng = seeded_rng();
+        let mut a = FishGenome::random(&mut rng);
+        let mut b = FishGenome::random(&mut rng);
+        a.base_hue = 350.0;
+        b.base_hue = 10.0;
+        let avg = FishGenome::average(&[&a, &b]).unwrap();
+        assert!(hue_distance(avg.base_hue, 0.0) < 0.5, "Expected ~0, got {}", avg.base_hue);
+    }
+
+    #[test]
+    fn average_pattern_uses_plurality() {
+        let mut rng = seeded_rng();
+        let mut gs: Vec<FishGenome> = (0..3).map(|_| FishGenome::random(&mut rng)).collect();
+        gs[0].pattern = PatternGene::Spotted { density: 0.4 };
+        gs[1].pattern = PatternGene::Spotted { density: 0.8 };
+        gs[2].pattern = PatternGene::Solid;
+        let refs: Vec<&FishGenome> = gs.iter().collect();
+        let avg = FishGenome::average(&refs).unwrap();
+        match avg.pattern {
+            PatternGene::Spotted { density } => assert!((density - 0.6).abs() < 0.001),
+            other => panic!("Expected spotted, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,174 @@
+use crate::simulation::ecosystem::SimEvent;
+use serde::{Deserialize, Serialize};
+
+/// Minimum ticks between trigger-driven journal entries, so a flapping
+/// condition doesn't flood the journal
+pub const JOURNAL_TRIGGER_COOLDOWN: u64 = 300;
+
+/// User-defined condition that prompts an immediate journal entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalTrigger {
+    PopulationAbove { threshold: u32 },
+    PopulationBelow { threshold: u32 },
+    WaterQualityBelow { threshold: f32 },
+    NewSpecies,
+    Extinction,
+    Revival,
+}
+
+/// Tank readings used to detect threshold crossings between frames
+#[derive(Debug, Clone, Copy)]
+pub struct TankReading {
+    pub population: u32,
+    pub water_quality: f32,
+}
+
+impl JournalTrigger {
+    /// Describe why this trigger fired between `prev` and `now`, or None.
+    /// Threshold triggers fire once on crossing, not every frame past it.
+    pub fn check(&self, prev: TankReading, now: TankReading, events: &[SimEvent]) -> Option<String> {
+        match *self {
+            JournalTrigger::PopulationAbove { threshold } => {
+                (prev.population <= threshold && now.population > threshold)
+                    .then(|| format!("The population just rose above {} (now {} fish)", threshold, now.population))
+            }
+            JournalTrigger::PopulationBelow { threshold } => {
+                (prev.population >= threshold && now.population < threshold)
+                    .then(|| format!("The population just fell below {} (now {} fish)", threshold, now.population))
+            }
+            JournalTrigger::WaterQualityBelow { threshold } => {
+                (prev.water_quality >= threshold && now.water_quality < threshold)
+                    .then(|| format!("Water quality just dropped below {:.0}%", threshold * 100.0))
+            }
+            JournalTrigger::NewSpecies => events.iter().find_map(|e| match e {
+                SimEvent::NewSpecies { species_id } => Some(format!("A new species (#{}) has just been recognized", species_id)),
+                _ => None,
+            }),
+            JournalTrigger::Extinction => events.iter().find_map(|e| match e {
+                SimEvent::Extinction { species_id } => Some(format!("Species #{} has just gone extinct", species_id)),
+                _ => None,
+            }),
+            JournalTrigger::Revival => events.iter().find_map(|e| match e {
+                SimEvent::SpeciesRevived { species_id, count } => Some(format!("Extinct species #{} has just been revived with {} fish", species_id, count)),
+                _ => None,
+            }),
+        }
+    }
+}
+
+/// Ticks looked back over when judging a journal entry's mood
+pub const MOOD_WINDOW_TICKS: u64 = 1500;
+
+/// How the tank was doing when an entry was written. Derived from the tank
+/// itself, not the LLM's text, so the timeline can be color-coded reliably.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalMood {
+    Thriving,
+    Struggling,
+    Crisis,
+}
+
+impl JournalMood {
+    /// `before` is the population `MOOD_WINDOW_TICKS` ago (None if unrecorded)
+    /// and `recent_deaths` the deaths logged since then
+    pub fn assess(now: TankReading, before: Option<u32>, recent_deaths: u32) -> Self {
+        let decline = match before {
+            Some(b) if b > 0 => 1.0 - now.population as f32 / b as f32,
+            _ => 0.0,
+        };
+        if now.population == 0 || now.water_quality < 0.3 || decline >= 0.4 || recent_deaths > now.population {
+            JournalMood::Crisis
+        } else if now.water_quality < 0.6 || decline >= 0.15 || recent_deaths * 2 > now.population {
+            JournalMood::Struggling
+        } else {
+            JournalMood::Thriving
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalMood::Thriving => "thriving",
+            JournalMood::Struggling => "struggling",
+            JournalMood::Crisis => "crisis",
+        }
+    }
+}
+
+/// First trigger that fired this frame, described for the journal prompt
+pub fn first_fired(triggers: &[JournalTrigger], prev: TankReading, now: TankReading, events: &[SimEvent]) -> Option<String> {
+    triggers.iter().find_map(|t| t.check(prev, now, events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(population: u32, water_quality: f32) -> TankReading {
+        TankReading { population, water_quality }
+    }
+
+    #[test]
+    fn population_above_fires_on_crossing_only() {
+        let t = JournalTrigger::PopulationAbove { threshold: 75 };
+        assert!(t.check(reading(75, 1.0), reading(76, 1.0), &[]).is_some());
+        assert!(t.check(reading(76, 1.0), reading(77, 1.0), &[]).is_none(), "Already above");
+        assert!(t.check(reading(70, 1.0), reading(75, 1.0), &[]).is_none(), "Not past it yet");
+    }
+
+    #[test]
+    fn population_below_and_water_quality_crossings() {
+        let below = JournalTrigger::PopulationBelow { threshold: 10 };
+        assert!(below.check(reading(10, 1.0), reading(9, 1.0), &[]).is_some());
+        assert!(below.check(reading(9, 1.0), reading(8, 1.0), &[]).is_none());
+
+        let water = JournalTrigger::WaterQualityBelow { threshold: 0.4 };
+        let msg = water.check(reading(5, 0.41), reading(5, 0.39), &[]).unwrap();
+        assert!(msg.contains("40%"));
+    }
+
+    #[test]
+    fn event_triggers_match_their_events() {
+        let events = vec![SimEvent::Extinction { species_id: 4 }];
+        let steady = reading(20, 1.0);
+        assert!(JournalTrigger::NewSpecies.check(steady, steady, &events).is_none());
+        let msg = JournalTrigger::Extinction.check(steady, steady, &events).unwrap();
+        assert!(msg.contains("#4"));
+    }
+
+    #[test]
+    fn first_fired_respects_order() {
+        let triggers = vec![
+            JournalTrigger::NewSpecies,
+            JournalTrigger::PopulationAbove { threshold: 5 },
+        ];
+        let fired = first_fired(&triggers, reading(5, 1.0), reading(6, 1.0), &[]).unwrap();
+        assert!(fired.contains("above 5"));
+        assert!(first_fired(&[], reading(5, 1.0), reading(6, 1.0), &[]).is_none());
+    }
+
+    #[test]
+    fn mood_reflects_trend_water_and_deaths() {
+        assert_eq!(JournalMood::assess(reading(30, 0.9), Some(28), 2), JournalMood::Thriving);
+        assert_eq!(JournalMood::assess(reading(30, 0.9), None, 0), JournalMood::Thriving);
+        assert_eq!(JournalMood::assess(reading(30, 0.5), Some(30), 0), JournalMood::Struggling, "Murky water");
+        assert_eq!(JournalMood::assess(reading(24, 0.9), Some(30), 0), JournalMood::Struggling, "20% decline");
+        assert_eq!(JournalMood::assess(reading(30, 0.9), Some(30), 16), JournalMood::Struggling, "Heavy losses");
+        assert_eq!(JournalMood::assess(reading(15, 0.9), Some(30), 0), JournalMood::Crisis, "Halved");
+        assert_eq!(JournalMood::assess(reading(30, 0.2), Some(30), 0), JournalMood::Crisis);
+        assert_eq!(JournalMood::assess(reading(0, 0.9), Some(0), 0), JournalMood::Crisis);
+        assert_eq!(JournalMood::assess(reading(10, 0.9), Some(10), 11), JournalMood::Crisis, "Die-off");
+    }
+
+    #[test]
+    fn triggers_deserialize_from_tagged_json() {
+        let json = serde_json::json!([
+            { "kind": "population_above", "threshold": 75 },
+            { "kind": "new_species" },
+        ]);
+        let triggers: Vec<JournalTrigger> = serde_json::from_value(json).unwrap();
+        assert_eq!(triggers[0], JournalTrigger::PopulationAbove { threshold: 75 });
+        assert_eq!(triggers[1], JournalTrigger::NewSpecies);
+    }
+}
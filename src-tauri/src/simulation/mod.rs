@@ -1,13 +1,25 @@
 pub mod achievements;
+pub mod autosave;
 pub mod boids;
+pub mod breeding;
+pub mod camera;
 pub mod config;
+pub mod conservation;
 pub mod ecosystem;
+pub mod economy;
 pub mod events;
 pub mod fish;
 pub mod genome;
+pub mod journal;
 pub mod ollama;
+pub mod outlook;
+pub mod pacing;
 pub mod persistence;
+pub mod portable;
+pub mod presets;
+pub mod rng;
 pub mod scenarios;
+pub mod webhook;
 
 use boids::BoidsEngine;
 use config::SimulationConfig;
@@ -15,11 +27,28 @@ use ecosystem::{EcosystemManager, SimEvent};
 use events::EventSystem;
 use fish::Fish;
 use genome::FishGenome;
+use persistence::ExtinctSpeciesProfile;
 use chrono::Timelike;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Minimum ticks between low-population warnings (~30s at 30Hz)
+pub const POPULATION_WARNING_INTERVAL: u64 = 900;
+/// Fish in a freshly created tank
+const INITIAL_POPULATION: usize = 18;
+/// Neutral-trait drift given to each revived fish, as a fraction of trait range
+const REVIVAL_DRIFT: f32 = 0.02;
+
+/// Why `revive_species` turned a request away
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReviveError {
+    /// The species still has living members
+    NotExtinct,
+    /// The tank is already at carrying capacity
+    NoRoom,
+}
+
 /// Frame payload sent to React each tick
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrameUpdate {
@@ -29,13 +58,16 @@ pub struct FrameUpdate {
     pub bubbles: Vec<BubbleState>,
     pub eggs: Vec<EggState>,
     pub decorations: Vec<DecorationState>,
+    pub algae: Vec<AlgaeState>,
+    pub carcasses: Vec<CarcassState>,
     pub events: Vec<SimEvent>,
     pub water_quality: f32,
     pub population: u32,
     pub max_generation: u32,
     pub species_count: u32,
     pub time_of_day: f32,
-    pub active_event: Option<String>,
+    pub light_level: f32, // 0 = night, 1 = day, smooth through dawn/dusk
+    pub active_events: Vec<String>,
     pub genetic_diversity: f32,
 }
 
@@ -56,6 +88,7 @@ pub struct FishState {
     pub energy: f32,
     pub is_infected: bool,
     pub is_juvenile: bool,
+    pub life_stage: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub territory_cx: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,9 +96,31 @@ pub struct FishState {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub territory_r: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub territory_strength: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_name: Option<String>,
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub is_favorite: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_quarantined: bool,
+}
+
+/// Per-frame payload for the fish the camera is following
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectedFishUpdate {
+    pub tick: u64,
+    pub id: u32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub vx: f32,
+    pub vy: f32,
+    pub heading: f32,
+    pub behavior: String,
+    pub hunger: f32,
+    pub health: f32,
+    pub energy: f32,
+    pub neighbors: u32, // other fish within the cohesion radius
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +146,25 @@ pub struct BubbleState {
     pub radius: f32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlgaeState {
+    pub id: u32,
+    pub x: f32,
+    pub y: f32,
+    pub size: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarcassState {
+    pub id: u32,
+    pub x: f32,
+    pub y: f32,
+    pub heading: f32,
+    pub body_length: f32,
+    pub hue: f32,
+    pub decay: f32, // 0 = fresh, 1 = gone
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecorationState {
     pub id: u32,
@@ -101,6 +175,21 @@ pub struct DecorationState {
     pub flip_x: bool,
 }
 
+/// Complete scene description for photo mode, enough for an external renderer
+/// to reproduce the exact moment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneSnapshot {
+    pub tank_name: String,
+    pub captured_at: String,
+    pub tick: u64,
+    pub population: u32,
+    pub tank_width: f32,
+    pub tank_height: f32,
+    pub theme: String,
+    pub frame: FrameUpdate,
+    pub genomes: Vec<FishGenome>,
+}
+
 /// Top-level simulation state managed by Tauri
 pub struct SimulationState {
     pub tick: u64,
@@ -111,25 +200,48 @@ pub struct SimulationState {
     pub genomes: HashMap<u32, FishGenome>,
     pub boids: BoidsEngine,
     pub ecosystem: EcosystemManager,
-    pub rng: StdRng,
+    pub rng: rng::SimRng,
     pub selected_fish_id: Option<u32>,
     pub time_of_day: f32, // 0.0-24.0
     pub event_system: EventSystem,
     pub genetic_diversity: f32,
     pub active_scenario_id: Option<String>,
+    pub currency: u32,
+    pub last_population_warning_tick: Option<u64>,
+    pub noise_seed: u32, // Perlin seed for the wander field, persisted per tank
+}
+
+impl Default for SimulationState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SimulationState {
     pub fn new() -> Self {
+        Self::from_rng(rng::SimRng::from_entropy(), INITIAL_POPULATION)
+    }
+
+    /// A reproducible tank: the starting population, wander field and every
+    /// random roll come from `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        Self::from_rng(rng::SimRng::seed_from_u64(seed), INITIAL_POPULATION)
+    }
+
+    /// A reproducible tank starting with `population` fish instead of the usual count
+    pub fn with_seed_and_population(seed: u64, population: u32) -> Self {
+        Self::from_rng(rng::SimRng::seed_from_u64(seed), population as usize)
+    }
+
+    fn from_rng(mut rng: rng::SimRng, initial_count: usize) -> Self {
         let config = SimulationConfig::default();
-        let boids = BoidsEngine::new(&config);
-        let mut rng = StdRng::from_entropy();
+        let noise_seed: u32 = rng.gen();
+        let boids = BoidsEngine::new(&config, noise_seed);
 
         let mut genomes = HashMap::new();
         let mut fish_list = Vec::new();
 
         // Seed initial population
-        let initial_count = 18;
         for i in 0..initial_count {
             let genome = FishGenome::random_diverse(&mut rng, i, initial_count);
             let x = rng.gen_range(100.0..config.tank_width - 100.0);
@@ -154,7 +266,25 @@ impl SimulationState {
             event_system: EventSystem::new(),
             genetic_diversity: 1.0,
             active_scenario_id: None,
+            currency: 0,
+            last_population_warning_tick: None,
+            noise_seed,
+        }
+    }
+
+    /// Reseed the Perlin wander field
+    pub fn set_noise_seed(&mut self, seed: u32) {
+        self.noise_seed = seed;
+        self.boids.perlin = noise::Perlin::new(seed);
+    }
+
+    /// Run `n` ticks back to back, returning every event raised along the way
+    pub fn step_n(&mut self, n: u32) -> Vec<SimEvent> {
+        let mut events = Vec::new();
+        for _ in 0..n {
+            events.extend(self.step().events);
         }
+        events
     }
 
     pub fn step(&mut self) -> FrameUpdate {
@@ -166,8 +296,8 @@ impl SimulationState {
 
         // Advance day/night cycle
         if self.config.day_night_speed > 0.0 {
-            // At speed=1: 1 sim-minute per real-second at 30Hz → 24h in 24 real-minutes
-            self.time_of_day += (1.0 / 30.0 / 60.0) * self.config.day_night_speed;
+            // At speed=1 a full day takes day_length_ticks (default 24 real-minutes at 30Hz)
+            self.time_of_day += 24.0 / self.config.day_length_ticks.max(1) as f32 * self.config.day_night_speed;
             self.time_of_day = self.time_of_day.rem_euclid(24.0);
         } else {
             // Real-time clock mode
@@ -175,9 +305,12 @@ impl SimulationState {
             self.time_of_day = now.hour() as f32 + now.minute() as f32 / 60.0;
         }
 
-        // Environmental events
+        // Environmental events; each one the tank lives through pays out as it ends
         if self.config.environmental_events_enabled {
-            self.event_system.update(self.config.event_frequency, &mut self.rng);
+            let ended = self.event_system.update(self.config.event_frequency, self.config.max_concurrent_events, &mut self.rng);
+            if !self.fish.is_empty() {
+                self.currency += economy::EVENT_SURVIVED_REWARD * ended.len() as u32;
+            }
         }
 
         // Spawn free food during plankton bloom
@@ -193,10 +326,13 @@ impl SimulationState {
             self.config.current_strength = cs;
         }
         self.config.hunger_rate *= self.event_system.metabolism_multiplier();
+        let fertility_scale = self.effective_fertility_scale();
 
         // Boids physics (speed modifier applied per-fish through behavior_speed_multiplier)
         let food_positions = self.ecosystem.food_positions();
         let obstacles = self.ecosystem.obstacle_positions();
+        self.boids.set_species(&self.ecosystem.species);
+        self.boids.set_algae(if self.config.algae_enabled { &self.ecosystem.algae } else { &[] });
         self.boids.update(
             &mut self.fish,
             &self.genomes,
@@ -207,16 +343,35 @@ impl SimulationState {
         );
 
         // Ecosystem (behavior, feeding, predation, reproduction, speciation)
-        let events = self.ecosystem.update(
+        let mut events = self.ecosystem.update(
             &mut self.fish,
             &mut self.genomes,
             &self.config,
             self.tick,
             &mut self.rng,
-            self.time_of_day,
+            self.config.light_level(self.time_of_day),
+            fertility_scale,
             &self.event_system,
+            &self.boids.grid,
         );
 
+        self.currency += economy::event_rewards(&events);
+
+        // Throttled low-population warning
+        if self.is_endangered() {
+            let due = match self.last_population_warning_tick {
+                Some(t) => self.tick - t >= POPULATION_WARNING_INTERVAL,
+                None => true,
+            };
+            if due {
+                self.last_population_warning_tick = Some(self.tick);
+                events.push(SimEvent::PopulationWarning {
+                    population: self.fish.len() as u32,
+                    minimum: self.config.min_viable_population,
+                });
+            }
+        }
+
         // Apply heatwave energy drain
         let energy_mult = self.event_system.energy_drain_multiplier();
         if energy_mult > 1.0 {
@@ -225,6 +380,16 @@ impl SimulationState {
             }
         }
 
+        // Cold snaps and heatwaves wear down fish outside their temperature tolerance
+        let exposure = self.event_system.temperature_exposure();
+        if exposure > 0.0 {
+            for f in &mut self.fish {
+                if let Some(g) = self.genomes.get(&f.genome_id) {
+                    f.health -= g.temperature_stress(exposure) * self.config.temperature_damage;
+                }
+            }
+        }
+
         // Restore config
         self.config.current_strength = saved_current_strength;
         self.config.hunger_rate = saved_hunger_rate;
@@ -239,6 +404,7 @@ impl SimulationState {
                     .flat_map(|s| s.member_genome_ids.iter().copied())
                     .collect();
             self.genomes.retain(|id, _| living_genome_ids.contains(id) || species_genome_ids.contains(id));
+            self.ecosystem.conservation.prune(&self.genomes);
         }
 
         // Recompute genetic diversity periodically (every 60 ticks ≈ 2sec)
@@ -285,7 +451,7 @@ impl SimulationState {
             tick: self.tick,
             fish: self.fish.iter().map(|f| {
                 let age_frac = self.genomes.get(&f.genome_id)
-                    .map(|g| f.age_fraction(g, ecosystem::BASE_LIFESPAN))
+                    .map(|g| f.age_fraction(g, self.config.max_lifespan))
                     .unwrap_or(0.0);
                 FishState {
                     id: f.id,
@@ -303,11 +469,14 @@ impl SimulationState {
                     energy: f.energy,
                     is_infected: f.is_infected,
                     is_juvenile: f.is_juvenile,
+                    life_stage: f.life_stage(age_frac).as_str().to_string(),
                     territory_cx: f.territory_center.map(|(cx, _)| cx),
                     territory_cy: f.territory_center.map(|(_, cy)| cy),
                     territory_r: if f.territory_center.is_some() { Some(f.territory_radius) } else { None },
+                    territory_strength: f.territory_center.map(|_| f.territory_strength),
                     custom_name: f.custom_name.clone(),
                     is_favorite: f.is_favorite,
+                    is_quarantined: f.is_quarantined,
                 }
             }).collect(),
             food: self.ecosystem.food.iter().map(|f| FoodState { x: f.x, y: f.y, food_type: f.food_type.as_str().to_string() }).collect(),
@@ -327,20 +496,826 @@ impl SimulationState {
                 scale: d.scale,
                 flip_x: d.flip_x,
             }).collect(),
+            algae: self.ecosystem.algae.iter().map(|a| AlgaeState { id: a.id, x: a.x, y: a.y, size: a.size }).collect(),
+            carcasses: self.ecosystem.carcasses.iter().map(|c| CarcassState {
+                id: c.id,
+                x: c.x,
+                y: c.y,
+                heading: c.heading,
+                body_length: c.body_length,
+                hue: c.hue,
+                decay: c.decay(&self.config),
+            }).collect(),
             events,
             water_quality: self.ecosystem.water_quality,
             population: self.fish.len() as u32,
             max_generation: max_gen,
             species_count,
             time_of_day: self.time_of_day,
-            active_event: self.event_system.active_event_name().map(|s| s.to_string()),
+            light_level: self.config.light_level(self.time_of_day),
+            active_events: self.event_system.active_event_names().into_iter().map(String::from).collect(),
             genetic_diversity: self.genetic_diversity,
         }
     }
 
+    /// Spawn adult apex predators as an ecological disturbance, up to the
+    /// current carrying capacity. Returns the new fish ids.
+    pub fn introduce_predators(&mut self, count: u32) -> Vec<u32> {
+        let capacity = (self.config.base_carrying_capacity as f32 * self.ecosystem.water_quality) as usize;
+        let room = capacity.saturating_sub(self.fish.len()).min(count as usize);
+        let mut ids = Vec::with_capacity(room);
+        for _ in 0..room {
+            let genome = FishGenome::apex_predator(&mut self.rng);
+            let x = self.config.random_x(&mut self.rng, 50.0);
+            let y = self.config.random_y(&mut self.rng, 80.0);
+            let mut f = Fish::new(genome.id, x, y, &mut self.rng);
+            // Arrive as mature adults
+            f.age = (self.config.max_lifespan as f32 * genome.lifespan_factor * genome.maturity_age) as u32;
+            self.ecosystem.events.push(SimEvent::PredatorIntroduced { fish_id: f.id, genome_id: genome.id });
+            ids.push(f.id);
+            self.genomes.insert(genome.id, genome);
+            self.fish.push(f);
+        }
+        ids
+    }
+
+    /// Spawn up to `count` newborn copies of a fish's genome next to it, for
+    /// comparing identical genomes under different conditions. Each clone gets
+    /// its own genome id with the generation kept and parents cleared; the
+    /// carrying capacity limits how many fit. Returns the new fish ids.
+    pub fn clone_fish(&mut self, fish_id: u32, count: u32) -> Vec<u32> {
+        let Some(source) = self.fish.iter().find(|f| f.id == fish_id) else {
+            return Vec::new();
+        };
+        let Some(template) = self.genomes.get(&source.genome_id).cloned() else {
+            return Vec::new();
+        };
+        let (sx, sy) = (source.x, source.y);
+        let capacity = (self.config.base_carrying_capacity as f32 * self.ecosystem.water_quality) as usize;
+        let room = capacity.saturating_sub(self.fish.len()).min(count as usize);
+        let mut ids = Vec::with_capacity(room);
+        for _ in 0..room {
+            let genome = FishGenome {
+                id: genome::next_genome_id(),
+                parent_a: None,
+                parent_b: None,
+                ..template.clone()
+            };
+            let dx = self.rng.gen_range(-20.0..20.0);
+            let dy = self.rng.gen_range(-20.0..20.0);
+            let (x, y) = self.config.clamp_to_tank(sx + dx, sy + dy, 10.0);
+            let f = Fish::new(genome.id, x, y, &mut self.rng);
+            ids.push(f.id);
+            self.genomes.insert(genome.id, genome);
+            self.fish.push(f);
+        }
+        ids
+    }
+
+    /// Bring an extinct species back from its fossil genome: up to `count`
+    /// adults close to the recorded average, alternating sexes so they can
+    /// breed, with a little drift so they aren't identical. The species
+    /// returns to the living list, re-created from the profile if it has
+    /// already been pruned. Returns the new fish ids.
+    pub fn revive_species(&mut self, profile: &ExtinctSpeciesProfile, count: u32) -> Result<Vec<u32>, ReviveError> {
+        if let Some(sp) = self.ecosystem.species.iter().find(|s| s.id == profile.species_id) {
+            if sp.extinct_at_tick.is_none() {
+                return Err(ReviveError::NotExtinct);
+            }
+        }
+        let capacity = (self.config.base_carrying_capacity as f32 * self.ecosystem.water_quality) as usize;
+        let room = capacity.saturating_sub(self.fish.len()).min(count as usize);
+        if room == 0 {
+            return Err(ReviveError::NoRoom);
+        }
+
+        let template = &profile.genome;
+        let mut ids = Vec::with_capacity(room);
+        let mut genome_ids = Vec::with_capacity(room);
+        for i in 0..room {
+            let mut genome = FishGenome {
+                id: genome::next_genome_id(),
+                parent_a: None,
+                parent_b: None,
+                sex: if i % 2 == 0 { genome::Sex::Female } else { genome::Sex::Male },
+                ..template.clone()
+            };
+            genome.drift(REVIVAL_DRIFT, &mut self.rng);
+            let x = self.config.random_x(&mut self.rng, 50.0);
+            let y = self.config.random_y(&mut self.rng, 80.0);
+            let mut f = Fish::new(genome.id, x, y, &mut self.rng);
+            f.age = (self.config.max_lifespan as f32 * genome.lifespan_factor * genome.maturity_age) as u32;
+            ids.push(f.id);
+            genome_ids.push(genome.id);
+            self.genomes.insert(genome.id, genome);
+            self.fish.push(f);
+        }
+
+        match self.ecosystem.species.iter_mut().find(|s| s.id == profile.species_id) {
+            Some(sp) => {
+                sp.extinct_at_tick = None;
+                sp.fossil = None;
+                sp.member_count = room as u32;
+                sp.member_genome_ids = genome_ids;
+            }
+            None => self.ecosystem.species.push(ecosystem::Species {
+                id: profile.species_id,
+                name: profile.name.clone(),
+                description: None,
+                discovered_at_tick: profile.discovered_at_tick,
+                extinct_at_tick: None,
+                centroid_hue: template.base_hue,
+                centroid_speed: template.speed,
+                centroid_size: template.body_length,
+                centroid_pattern: format!("{:?}", template.pattern),
+                member_count: room as u32,
+                member_genome_ids: genome_ids,
+                distinct_marker: None,
+                fossil: None,
+            }),
+        }
+        self.ecosystem.assign_species_markers();
+        self.ecosystem.events.push(SimEvent::SpeciesRevived { species_id: profile.species_id, count: room as u32 });
+        Ok(ids)
+    }
+
+    /// Position and state of `selected_fish_id`, or None if nothing is
+    /// selected or the fish is gone
+    pub fn selected_fish_update(&self) -> Option<SelectedFishUpdate> {
+        let f = self.fish.iter().find(|f| Some(f.id) == self.selected_fish_id)?;
+        let wrap = self.config.boundary_mode == config::BoundaryMode::Wrap;
+        let delta = |d: f32, size: f32| if wrap { boids::wrap_delta(d, size) } else { d };
+        let r2 = self.config.cohesion_radius * self.config.cohesion_radius;
+        let neighbors = self.fish.iter()
+            .filter(|o| o.id != f.id)
+            .filter(|o| {
+                let dx = delta(o.x - f.x, self.config.tank_width);
+                let dy = delta(o.y - f.y, self.config.tank_height);
+                dx * dx + dy * dy <= r2
+            })
+            .count() as u32;
+        Some(SelectedFishUpdate {
+            tick: self.tick,
+            id: f.id,
+            x: f.x,
+            y: f.y,
+            z: f.z,
+            vx: f.vx,
+            vy: f.vy,
+            heading: f.heading,
+            behavior: f.behavior.as_str().to_string(),
+            hunger: f.hunger,
+            health: f.health,
+            energy: f.energy,
+            neighbors,
+        })
+    }
+
+    /// Average boids force components for the current tank, without stepping it
+    pub fn boids_diagnostics(&self) -> boids::ForceBreakdown {
+        let food_positions = self.ecosystem.food_positions();
+        let obstacles = self.ecosystem.obstacle_positions();
+        self.boids.force_breakdown(&self.fish, &self.genomes, &self.config, self.tick, &food_positions, &obstacles)
+    }
+
+    /// Spatial grid occupancy for the fish as they stand now, rather than as of
+    /// the start of the last step
+    pub fn grid_stats(&self) -> boids::GridStats {
+        self.boids.grid.stats_for(&self.fish)
+    }
+
+    /// Pin the spatial grid to a fixed cell size. Adaptive retuning is switched
+    /// off, otherwise the next retune would quietly undo the manual choice.
+    pub fn set_grid_cell_size(&mut self, cell_size: f32) -> Result<(), String> {
+        if !(cell_size.is_finite() && cell_size >= boids::MIN_CELL_SIZE) {
+            return Err(format!("Cell size must be at least {}, got {}", boids::MIN_CELL_SIZE, cell_size));
+        }
+        self.config.adaptive_grid_enabled = false;
+        self.boids.grid = boids::SpatialGrid::new(self.config.tank_width, self.config.tank_height, cell_size);
+        Ok(())
+    }
+
+    /// Resize the tank, keeping the grid's current cell size whether it was set
+    /// by hand or by adaptive retuning. Returns a warning when the new tank is so
+    /// large the grid had to use coarser cells.
+    pub fn resize_tank(&mut self, width: f32, height: f32) -> Option<String> {
+        self.config.tank_width = width;
+        self.config.tank_height = height;
+        let requested = self.boids.grid.cell_size();
+        self.boids.grid = boids::SpatialGrid::new(width, height, requested);
+        let used = self.boids.grid.cell_size();
+        (used > requested).then(|| format!(
+            "Tank is very large: spatial grid cells widened from {:.0} to {:.0}, neighbor queries will be slower",
+            requested, used,
+        ))
+    }
+
+    /// Fertility scale breeding uses this tick. Endangered buff: small
+    /// populations breed a little more readily.
+    pub fn effective_fertility_scale(&self) -> f32 {
+        if self.config.endangered_boost_enabled && self.is_endangered() {
+            self.config.fertility_scale * self.config.endangered_fertility_multiplier
+        } else {
+            self.config.fertility_scale
+        }
+    }
+
+    /// Living population is below the configured minimum viable population
+    pub fn is_endangered(&self) -> bool {
+        let population = self.fish.len() as u32;
+        population > 0 && population < self.config.min_viable_population
+    }
+
+    /// Mean enrichment of the living fish; 1 when enrichment is off or the tank is empty
+    pub fn tank_enrichment(&self) -> f32 {
+        let living: Vec<f32> = self.fish.iter().filter(|f| f.is_alive).map(|f| f.enrichment).collect();
+        if !self.config.enrichment_enabled || living.is_empty() {
+            return 1.0;
+        }
+        living.iter().sum::<f32>() / living.len() as f32
+    }
+
+    /// Genetic effective population size (Ne) of the living adults, which
+    /// predicts inbreeding better than a raw head count. Each sex contributes
+    /// N * 4 / (Vk + 2), where Vk is the variance in spawnings standardized to
+    /// a mean of 2, and the sexes combine as 4 * Ne_m * Ne_f / (Ne_m + Ne_f).
+    /// A sex with no recorded spawnings counts at its head count. Zero when
+    /// either sex is missing.
+    pub fn effective_population_size(&self) -> f32 {
+        let per_sex = |sex: genome::Sex| -> f32 {
+            let counts: Vec<f32> = self.fish.iter()
+                .filter(|f| f.is_alive && !f.is_juvenile)
+                .filter(|f| self.genomes.get(&f.genome_id).is_some_and(|g| g.sex == sex))
+                .map(|f| f.offspring_count as f32)
+                .collect();
+            let n = counts.len() as f32;
+            let mean = counts.iter().sum::<f32>() / n.max(1.0);
+            if mean <= 0.0 {
+                return n;
+            }
+            let variance = counts.iter().map(|k| (k - mean).powi(2)).sum::<f32>() / n;
+            let standardized = variance * 4.0 / (mean * mean);
+            n * 4.0 / (standardized + 2.0)
+        };
+        let (males, females) = (per_sex(genome::Sex::Male), per_sex(genome::Sex::Female));
+        if males <= 0.0 || females <= 0.0 {
+            return 0.0;
+        }
+        4.0 * males * females / (males + females)
+    }
+
+    /// One-line summary for the system tray, e.g. "24 fish · 3 species · Heatwave"
+    pub fn tray_status(&self) -> String {
+        let species = self.ecosystem.species.iter().filter(|s| s.extinct_at_tick.is_none()).count();
+        let mut parts = vec![
+            format!("{} fish", self.fish.len()),
+            format!("{} species", species),
+        ];
+        for (event, _) in &self.event_system.active_events {
+            parts.push(event.display_name().to_string());
+        }
+        if self.paused {
+            parts.push("Paused".to_string());
+        }
+        parts.join(" · ")
+    }
+
+    /// Goal checklist for the active scenario, None when no scenario is running
+    pub fn scenario_progress(&self) -> Option<serde_json::Value> {
+        let scenario_id = self.active_scenario_id.as_ref()?;
+        let scenario = scenarios::find_scenario(scenario_id)?;
+
+        let population = self.fish.len() as u32;
+        let max_gen = self.genomes.values().map(|g| g.generation).max().unwrap_or(0);
+        let species_count = self.ecosystem.species.iter().filter(|s| s.extinct_at_tick.is_none()).count() as u32;
+
+        let goal_status = scenarios::check_goals(
+            &scenario, population, max_gen, species_count,
+            self.tick, self.genetic_diversity, &self.genomes, &self.fish,
+        );
+
+        let all_complete = goal_status.iter().all(|(_, met)| *met);
+
+        Some(serde_json::json!({
+            "scenario_id": scenario_id,
+            "scenario_name": scenario.name,
+            "goals": scenario.goals.iter().enumerate().map(|(i, g)| {
+                serde_json::json!({
+                    "description": g.description(),
+                    "complete": goal_status.iter().find(|(gi, _)| *gi == i).map(|(_, m)| *m).unwrap_or(false),
+                })
+            }).collect::<Vec<_>>(),
+            "all_complete": all_complete,
+        }))
+    }
+
+    /// Headline numbers for the dashboard panel: population, living species,
+    /// diversity, events and how many living fish are in each behavior
+    pub fn dashboard(&self) -> serde_json::Value {
+        let mut behaviors: std::collections::BTreeMap<&str, u32> = std::collections::BTreeMap::new();
+        let mut population = 0;
+        for f in self.fish.iter().filter(|f| f.is_alive) {
+            *behaviors.entry(f.behavior.as_str()).or_default() += 1;
+            population += 1;
+        }
+        let active_events = self.event_system.active_event_names();
+        serde_json::json!({
+            "tick": self.tick,
+            "population": population,
+            "species_count": self.ecosystem.species.iter().filter(|s| s.extinct_at_tick.is_none()).count(),
+            "max_generation": self.genomes.values().map(|g| g.generation).max().unwrap_or(0),
+            "genetic_diversity": self.genetic_diversity,
+            "effective_population_size": self.effective_population_size(),
+            "tank_enrichment": self.tank_enrichment(),
+            "water_quality": self.ecosystem.water_quality,
+            "active_event": active_events.first(),
+            "active_events": active_events,
+            "behaviors": behaviors,
+            "scenario": self.scenario_progress(),
+        })
+    }
+
     /// Get genome data for a specific fish (for frontend caching)
     pub fn get_genome(&self, genome_id: u32) -> Option<&FishGenome> {
         self.genomes.get(&genome_id)
     }
 
+    /// Capture the current frame plus the full genome of every visible fish
+    pub fn capture_scene(&self, tank_name: &str) -> SceneSnapshot {
+        let mut genome_ids: Vec<u32> = self.fish.iter().map(|f| f.genome_id).collect();
+        genome_ids.sort_unstable();
+        genome_ids.dedup();
+        SceneSnapshot {
+            tank_name: tank_name.to_string(),
+            captured_at: chrono::Local::now().to_rfc3339(),
+            tick: self.tick,
+            population: self.fish.len() as u32,
+            tank_width: self.config.tank_width,
+            tank_height: self.config.tank_height,
+            theme: self.config.theme.clone(),
+            frame: self.build_frame(Vec::new()),
+            genomes: genome_ids.iter().filter_map(|id| self.genomes.get(id).cloned()).collect(),
+        }
+    }
+
+    /// Spend currency on a shop item and apply it. Returns the placed decoration, if any.
+    pub fn purchase(&mut self, item: economy::ShopItem) -> Result<Option<ecosystem::Decoration>, String> {
+        economy::spend(&mut self.currency, item)?;
+        if let Some((food_type, count)) = item.food() {
+            for _ in 0..count {
+                let x = self.config.random_x(&mut self.rng, 50.0);
+                self.ecosystem.drop_food_typed(x, 5.0, food_type);
+            }
+            return Ok(None);
+        }
+        let dtype = item.decoration().ok_or("Unknown item")?;
+        let x = self.config.random_x(&mut self.rng, 60.0);
+        let (x, y) = self.config.clamp_to_tank(x, self.config.tank_height - 40.0, 0.0);
+        let flip_x = self.rng.gen_bool(0.5);
+        Ok(Some(self.ecosystem.add_decoration(dtype, x, y, 1.0, flip_x)))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn population_warnings(events: &[SimEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SimEvent::PopulationWarning { .. })).count()
+    }
+
+    // --- Minimum viable population ---
+
+    #[test]
+    fn small_population_warns_once_per_interval() {
+        let mut sim = SimulationState::new();
+        sim.config.environmental_events_enabled = false;
+        sim.fish.truncate(3);
+        let first = sim.step();
+        assert_eq!(population_warnings(&first.events), 1);
+        let second = sim.step();
+        assert_eq!(population_warnings(&second.events), 0, "Warning should be throttled");
+        sim.tick = POPULATION_WARNING_INTERVAL;
+        sim.last_population_warning_tick = Some(0);
+        let later = sim.step();
+        assert_eq!(population_warnings(&later.events), 1);
+    }
+
+    #[test]
+    fn selected_fish_update_counts_neighbors() {
+        let mut sim = SimulationState::with_seed(7);
+        assert!(sim.selected_fish_update().is_none());
+        sim.fish.truncate(3);
+        let r = sim.config.cohesion_radius;
+        for (f, x) in sim.fish.iter_mut().zip([300.0, 300.0 + r * 0.5, 300.0 + r * 3.0]) {
+            (f.x, f.y) = (x, 300.0);
+        }
+        let id = sim.fish[0].id;
+        sim.selected_fish_id = Some(id);
+        let update = sim.selected_fish_update().unwrap();
+        assert_eq!(update.id, id);
+        assert_eq!(update.neighbors, 1);
+        sim.fish.remove(0);
+        assert!(sim.selected_fish_update().is_none(), "Gone fish stop the stream");
+    }
+
+    #[test]
+    fn healthy_or_empty_tank_does_not_warn() {
+        let mut sim = SimulationState::new();
+        sim.config.environmental_events_enabled = false;
+        assert!(!sim.is_endangered());
+        assert_eq!(population_warnings(&sim.step().events), 0);
+        sim.fish.clear();
+        assert!(!sim.is_endangered(), "Nothing left to save in an empty tank");
+        assert_eq!(population_warnings(&sim.step().events), 0);
+    }
+
+    #[test]
+    fn endangered_boost_does_not_leak_into_config() {
+        let mut sim = SimulationState::new();
+        sim.fish.truncate(2);
+        let before = sim.config.fertility_scale;
+        sim.step();
+        assert_eq!(sim.config.fertility_scale, before);
+    }
+
+    #[test]
+    fn endangered_tanks_breed_at_a_boosted_scale() {
+        let mut sim = SimulationState::new();
+        let base = sim.config.fertility_scale;
+        assert_eq!(sim.effective_fertility_scale(), base, "Healthy tank breeds at the configured scale");
+
+        sim.fish.truncate(2);
+        assert_eq!(sim.effective_fertility_scale(), base * sim.config.endangered_fertility_multiplier);
+        sim.config.endangered_boost_enabled = false;
+        assert_eq!(sim.effective_fertility_scale(), base);
+    }
+
+    // --- Photo mode ---
+
+    #[test]
+    fn scene_includes_genome_for_every_fish() {
+        let sim = SimulationState::new();
+        let scene = sim.capture_scene("My Aquarium");
+        assert_eq!(scene.tank_name, "My Aquarium");
+        assert_eq!(scene.population as usize, sim.fish.len());
+        assert_eq!(scene.frame.fish.len(), sim.fish.len());
+        for f in &scene.frame.fish {
+            assert!(scene.genomes.iter().any(|g| g.id == f.genome_id), "Missing genome {}", f.genome_id);
+        }
+    }
+
+    #[test]
+    fn scene_genomes_are_unique() {
+        let mut sim = SimulationState::new();
+        // Two fish sharing a genome must only list it once
+        let gid = sim.fish[0].genome_id;
+        let clone = Fish::new(gid, 200.0, 200.0, &mut sim.rng);
+        sim.fish.push(clone);
+        let scene = sim.capture_scene("Tank");
+        assert_eq!(scene.genomes.iter().filter(|g| g.id == gid).count(), 1);
+    }
+
+    #[test]
+    fn scene_of_empty_tank() {
+        let mut sim = SimulationState::new();
+        sim.fish.clear();
+        let scene = sim.capture_scene("Empty");
+        assert_eq!(scene.population, 0);
+        assert!(scene.genomes.is_empty());
+        assert!(serde_json::to_string(&scene).is_ok());
+    }
+
+    // --- Predator introduction ---
+
+    #[test]
+    fn introduced_predators_are_adult_apex_fish() {
+        let mut sim = SimulationState::new();
+        let before = sim.fish.len();
+        let ids = sim.introduce_predators(3);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(sim.fish.len(), before + 3);
+        for id in &ids {
+            let f = sim.fish.iter().find(|f| f.id == *id).unwrap();
+            let g = &sim.genomes[&f.genome_id];
+            assert!(g.aggression > 0.8);
+            assert!(f.age_fraction(g, sim.config.max_lifespan) >= g.maturity_age - 0.01);
+        }
+        let announced = sim.ecosystem.events.iter()
+            .filter(|e| matches!(e, SimEvent::PredatorIntroduced { .. }))
+            .count();
+        assert_eq!(announced, 3);
+
+        sim.config.tank_width = 60.0;
+        sim.config.tank_height = 40.0;
+        let more = sim.introduce_predators(3);
+        assert_eq!(more.len(), 3);
+        for id in more {
+            let f = sim.fish.iter().find(|f| f.id == id).unwrap();
+            assert!((0.0..=60.0).contains(&f.x) && (0.0..=40.0).contains(&f.y), "Small tanks keep predators inside");
+        }
+    }
+
+    // --- Revival ---
+
+    #[test]
+    fn revived_species_returns_to_the_living() {
+        let mut sim = SimulationState::new();
+        sim.fish.truncate(3);
+        let mut fossil = FishGenome::random(&mut sim.rng);
+        fossil.id = 0;
+        let profile = ExtinctSpeciesProfile {
+            species_id: 40, name: Some("Ghostfin".to_string()),
+            discovered_at_tick: 10, extinct_at_tick: 900, genome: fossil.clone(),
+        };
+
+        let ids = sim.revive_species(&profile, 4).unwrap();
+        assert_eq!(ids.len(), 4);
+        let revived: Vec<&FishGenome> = ids.iter()
+            .map(|id| &sim.genomes[&sim.fish.iter().find(|f| f.id == *id).unwrap().genome_id])
+            .collect();
+        assert!(revived.iter().any(|g| g.sex == genome::Sex::Male) && revived.iter().any(|g| g.sex == genome::Sex::Female));
+        assert!(revived.iter().all(|g| g.speed == fossil.speed && g.parent_a.is_none()));
+        let sp = sim.ecosystem.species.iter().find(|s| s.id == 40).unwrap();
+        assert_eq!((sp.extinct_at_tick, sp.member_count, sp.name.as_deref()), (None, 4, Some("Ghostfin")));
+        assert!(sim.ecosystem.events.iter().any(|e| matches!(e, SimEvent::SpeciesRevived { species_id: 40, count: 4 })));
+
+        assert_eq!(sim.revive_species(&profile, 2), Err(ReviveError::NotExtinct), "A living species can't be revived");
+        sim.config.base_carrying_capacity = 0;
+        let other = ExtinctSpeciesProfile { species_id: 99, ..profile };
+        assert_eq!(sim.revive_species(&other, 2), Err(ReviveError::NoRoom));
+    }
+
+    #[test]
+    fn revived_fish_spawn_inside_a_small_tank() {
+        let mut sim = SimulationState::new();
+        sim.fish.truncate(3);
+        sim.config.tank_width = 60.0;
+        sim.config.tank_height = 40.0;
+        let profile = ExtinctSpeciesProfile {
+            species_id: 41, name: None, discovered_at_tick: 10, extinct_at_tick: 900,
+            genome: FishGenome::random(&mut sim.rng),
+        };
+        let ids = sim.revive_species(&profile, 6).unwrap();
+        assert_eq!(ids.len(), 6);
+        for f in sim.fish.iter().filter(|f| ids.contains(&f.id)) {
+            assert!((0.0..=60.0).contains(&f.x) && (0.0..=40.0).contains(&f.y), "revived at ({}, {})", f.x, f.y);
+        }
+    }
+
+    // --- Carcasses ---
+
+    #[test]
+    fn dead_fish_vanish_by_default() {
+        let mut sim = SimulationState::new();
+        sim.config.environmental_events_enabled = false;
+        sim.fish[0].is_alive = false;
+        sim.step();
+        assert!(sim.ecosystem.carcasses.is_empty());
+    }
+
+    #[test]
+    fn decay_mode_leaves_rotting_carcass() {
+        let mut sim = SimulationState::new();
+        sim.config.environmental_events_enabled = false;
+        sim.config.carcass_decay_enabled = true;
+        sim.config.carcass_decay_ticks = 5;
+        let dead_id = sim.fish[0].id;
+        sim.fish[0].is_alive = false;
+        sim.fish[1].is_alive = false;
+        sim.fish[1].killed_by_predator = true;
+        let frame = sim.step();
+        assert_eq!(sim.ecosystem.carcasses.len(), 1, "Eaten fish leave no carcass");
+        assert_eq!(frame.carcasses[0].id, dead_id);
+        for _ in 0..5 {
+            sim.step();
+        }
+        assert!(sim.ecosystem.carcasses.iter().all(|c| c.id != dead_id), "Carcass rots away");
+    }
+
+    // --- Cloning ---
+
+    #[test]
+    fn clones_share_the_source_genome() {
+        let mut sim = SimulationState::new();
+        sim.fish.truncate(3);
+        let source = sim.fish[0].clone();
+        let ids = sim.clone_fish(source.id, 2);
+        assert_eq!(ids.len(), 2);
+        let original = sim.genomes[&source.genome_id].clone();
+        for id in &ids {
+            let f = sim.fish.iter().find(|f| f.id == *id).unwrap();
+            let g = &sim.genomes[&f.genome_id];
+            assert_ne!(g.id, original.id);
+            assert_eq!((g.base_hue, g.speed, g.aggression, g.generation), (original.base_hue, original.speed, original.aggression, original.generation));
+            assert!(g.parent_a.is_none() && g.parent_b.is_none());
+            assert!((f.x - source.x).abs() <= 20.0 && (f.y - source.y).abs() <= 20.0);
+        }
+        assert!(sim.clone_fish(999_999, 2).is_empty(), "Unknown fish clones nothing");
+    }
+
+    #[test]
+    fn clones_fit_a_tiny_tank() {
+        let mut sim = SimulationState::new();
+        sim.fish.truncate(1);
+        sim.config.tank_width = 15.0;
+        sim.config.tank_height = 8.0;
+        let source = sim.fish[0].id;
+        let ids = sim.clone_fish(source, 3);
+        assert_eq!(ids.len(), 3);
+        for clone in sim.fish.iter().filter(|f| ids.contains(&f.id)) {
+            assert!((0.0..=15.0).contains(&clone.x), "x {} is inside the tank", clone.x);
+            assert!((0.0..=8.0).contains(&clone.y), "y {} is inside the tank", clone.y);
+        }
+    }
+
+    #[test]
+    fn purchases_fit_a_narrow_tank() {
+        let mut sim = SimulationState::new();
+        sim.config.tank_width = 40.0;
+        sim.config.tank_height = 30.0;
+        sim.currency = 10_000;
+        let food_before = sim.ecosystem.food.len();
+        assert!(sim.purchase(economy::ShopItem::PelletFeast).unwrap().is_none());
+        let dropped = &sim.ecosystem.food[food_before..];
+        assert!(!dropped.is_empty() && dropped.iter().all(|f| (0.0..=40.0).contains(&f.x)));
+        let rock = sim.purchase(economy::ShopItem::Rock).unwrap().unwrap();
+        assert!((0.0..=40.0).contains(&rock.x) && (0.0..=30.0).contains(&rock.y), "rock at ({}, {})", rock.x, rock.y);
+
+        sim.currency = 0;
+        assert!(sim.purchase(economy::ShopItem::Coral).is_err(), "Can't afford it");
+    }
+
+    #[test]
+    fn seeded_population_is_sized_and_reproducible() {
+        let a = SimulationState::with_seed_and_population(3, 50);
+        let b = SimulationState::with_seed_and_population(3, 50);
+        assert_eq!(a.fish.len(), 50);
+        assert_eq!(a.genomes.len(), 50);
+        let positions = |s: &SimulationState| s.fish.iter().map(|f| (f.x, f.y)).collect::<Vec<_>>();
+        assert_eq!(positions(&a), positions(&b));
+        assert_eq!(SimulationState::with_seed(3).fish.len(), INITIAL_POPULATION);
+    }
+
+    // --- Effective population size ---
+
+    /// Replace the tank with `males` + `females` adults whose spawning counts are given in order
+    fn breeding_tank(males: &[u32], females: &[u32]) -> SimulationState {
+        let mut sim = SimulationState::with_seed(7);
+        sim.fish.clear();
+        for (sex, counts) in [(genome::Sex::Male, males), (genome::Sex::Female, females)] {
+            for &k in counts {
+                let mut g = FishGenome::random(&mut sim.rng);
+                g.sex = sex;
+                let mut f = Fish::new(g.id, 100.0, 100.0, &mut sim.rng);
+                f.offspring_count = k;
+                sim.genomes.insert(g.id, g);
+                sim.fish.push(f);
+            }
+        }
+        sim
+    }
+
+    #[test]
+    fn effective_size_matches_head_count_for_even_breeding() {
+        // Equal sexes and no spawning yet: Ne is the head count
+        assert!((breeding_tank(&[0; 10], &[0; 10]).effective_population_size() - 20.0).abs() < 1e-3);
+        // Skewed sex ratio shrinks it: 4 * 2 * 18 / 20
+        assert!((breeding_tank(&[0; 2], &[0; 18]).effective_population_size() - 7.2).abs() < 1e-3);
+        assert_eq!(breeding_tank(&[], &[0; 5]).effective_population_size(), 0.0);
+    }
+
+    #[test]
+    fn few_breeding_males_shrink_effective_size() {
+        let mut males = vec![0; 25];
+        males[..3].copy_from_slice(&[8, 8, 8]);
+        let skewed = breeding_tank(&males, &[1; 25]).effective_population_size();
+        let even = breeding_tank(&[1; 25], &[1; 25]).effective_population_size();
+        assert!(skewed < 15.0, "Three sires among 50 fish should give a small Ne, got {}", skewed);
+        assert!(even > 50.0, "Equal family sizes push Ne above the head count, got {}", even);
+    }
+
+    #[test]
+    fn tray_status_lists_population_and_event() {
+        let mut sim = SimulationState::new();
+        sim.fish.truncate(5);
+        sim.ecosystem.species.clear();
+        assert_eq!(sim.tray_status(), "5 fish · 0 species");
+        sim.event_system.trigger(events::EnvironmentalEvent::Heatwave, 1);
+        sim.paused = true;
+        assert_eq!(sim.tray_status(), "5 fish · 0 species · Heatwave · Paused");
+    }
+
+    // --- Event rewards ---
+
+    #[test]
+    fn each_overlapping_event_pays_out_as_it_ends() {
+        let mut sim = SimulationState::new();
+        sim.fish.truncate(1); // a lone fish can't breed, so births don't pay out too
+        sim.config.event_frequency = 0.0;
+        sim.config.max_concurrent_events = 2;
+        sim.event_system.trigger(events::EnvironmentalEvent::CurrentSurge, 2); // 300 ticks
+        sim.event_system.trigger(events::EnvironmentalEvent::AlgaeBloom, 2); // 600 ticks
+        let mut payouts = Vec::new();
+        for tick in 1..=700 {
+            let before = sim.currency;
+            sim.step();
+            if sim.currency != before {
+                payouts.push((tick, sim.currency - before));
+            }
+        }
+        // Each event clears on the tick after its last, as a lone event always has
+        let reward = economy::EVENT_SURVIVED_REWARD;
+        assert_eq!(payouts, vec![(301, reward), (601, reward)]);
+        assert!(sim.event_system.active_events.is_empty());
+
+        // An empty tank survives nothing
+        sim.fish.clear();
+        sim.event_system.trigger(events::EnvironmentalEvent::CurrentSurge, 2);
+        let before = sim.currency;
+        sim.step_n(302);
+        assert_eq!(sim.currency, before);
+    }
+
+    // --- Dashboard ---
+
+    #[test]
+    fn dashboard_counts_only_living_fish_and_species() {
+        let mut sim = SimulationState::new();
+        sim.fish.truncate(4);
+        for f in &mut sim.fish {
+            f.behavior = fish::BehaviorState::Swimming;
+        }
+        sim.fish[0].behavior = fish::BehaviorState::Resting;
+        sim.fish[1].behavior = fish::BehaviorState::Resting;
+        sim.fish[1].is_alive = false;
+        sim.ecosystem.species.clear();
+        let profile = ExtinctSpeciesProfile {
+            species_id: 7, name: None, discovered_at_tick: 10, extinct_at_tick: 90,
+            genome: FishGenome::random(&mut sim.rng),
+        };
+        sim.revive_species(&profile, 2).unwrap();
+        let mut gone = sim.ecosystem.species[0].clone();
+        gone.id = 8;
+        gone.extinct_at_tick = Some(120);
+        sim.ecosystem.species.push(gone);
+
+        let dash = sim.dashboard();
+        assert_eq!(dash["behaviors"]["resting"], 1, "The dead fish isn't counted");
+        assert_eq!(dash["behaviors"]["swimming"], 4);
+        assert_eq!(dash["population"], 5, "Population matches the behavior totals");
+        assert_eq!(dash["species_count"], 1, "Extinct species are left out");
+        assert!(dash["scenario"].is_null());
+    }
+
+    #[test]
+    fn dashboard_of_empty_tank() {
+        let mut sim = SimulationState::new();
+        sim.fish.clear();
+        sim.genomes.clear();
+        sim.ecosystem.species.clear();
+        let dash = sim.dashboard();
+        assert_eq!(dash["population"], 0);
+        assert_eq!(dash["max_generation"], 0);
+        assert!(dash["active_event"].is_null());
+        assert_eq!(dash["active_events"], serde_json::json!([]));
+        assert_eq!(dash["behaviors"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn manual_grid_size_is_not_retuned() {
+        let mut sim = SimulationState::new();
+        sim.config.environmental_events_enabled = false;
+        sim.set_grid_cell_size(40.0).unwrap();
+        sim.tick = sim.config.adaptive_grid_interval as u64;
+        sim.step();
+        assert_eq!(sim.boids.grid.cell_size(), 40.0);
+        assert!(!sim.config.adaptive_grid_enabled);
+
+        assert!(sim.set_grid_cell_size(0.0).is_err());
+        assert!(sim.set_grid_cell_size(f32::NAN).is_err());
+        assert_eq!(sim.boids.grid.cell_size(), 40.0, "Rejected sizes leave the grid alone");
+    }
+
+    #[test]
+    fn resizing_keeps_the_grid_cell_size() {
+        let mut sim = SimulationState::new();
+        sim.set_grid_cell_size(40.0).unwrap();
+        assert!(sim.resize_tank(1000.0, 700.0).is_none());
+        assert_eq!(sim.boids.grid.cell_size(), 40.0);
+        assert_eq!((sim.config.tank_width, sim.config.tank_height), (1000.0, 700.0));
+
+        let warning = sim.resize_tank(1e7, 1e7);
+        assert!(warning.is_some_and(|w| w.contains("from 40")), "Coarsened cells are reported");
+    }
+
+    #[test]
+    fn predator_introduction_respects_capacity() {
+        let mut sim = SimulationState::new();
+        sim.config.base_carrying_capacity = sim.fish.len() as u32 + 2;
+        sim.ecosystem.water_quality = 1.0;
+        assert_eq!(sim.introduce_predators(5).len(), 2);
+        assert!(sim.introduce_predators(1).is_empty(), "Full tank accepts no more");
+    }
 }
@@ -69,6 +69,7 @@ pub async fn name_species(
     Some((name, desc))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_journal_entry(
     url: &str,
     model: &str,
@@ -76,18 +77,29 @@ pub async fn generate_journal_entry(
     population: u32,
     water_quality: f32,
     species_summary: &str,
+    keeper_goal: Option<&str>,
+    occasion: Option<&str>,
 ) -> Option<String> {
     let day = tick / 1800; // ~1 minute = 1 day
 
-    let prompt = format!(
+    let mut prompt = format!(
         "Current aquarium state:\n\
          - Day: {}\n\
          - Population: {} fish\n\
          - Water quality: {:.0}%\n\
-         - Species: {}\n\n\
-         Write a brief 2-3 sentence field note.",
+         - Species: {}\n\n",
         day, population, water_quality * 100.0, species_summary
     );
+    if let Some(goal) = keeper_goal {
+        prompt.push_str(&format!("The keeper's goal for this tank: \"{}\". Mention progress toward it if relevant.\n\n", goal));
+    }
+    match occasion {
+        Some(what) => prompt.push_str(&format!(
+            "Something notable just happened: {}.\nWrite a brief 2-3 sentence field note about this moment.",
+            what
+        )),
+        None => prompt.push_str("Write a brief 2-3 sentence field note."),
+    }
 
     let req = OllamaRequest {
         model: model.to_string(),
@@ -0,0 +1,218 @@
+use super::config::{BreedingSeason, SimulationConfig};
+use super::events::{EnvironmentalEvent, EventSystem};
+use super::pacing;
+use serde::Serialize;
+
+/// Resolution of the day/night scan, in hours (one sim minute)
+const SCAN_STEP_HOURS: f32 = 1.0 / 60.0;
+
+/// Horizon used when the sim clock can't be converted to ticks
+const FALLBACK_HORIZON_TICKS: u64 = 43_200;
+
+/// Broad stretch of the day/night cycle, split on the breeding-season light thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayPhase {
+    Day,
+    Night,
+    Twilight,
+}
+
+impl DayPhase {
+    pub fn at(light_level: f32) -> Self {
+        if BreedingSeason::Day.allows(light_level) {
+            DayPhase::Day
+        } else if BreedingSeason::Night.allows(light_level) {
+            DayPhase::Night
+        } else {
+            DayPhase::Twilight
+        }
+    }
+}
+
+/// Something that flips to `to` after `in_hours` of sim clock; `in_ticks`
+/// is None when the clock can't be mapped onto ticks
+#[derive(Debug, Clone, Serialize)]
+pub struct Change<T> {
+    pub to: T,
+    pub in_hours: f32,
+    pub in_ticks: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveEvent {
+    pub event: &'static str,
+    pub name: &'static str,
+    pub remaining_ticks: u32,
+    pub advice: &'static str,
+}
+
+/// Chance a random event starts somewhere inside the horizon
+#[derive(Debug, Clone, Serialize)]
+pub struct EventRisk {
+    pub event: &'static str,
+    pub name: &'static str,
+    pub chance: f32,
+    pub advice: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Outlook {
+    pub horizon_ticks: u64,
+    pub time_of_day: f32,
+    pub phase: DayPhase,
+    pub next_phase: Option<Change<DayPhase>>,
+    pub breeding_open: bool,
+    pub breeding_change: Option<Change<bool>>,
+    pub events_enabled: bool,
+    pub active_events: Vec<ActiveEvent>,
+    pub quiet_ticks: Option<u64>, // ticks before a random event can roll; None when none ever can
+    pub event_chance: f32,        // any random event within the horizon
+    pub risks: Vec<EventRisk>,    // most likely first
+}
+
+/// Sim-clock hours that pass per tick: fixed in accelerated mode, wall-clock
+/// paced in real-time mode
+fn hours_per_tick(config: &SimulationConfig, speed_multiplier: f32) -> Option<f32> {
+    if config.day_night_speed > 0.0 {
+        return Some(24.0 / config.day_length_ticks.max(1) as f32 * config.day_night_speed);
+    }
+    let tps = pacing::target_ticks_per_second(speed_multiplier);
+    (tps > 0.0).then(|| 1.0 / (3600.0 * tps))
+}
+
+/// First point within the next 24 hours where `label` of the light level changes
+fn next_change<T: PartialEq>(
+    config: &SimulationConfig,
+    time_of_day: f32,
+    hours_per_tick: Option<f32>,
+    label: impl Fn(f32) -> T,
+) -> Option<Change<T>> {
+    if !config.day_night_cycle {
+        return None;
+    }
+    let now = label(config.light_level(time_of_day));
+    let steps = (24.0 / SCAN_STEP_HOURS) as u32;
+    (1..=steps).find_map(|i| {
+        let in_hours = i as f32 * SCAN_STEP_HOURS;
+        let to = label(config.light_level(time_of_day + in_hours));
+        (to != now).then(|| Change {
+            to,
+            in_hours,
+            in_ticks: hours_per_tick.map(|h| (in_hours / h).round() as u64),
+        })
+    })
+}
+
+/// Forecast the next sim day: phase and breeding-window changes from the
+/// day/night cycle, plus running events and random-event odds
+pub fn forecast(config: &SimulationConfig, events: &EventSystem, time_of_day: f32, speed_multiplier: f32) -> Outlook {
+    let hpt = hours_per_tick(config, speed_multiplier);
+    let horizon_ticks = hpt.map_or(FALLBACK_HORIZON_TICKS, |h| (24.0 / h).round() as u64);
+    let light = config.light_level(time_of_day);
+    let season = config.breeding_season;
+
+    let active_events = events.active_events.iter()
+        .map(|&(e, remaining)| ActiveEvent {
+            event: e.as_str(),
+            name: e.display_name(),
+            remaining_ticks: remaining,
+            advice: e.advice(),
+        })
+        .collect();
+
+    let quiet_ticks = if config.environmental_events_enabled {
+        events.ticks_until_eligible(config.max_concurrent_events)
+    } else {
+        None
+    };
+    // Each roll picks one of the event types evenly; a type that's already
+    // running only becomes possible again once it has ended
+    let per_type = EventSystem::trigger_chance(config.event_frequency) as f64 / EnvironmentalEvent::ALL.len() as f64;
+    let mut risks: Vec<EventRisk> = EnvironmentalEvent::ALL.iter()
+        .map(|&e| {
+            let chance = quiet_ticks.map_or(0.0, |quiet| {
+                let running = events.active_events.iter()
+                    .find(|&&(a, _)| a == e)
+                    .map_or(0, |&(_, r)| r as u64);
+                let window = horizon_ticks.saturating_sub(quiet.max(running));
+                1.0 - (1.0 - per_type).powf(window as f64)
+            });
+            EventRisk { event: e.as_str(), name: e.display_name(), chance: chance as f32, advice: e.advice() }
+        })
+        .collect();
+    risks.sort_by(|a, b| b.chance.total_cmp(&a.chance));
+    let event_chance = 1.0 - risks.iter().map(|r| 1.0 - r.chance).product::<f32>();
+
+    Outlook {
+        horizon_ticks,
+        time_of_day,
+        phase: DayPhase::at(light),
+        next_phase: next_change(config, time_of_day, hpt, DayPhase::at),
+        breeding_open: season.allows(light),
+        breeding_change: next_change(config, time_of_day, hpt, |l| season.allows(l)),
+        events_enabled: config.environmental_events_enabled,
+        active_events,
+        quiet_ticks,
+        event_chance,
+        risks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forecasts_the_next_phase_and_breeding_window() {
+        let mut config = SimulationConfig::default();
+        config.breeding_season = BreedingSeason::Day;
+        let outlook = forecast(&config, &EventSystem::new(), 3.0, 1.0);
+        assert_eq!(outlook.phase, DayPhase::Night);
+        assert!(!outlook.breeding_open);
+        assert_eq!(outlook.horizon_ticks, 43_200);
+
+        // Dawn ramp starts at 05:00; twilight light arrives partway into it
+        let next = outlook.next_phase.unwrap();
+        assert_eq!(next.to, DayPhase::Twilight);
+        assert!(next.in_hours > 2.0 && next.in_hours < 3.0);
+        assert_eq!(next.in_ticks, Some((next.in_hours * 1800.0).round() as u64));
+        let breeding = outlook.breeding_change.unwrap();
+        assert!(breeding.to && breeding.in_hours > next.in_hours);
+
+        config.day_night_cycle = false;
+        let outlook = forecast(&config, &EventSystem::new(), 3.0, 1.0);
+        assert_eq!(outlook.phase, DayPhase::Day);
+        assert!(outlook.next_phase.is_none() && outlook.breeding_change.is_none());
+    }
+
+    #[test]
+    fn event_odds_follow_cooldown_and_running_events() {
+        let config = SimulationConfig::default();
+        let mut events = EventSystem::new();
+        let fresh = forecast(&config, &events, 12.0, 1.0);
+        assert_eq!(fresh.quiet_ticks, Some(3000));
+        assert!(fresh.event_chance > 0.0 && fresh.event_chance < 1.0);
+
+        events.cooldown = 0;
+        let ready = forecast(&config, &events, 12.0, 1.0);
+        assert!(ready.event_chance > fresh.event_chance);
+
+        // A running heatwave can't recur until it ends, though others can start alongside it
+        let mut overlapping = config.clone();
+        overlapping.max_concurrent_events = 2;
+        events.trigger(EnvironmentalEvent::Heatwave, 2);
+        let busy = forecast(&overlapping, &events, 12.0, 1.0);
+        assert_eq!(busy.active_events.len(), 1);
+        let heat = busy.risks.iter().find(|r| r.event == "heatwave").unwrap();
+        let cold = busy.risks.iter().find(|r| r.event == "cold_snap").unwrap();
+        assert_eq!(busy.quiet_ticks, Some(0));
+        assert!(heat.chance < cold.chance);
+
+        let mut off = config.clone();
+        off.environmental_events_enabled = false;
+        let quiet = forecast(&off, &events, 12.0, 1.0);
+        assert_eq!(quiet.quiet_ticks, None);
+        assert_eq!(quiet.event_chance, 0.0);
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Target wall time per frame of the simulation loop (30Hz)
+pub const FRAME_DURATION: Duration = Duration::from_micros(33_333);
+
+/// Frames kept for the rolling averages (~3s at 30Hz)
+const WINDOW: usize = 90;
+
+#[derive(Debug, Clone, Copy)]
+struct FrameSample {
+    step_time: Duration,  // time spent in sim.step() calls
+    frame_time: Duration, // all work in the frame, before sleeping
+    steps: u32,
+}
+
+/// Rolling timings of the simulation loop, used to tell when it can't keep pace
+#[derive(Debug, Default)]
+pub struct LoopStats {
+    samples: VecDeque<FrameSample>,
+}
+
+impl LoopStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, step_time: Duration, frame_time: Duration, steps: u32) {
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(FrameSample { step_time, frame_time, steps });
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Mean time for a single sim step over frames that stepped
+    pub fn avg_step_time(&self) -> Duration {
+        let steps: u32 = self.samples.iter().map(|s| s.steps).sum();
+        if steps == 0 {
+            return Duration::ZERO;
+        }
+        self.samples.iter().map(|s| s.step_time).sum::<Duration>() / steps
+    }
+
+    pub fn avg_frame_time(&self) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        self.samples.iter().map(|s| s.frame_time).sum::<Duration>() / self.samples.len() as u32
+    }
+
+    pub fn max_frame_time(&self) -> Duration {
+        self.samples.iter().map(|s| s.frame_time).max().unwrap_or(Duration::ZERO)
+    }
+
+    /// Simulation ticks actually achieved per wall-clock second; frames that
+    /// overran take their own length, the rest are padded out to FRAME_DURATION
+    pub fn ticks_per_second(&self) -> f32 {
+        let wall: Duration = self.samples.iter().map(|s| s.frame_time.max(FRAME_DURATION)).sum();
+        if wall.is_zero() {
+            return 0.0;
+        }
+        let steps: u32 = self.samples.iter().map(|s| s.steps).sum();
+        steps as f32 / wall.as_secs_f32()
+    }
+
+    /// Whether recent frames fit in the 30Hz budget on average
+    pub fn keeping_up(&self) -> bool {
+        self.avg_frame_time() <= FRAME_DURATION
+    }
+}
+
+/// Average sim steps per frame for a speed multiplier: whole steps at 1x and
+/// above (the fraction is dropped), fractional steps in slow motion
+pub fn steps_per_frame(speed_multiplier: f32) -> f32 {
+    if speed_multiplier >= 1.0 {
+        speed_multiplier.floor()
+    } else {
+        speed_multiplier
+    }
+}
+
+/// Sim ticks per wall-clock second the loop aims for at a speed multiplier
+pub fn target_ticks_per_second(speed_multiplier: f32) -> f32 {
+    steps_per_frame(speed_multiplier) / FRAME_DURATION.as_secs_f32()
+}
+
+/// Wall-clock seconds `ticks` take at a speed multiplier, assuming the loop
+/// keeps pace; None when that speed never advances the sim
+pub fn ticks_to_seconds(ticks: f32, speed_multiplier: f32) -> Option<f32> {
+    let tps = target_ticks_per_second(speed_multiplier);
+    (tps > 0.0).then(|| ticks / tps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_keeps_recent_frames() {
+        let mut stats = LoopStats::new();
+        for _ in 0..WINDOW + 10 {
+            stats.record(Duration::from_millis(1), Duration::from_millis(2), 1);
+        }
+        assert_eq!(stats.frame_count(), WINDOW);
+    }
+
+    #[test]
+    fn averages_step_time_per_step() {
+        let mut stats = LoopStats::new();
+        stats.record(Duration::from_millis(8), Duration::from_millis(10), 4);
+        stats.record(Duration::ZERO, Duration::from_millis(1), 0);
+        assert_eq!(stats.avg_step_time(), Duration::from_millis(2));
+        assert_eq!(stats.max_frame_time(), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn overrunning_frames_fall_behind() {
+        let mut fast = LoopStats::new();
+        let mut slow = LoopStats::new();
+        for _ in 0..30 {
+            fast.record(Duration::from_millis(5), Duration::from_millis(6), 1);
+            slow.record(Duration::from_millis(60), Duration::from_millis(66), 4);
+        }
+        assert!(fast.keeping_up());
+        assert!((fast.ticks_per_second() - 30.0).abs() < 0.1);
+        assert!(!slow.keeping_up());
+        // 4 steps per 66ms frame instead of 4 per 33ms
+        assert!((slow.ticks_per_second() - 4.0 / 0.066).abs() < 0.5);
+    }
+
+    #[test]
+    fn steps_per_frame_follows_loop_rounding() {
+        assert_eq!(steps_per_frame(4.0), 4.0);
+        assert_eq!(steps_per_frame(2.5), 2.0);
+        assert_eq!(steps_per_frame(0.25), 0.25);
+    }
+
+    #[test]
+    fn ticks_convert_to_wall_time_at_speed() {
+        // 30 ticks is one second at 1x, a quarter second at 4x
+        assert!((ticks_to_seconds(30.0, 1.0).unwrap() - 1.0).abs() < 1e-3);
+        assert!((ticks_to_seconds(30.0, 4.0).unwrap() - 0.25).abs() < 1e-3);
+        assert!((ticks_to_seconds(30.0, 0.5).unwrap() - 2.0).abs() < 1e-3);
+        assert_eq!(ticks_to_seconds(30.0, 0.0), None);
+    }
+}
@@ -1,7 +1,9 @@
-use crate::simulation::ecosystem::{Egg, Species};
+use crate::simulation::conservation::ConservationRegistry;
+use crate::simulation::ecosystem::{DeathCause, Decoration, Egg, SimEvent, Species};
 use crate::simulation::fish::{BehaviorState, Fish};
-use crate::simulation::genome::{FishGenome, PatternGene, Sex};
-use rusqlite::{params, Connection, Result};
+use crate::simulation::genome::{self, circular_mean, FishGenome, ParentLinks, PatternGene, Sex};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Result};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -11,6 +13,11 @@ pub fn open_db(path: &Path) -> Result<Connection> {
     Ok(conn)
 }
 
+/// Open a tank DB for inspection without taking write locks or creating the file
+pub fn open_db_readonly(path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+}
+
 pub fn init_schema(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "
@@ -153,15 +160,16 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             unlocked_at TEXT
         );
 
-        -- Migration: add disease_resistance column if missing
+        -- Migration: add columns for genome traits added since the first schema
         -- SQLite doesn't have IF NOT EXISTS for ALTER TABLE, so check column existence via pragma
         ",
     )?;
-    // Add disease_resistance column if not present
-    let has_disease_col: bool = conn.prepare("SELECT disease_resistance FROM genomes LIMIT 0")
-        .is_ok();
-    if !has_disease_col {
-        conn.execute_batch("ALTER TABLE genomes ADD COLUMN disease_resistance REAL NOT NULL DEFAULT 0.5;").ok();
+    // One column per trait in genome::ADDED_TRAITS, filled with its default for existing rows
+    for t in genome::ADDED_TRAITS {
+        let has_col = conn.prepare(&format!("SELECT {} FROM genomes LIMIT 0", t.name)).is_ok();
+        if !has_col {
+            conn.execute_batch(&format!("ALTER TABLE genomes ADD COLUMN {} REAL NOT NULL DEFAULT {};", t.name, t.default)).ok();
+        }
     }
     // Migration: add extended trait columns to population_snapshots
     let has_boldness_col: bool = conn.prepare("SELECT avg_boldness FROM population_snapshots LIMIT 0").is_ok();
@@ -189,6 +197,99 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             ALTER TABLE fish ADD COLUMN is_favorite INTEGER NOT NULL DEFAULT 0;
         ").ok();
     }
+    // Migration: add is_quarantined column to fish
+    let has_quarantine_col: bool = conn.prepare("SELECT is_quarantined FROM fish LIMIT 0").is_ok();
+    if !has_quarantine_col {
+        conn.execute_batch("ALTER TABLE fish ADD COLUMN is_quarantined INTEGER NOT NULL DEFAULT 0;").ok();
+    }
+    // Migration: add offspring_count column to fish
+    let has_offspring_col: bool = conn.prepare("SELECT offspring_count FROM fish LIMIT 0").is_ok();
+    if !has_offspring_col {
+        conn.execute_batch("ALTER TABLE fish ADD COLUMN offspring_count INTEGER NOT NULL DEFAULT 0;").ok();
+    }
+    // Migration: add the temp_tolerance trend to population_snapshots
+    let has_temp_col: bool = conn.prepare("SELECT avg_temp_tolerance FROM population_snapshots LIMIT 0").is_ok();
+    if !has_temp_col {
+        conn.execute_batch("ALTER TABLE population_snapshots ADD COLUMN avg_temp_tolerance REAL DEFAULT 0.5;").ok();
+    }
+    // Migration: add currency column to aquarium
+    let has_currency_col: bool = conn.prepare("SELECT currency FROM aquarium LIMIT 0").is_ok();
+    if !has_currency_col {
+        conn.execute_batch("ALTER TABLE aquarium ADD COLUMN currency INTEGER NOT NULL DEFAULT 0;").ok();
+    }
+    // Migration: user-written tank description for the tank picker
+    let has_description_col: bool = conn.prepare("SELECT description FROM aquarium LIMIT 0").is_ok();
+    if !has_description_col {
+        conn.execute_batch("ALTER TABLE aquarium ADD COLUMN description TEXT NOT NULL DEFAULT '';").ok();
+    }
+    // Migration: per-tank wander noise seed (NULL until first assigned)
+    let has_noise_seed_col: bool = conn.prepare("SELECT noise_seed FROM aquarium LIMIT 0").is_ok();
+    if !has_noise_seed_col {
+        conn.execute_batch("ALTER TABLE aquarium ADD COLUMN noise_seed INTEGER DEFAULT NULL;").ok();
+    }
+    // Migration: simulation RNG checkpoint, so reloads continue the same random sequence
+    let has_rng_state_col: bool = conn.prepare("SELECT rng_state FROM aquarium LIMIT 0").is_ok();
+    if !has_rng_state_col {
+        conn.execute_batch("ALTER TABLE aquarium ADD COLUMN rng_state TEXT DEFAULT NULL;").ok();
+    }
+    // Migration: add structured death_cause column to events, backfilled from the description text
+    let has_cause_col: bool = conn.prepare("SELECT death_cause FROM events LIMIT 0").is_ok();
+    if !has_cause_col {
+        conn.execute_batch("ALTER TABLE events ADD COLUMN death_cause TEXT DEFAULT NULL;").ok();
+        for cause in DeathCause::all() {
+            conn.execute(
+                "UPDATE events SET death_cause = ?1 WHERE event_type = 'death' AND description LIKE ?2",
+                params![cause.as_str(), format!("%died: {:?}", cause)],
+            ).ok();
+        }
+    }
+
+    // Migration: record age at death so survivorship can be computed
+    let has_death_age_col: bool = conn.prepare("SELECT death_age FROM events LIMIT 0").is_ok();
+    if !has_death_age_col {
+        conn.execute_batch("
+            ALTER TABLE events ADD COLUMN death_age INTEGER DEFAULT NULL;
+            ALTER TABLE events ADD COLUMN death_age_fraction REAL DEFAULT NULL;
+        ").ok();
+    }
+    // Migration: key traits of the fish at death, for analysis without joining genomes
+    let has_death_traits_col: bool = conn.prepare("SELECT death_speed FROM events LIMIT 0").is_ok();
+    if !has_death_traits_col {
+        conn.execute_batch("
+            ALTER TABLE events ADD COLUMN death_speed REAL DEFAULT NULL;
+            ALTER TABLE events ADD COLUMN death_body_length REAL DEFAULT NULL;
+            ALTER TABLE events ADD COLUMN death_aggression REAL DEFAULT NULL;
+        ").ok();
+    }
+    // Migration: whether the fish ever bred, for selection analysis
+    let has_death_reproduced_col: bool = conn.prepare("SELECT death_reproduced FROM events LIMIT 0").is_ok();
+    if !has_death_reproduced_col {
+        conn.execute("ALTER TABLE events ADD COLUMN death_reproduced INTEGER DEFAULT NULL", []).ok();
+    }
+
+    // Migration: immunity acquired by surviving disease
+    let has_immunity_col: bool = conn.prepare("SELECT acquired_immunity FROM fish LIMIT 0").is_ok();
+    if !has_immunity_col {
+        conn.execute("ALTER TABLE fish ADD COLUMN acquired_immunity REAL NOT NULL DEFAULT 0", []).ok();
+    }
+
+    // Migration: tank mood at the time each journal entry was written
+    let has_mood_col: bool = conn.prepare("SELECT mood FROM journal_entries LIMIT 0").is_ok();
+    if !has_mood_col {
+        conn.execute("ALTER TABLE journal_entries ADD COLUMN mood TEXT DEFAULT NULL", []).ok();
+    }
+
+    // Migration: per-species non-color marker
+    let has_marker_col: bool = conn.prepare("SELECT distinct_marker FROM species LIMIT 0").is_ok();
+    if !has_marker_col {
+        conn.execute("ALTER TABLE species ADD COLUMN distinct_marker TEXT DEFAULT NULL", []).ok();
+    }
+
+    // Migration: average genome of a species at extinction
+    let has_fossil_col: bool = conn.prepare("SELECT fossil_genome FROM species LIMIT 0").is_ok();
+    if !has_fossil_col {
+        conn.execute("ALTER TABLE species ADD COLUMN fossil_genome TEXT DEFAULT NULL", []).ok();
+    }
 
     conn.execute_batch("
         CREATE TABLE IF NOT EXISTS eggs (
@@ -200,6 +301,14 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             parent_a INTEGER NOT NULL,
             parent_b INTEGER NOT NULL
         );
+        CREATE TABLE IF NOT EXISTS conservation_founders (
+            genome_id INTEGER PRIMARY KEY,
+            genome_json TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS conservation_members (
+            genome_id INTEGER PRIMARY KEY,
+            founder_id INTEGER NOT NULL
+        );
         CREATE INDEX IF NOT EXISTS idx_genomes_generation ON genomes(generation);
         CREATE INDEX IF NOT EXISTS idx_snapshots_tick ON population_snapshots(tick);
         CREATE INDEX IF NOT EXISTS idx_events_type ON events(event_type);
@@ -213,6 +322,7 @@ pub fn save_state(
     conn: &Connection,
     tick: u64,
     water_quality: f32,
+    currency: u32,
     fish: &[Fish],
     genomes: &HashMap<u32, FishGenome>,
     species: &[Species],
@@ -222,8 +332,8 @@ pub fn save_state(
 
     // Update aquarium
     tx.execute(
-        "UPDATE aquarium SET tick_count = ?1, water_quality = ?2, last_saved_at = datetime('now') WHERE id = 1",
-        params![tick as i64, water_quality],
+        "UPDATE aquarium SET tick_count = ?1, water_quality = ?2, currency = ?3, last_saved_at = datetime('now') WHERE id = 1",
+        params![tick as i64, water_quality, currency],
     )?;
 
     // Upsert genomes
@@ -236,15 +346,15 @@ pub fn save_state(
                 dorsal_fin_size, pectoral_fin_size, pattern_type, pattern_data,
                 pattern_intensity, pattern_color_offset, eye_size, speed, aggression,
                 school_affinity, curiosity, boldness, metabolism, fertility,
-                lifespan_factor, maturity_age, born_at_tick, disease_resistance)
-             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20,?21,?22,?23,?24,?25,?26,?27,?28,?29)",
+                lifespan_factor, maturity_age, born_at_tick, disease_resistance, gape_size, temp_tolerance, ideal_school_size, polarization, parental_care)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20,?21,?22,?23,?24,?25,?26,?27,?28,?29,?30,?31,?32,?33,?34)",
             params![
                 g.id, g.generation, g.parent_a, g.parent_b, sex_str,
                 g.base_hue, g.saturation, g.lightness, g.body_length, g.body_width, g.tail_size,
                 g.dorsal_fin_size, g.pectoral_fin_size, pat_type, pat_data,
                 g.pattern_intensity, g.pattern_color_offset, g.eye_size, g.speed, g.aggression,
                 g.school_affinity, g.curiosity, g.boldness, g.metabolism, g.fertility,
-                g.lifespan_factor, g.maturity_age, 0i64, g.disease_resistance,
+                g.lifespan_factor, g.maturity_age, 0i64, g.disease_resistance, g.gape_size, g.temp_tolerance, g.ideal_school_size, g.polarization, g.parental_care,
             ],
         )?;
     }
@@ -256,13 +366,14 @@ pub fn save_state(
             "INSERT INTO fish (id, genome_id, position_x, position_y, position_z,
                 velocity_x, velocity_y, heading, age, hunger, health, energy,
                 behavior_state, meals_eaten, last_reproduced_tick, is_alive,
-                custom_name, is_favorite)
-             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18)",
+                custom_name, is_favorite, is_quarantined, offspring_count, acquired_immunity)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20,?21)",
             params![
                 f.id, f.genome_id, f.x, f.y, f.z, f.vx, f.vy, f.heading,
                 f.age, f.hunger, f.health, f.energy, f.behavior.as_str(),
                 f.meals_eaten, f.last_reproduced_tick.map(|t| t as i64), f.is_alive as i32,
-                f.custom_name, f.is_favorite as i32,
+                f.custom_name, f.is_favorite as i32, f.is_quarantined as i32, f.offspring_count,
+                f.acquired_immunity,
             ],
         )?;
     }
@@ -282,12 +393,13 @@ pub fn save_state(
         tx.execute(
             "INSERT OR REPLACE INTO species (id, name, description, discovered_at_tick,
                 extinct_at_tick, centroid_hue, centroid_speed, centroid_size,
-                centroid_pattern, member_count_at_discovery)
-             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10)",
+                centroid_pattern, member_count_at_discovery, distinct_marker, fossil_genome)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12)",
             params![
                 s.id, s.name, s.description, s.discovered_at_tick as i64,
                 s.extinct_at_tick.map(|t| t as i64), s.centroid_hue, s.centroid_speed,
-                s.centroid_size, s.centroid_pattern, s.member_count,
+                s.centroid_size, s.centroid_pattern, s.member_count, s.distinct_marker,
+                s.fossil.as_ref().map(|g| g.to_versioned_json().to_string()),
             ],
         )?;
     }
@@ -296,6 +408,60 @@ pub fn save_state(
     Ok(())
 }
 
+fn parse_fossil(json: Option<String>) -> Option<FishGenome> {
+    let value = serde_json::from_str(&json?).ok()?;
+    FishGenome::from_versioned_json(value).ok()
+}
+
+/// Genetic profile of a species kept from the moment it went extinct
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtinctSpeciesProfile {
+    pub species_id: u32,
+    pub name: Option<String>,
+    pub discovered_at_tick: u64,
+    pub extinct_at_tick: u64,
+    /// Average genome of the last members
+    pub genome: FishGenome,
+}
+
+impl ExtinctSpeciesProfile {
+    pub fn of(species: &Species) -> Option<Self> {
+        Some(Self {
+            species_id: species.id,
+            name: species.name.clone(),
+            discovered_at_tick: species.discovered_at_tick,
+            extinct_at_tick: species.extinct_at_tick?,
+            genome: species.fossil.clone()?,
+        })
+    }
+}
+
+/// Saved profile of an extinct species. Outlives the species' in-memory
+/// record, which is pruned some time after extinction.
+pub fn extinct_species_profile(conn: &Connection, species_id: u32) -> Result<Option<ExtinctSpeciesProfile>> {
+    conn.query_row(
+        "SELECT name, discovered_at_tick, extinct_at_tick, fossil_genome FROM species WHERE id = ?1",
+        params![species_id],
+        |row| {
+            let extinct: Option<i64> = row.get(2)?;
+            Ok(extinct.zip(parse_fossil(row.get(3)?)).map(|(extinct, genome)| ExtinctSpeciesProfile {
+                species_id,
+                name: row.get(0).unwrap_or(None),
+                discovered_at_tick: row.get::<_, i64>(1).unwrap_or(0) as u64,
+                extinct_at_tick: extinct as u64,
+                genome,
+            }))
+        },
+    ).optional().map(Option::flatten)
+}
+
+/// Whether a genome is close enough to a species centroid to count as a member
+/// when membership is rebuilt on load
+fn near_centroid(hue: f32, speed: f32, size: f32, centroid_hue: f32, centroid_speed: f32, centroid_size: f32) -> bool {
+    let hue_diff = (hue - centroid_hue).abs().min(360.0 - (hue - centroid_hue).abs());
+    hue_diff < 30.0 && (speed - centroid_speed).abs() < 0.5 && (size - centroid_size).abs() < 0.5
+}
+
 /// Returns (tick, water_quality, fish, genomes, species, eggs, max_species_id)
 pub fn load_state(
     conn: &Connection,
@@ -315,7 +481,7 @@ pub fn load_state(
                 body_length, body_width, tail_size, dorsal_fin_size, pectoral_fin_size,
                 pattern_type, pattern_data, pattern_intensity, pattern_color_offset, eye_size,
                 speed, aggression, school_affinity, curiosity, boldness, metabolism, fertility,
-                lifespan_factor, maturity_age, disease_resistance FROM genomes"
+                lifespan_factor, maturity_age, disease_resistance, gape_size, temp_tolerance, ideal_school_size, polarization, parental_care FROM genomes"
     )?;
     let genome_rows = stmt.query_map([], |row| {
         let sex_str: String = row.get(4)?;
@@ -348,7 +514,12 @@ pub fn load_state(
             fertility: row.get(24)?,
             lifespan_factor: row.get(25)?,
             maturity_age: row.get(26)?,
-            disease_resistance: row.get::<_, f64>(27).unwrap_or(0.5) as f32,
+            disease_resistance: row.get::<_, f64>(27).map_or(genome::DEFAULT_DISEASE_RESISTANCE, |v| v as f32),
+            gape_size: row.get::<_, f64>(28).map_or(genome::DEFAULT_GAPE_SIZE, |v| v as f32),
+            temp_tolerance: row.get::<_, f64>(29).map_or(genome::DEFAULT_TEMP_TOLERANCE, |v| v as f32),
+            ideal_school_size: row.get::<_, f64>(30).map_or(genome::DEFAULT_IDEAL_SCHOOL_SIZE, |v| v as f32),
+            polarization: row.get::<_, f64>(31).map_or(genome::DEFAULT_POLARIZATION, |v| v as f32),
+            parental_care: row.get::<_, f64>(32).map_or(genome::DEFAULT_PARENTAL_CARE, |v| v as f32),
         })
     })?;
     for g in genome_rows {
@@ -361,7 +532,7 @@ pub fn load_state(
     let mut stmt = conn.prepare(
         "SELECT id, genome_id, position_x, position_y, position_z, velocity_x, velocity_y,
                 heading, age, hunger, health, energy, behavior_state, meals_eaten,
-                last_reproduced_tick, is_alive, custom_name, is_favorite FROM fish WHERE is_alive = 1"
+                last_reproduced_tick, is_alive, custom_name, is_favorite, is_quarantined, offspring_count, acquired_immunity FROM fish WHERE is_alive = 1"
     )?;
     let fish_rows = stmt.query_map([], |row| {
         let beh_str: String = row.get(12)?;
@@ -379,41 +550,42 @@ pub fn load_state(
             hunger: row.get(9)?,
             health: row.get(10)?,
             energy: row.get(11)?,
-            behavior: match beh_str.as_str() {
-                "foraging" => BehaviorState::Foraging,
-                "fleeing" => BehaviorState::Fleeing,
-                "satiated" => BehaviorState::Satiated,
-                "courting" => BehaviorState::Courting,
-                "resting" => BehaviorState::Resting,
-                "hunting" => BehaviorState::Hunting,
-                "dying" => BehaviorState::Dying,
-                _ => BehaviorState::Swimming,
-            },
+            behavior: BehaviorState::from_str(&beh_str).unwrap_or(BehaviorState::Swimming),
             meals_eaten: row.get(13)?,
             last_reproduced_tick: last_repro.map(|t| t as u64),
+            offspring_count: row.get::<_, u32>(19).unwrap_or(0),
             is_alive: true,
             prev_force_x: 0.0,
             prev_force_y: 0.0,
             satiated_timer: 0,
             courting_partner: None,
             courting_timer: 0,
+            courtship_accepted: None,
             dying_timer: 0,
             starvation_ticks: 0,
             fleeing_from: None,
             killed_by_predator: false,
             is_juvenile: false,
             juvenile_timer: 0,
+            spawn_protection: 0,
             stress: 0.0,
             tap_flee_timer: 0,
             hunting_target: None,
             hunting_timer: 0,
+            hunt_cooldown: 0,
+            stomach_fullness: 0.0,
+            enrichment: 1.0,
             territory_center: None,
             territory_radius: 0.0,
+            territory_strength: 0.0,
+            guarding: None,
             custom_name: row.get::<_, Option<String>>(16).unwrap_or(None),
             is_favorite: row.get::<_, i32>(17).unwrap_or(0) != 0,
             is_infected: false,
             infection_timer: 0,
             recovery_timer: 0,
+            acquired_immunity: row.get::<_, f32>(20).unwrap_or(0.0),
+            is_quarantined: row.get::<_, i32>(18).unwrap_or(0) != 0,
         })
     })?;
     for f in fish_rows {
@@ -425,7 +597,7 @@ pub fn load_state(
     let mut stmt = conn.prepare(
         "SELECT id, name, description, discovered_at_tick, extinct_at_tick,
                 centroid_hue, centroid_speed, centroid_size, centroid_pattern,
-                member_count_at_discovery FROM species"
+                member_count_at_discovery, distinct_marker, fossil_genome FROM species"
     )?;
     let species_rows = stmt.query_map([], |row| {
         let extinct: Option<i64> = row.get(4)?;
@@ -441,6 +613,8 @@ pub fn load_state(
             centroid_pattern: row.get(8)?,
             member_count: row.get::<_, u32>(9).unwrap_or(0),
             member_genome_ids: Vec::new(),
+            distinct_marker: row.get(10).unwrap_or(None),
+            fossil: parse_fossil(row.get(11).unwrap_or(None)),
         })
     })?;
     for s in species_rows {
@@ -460,16 +634,9 @@ pub fn load_state(
         if sp.extinct_at_tick.is_some() { continue; }
         sp.member_genome_ids = fish_genome_ids.iter()
             .filter_map(|&(_fid, gid)| {
-                if let Some(g) = genomes.get(&gid) {
-                    // Check if genome is close enough to this species centroid
-                    let hue_diff = (g.base_hue - sp.centroid_hue).abs().min(360.0 - (g.base_hue - sp.centroid_hue).abs());
-                    let speed_diff = (g.speed - sp.centroid_speed).abs();
-                    let size_diff = (g.body_length - sp.centroid_size).abs();
-                    if hue_diff < 30.0 && speed_diff < 0.5 && size_diff < 0.5 {
-                        return Some(gid);
-                    }
-                }
-                None
+                genomes.get(&gid)
+                    .filter(|g| near_centroid(g.base_hue, g.speed, g.body_length, sp.centroid_hue, sp.centroid_speed, sp.centroid_size))
+                    .map(|_| gid)
             })
             .collect();
         sp.member_count = sp.member_genome_ids.len() as u32;
@@ -500,6 +667,516 @@ pub fn load_state(
     Ok(Some((tick as u64, water_quality as f32, fish, genomes, species, eggs, max_species_id)))
 }
 
+/// Headline stats for a saved tank, read straight from its DB
+#[derive(Debug, Clone, Serialize)]
+pub struct TankSummary {
+    pub tick: u64,
+    pub population: u32,
+    pub species_count: u32,
+    pub max_generation: u32,
+    pub genetic_diversity: f32,
+    pub water_quality: f32,
+}
+
+pub fn tank_summary(conn: &Connection) -> Result<TankSummary> {
+    let (tick, water_quality): (i64, f64) = conn.query_row(
+        "SELECT tick_count, water_quality FROM aquarium WHERE id = 1", [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let population: u32 = conn.query_row("SELECT COUNT(*) FROM fish WHERE is_alive = 1", [], |row| row.get(0))?;
+    let species_count: u32 = conn.query_row("SELECT COUNT(*) FROM species WHERE extinct_at_tick IS NULL", [], |row| row.get(0))?;
+    let max_generation: Option<u32> = conn.query_row(
+        "SELECT MAX(g.generation) FROM fish f JOIN genomes g ON g.id = f.genome_id WHERE f.is_alive = 1", [],
+        |row| row.get(0),
+    )?;
+    // Diversity is only recorded with snapshots, so use the most recent one
+    let genetic_diversity: f64 = conn.query_row(
+        "SELECT genetic_diversity FROM population_snapshots ORDER BY tick DESC LIMIT 1", [],
+        |row| row.get::<_, Option<f64>>(0),
+    ).unwrap_or(None).unwrap_or(0.0);
+    Ok(TankSummary {
+        tick: tick as u64,
+        population,
+        species_count,
+        max_generation: max_generation.unwrap_or(0),
+        genetic_diversity: genetic_diversity as f32,
+        water_quality: water_quality as f32,
+    })
+}
+
+pub fn load_currency(conn: &Connection) -> u32 {
+    conn.query_row("SELECT currency FROM aquarium WHERE id = 1", [], |row| row.get::<_, u32>(0))
+        .unwrap_or(0)
+}
+
+pub fn save_currency(conn: &Connection, currency: u32) -> Result<()> {
+    conn.execute("UPDATE aquarium SET currency = ?1 WHERE id = 1", params![currency])?;
+    Ok(())
+}
+
+pub fn load_noise_seed(conn: &Connection) -> Option<u32> {
+    conn.query_row("SELECT noise_seed FROM aquarium WHERE id = 1", [], |row| row.get::<_, Option<u32>>(0))
+        .ok()
+        .flatten()
+}
+
+pub fn save_noise_seed(conn: &Connection, seed: u32) -> Result<()> {
+    conn.execute("UPDATE aquarium SET noise_seed = ?1 WHERE id = 1", params![seed])?;
+    Ok(())
+}
+
+pub fn load_rng_state(conn: &Connection) -> Option<String> {
+    conn.query_row("SELECT rng_state FROM aquarium WHERE id = 1", [], |row| row.get::<_, Option<String>>(0))
+        .ok()
+        .flatten()
+}
+
+pub fn save_rng_state(conn: &Connection, checkpoint: &str) -> Result<()> {
+    conn.execute("UPDATE aquarium SET rng_state = ?1 WHERE id = 1", params![checkpoint])?;
+    Ok(())
+}
+
+/// Replace the stored decorations with these
+pub fn save_decorations(conn: &Connection, decorations: &[Decoration]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM decorations", [])?;
+    for d in decorations {
+        tx.execute(
+            "INSERT INTO decorations (id, decoration_type, position_x, position_y, scale, flip_x) VALUES (?1,?2,?3,?4,?5,?6)",
+            params![d.id, d.decoration_type.as_str(), d.x, d.y, d.scale, d.flip_x as i32],
+        )?;
+    }
+    tx.commit()
+}
+
+/// Replace the stored conservation programs with the registry's founders and members
+pub fn save_conservation(conn: &Connection, registry: &ConservationRegistry) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch("DELETE FROM conservation_founders; DELETE FROM conservation_members;")?;
+    for founder in registry.founders() {
+        let json = founder.to_versioned_json().to_string();
+        tx.execute("INSERT INTO conservation_founders (genome_id, genome_json) VALUES (?1, ?2)", params![founder.id, json])?;
+    }
+    for (genome_id, founder_id) in registry.members() {
+        tx.execute("INSERT INTO conservation_members (genome_id, founder_id) VALUES (?1, ?2)", params![genome_id, founder_id])?;
+    }
+    tx.commit()
+}
+
+/// Founders whose stored genome no longer parses are dropped along with their members
+pub fn load_conservation(conn: &Connection) -> ConservationRegistry {
+    let founders: Vec<FishGenome> = conn.prepare("SELECT genome_json FROM conservation_founders")
+        .and_then(|mut stmt| stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<Result<Vec<_>>>())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|json| serde_json::from_str(json).ok())
+        .filter_map(|value| FishGenome::from_versioned_json(value).ok())
+        .collect();
+    let members: Vec<(u32, u32)> = conn.prepare("SELECT genome_id, founder_id FROM conservation_members")
+        .and_then(|mut stmt| stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect())
+        .unwrap_or_default();
+    ConservationRegistry::restore(founders, members)
+}
+
+/// Death counts per cause for events with from_tick <= tick <= to_tick.
+/// Every known cause is present (possibly zero); unrecognized stored causes are kept as-is.
+pub fn death_counts(conn: &Connection, from_tick: u64, to_tick: u64) -> Result<Vec<(String, u32)>> {
+    let mut counts: Vec<(String, u32)> = DeathCause::all().iter().map(|c| (c.as_str().to_string(), 0)).collect();
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(death_cause, 'unknown'), COUNT(*) FROM events
+         WHERE event_type = 'death' AND tick >= ?1 AND tick <= ?2
+         GROUP BY 1"
+    )?;
+    let rows = stmt.query_map(params![from_tick as i64, to_tick as i64], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?))
+    })?;
+    for r in rows {
+        let (cause, n) = r?;
+        match counts.iter_mut().find(|(c, _)| *c == cause) {
+            Some(entry) => entry.1 = n,
+            None => counts.push((cause, n)),
+        }
+    }
+    Ok(counts)
+}
+
+/// Survivorship curve over recorded deaths: for each of `buckets` + 1 evenly spaced
+/// lifespan fractions, the share of the dead that lived at least that long, plus the
+/// number of deaths in the bucket starting there. Deaths without a recorded age are skipped.
+pub fn survivorship_curve(conn: &Connection, buckets: u32) -> Result<Vec<(f32, f64, u32)>> {
+    let mut stmt = conn.prepare(
+        "SELECT death_age_fraction FROM events WHERE event_type = 'death' AND death_age_fraction IS NOT NULL"
+    )?;
+    let fractions: Vec<f64> = stmt.query_map([], |row| row.get(0))?.collect::<Result<_>>()?;
+    if fractions.is_empty() || buckets == 0 {
+        return Ok(Vec::new());
+    }
+    let total = fractions.len() as f64;
+    let step = 1.0 / buckets as f64;
+    Ok((0..=buckets).map(|i| {
+        let lo = i as f64 * step;
+        let surviving = fractions.iter().filter(|&&f| f >= lo).count() as f64 / total;
+        // The last bucket also collects fish that outlived their nominal lifespan
+        let deaths = fractions.iter()
+            .filter(|&&f| f >= lo && (i == buckets || f < lo + step))
+            .count() as u32;
+        (lo as f32, surviving, deaths)
+    }).collect())
+}
+
+/// Append a sim event to the event log. FeedingDrop is too frequent to be worth
+/// keeping and is skipped; death traits are stored as NULL when unknown.
+pub fn log_event(conn: &Connection, tick: u64, ev: &SimEvent) -> Result<()> {
+    let (death_cause, death_age, death_age_fraction, death_traits, death_reproduced) = match ev {
+        SimEvent::Death { cause, age, age_fraction, speed, body_length, aggression, reproduced, .. } => (
+            Some(cause.as_str()), Some(*age as i64), Some(*age_fraction as f64),
+            [speed.map(f64::from), body_length.map(f64::from), aggression.map(f64::from)],
+            Some(*reproduced as i32),
+        ),
+        _ => (None, None, None, [None; 3], None),
+    };
+    let (etype, fish_id, species_id, desc) = match ev {
+        SimEvent::Birth { fish_id, genome_id, parent_a, parent_b } => {
+            ("birth", Some(*fish_id as i64), None::<i64>, format!("Fish #{} born (genome {}) from parents #{}, #{}", fish_id, genome_id, parent_a, parent_b))
+        }
+        SimEvent::Death { fish_id, genome_id, cause, age, .. } => {
+            ("death", Some(*fish_id as i64), None, format!("Fish #{} (genome {}) died: {:?} at age {}", fish_id, genome_id, cause, age))
+        }
+        SimEvent::Predation { predator_id, prey_id } => {
+            ("predation", Some(*prey_id as i64), None, format!("Fish #{} eaten by #{}", prey_id, predator_id))
+        }
+        SimEvent::NewSpecies { species_id } => {
+            ("new_species", None, Some(*species_id as i64), format!("New species #{} discovered", species_id))
+        }
+        SimEvent::Extinction { species_id } => {
+            ("extinction", None, Some(*species_id as i64), format!("Species #{} went extinct", species_id))
+        }
+        SimEvent::PopulationWarning { population, minimum } => {
+            ("population_warning", None, None, format!("Population of {} is below the minimum viable {}", population, minimum))
+        }
+        SimEvent::PredatorIntroduced { fish_id, genome_id } => {
+            ("predator_introduced", Some(*fish_id as i64), None, format!("Apex predator #{} (genome {}) introduced to the tank", fish_id, genome_id))
+        }
+        SimEvent::SpeciesRevived { species_id, count } => {
+            ("species_revived", None, Some(*species_id as i64), format!("Species #{} revived with {} fish", species_id, count))
+        }
+        SimEvent::FeedingDrop { .. } => return Ok(()),
+    };
+    conn.execute(
+        "INSERT INTO events (tick, event_type, subject_fish_id, subject_species_id, description, death_cause, death_age, death_age_fraction, death_speed, death_body_length, death_aggression, death_reproduced) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12)",
+        params![
+            tick as i64, etype, fish_id, species_id, desc, death_cause, death_age, death_age_fraction,
+            death_traits[0], death_traits[1], death_traits[2], death_reproduced,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Speed, body length and aggression of fish that died since `since_tick`,
+/// with whether each had reproduced. Deaths logged before these were recorded are skipped.
+pub fn death_selection_samples(conn: &Connection, since_tick: u64) -> Result<Vec<([f32; 3], bool)>> {
+    let mut stmt = conn.prepare(
+        "SELECT death_speed, death_body_length, death_aggression, death_reproduced FROM events
+         WHERE event_type = 'death' AND tick >= ?1 AND death_speed IS NOT NULL AND death_body_length IS NOT NULL
+           AND death_aggression IS NOT NULL AND death_reproduced IS NOT NULL"
+    )?;
+    let rows = stmt.query_map(params![since_tick as i64], |row| {
+        let traits = [row.get::<_, f64>(0)? as f32, row.get::<_, f64>(1)? as f32, row.get::<_, f64>(2)? as f32];
+        Ok((traits, row.get::<_, i32>(3)? != 0))
+    })?;
+    rows.collect()
+}
+
+/// Why `import_tank_file` turned a file away
+#[derive(Debug)]
+pub enum ImportError {
+    /// A tank is already saved at the destination
+    Exists,
+    Io(std::io::Error),
+    /// The file doesn't open as a tank save; the copy has been removed
+    NotATank(rusqlite::Error),
+}
+
+/// Copy a save file into the tank slot at `dest`. The copy is removed again if
+/// it doesn't read as a tank, so a bad file never leaves a broken slot behind.
+pub fn import_tank_file(src: &Path, dest: &Path) -> std::result::Result<TankSummary, ImportError> {
+    if dest.exists() {
+        return Err(ImportError::Exists);
+    }
+    std::fs::copy(src, dest).map_err(ImportError::Io)?;
+    let summary = open_db_readonly(dest).and_then(|conn| tank_summary(&conn));
+    summary.map_err(|e| {
+        std::fs::remove_file(dest).ok();
+        ImportError::NotATank(e)
+    })
+}
+
+/// Picker details for a saved tank
+#[derive(Debug, Clone, Serialize)]
+pub struct TankMetadata {
+    pub created_at: String,
+    /// Last save, i.e. when the tank was last played
+    pub last_saved_at: String,
+    pub description: String,
+    /// Mean hue of the living fish, for tinting the tank's thumbnail
+    pub thumbnail_hue: Option<f32>,
+}
+
+/// Works on tanks whose schema predates descriptions (read-only connections can't migrate)
+pub fn tank_metadata(conn: &Connection) -> Result<TankMetadata> {
+    let (created_at, last_saved_at): (String, String) = conn.query_row(
+        "SELECT created_at, last_saved_at FROM aquarium WHERE id = 1", [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let description: String = conn.query_row("SELECT description FROM aquarium WHERE id = 1", [], |row| row.get(0))
+        .unwrap_or_default();
+    let mut stmt = conn.prepare(
+        "SELECT g.base_hue FROM fish f JOIN genomes g ON g.id = f.genome_id WHERE f.is_alive = 1"
+    )?;
+    let hues: Vec<f64> = stmt.query_map([], |row| row.get(0))?.collect::<Result<_>>()?;
+    let thumbnail_hue = if hues.is_empty() {
+        None
+    } else {
+        Some(circular_mean(hues.iter().map(|&h| h as f32)))
+    };
+    Ok(TankMetadata { created_at, last_saved_at, description, thumbnail_hue })
+}
+
+/// Inconsistencies that loading would silently drop or paper over, by id
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IntegrityReport {
+    /// SQLite's own `quick_check` verdict, "ok" when the file itself is sound
+    pub sqlite_check: String,
+    /// Living fish whose genome row is missing
+    pub orphan_fish: Vec<u32>,
+    /// Living fish with an unrecognized behavior_state
+    pub invalid_behavior: Vec<u32>,
+    /// Eggs whose genome row is missing
+    pub orphan_eggs: Vec<u32>,
+    /// Living species that no living fish is close enough to belong to
+    pub memberless_species: Vec<u32>,
+    /// Species rows missing a centroid column, which `load_state` can't read
+    pub incomplete_species: Vec<u32>,
+    /// species_snapshots rows naming a species that isn't stored
+    pub orphan_species_snapshots: u32,
+    /// Decorations outside the tank bounds or with a non-positive scale
+    pub misplaced_decorations: Vec<u32>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.sqlite_check == "ok"
+            && self.orphan_fish.is_empty()
+            && self.invalid_behavior.is_empty()
+            && self.orphan_eggs.is_empty()
+            && self.memberless_species.is_empty()
+            && self.incomplete_species.is_empty()
+            && self.orphan_species_snapshots == 0
+            && self.misplaced_decorations.is_empty()
+    }
+}
+
+/// Cross-table consistency checks for a saved tank. Read-only; decorations
+/// are checked against the given tank size.
+pub fn check_integrity(conn: &Connection, tank_width: f32, tank_height: f32) -> Result<IntegrityReport> {
+    let sqlite_check: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+    let ids = |sql: &str| -> Result<Vec<u32>> {
+        let mut stmt = conn.prepare(sql)?;
+        let found = stmt.query_map([], |row| row.get(0))?.collect::<Result<_>>()?;
+        Ok(found)
+    };
+    let orphan_fish = ids("SELECT id FROM fish WHERE is_alive = 1 AND genome_id NOT IN (SELECT id FROM genomes) ORDER BY id")?;
+    let orphan_eggs = ids("SELECT id FROM eggs WHERE genome_id NOT IN (SELECT id FROM genomes) ORDER BY id")?;
+
+    let mut stmt = conn.prepare("SELECT id, behavior_state FROM fish WHERE is_alive = 1 ORDER BY id")?;
+    let behaviors: Vec<(u32, String)> = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<_>>()?;
+    let invalid_behavior = behaviors.into_iter()
+        .filter(|(_, b)| BehaviorState::from_str(b).is_none())
+        .map(|(id, _)| id)
+        .collect();
+
+    let mut stmt = conn.prepare(
+        "SELECT g.base_hue, g.speed, g.body_length FROM fish f JOIN genomes g ON g.id = f.genome_id WHERE f.is_alive = 1"
+    )?;
+    let living: Vec<(f32, f32, f32)> = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?.collect::<Result<_>>()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, centroid_hue, centroid_speed, centroid_size FROM species WHERE extinct_at_tick IS NULL ORDER BY id"
+    )?;
+    let species: Vec<(u32, Option<f32>, Option<f32>, Option<f32>)> =
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?.collect::<Result<_>>()?;
+    let memberless_species = species.into_iter()
+        .filter(|&(_, hue, speed, size)| match (hue, speed, size) {
+            (Some(ch), Some(cs), Some(cz)) => !living.iter().any(|&(h, s, z)| near_centroid(h, s, z, ch, cs, cz)),
+            _ => true,
+        })
+        .map(|(id, ..)| id)
+        .collect();
+    let incomplete_species = ids(
+        "SELECT id FROM species WHERE centroid_hue IS NULL OR centroid_speed IS NULL
+            OR centroid_size IS NULL OR centroid_pattern IS NULL ORDER BY id"
+    )?;
+
+    let orphan_species_snapshots = conn.query_row(
+        "SELECT COUNT(*) FROM species_snapshots WHERE species_id NOT IN (SELECT id FROM species)", [],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare("SELECT id, position_x, position_y, scale FROM decorations ORDER BY id")?;
+    let decorations: Vec<(u32, f32, f32, f32)> =
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?.collect::<Result<_>>()?;
+    let misplaced_decorations = decorations.into_iter()
+        .filter(|&(_, x, y, scale)| !((0.0..=tank_width).contains(&x) && (0.0..=tank_height).contains(&y) && scale > 0.0))
+        .map(|(id, ..)| id)
+        .collect();
+
+    Ok(IntegrityReport {
+        sqlite_check,
+        orphan_fish,
+        invalid_behavior,
+        orphan_eggs,
+        memberless_species,
+        incomplete_species,
+        orphan_species_snapshots,
+        misplaced_decorations,
+    })
+}
+
+/// Fix everything `check_integrity` finds, in one transaction: orphaned fish,
+/// eggs and species snapshots are deleted, unknown behaviors reset to swimming,
+/// memberless species marked extinct at the saved tick, missing species
+/// centroid columns filled with neutral defaults, and decorations pulled
+/// back inside the tank. File-level damage in `sqlite_check` is reported but
+/// left alone. Returns what was found before repairing.
+pub fn repair_integrity(conn: &Connection, tank_width: f32, tank_height: f32) -> Result<IntegrityReport> {
+    let report = check_integrity(conn, tank_width, tank_height)?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM fish WHERE is_alive = 1 AND genome_id NOT IN (SELECT id FROM genomes)", [])?;
+    for id in &report.invalid_behavior {
+        tx.execute("UPDATE fish SET behavior_state = ?1 WHERE id = ?2", params![BehaviorState::Swimming.as_str(), id])?;
+    }
+    tx.execute("DELETE FROM eggs WHERE genome_id NOT IN (SELECT id FROM genomes)", [])?;
+    for id in &report.memberless_species {
+        tx.execute(
+            "UPDATE species SET extinct_at_tick = (SELECT tick_count FROM aquarium WHERE id = 1) WHERE id = ?1",
+            params![id],
+        )?;
+    }
+    for id in &report.incomplete_species {
+        tx.execute(
+            "UPDATE species SET centroid_hue = COALESCE(centroid_hue, 0.0), centroid_speed = COALESCE(centroid_speed, 1.0),
+                centroid_size = COALESCE(centroid_size, 1.0), centroid_pattern = COALESCE(centroid_pattern, 'Solid')
+                WHERE id = ?1",
+            params![id],
+        )?;
+    }
+    tx.execute("DELETE FROM species_snapshots WHERE species_id NOT IN (SELECT id FROM species)", [])?;
+    for id in &report.misplaced_decorations {
+        tx.execute(
+            "UPDATE decorations SET position_x = MIN(MAX(position_x, 0), ?1), position_y = MIN(MAX(position_y, 0), ?2),
+                scale = CASE WHEN scale > 0 THEN scale ELSE 1.0 END WHERE id = ?3",
+            params![tank_width, tank_height, id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(report)
+}
+
+pub fn save_tank_description(conn: &Connection, description: &str) -> Result<()> {
+    conn.execute("UPDATE aquarium SET description = ?1 WHERE id = 1", params![description])?;
+    Ok(())
+}
+
+/// `settings` key for the keeper's own free-text objective for the tank
+pub const TANK_GOAL_KEY: &str = "tank_goal";
+
+pub fn load_setting(conn: &Connection, key: &str) -> Result<Option<String>> {
+    conn.query_row("SELECT value FROM settings WHERE key = ?1", params![key], |row| row.get(0)).optional()
+}
+
+/// Store a per-tank setting; an empty value removes it
+pub fn save_setting(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    if value.is_empty() {
+        conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+    } else {
+        conn.execute("INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)", params![key, value])?;
+    }
+    Ok(())
+}
+
+/// Parent links for every stored genome
+pub fn genome_parent_links(conn: &Connection) -> Result<ParentLinks> {
+    let mut stmt = conn.prepare("SELECT id, parent_a, parent_b FROM genomes")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, u32>(0)?, (row.get(1)?, row.get(2)?))))?;
+    rows.collect()
+}
+
+/// Merge the event log and journal into a markdown chronicle ordered by tick,
+/// grouped into in-game days of `ticks_per_day` ticks
+pub fn build_chronicle(conn: &Connection, title: &str, ticks_per_day: u64) -> Result<String> {
+    // (tick, order within tick, line) — events come before the journal entry they prompted
+    let mut items: Vec<(i64, u8, String)> = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT e.tick, e.event_type, e.description, s.name FROM events e
+             LEFT JOIN species s ON s.id = e.subject_species_id
+             ORDER BY e.tick, e.id"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, Option<String>>(3)?))
+        })?;
+        for r in rows {
+            let (tick, etype, desc, species_name) = r?;
+            let line = match species_name {
+                Some(name) => format!("- **{}** — {} ({})", chronicle_label(&etype), desc, name),
+                None => format!("- **{}** — {}", chronicle_label(&etype), desc),
+            };
+            items.push((tick, 0, line));
+        }
+    }
+    {
+        let mut stmt = conn.prepare("SELECT tick, entry_text FROM journal_entries ORDER BY tick, id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+        for r in rows {
+            let (tick, text) = r?;
+            let quoted: Vec<String> = text.trim().lines().map(|l| format!("> {}", l)).collect();
+            items.push((tick, 1, format!("\n{}\n", quoted.join("\n"))));
+        }
+    }
+    items.sort_by_key(|(tick, order, _)| (*tick, *order));
+
+    let mut out = format!("# The Chronicle of {}\n", title);
+    if items.is_empty() {
+        out.push_str("\nNothing has happened yet.\n");
+    }
+    let ticks_per_day = ticks_per_day.max(1) as i64;
+    let mut current_day = None;
+    for (tick, _, line) in items {
+        let day = tick / ticks_per_day + 1;
+        if current_day != Some(day) {
+            out.push_str(&format!("\n## Day {}\n\n", day));
+            current_day = Some(day);
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn chronicle_label(event_type: &str) -> String {
+    match event_type {
+        "new_species" => "New species".to_string(),
+        "population_warning" => "Warning".to_string(),
+        other => {
+            let spaced = other.replace('_', " ");
+            let mut chars = spaced.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+    }
+}
+
 pub fn save_snapshot(
     conn: &Connection,
     tick: u64,
@@ -513,11 +1190,11 @@ pub fn save_snapshot(
     genetic_diversity: f32,
 ) -> Result<()> {
     let (avg_hue, avg_speed, avg_size, avg_aggression, avg_metabolism,
-         avg_boldness, avg_school_affinity, avg_disease_resistance,
+         avg_boldness, avg_school_affinity, avg_disease_resistance, avg_temp_tolerance,
          min_speed, max_speed, min_size, max_size) = if !fish.is_empty() {
         let mut sin_sum = 0.0_f32; let mut cos_sum = 0.0_f32;
         let mut sp = 0.0_f32; let mut sz = 0.0_f32; let mut ag = 0.0_f32; let mut met = 0.0_f32;
-        let mut bold = 0.0_f32; let mut school = 0.0_f32; let mut disease_r = 0.0_f32;
+        let mut bold = 0.0_f32; let mut school = 0.0_f32; let mut disease_r = 0.0_f32; let mut temp_t = 0.0_f32;
         let mut sp_min = f32::MAX; let mut sp_max = f32::MIN;
         let mut sz_min = f32::MAX; let mut sz_max = f32::MIN;
         let mut count = 0_u32;
@@ -528,6 +1205,7 @@ pub fn save_snapshot(
                 cos_sum += rad.cos();
                 sp += g.speed; sz += g.body_length; ag += g.aggression; met += g.metabolism;
                 bold += g.boldness; school += g.school_affinity; disease_r += g.disease_resistance;
+                temp_t += g.temp_tolerance;
                 if g.speed < sp_min { sp_min = g.speed; }
                 if g.speed > sp_max { sp_max = g.speed; }
                 if g.body_length < sz_min { sz_min = g.body_length; }
@@ -538,10 +1216,10 @@ pub fn save_snapshot(
         let n = count.max(1) as f32;
         let avg_h = sin_sum.atan2(cos_sum).to_degrees().rem_euclid(360.0);
         if count == 0 { sp_min = 0.0; sp_max = 0.0; sz_min = 0.0; sz_max = 0.0; }
-        (avg_h, sp/n, sz/n, ag/n, met/n, bold/n, school/n, disease_r/n,
+        (avg_h, sp/n, sz/n, ag/n, met/n, bold/n, school/n, disease_r/n, temp_t/n,
          sp_min, sp_max, sz_min, sz_max)
     } else {
-        (0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+        (0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
     };
 
     conn.execute(
@@ -549,14 +1227,14 @@ pub fn save_snapshot(
             avg_hue, avg_speed, avg_size, avg_aggression, avg_metabolism,
             births_since_last, deaths_since_last,
             avg_boldness, avg_school_affinity, avg_disease_resistance,
-            min_speed, max_speed, min_size, max_size, genetic_diversity)
-         VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19)",
+            min_speed, max_speed, min_size, max_size, genetic_diversity, avg_temp_tolerance)
+         VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20)",
         params![
             tick as i64, population, species_count, water_quality,
             avg_hue, avg_speed, avg_size, avg_aggression, avg_metabolism,
             births, deaths,
             avg_boldness, avg_school_affinity, avg_disease_resistance,
-            min_speed, max_speed, min_size, max_size, genetic_diversity,
+            min_speed, max_speed, min_size, max_size, genetic_diversity, avg_temp_tolerance,
         ],
     )?;
     Ok(())
@@ -592,6 +1270,83 @@ pub fn get_species_snapshots(conn: &Connection) -> Vec<(i64, u32, String, u32)>
     results
 }
 
+/// Population in the latest snapshot at or before `tick`
+pub fn population_at_or_before(conn: &Connection, tick: u64) -> Result<Option<u32>> {
+    conn.query_row(
+        "SELECT population FROM population_snapshots WHERE tick <= ?1 ORDER BY tick DESC LIMIT 1",
+        params![tick as i64],
+        |row| row.get::<_, u32>(0),
+    ).optional()
+}
+
+/// Tick of the population snapshot closest to `tick` (the earlier on a tie)
+pub fn nearest_snapshot_tick(conn: &Connection, tick: u64) -> Result<Option<u64>> {
+    conn.query_row(
+        "SELECT tick FROM population_snapshots ORDER BY ABS(tick - ?1), tick LIMIT 1",
+        params![tick as i64],
+        |row| row.get::<_, i64>(0),
+    ).optional().map(|t| t.map(|t| t as u64))
+}
+
+/// (species_id, species_name, population) recorded at exactly `tick`
+pub fn species_snapshot_at(conn: &Connection, tick: u64) -> Vec<(u32, String, u32)> {
+    let mut results = Vec::new();
+    if let Ok(mut stmt) = conn.prepare(
+        "SELECT species_id, species_name, population FROM species_snapshots WHERE tick = ?1 ORDER BY population DESC"
+    ) {
+        if let Ok(rows) = stmt.query_map(params![tick as i64], |row| {
+            Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?, row.get::<_, u32>(2)?))
+        }) {
+            for r in rows.flatten() { results.push(r); }
+        }
+    }
+    results
+}
+
+/// Downsample population snapshots older than `before_tick` to one row per
+/// `bucket_ticks` window. The latest row in each window is kept and absorbs the
+/// births/deaths of the rows dropped around it; species snapshots at dropped
+/// ticks go with them. Safe to run repeatedly. Returns the number of rows removed.
+pub fn rollup_snapshots(conn: &Connection, before_tick: u64, bucket_ticks: u64) -> Result<usize> {
+    if bucket_ticks == 0 {
+        return Ok(0);
+    }
+    let rows: Vec<(i64, i64, u32, u32)> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, tick, births_since_last, deaths_since_last FROM population_snapshots
+             WHERE tick < ?1 ORDER BY tick ASC, id ASC"
+        )?;
+        let mapped = stmt.query_map(params![before_tick as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?;
+        mapped.collect::<Result<_>>()?
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    let mut removed = 0;
+    for bucket in rows.chunk_by(|a, b| a.1 as u64 / bucket_ticks == b.1 as u64 / bucket_ticks) {
+        let (keep_id, keep_tick, _, _) = bucket[bucket.len() - 1];
+        if bucket.len() < 2 {
+            continue;
+        }
+        let births: u32 = bucket.iter().map(|r| r.2).sum();
+        let deaths: u32 = bucket.iter().map(|r| r.3).sum();
+        tx.execute(
+            "UPDATE population_snapshots SET births_since_last = ?1, deaths_since_last = ?2 WHERE id = ?3",
+            params![births, deaths, keep_id],
+        )?;
+        for &(id, tick, _, _) in &bucket[..bucket.len() - 1] {
+            tx.execute("DELETE FROM population_snapshots WHERE id = ?1", params![id])?;
+            if tick != keep_tick {
+                tx.execute("DELETE FROM species_snapshots WHERE tick = ?1", params![tick])?;
+            }
+            removed += 1;
+        }
+    }
+    tx.commit()?;
+    Ok(removed)
+}
+
 fn serialize_pattern(p: &PatternGene) -> (String, Option<String>) {
     match p {
         PatternGene::Solid => ("solid".to_string(), None),
@@ -629,3 +1384,620 @@ fn extract_f32(json: Option<&str>, key: &str) -> Option<f32> {
     let v: serde_json::Value = serde_json::from_str(s).ok()?;
     v.get(key)?.as_f64().map(|f| f as f32)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn saved_decorations_replace_the_old_set() {
+        use crate::simulation::ecosystem::DecorationType;
+        let conn = test_db();
+        let deco = |id, decoration_type| Decoration { id, decoration_type, x: 100.0, y: 500.0, scale: 1.0, flip_x: id % 2 == 0 };
+        save_decorations(&conn, &[deco(1, DecorationType::Rock), deco(2, DecorationType::Coral)]).unwrap();
+        save_decorations(&conn, &[deco(7, DecorationType::TallPlant)]).unwrap();
+
+        let rows: Vec<(u32, String)> = conn.prepare("SELECT id, decoration_type FROM decorations").unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?))).unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(rows, vec![(7, "tall_plant".to_string())]);
+        save_decorations(&conn, &[]).unwrap();
+        let left: u32 = conn.query_row("SELECT COUNT(*) FROM decorations", [], |r| r.get(0)).unwrap();
+        assert_eq!(left, 0);
+    }
+
+    // --- TankSummary ---
+
+    #[test]
+    fn summary_of_fresh_tank() {
+        let conn = test_db();
+        let s = tank_summary(&conn).unwrap();
+        assert_eq!(s.tick, 0);
+        assert_eq!(s.population, 0);
+        assert_eq!(s.species_count, 0);
+        assert_eq!(s.max_generation, 0);
+        assert!((s.water_quality - 1.0).abs() < 0.01);
+        assert_eq!(s.genetic_diversity, 0.0);
+    }
+
+    #[test]
+    fn summary_counts_saved_fish() {
+        let conn = test_db();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut genomes = HashMap::new();
+        let mut fish = Vec::new();
+        for i in 0..5 {
+            let mut g = FishGenome::random(&mut rng);
+            g.generation = i;
+            fish.push(Fish::new(g.id, 100.0, 100.0, &mut rng));
+            genomes.insert(g.id, g);
+        }
+        save_state(&conn, 500, 0.8, 0, &fish, &genomes, &[], &[]).unwrap();
+        save_snapshot(&conn, 500, 5, 0, 0.8, &genomes, &fish, 0, 0, 0.7).unwrap();
+
+        let s = tank_summary(&conn).unwrap();
+        assert_eq!(s.tick, 500);
+        assert_eq!(s.population, 5);
+        assert_eq!(s.max_generation, 4);
+        assert!((s.water_quality - 0.8).abs() < 0.01);
+        assert!((s.genetic_diversity - 0.7).abs() < 0.01);
+    }
+
+    #[test]
+    fn metadata_reports_description_and_fish_hue() {
+        let conn = test_db();
+        let meta = tank_metadata(&conn).unwrap();
+        assert_eq!(meta.description, "");
+        assert_eq!(meta.thumbnail_hue, None, "No fish, no hue");
+        assert!(!meta.created_at.is_empty());
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut genomes = HashMap::new();
+        let mut fish = Vec::new();
+        // Hues either side of 0 average to red, not cyan
+        for hue in [350.0, 10.0] {
+            let mut g = FishGenome::random(&mut rng);
+            g.base_hue = hue;
+            fish.push(Fish::new(g.id, 100.0, 100.0, &mut rng));
+            genomes.insert(g.id, g);
+        }
+        save_state(&conn, 10, 1.0, 0, &fish, &genomes, &[], &[]).unwrap();
+        save_tank_description(&conn, "Cichlid breeding project").unwrap();
+
+        let meta = tank_metadata(&conn).unwrap();
+        assert_eq!(meta.description, "Cichlid breeding project");
+        let hue = meta.thumbnail_hue.unwrap();
+        assert!(!(1.0..359.0).contains(&hue), "Got {}", hue);
+    }
+
+    #[test]
+    fn settings_roundtrip_and_clear() {
+        let conn = test_db();
+        assert_eq!(load_setting(&conn, TANK_GOAL_KEY).unwrap(), None);
+        save_setting(&conn, TANK_GOAL_KEY, "Breed an all-gold line").unwrap();
+        save_setting(&conn, TANK_GOAL_KEY, "Breed an all-gold line, fast").unwrap();
+        assert_eq!(load_setting(&conn, TANK_GOAL_KEY).unwrap().as_deref(), Some("Breed an all-gold line, fast"));
+        save_setting(&conn, TANK_GOAL_KEY, "").unwrap();
+        assert_eq!(load_setting(&conn, TANK_GOAL_KEY).unwrap(), None);
+    }
+
+    #[test]
+    fn metadata_of_tank_without_description_column() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("
+            CREATE TABLE aquarium (
+                id INTEGER PRIMARY KEY DEFAULT 1,
+                last_saved_at TEXT NOT NULL DEFAULT (datetime('now')),
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            INSERT INTO aquarium (id) VALUES (1);
+            CREATE TABLE fish (id INTEGER PRIMARY KEY, genome_id INTEGER, is_alive INTEGER);
+            CREATE TABLE genomes (id INTEGER PRIMARY KEY, base_hue REAL);
+        ").unwrap();
+        assert_eq!(tank_metadata(&conn).unwrap().description, "");
+    }
+
+    #[test]
+    fn summary_fails_without_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert!(tank_summary(&conn).is_err());
+    }
+
+    #[test]
+    fn readonly_open_of_missing_file_fails() {
+        let path = std::env::temp_dir().join("deeptank_missing_tank_for_test.db");
+        let _ = std::fs::remove_file(&path);
+        assert!(open_db_readonly(&path).is_err());
+        assert!(!path.exists(), "Read-only open must not create the file");
+    }
+
+    fn import_paths(tag: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+        let dir = std::env::temp_dir();
+        let paths = (dir.join(format!("deeptank_import_src_{}.db", tag)), dir.join(format!("deeptank_import_dest_{}.db", tag)));
+        let _ = std::fs::remove_file(&paths.0);
+        let _ = std::fs::remove_file(&paths.1);
+        paths
+    }
+
+    #[test]
+    fn import_copies_a_valid_save() {
+        let (src, dest) = import_paths("valid");
+        {
+            let conn = Connection::open(&src).unwrap();
+            init_schema(&conn).unwrap();
+            save_state(&conn, 120, 0.8, 0, &[], &HashMap::new(), &[], &[]).unwrap();
+        }
+        let summary = import_tank_file(&src, &dest).unwrap();
+        assert_eq!(summary.tick, 120);
+        assert!(dest.exists());
+        assert!(src.exists(), "The original is left in place");
+        let _ = std::fs::remove_file(&src);
+        let _ = std::fs::remove_file(&dest);
+    }
+
+    #[test]
+    fn import_rejects_non_tank_files_and_cleans_up() {
+        let (src, dest) = import_paths("junk");
+        std::fs::write(&src, b"definitely not sqlite").unwrap();
+        assert!(matches!(import_tank_file(&src, &dest), Err(ImportError::NotATank(_))));
+        assert!(!dest.exists(), "The rejected copy is removed");
+
+        let missing = std::env::temp_dir().join("deeptank_import_missing_src.db");
+        let _ = std::fs::remove_file(&missing);
+        assert!(matches!(import_tank_file(&missing, &dest), Err(ImportError::Io(_))));
+        let _ = std::fs::remove_file(&src);
+    }
+
+    #[test]
+    fn import_refuses_an_existing_destination() {
+        let (src, dest) = import_paths("taken");
+        std::fs::write(&src, b"whatever").unwrap();
+        std::fs::write(&dest, b"existing tank").unwrap();
+        assert!(matches!(import_tank_file(&src, &dest), Err(ImportError::Exists)));
+        assert_eq!(std::fs::read(&dest).unwrap(), b"existing tank", "The existing tank is untouched");
+        let _ = std::fs::remove_file(&src);
+        let _ = std::fs::remove_file(&dest);
+    }
+
+    // --- Death stats ---
+
+    fn insert_death(conn: &Connection, tick: i64, cause: Option<&str>, desc: &str) {
+        conn.execute(
+            "INSERT INTO events (tick, event_type, subject_fish_id, description, death_cause) VALUES (?1, 'death', 1, ?2, ?3)",
+            params![tick, desc, cause],
+        ).unwrap();
+    }
+
+    #[test]
+    fn death_counts_by_cause_in_range() {
+        let conn = test_db();
+        insert_death(&conn, 10, Some("starvation"), "");
+        insert_death(&conn, 20, Some("starvation"), "");
+        insert_death(&conn, 30, Some("predation"), "");
+        insert_death(&conn, 500, Some("old_age"), "");
+
+        let counts = death_counts(&conn, 0, 100).unwrap();
+        let get = |name: &str| counts.iter().find(|(c, _)| c == name).map(|(_, n)| *n);
+        assert_eq!(get("starvation"), Some(2));
+        assert_eq!(get("predation"), Some(1));
+        assert_eq!(get("old_age"), Some(0), "Out-of-range deaths excluded but cause still listed");
+        assert_eq!(get("poor_water"), Some(0));
+    }
+
+    #[test]
+    fn death_counts_keeps_unrecognized_causes() {
+        let conn = test_db();
+        insert_death(&conn, 5, Some("disease"), "");
+        insert_death(&conn, 6, None, "");
+        let counts = death_counts(&conn, 0, 10).unwrap();
+        assert!(counts.contains(&("disease".to_string(), 1)));
+        assert!(counts.contains(&("unknown".to_string(), 1)));
+    }
+
+    #[test]
+    fn death_cause_backfilled_from_description() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("
+            CREATE TABLE events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                tick INTEGER NOT NULL,
+                event_type TEXT NOT NULL,
+                subject_fish_id INTEGER,
+                subject_species_id INTEGER,
+                description TEXT NOT NULL,
+                timestamp TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            INSERT INTO events (tick, event_type, description) VALUES (1, 'death', 'Fish #3 (genome 3) died: PoorWater');
+            INSERT INTO events (tick, event_type, description) VALUES (2, 'birth', 'Fish #4 born');
+        ").unwrap();
+        init_schema(&conn).unwrap();
+
+        let counts = death_counts(&conn, 0, 10).unwrap();
+        assert!(counts.contains(&("poor_water".to_string(), 1)));
+        assert_eq!(counts.iter().map(|(_, n)| n).sum::<u32>(), 1);
+    }
+
+    // --- Survivorship ---
+
+    fn insert_aged_death(conn: &Connection, age_fraction: Option<f64>) {
+        conn.execute(
+            "INSERT INTO events (tick, event_type, subject_fish_id, description, death_cause, death_age_fraction) VALUES (1, 'death', 1, '', 'old_age', ?1)",
+            params![age_fraction],
+        ).unwrap();
+    }
+
+    #[test]
+    fn survivorship_curve_declines_with_age() {
+        let conn = test_db();
+        for f in [0.05, 0.1, 0.55, 0.9, 1.2] {
+            insert_aged_death(&conn, Some(f));
+        }
+        insert_aged_death(&conn, None);
+
+        let curve = survivorship_curve(&conn, 4).unwrap();
+        assert_eq!(curve.len(), 5);
+        assert_eq!(curve[0], (0.0, 1.0, 2));
+        assert!((curve[1].1 - 0.6).abs() < 1e-9, "Two of five died before a quarter of their lifespan");
+        assert_eq!(curve[2].2, 1);
+        assert_eq!(curve[4].2, 1, "Outliving the nominal lifespan lands in the last bucket");
+        assert_eq!(curve.iter().map(|c| c.2).sum::<u32>(), 5, "Ageless deaths are skipped");
+    }
+
+    #[test]
+    fn survivorship_curve_empty_without_ages() {
+        let conn = test_db();
+        insert_death(&conn, 1, Some("predation"), "");
+        assert!(survivorship_curve(&conn, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn selection_samples_skip_old_and_untracked_deaths() {
+        let conn = test_db();
+        let insert = |tick: i64, speed: Option<f64>, reproduced: Option<i32>| {
+            conn.execute(
+                "INSERT INTO events (tick, event_type, description, death_speed, death_body_length, death_aggression, death_reproduced) VALUES (?1, 'death', '', ?2, 1.0, 0.5, ?3)",
+                params![tick, speed, reproduced],
+            ).unwrap();
+        };
+        insert(50, Some(1.0), Some(1));
+        insert(200, Some(1.5), Some(1));
+        insert(300, Some(0.8), Some(0));
+        insert(400, Some(1.2), None);
+        insert(500, None, Some(0));
+
+        let samples = death_selection_samples(&conn, 100).unwrap();
+        assert_eq!(samples.len(), 2);
+        assert!(samples.contains(&([1.5, 1.0, 0.5], true)));
+        assert!(samples.contains(&([0.8, 1.0, 0.5], false)));
+    }
+
+    fn death(speed: Option<f32>) -> SimEvent {
+        SimEvent::Death {
+            fish_id: 3, genome_id: 8, cause: DeathCause::Starvation, custom_name: None, is_favorite: false,
+            age: 900, age_fraction: 0.4, speed, body_length: speed.map(|_| 1.2), aggression: speed.map(|_| 0.3),
+            reproduced: false,
+        }
+    }
+
+    #[test]
+    fn logged_deaths_carry_age_and_traits() {
+        let conn = test_db();
+        log_event(&conn, 70, &death(Some(1.5))).unwrap();
+        let row: (String, String, i64, f64, f64, f64, i32) = conn.query_row(
+            "SELECT event_type, death_cause, death_age, death_speed, death_body_length, death_aggression, death_reproduced FROM events",
+            [], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?, r.get(5)?, r.get(6)?)),
+        ).unwrap();
+        assert_eq!(row.0, "death");
+        assert_eq!(row.1, "starvation");
+        assert_eq!(row.2, 900);
+        assert!((row.3 - 1.5).abs() < 1e-6 && (row.4 - 1.2).abs() < 1e-6 && (row.5 - 0.3).abs() < 1e-6);
+        assert_eq!(row.6, 0);
+    }
+
+    #[test]
+    fn deaths_without_a_genome_store_null_traits() {
+        let conn = test_db();
+        log_event(&conn, 70, &death(None)).unwrap();
+        log_event(&conn, 71, &SimEvent::FeedingDrop { x: 1.0, y: 2.0 }).unwrap();
+        let traits: (Option<f64>, Option<f64>, Option<f64>, Option<i64>) = conn.query_row(
+            "SELECT death_speed, death_body_length, death_aggression, death_age FROM events", [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        ).unwrap();
+        assert_eq!(traits, (None, None, None, Some(900)), "Only the unknown traits are NULL");
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM events", [], |r| r.get(0)).unwrap();
+        assert_eq!(count, 1, "Feeding drops aren't logged");
+        assert!(death_selection_samples(&conn, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn schema_migration_adds_death_columns_to_old_events_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("
+            CREATE TABLE events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                tick INTEGER NOT NULL,
+                event_type TEXT NOT NULL,
+                subject_fish_id INTEGER,
+                subject_species_id INTEGER,
+                description TEXT NOT NULL,
+                timestamp TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            INSERT INTO events (tick, event_type, description) VALUES (5, 'death', 'Fish #1 (genome 1) died: OldAge');
+        ").unwrap();
+        init_schema(&conn).unwrap();
+        let old: (Option<f64>, Option<f64>, Option<f64>, Option<i32>) = conn.query_row(
+            "SELECT death_speed, death_body_length, death_aggression, death_reproduced FROM events", [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        ).unwrap();
+        assert_eq!(old, (None, None, None, None), "Existing rows are left untracked");
+        log_event(&conn, 6, &death(Some(1.0))).unwrap();
+        assert_eq!(death_selection_samples(&conn, 0).unwrap().len(), 1);
+    }
+
+    // --- Conservation ---
+
+    #[test]
+    fn conservation_programs_roundtrip() {
+        let conn = test_db();
+        let mut rng = StdRng::seed_from_u64(42);
+        let founder = FishGenome::random(&mut rng);
+        let mate = FishGenome::random(&mut rng);
+        let kid = FishGenome::inherit(&founder, &mate, &mut rng, 0.0, genome::MutationRates::NONE, genome::MutationRates::NONE, &[]);
+        let mut reg = ConservationRegistry::new();
+        reg.protect(&founder, &HashMap::new());
+        reg.register_child(&kid);
+
+        save_conservation(&conn, &reg).unwrap();
+        let loaded = load_conservation(&conn);
+        assert_eq!(loaded.founder_for(kid.id).map(|g| g.base_hue), Some(founder.base_hue));
+        assert!(!loaded.is_protected(mate.id));
+
+        reg.unprotect(founder.id);
+        save_conservation(&conn, &reg).unwrap();
+        assert!(!load_conservation(&conn).is_protected(kid.id));
+    }
+
+    // --- Chronicle ---
+
+    #[test]
+    fn chronicle_interleaves_events_and_journal_by_day() {
+        let conn = test_db();
+        conn.execute("INSERT INTO species (id, name, discovered_at_tick) VALUES (3, 'Glimmerfin', 50)", []).unwrap();
+        conn.execute("INSERT INTO events (tick, event_type, description) VALUES (10, 'birth', 'Fish #1 born')", []).unwrap();
+        conn.execute(
+            "INSERT INTO events (tick, event_type, subject_species_id, description) VALUES (150, 'new_species', 3, 'New species #3 discovered')", [],
+        ).unwrap();
+        conn.execute("INSERT INTO journal_entries (tick, entry_text) VALUES (150, 'A shimmer in the reeds.')", []).unwrap();
+        conn.execute("INSERT INTO journal_entries (tick, entry_text) VALUES (40, 'Quiet waters.')", []).unwrap();
+
+        let text = build_chronicle(&conn, "Reef", 100).unwrap();
+        assert!(text.starts_with("# The Chronicle of Reef"));
+        let pos = |needle: &str| text.find(needle).unwrap_or_else(|| panic!("missing {:?} in\n{}", needle, text));
+        assert!(pos("## Day 1") < pos("**Birth** — Fish #1 born"));
+        assert!(pos("Fish #1 born") < pos("> Quiet waters."));
+        assert!(pos("> Quiet waters.") < pos("## Day 2"));
+        assert!(pos("**New species** — New species #3 discovered (Glimmerfin)") < pos("> A shimmer"));
+    }
+
+    #[test]
+    fn chronicle_of_empty_tank() {
+        let conn = test_db();
+        let text = build_chronicle(&conn, "Empty", 100).unwrap();
+        assert!(text.contains("Nothing has happened yet."));
+        assert!(!text.contains("## Day"));
+    }
+
+    // --- Snapshot rollup ---
+
+    fn snapshot_rows(conn: &Connection) -> Vec<(i64, u32, u32)> {
+        let mut stmt = conn.prepare(
+            "SELECT tick, births_since_last, deaths_since_last FROM population_snapshots ORDER BY tick"
+        ).unwrap();
+        stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?))).unwrap().map(|r| r.unwrap()).collect()
+    }
+
+    fn fill_snapshots(conn: &Connection, ticks: impl Iterator<Item = u64>) {
+        let genomes = HashMap::new();
+        for t in ticks {
+            save_snapshot(conn, t, 10, 1, 1.0, &genomes, &[], 1, 2, 0.5).unwrap();
+            conn.execute(
+                "INSERT INTO species_snapshots (tick, species_id, species_name, population) VALUES (?1, 1, 'a', 10)",
+                params![t as i64],
+            ).unwrap();
+        }
+    }
+
+    #[test]
+    fn rollup_downsamples_old_snapshots_only() {
+        let conn = test_db();
+        // 0, 300, ..., 5700: 20 rows, the last 4 newer than the cutoff
+        fill_snapshots(&conn, (0..20).map(|i| i * 300));
+        let removed = rollup_snapshots(&conn, 4800, 3000).unwrap();
+        assert_eq!(removed, 14);
+
+        let rows = snapshot_rows(&conn);
+        let ticks: Vec<i64> = rows.iter().map(|r| r.0).collect();
+        assert_eq!(ticks, vec![2700, 4500, 4800, 5100, 5400, 5700]);
+        // Births/deaths of dropped rows fold into the survivor
+        assert_eq!((rows[0].1, rows[0].2), (10, 20));
+        assert_eq!((rows[1].1, rows[1].2), (6, 12));
+        assert_eq!((rows[2].1, rows[2].2), (1, 2));
+
+        let species_rows: i64 = conn.query_row("SELECT COUNT(*) FROM species_snapshots", [], |r| r.get(0)).unwrap();
+        assert_eq!(species_rows, 6);
+    }
+
+    #[test]
+    fn rollup_is_idempotent() {
+        let conn = test_db();
+        fill_snapshots(&conn, (0..20).map(|i| i * 300));
+        rollup_snapshots(&conn, 6000, 3000).unwrap();
+        let once = snapshot_rows(&conn);
+        assert_eq!(rollup_snapshots(&conn, 6000, 3000).unwrap(), 0);
+        assert_eq!(snapshot_rows(&conn), once);
+    }
+
+    #[test]
+    fn nearest_snapshot_picks_closest_tick() {
+        let conn = test_db();
+        assert_eq!(nearest_snapshot_tick(&conn, 100).unwrap(), None);
+
+        fill_snapshots(&conn, [300, 600, 900].into_iter());
+        assert_eq!(nearest_snapshot_tick(&conn, 0).unwrap(), Some(300));
+        assert_eq!(nearest_snapshot_tick(&conn, 700).unwrap(), Some(600));
+        assert_eq!(nearest_snapshot_tick(&conn, 750).unwrap(), Some(600), "Ties go to the earlier snapshot");
+        assert_eq!(nearest_snapshot_tick(&conn, 5000).unwrap(), Some(900));
+        assert_eq!(species_snapshot_at(&conn, 600), vec![(1, "a".to_string(), 10)]);
+        assert!(species_snapshot_at(&conn, 601).is_empty());
+        assert_eq!(population_at_or_before(&conn, 299).unwrap(), None);
+        assert_eq!(population_at_or_before(&conn, 650).unwrap(), Some(10));
+    }
+
+    // --- Genome round-trip ---
+
+    #[test]
+    fn noise_seed_unset_until_saved() {
+        let conn = test_db();
+        assert_eq!(load_noise_seed(&conn), None);
+        save_noise_seed(&conn, 3_000_000_000).unwrap();
+        assert_eq!(load_noise_seed(&conn), Some(3_000_000_000));
+    }
+
+    #[test]
+    fn rng_state_unset_until_saved() {
+        let conn = test_db();
+        assert_eq!(load_rng_state(&conn), None);
+        save_rng_state(&conn, "chacha12:00:0:0").unwrap();
+        assert_eq!(load_rng_state(&conn).as_deref(), Some("chacha12:00:0:0"));
+    }
+
+    #[test]
+    fn gape_size_persists() {
+        let conn = test_db();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut g = FishGenome::random(&mut rng);
+        g.gape_size = 1.27;
+        let fish = vec![Fish::new(g.id, 100.0, 100.0, &mut rng)];
+        let genomes = HashMap::from([(g.id, g.clone())]);
+        save_state(&conn, 10, 1.0, 0, &fish, &genomes, &[], &[]).unwrap();
+
+        let (_, _, _, loaded, _, _, _) = load_state(&conn).unwrap().unwrap();
+        assert!((loaded[&g.id].gape_size - 1.27).abs() < 1e-5);
+    }
+
+    #[test]
+    fn temp_tolerance_persists() {
+        let conn = test_db();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut g = FishGenome::random(&mut rng);
+        g.temp_tolerance = 0.83;
+        let fish = vec![Fish::new(g.id, 100.0, 100.0, &mut rng)];
+        let genomes = HashMap::from([(g.id, g.clone())]);
+        save_state(&conn, 10, 1.0, 0, &fish, &genomes, &[], &[]).unwrap();
+        save_snapshot(&conn, 10, 1, 1, 1.0, &genomes, &fish, 0, 0, 0.5).unwrap();
+
+        let (_, _, _, loaded, _, _, _) = load_state(&conn).unwrap().unwrap();
+        assert!((loaded[&g.id].temp_tolerance - 0.83).abs() < 1e-5);
+        let avg: f64 = conn.query_row("SELECT avg_temp_tolerance FROM population_snapshots", [], |r| r.get(0)).unwrap();
+        assert!((avg - 0.83).abs() < 1e-5);
+    }
+
+    #[test]
+    fn fossil_genome_persists() {
+        let conn = test_db();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut fossil = FishGenome::random(&mut rng);
+        fossil.id = 0;
+        fossil.speed = 1.42;
+        let species = |extinct: Option<u64>, fossil: Option<FishGenome>| Species {
+            id: 4, name: Some("Ashfin".to_string()), description: None, discovered_at_tick: 20,
+            extinct_at_tick: extinct, centroid_hue: 10.0, centroid_speed: 1.4, centroid_size: 1.0,
+            centroid_pattern: "Solid".to_string(), member_count: 3, member_genome_ids: Vec::new(),
+            distinct_marker: None, fossil,
+        };
+
+        save_state(&conn, 10, 1.0, 0, &[], &HashMap::new(), &[species(None, None)], &[]).unwrap();
+        assert!(extinct_species_profile(&conn, 4).unwrap().is_none(), "Living species have no fossil");
+
+        save_state(&conn, 90, 1.0, 0, &[], &HashMap::new(), &[species(Some(80), Some(fossil))], &[]).unwrap();
+        let profile = extinct_species_profile(&conn, 4).unwrap().unwrap();
+        assert_eq!((profile.name.as_deref(), profile.discovered_at_tick, profile.extinct_at_tick), (Some("Ashfin"), 20, 80));
+        assert!((profile.genome.speed - 1.42).abs() < 1e-5);
+        let (_, _, _, _, loaded, _, _) = load_state(&conn).unwrap().unwrap();
+        assert!(loaded[0].fossil.is_some());
+        assert!(extinct_species_profile(&conn, 99).unwrap().is_none());
+    }
+
+    #[test]
+    fn quarantine_flag_persists() {
+        let conn = test_db();
+        let mut rng = StdRng::seed_from_u64(7);
+        let g = FishGenome::random(&mut rng);
+        let mut fish = vec![
+            Fish::new(g.id, 100.0, 100.0, &mut rng),
+            Fish::new(g.id, 200.0, 100.0, &mut rng),
+        ];
+        fish[0].quarantine();
+        let genomes = HashMap::from([(g.id, g)]);
+        save_state(&conn, 10, 1.0, 0, &fish, &genomes, &[], &[]).unwrap();
+
+        let (_, _, loaded, _, _, _, _) = load_state(&conn).unwrap().unwrap();
+        let quarantined: Vec<bool> = loaded.iter().map(|f| f.is_quarantined).collect();
+        assert_eq!(quarantined, vec![true, false]);
+    }
+
+    #[test]
+    fn integrity_check_finds_and_repairs_inconsistencies() {
+        let conn = test_db();
+        let mut rng = StdRng::seed_from_u64(7);
+        let g = FishGenome::random(&mut rng);
+        let fish = vec![Fish::new(g.id, 100.0, 100.0, &mut rng), Fish::new(g.id, 200.0, 100.0, &mut rng)];
+        let species = Species {
+            id: 4, name: None, description: None, discovered_at_tick: 20, extinct_at_tick: None,
+            centroid_hue: g.base_hue, centroid_speed: g.speed, centroid_size: g.body_length,
+            centroid_pattern: "Solid".to_string(), member_count: 2, member_genome_ids: Vec::new(),
+            distinct_marker: None, fossil: None,
+        };
+        save_state(&conn, 50, 1.0, 0, &fish, &HashMap::from([(g.id, g.clone())]), &[species], &[]).unwrap();
+        assert!(check_integrity(&conn, 800.0, 600.0).unwrap().is_clean());
+
+        conn.execute_batch(&format!("
+            UPDATE fish SET behavior_state = 'sulking' WHERE id = {};
+            INSERT INTO fish (id, genome_id, position_x, position_y, position_z, velocity_x, velocity_y, heading)
+                VALUES (9001, 424242, 10, 10, 0, 0, 0, 0);
+            INSERT INTO species (id, discovered_at_tick, centroid_hue, centroid_speed, centroid_size)
+                VALUES (5, 30, {}, 9.0, 9.0);
+            INSERT INTO species_snapshots (tick, species_id, population) VALUES (40, 77, 3);
+            INSERT INTO decorations (id, decoration_type, position_x, position_y, scale) VALUES (1, 'rock', 950, 300, 1.0);
+        ", fish[1].id, g.base_hue)).unwrap();
+
+        let report = check_integrity(&conn, 800.0, 600.0).unwrap();
+        assert_eq!(report.sqlite_check, "ok");
+        assert_eq!(report.orphan_fish, vec![9001]);
+        assert_eq!(report.invalid_behavior, vec![fish[1].id]);
+        assert_eq!(report.memberless_species, vec![5]);
+        assert_eq!(report.incomplete_species, vec![5]);
+        assert_eq!(report.orphan_species_snapshots, 1);
+        assert_eq!(report.misplaced_decorations, vec![1]);
+        assert!(!report.is_clean());
+
+        repair_integrity(&conn, 800.0, 600.0).unwrap();
+        assert!(check_integrity(&conn, 800.0, 600.0).unwrap().is_clean());
+        let extinct: Option<i64> = conn.query_row("SELECT extinct_at_tick FROM species WHERE id = 5", [], |r| r.get(0)).unwrap();
+        assert_eq!(extinct, Some(50));
+        let x: f64 = conn.query_row("SELECT position_x FROM decorations WHERE id = 1", [], |r| r.get(0)).unwrap();
+        assert_eq!(x, 800.0);
+        let pattern: String = conn.query_row("SELECT centroid_pattern FROM species WHERE id = 5", [], |r| r.get(0)).unwrap();
+        assert_eq!(pattern, "Solid");
+        let (_, _, loaded, _, loaded_species, _, _) = load_state(&conn).unwrap().unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded_species.len(), 2);
+    }
+}
@@ -0,0 +1,207 @@
+use super::config::SimulationConfig;
+use super::ecosystem::{self, Decoration, Egg, Species};
+use super::fish::{self, Fish};
+use super::genome::{self, FishGenome};
+use super::SimulationState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Marks a JSON document as a DeepTank live-state export
+pub const PORTABLE_FORMAT: &str = "deeptank-state";
+/// Bumped when a change to the document can't be read by older builds
+pub const PORTABLE_VERSION: u32 = 1;
+
+/// The whole live tank as one self-describing JSON document. Unlike the
+/// SQLite export it doesn't depend on the table layout, so it diffs cleanly
+/// and survives schema changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortableState {
+    pub format: String,
+    pub version: u32,
+    pub tick: u64,
+    pub time_of_day: f32,
+    pub water_quality: f32,
+    pub currency: u32,
+    pub noise_seed: u32,
+    /// Every config field; unknown keys are skipped on import
+    pub config: serde_json::Value,
+    pub fish: Vec<Fish>,
+    /// Sorted by id so exports of the same tank diff line by line
+    pub genomes: Vec<FishGenome>,
+    pub species: Vec<Species>,
+    /// Fossil genome of each extinct species by species id; `Species` leaves
+    /// them out of its own serialization to keep frames small
+    #[serde(default)]
+    pub fossils: BTreeMap<u32, FishGenome>,
+    pub decorations: Vec<Decoration>,
+    pub eggs: Vec<Egg>,
+}
+
+impl PortableState {
+    pub fn capture(sim: &SimulationState) -> Self {
+        let mut genomes: Vec<FishGenome> = sim.genomes.values().cloned().collect();
+        genomes.sort_by_key(|g| g.id);
+        Self {
+            format: PORTABLE_FORMAT.to_string(),
+            version: PORTABLE_VERSION,
+            tick: sim.tick,
+            time_of_day: sim.time_of_day,
+            water_quality: sim.ecosystem.water_quality,
+            currency: sim.currency,
+            noise_seed: sim.noise_seed,
+            config: serde_json::to_value(&sim.config).unwrap_or_default(),
+            fish: sim.fish.clone(),
+            genomes,
+            species: sim.ecosystem.species.clone(),
+            fossils: sim.ecosystem.species.iter()
+                .filter_map(|s| Some((s.id, s.fossil.clone()?)))
+                .collect(),
+            decorations: sim.ecosystem.decorations.clone(),
+            eggs: sim.ecosystem.eggs.clone(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let doc: Self = serde_json::from_str(json).map_err(|e| format!("Not a valid state export: {}", e))?;
+        if doc.format != PORTABLE_FORMAT {
+            return Err(format!("Expected format \"{}\", got \"{}\"", PORTABLE_FORMAT, doc.format));
+        }
+        if doc.version > PORTABLE_VERSION {
+            return Err(format!("State export version {} is newer than this build supports", doc.version));
+        }
+        Ok(doc)
+    }
+
+    /// Build a fresh simulation from the document and move the global id
+    /// counters past everything in it. Config keys go through `set_value`, so
+    /// a shared file gets the same bounds as the settings panel; keys it
+    /// rejects (unknown, malformed or not user-settable) are returned and
+    /// keep their defaults, as do settings missing from the document.
+    pub fn restore(self) -> Result<(SimulationState, Vec<String>), String> {
+        let mut config = SimulationConfig::default();
+        let mut skipped = Vec::new();
+        if let serde_json::Value::Object(fields) = self.config {
+            for (key, value) in fields {
+                if config.set_value(&key, value).is_err() {
+                    skipped.push(key);
+                }
+            }
+        }
+        let mut sim = SimulationState::new();
+        sim.config = config;
+        sim.tick = self.tick;
+        sim.time_of_day = self.time_of_day;
+        sim.currency = self.currency;
+        sim.set_noise_seed(self.noise_seed);
+        sim.fish = self.fish;
+        sim.genomes = self.genomes.into_iter().map(|g| (g.id, g)).collect();
+        sim.ecosystem.water_quality = self.water_quality;
+        sim.ecosystem.species = self.species;
+        let mut fossils = self.fossils;
+        for s in &mut sim.ecosystem.species {
+            s.fossil = fossils.remove(&s.id);
+        }
+        sim.ecosystem.decorations = self.decorations;
+        sim.ecosystem.eggs = self.eggs;
+
+        let max_species_id = sim.ecosystem.species.iter().map(|s| s.id).max().unwrap_or(0);
+        sim.ecosystem.restore_species_counter(max_species_id + 1);
+        sim.ecosystem.restore_speciation_tick(sim.tick);
+        let max_dec_id = sim.ecosystem.decorations.iter().map(|d| d.id).max().unwrap_or(0);
+        sim.ecosystem.restore_decoration_counter(max_dec_id + 1);
+        sim.ecosystem.recompute_plant_count();
+        fish::set_fish_id_counter(sim.fish.iter().map(|f| f.id).max().unwrap_or(0) + 1);
+        ecosystem::set_egg_id_counter(sim.ecosystem.eggs.iter().map(|e| e.id).max().unwrap_or(0) + 1);
+        let max_genome_id = sim.genomes.keys().copied().max().unwrap_or(0);
+        genome::set_genome_id_counter(max_genome_id + 1);
+        Ok((sim, skipped))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_roundtrips_through_json() {
+        let mut sim = SimulationState::with_seed(11);
+        sim.step_n(50);
+        sim.config.hunger_rate = 0.002;
+        sim.currency = 42;
+
+        let json = PortableState::capture(&sim).to_json().unwrap();
+        let (restored, skipped) = PortableState::from_json(&json).unwrap().restore().unwrap();
+        assert!(!skipped.contains(&"hunger_rate".to_string()), "skipped {:?}", skipped);
+        assert_eq!(restored.tick, sim.tick);
+        assert_eq!(restored.currency, 42);
+        assert_eq!(restored.config.hunger_rate, 0.002);
+        assert_eq!(restored.fish.len(), sim.fish.len());
+        assert_eq!(restored.genomes.len(), sim.genomes.len());
+        assert_eq!(restored.noise_seed, sim.noise_seed);
+        assert_eq!(PortableState::capture(&restored).to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn rejects_foreign_or_newer_documents() {
+        let sim = SimulationState::with_seed(11);
+        let mut doc = PortableState::capture(&sim);
+        doc.format = "something-else".into();
+        assert!(PortableState::from_json(&doc.to_json().unwrap()).is_err());
+        doc.format = PORTABLE_FORMAT.into();
+        doc.version = PORTABLE_VERSION + 1;
+        assert!(PortableState::from_json(&doc.to_json().unwrap()).is_err());
+        assert!(PortableState::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn imported_config_is_clamped_like_the_settings_panel() {
+        let mut doc = PortableState::capture(&SimulationState::with_seed(11));
+        doc.config["max_lifespan"] = serde_json::json!(0);
+        doc.config["hunger_rate"] = serde_json::json!(1e9);
+        doc.config["tank_width"] = serde_json::json!(5.0);
+        let (restored, skipped) = doc.restore().unwrap();
+        assert_eq!(restored.config.max_lifespan, 1_000);
+        assert!(restored.config.hunger_rate < 1e9);
+        assert!(skipped.contains(&"tank_width".to_string()), "Tank size isn't user-settable");
+        assert_eq!(restored.config.tank_width, SimulationConfig::default().tank_width);
+    }
+
+    #[test]
+    fn fossils_survive_the_roundtrip() {
+        let mut sim = SimulationState::with_seed(11);
+        let mut fossil = sim.genomes.values().next().unwrap().clone();
+        fossil.speed = 1.42;
+        let species = |id: u32, fossil: Option<FishGenome>| Species {
+            id, name: None, description: None, discovered_at_tick: 0,
+            extinct_at_tick: Some(5), centroid_hue: 10.0, centroid_speed: 1.4, centroid_size: 1.0,
+            centroid_pattern: "Solid".to_string(), member_count: 0, member_genome_ids: Vec::new(),
+            distinct_marker: None, fossil,
+        };
+        let id = 4;
+        sim.ecosystem.species = vec![species(id, Some(fossil)), species(id + 1, None)];
+
+        let json = PortableState::capture(&sim).to_json().unwrap();
+        let (restored, _) = PortableState::from_json(&json).unwrap().restore().unwrap();
+        let species = restored.ecosystem.species.iter().find(|s| s.id == id).unwrap();
+        assert_eq!(species.fossil.as_ref().map(|g| g.speed), Some(1.42));
+        assert!(restored.ecosystem.species.iter().filter(|s| s.id != id).all(|s| s.fossil.is_none()));
+
+        // Documents from before fossils were exported still load
+        let mut old: serde_json::Value = serde_json::from_str(&json).unwrap();
+        old.as_object_mut().unwrap().remove("fossils");
+        assert!(PortableState::from_json(&old.to_string()).unwrap().restore().is_ok());
+    }
+
+    #[test]
+    fn unknown_config_keys_are_skipped() {
+        let mut doc = PortableState::capture(&SimulationState::with_seed(11));
+        doc.config["retired_setting"] = serde_json::json!(3);
+        let (_, skipped) = doc.restore().unwrap();
+        assert!(skipped.contains(&"retired_setting".to_string()));
+        assert!(!skipped.contains(&"drag".to_string()));
+    }
+}
@@ -0,0 +1,121 @@
+use super::config::SimulationConfig;
+use serde_json::json;
+
+/// A curated bundle of config values applied over the current config
+#[derive(Debug, Clone)]
+pub struct ConfigPreset {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// (key, value) pairs in `update_config` form
+    pub overrides: Vec<(&'static str, serde_json::Value)>,
+}
+
+impl ConfigPreset {
+    /// Apply this preset's values; untouched keys keep their current value
+    pub fn apply(&self, config: &mut SimulationConfig) -> Result<(), String> {
+        for (key, value) in &self.overrides {
+            config.set_value(key, value.clone())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn all_presets() -> Vec<ConfigPreset> {
+    let d = SimulationConfig::default();
+    vec![
+        ConfigPreset {
+            id: "easy",
+            name: "Peaceful",
+            description: "No disease, slow hunger, rare environmental events and automatic feeding.",
+            overrides: vec![
+                ("hunger_rate", json!(d.hunger_rate * 0.5)),
+                ("disease_enabled", json!(false)),
+                ("event_frequency", json!(0.3)),
+                ("auto_feed_enabled", json!(true)),
+                ("crowding_enabled", json!(false)),
+            ],
+        },
+        ConfigPreset {
+            id: "normal",
+            name: "Normal",
+            description: "The default balance of hunger, disease and events.",
+            overrides: vec![
+                ("hunger_rate", json!(d.hunger_rate)),
+                ("disease_enabled", json!(d.disease_enabled)),
+                ("disease_infection_chance", json!(d.disease_infection_chance)),
+                ("environmental_events_enabled", json!(d.environmental_events_enabled)),
+                ("event_frequency", json!(d.event_frequency)),
+                ("auto_feed_enabled", json!(d.auto_feed_enabled)),
+                ("crowding_enabled", json!(d.crowding_enabled)),
+                ("mutation_rate_small", json!(d.mutation_rate_small)),
+                ("mutation_rate_large", json!(d.mutation_rate_large)),
+            ],
+        },
+        ConfigPreset {
+            id: "hardcore",
+            name: "Harsh",
+            description: "Fast hunger, contagious disease, frequent events and no auto-feeder.",
+            overrides: vec![
+                ("hunger_rate", json!(d.hunger_rate * 2.0)),
+                ("disease_enabled", json!(true)),
+                ("disease_infection_chance", json!(0.5)),
+                ("environmental_events_enabled", json!(true)),
+                ("event_frequency", json!(2.0)),
+                ("auto_feed_enabled", json!(false)),
+                ("crowding_enabled", json!(true)),
+            ],
+        },
+        ConfigPreset {
+            id: "sandbox",
+            name: "Sandbox",
+            description: "Well-fed fish, no disease or events, and fast mutation for experimenting.",
+            overrides: vec![
+                ("hunger_rate", json!(d.hunger_rate * 0.25)),
+                ("disease_enabled", json!(false)),
+                ("environmental_events_enabled", json!(false)),
+                ("auto_feed_enabled", json!(true)),
+                ("mutation_rate_small", json!(0.25)),
+                ("mutation_rate_large", json!(0.08)),
+            ],
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::config::ConfigUpdate;
+
+    #[test]
+    fn presets_use_known_keys() {
+        for p in all_presets() {
+            for (key, value) in &p.overrides {
+                let mut c = SimulationConfig::default();
+                assert_eq!(c.set_value(key, value.clone()), Ok(ConfigUpdate::Accepted), "Preset {} key {}", p.id, key);
+            }
+        }
+    }
+
+    #[test]
+    fn preset_applies_over_current_config() {
+        let mut c = SimulationConfig { separation_weight: 3.0, ..SimulationConfig::default() };
+        let harsh = all_presets().into_iter().find(|p| p.id == "hardcore").unwrap();
+        harsh.apply(&mut c).unwrap();
+        assert!(c.disease_enabled);
+        assert!((c.hunger_rate - SimulationConfig::default().hunger_rate * 2.0).abs() < 1e-9);
+        assert_eq!(c.separation_weight, 3.0, "Keys outside the preset are untouched");
+    }
+
+    #[test]
+    fn normal_undoes_other_presets() {
+        let mut c = SimulationConfig::default();
+        let presets = all_presets();
+        presets.iter().find(|p| p.id == "sandbox").unwrap().apply(&mut c).unwrap();
+        presets.iter().find(|p| p.id == "normal").unwrap().apply(&mut c).unwrap();
+        let d = SimulationConfig::default();
+        assert_eq!(c.hunger_rate, d.hunger_rate);
+        assert_eq!(c.mutation_rate_small, d.mutation_rate_small);
+        assert_eq!(c.environmental_events_enabled, d.environmental_events_enabled);
+    }
+}
@@ -0,0 +1,72 @@
+use rand_chacha::ChaCha12Rng;
+
+/// The simulation's generator. The same ChaCha12 stream as `StdRng`, but its
+/// position can be read back, so a saved tank continues the exact sequence.
+pub type SimRng = ChaCha12Rng;
+
+const CHECKPOINT_PREFIX: &str = "chacha12";
+
+/// The generator's full state as `chacha12:<seed hex>:<stream>:<word position>`
+pub fn checkpoint(rng: &SimRng) -> String {
+    let seed: String = rng.get_seed().iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}:{}:{}:{}", CHECKPOINT_PREFIX, seed, rng.get_stream(), rng.get_word_pos())
+}
+
+/// Rebuild a generator from `checkpoint`, positioned where it was taken
+pub fn from_checkpoint(checkpoint: &str) -> Result<SimRng, String> {
+    let invalid = || format!("Invalid RNG checkpoint: {}", checkpoint);
+    let parts: Vec<&str> = checkpoint.trim().split(':').collect();
+    let [prefix, seed_hex, stream, word_pos] = parts[..] else {
+        return Err(invalid());
+    };
+    // Check the bytes before slicing so multi-byte characters can't split a pair
+    if prefix != CHECKPOINT_PREFIX || seed_hex.len() != 64 || !seed_hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let mut seed = [0u8; 32];
+    for (i, byte) in seed.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&seed_hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    let mut rng = <SimRng as rand::SeedableRng>::from_seed(seed);
+    rng.set_stream(stream.parse().map_err(|_| invalid())?);
+    rng.set_word_pos(word_pos.parse().map_err(|_| invalid())?);
+    Ok(rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn checkpoint_resumes_the_sequence() {
+        let mut rng = SimRng::seed_from_u64(9);
+        for _ in 0..37 {
+            rng.gen::<u32>();
+        }
+        let mut resumed = from_checkpoint(&checkpoint(&rng)).unwrap();
+        let ahead: Vec<u64> = (0..10).map(|_| rng.gen()).collect();
+        let replay: Vec<u64> = (0..10).map(|_| resumed.gen()).collect();
+        assert_eq!(ahead, replay);
+    }
+
+    #[test]
+    fn matches_std_rng_for_the_same_seed() {
+        let mut sim = SimRng::seed_from_u64(5);
+        let mut std = rand::rngs::StdRng::seed_from_u64(5);
+        assert_eq!(sim.gen::<u64>(), std.gen::<u64>());
+    }
+
+    #[test]
+    fn rejects_malformed_checkpoints() {
+        assert!(from_checkpoint("").is_err());
+        assert!(from_checkpoint("pcg:00:0:0").is_err());
+        let good = checkpoint(&SimRng::seed_from_u64(1));
+        assert!(from_checkpoint(&good.replace(":0:", ":x:")).is_err());
+        assert!(from_checkpoint(&format!("chacha12:zz{}", &good[11..])).is_err());
+        // 64 bytes, but "é" is two of them and straddles a hex pair
+        let seed = format!("0{}{}", "é", "0".repeat(61));
+        assert_eq!(seed.len(), 64);
+        assert!(from_checkpoint(&format!("chacha12:{}:0:0", seed)).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +11,8 @@ pub struct Scenario {
     pub initial_fish_count: u32,
     /// Config overrides as (key, value) pairs applied on top of defaults
     pub config_overrides: Vec<(&'static str, f32)>,
+    /// Start from `SimulationState::with_seed` so every player gets the same tank
+    pub seed: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +52,7 @@ pub fn all_scenarios() -> Vec<Scenario> {
                 ("hunger_rate", 0.001),
                 ("auto_feed_enabled", 0.0),
             ],
+            seed: None,
         },
         Scenario {
             id: "apex_predator",
@@ -59,6 +64,7 @@ pub fn all_scenarios() -> Vec<Scenario> {
             ],
             initial_fish_count: 15,
             config_overrides: vec![],
+            seed: None,
         },
         Scenario {
             id: "biodiversity",
@@ -72,6 +78,7 @@ pub fn all_scenarios() -> Vec<Scenario> {
             config_overrides: vec![
                 ("mutation_rate_large", 0.05),
             ],
+            seed: None,
         },
         Scenario {
             id: "peaceful_kingdom",
@@ -82,6 +89,7 @@ pub fn all_scenarios() -> Vec<Scenario> {
             ],
             initial_fish_count: 20,
             config_overrides: vec![],
+            seed: None,
         },
         Scenario {
             id: "ice_age",
@@ -90,10 +98,78 @@ pub fn all_scenarios() -> Vec<Scenario> {
             goals: vec![ScenarioGoal::ReachGeneration(50)],
             initial_fish_count: 15,
             config_overrides: vec![],
+            seed: None,
         },
     ]
 }
 
+/// Scenario ids of daily challenges are this prefix plus the ISO date
+pub const DAILY_PREFIX: &str = "daily-";
+
+/// Look up a scenario by id, including dated daily challenges
+pub fn find_scenario(id: &str) -> Option<Scenario> {
+    if let Some(date) = id.strip_prefix(DAILY_PREFIX) {
+        let date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+        return Some(daily_challenge(date));
+    }
+    all_scenarios().into_iter().find(|s| s.id == id)
+}
+
+/// Scenario id of the daily challenge for `date`
+pub fn daily_challenge_id(date: chrono::NaiveDate) -> String {
+    format!("{}{}", DAILY_PREFIX, date.format("%Y-%m-%d"))
+}
+
+/// Tank seed for a date. Mixed with splitmix64 so consecutive days don't
+/// produce similar-looking seeds.
+pub fn daily_seed(date: chrono::NaiveDate) -> u64 {
+    use chrono::Datelike;
+    let mut z = (date.year() as u64) * 10_000 + date.month() as u64 * 100 + date.day() as u64;
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// The shared challenge for a date: the same seeded tank, goals and
+/// overrides for everyone who plays that day
+pub fn daily_challenge(date: chrono::NaiveDate) -> Scenario {
+    let seed = daily_seed(date);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut pool = vec![
+        ScenarioGoal::ReachPopulation(rng.gen_range(25..=45)),
+        ScenarioGoal::ReachGeneration(rng.gen_range(10..=30)),
+        ScenarioGoal::ReachSpeciesCount(rng.gen_range(3..=5)),
+        ScenarioGoal::DiversityAbove((rng.gen_range(0.4..0.7_f32) * 20.0).round() / 20.0),
+        ScenarioGoal::TraitAbove { trait_name: "speed".to_string(), value: (rng.gen_range(1.5..1.8_f32) * 20.0).round() / 20.0 },
+        ScenarioGoal::TraitAbove { trait_name: "body_length".to_string(), value: (rng.gen_range(1.5..1.8_f32) * 20.0).round() / 20.0 },
+        ScenarioGoal::TraitBelow { trait_name: "aggression".to_string(), value: (rng.gen_range(0.2..0.35_f32) * 20.0).round() / 20.0 },
+    ];
+    let mut goals = Vec::new();
+    for _ in 0..2 {
+        goals.push(pool.swap_remove(rng.gen_range(0..pool.len())));
+    }
+
+    let mut config_overrides = vec![
+        ("hunger_rate", rng.gen_range(3..=10) as f32 * 0.0001),
+        ("mutation_rate_large", rng.gen_range(1..=5) as f32 * 0.01),
+    ];
+    if rng.gen_bool(0.3) {
+        config_overrides.push(("auto_feed_enabled", 0.0));
+    }
+
+    Scenario {
+        id: "daily",
+        name: "Daily Challenge",
+        description: "Today's shared tank: every player starts from the same fish with the same goals.",
+        goals,
+        initial_fish_count: rng.gen_range(5..=15),
+        config_overrides,
+        seed: Some(seed),
+    }
+}
+
 /// Check if all goals of the active scenario are met.
 /// Returns a Vec of (goal_index, is_complete) pairs.
 pub fn check_goals(
@@ -137,7 +213,120 @@ pub fn check_goals(
     }).collect()
 }
 
-fn get_trait(g: &super::genome::FishGenome, name: &str) -> f32 {
+/// Projected time to finish an unmet goal at the recent rate of progress
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum GoalEta {
+    Complete,
+    OnTrack { ticks: u64 },
+    /// No measurable movement toward the target
+    Stalled,
+    /// Moving away from the target
+    Regressing,
+    /// Not enough history to tell yet
+    Unknown,
+}
+
+impl ScenarioGoal {
+    /// The value the goal's measure must pass
+    pub fn target(&self) -> f32 {
+        match self {
+            Self::ReachPopulation(n) | Self::ReachGeneration(n) | Self::ReachSpeciesCount(n) => *n as f32,
+            Self::SurviveTicks(n) => *n as f32,
+            Self::TraitAbove { value, .. } | Self::TraitBelow { value, .. } => *value,
+            Self::DiversityAbove(v) => *v,
+        }
+    }
+
+    /// Whether progress means the measure going up
+    pub fn rising(&self) -> bool {
+        !matches!(self, Self::TraitBelow { .. })
+    }
+
+    /// `population_snapshots` column that tracks this goal's measure over
+    /// time, where one exists. Trait goals follow the tank's extreme where it
+    /// is recorded and its average otherwise.
+    pub fn snapshot_metric(&self) -> Option<&'static str> {
+        match self {
+            Self::ReachPopulation(_) => Some("population"),
+            Self::ReachSpeciesCount(_) => Some("species_count"),
+            Self::DiversityAbove(_) => Some("genetic_diversity"),
+            Self::TraitAbove { trait_name, .. } | Self::TraitBelow { trait_name, .. } => match trait_name.as_str() {
+                "speed" => Some("max_speed"),
+                "size" | "body_length" => Some("max_size"),
+                "aggression" => Some("avg_aggression"),
+                "boldness" => Some("avg_boldness"),
+                "school_affinity" => Some("avg_school_affinity"),
+                "disease_resistance" => Some("avg_disease_resistance"),
+                "temp_tolerance" => Some("avg_temp_tolerance"),
+                _ => None,
+            },
+            Self::ReachGeneration(_) | Self::SurviveTicks(_) => None,
+        }
+    }
+}
+
+/// Current value of a goal's measure; trait goals use the most extreme living fish
+#[allow(clippy::too_many_arguments)]
+pub fn goal_value(
+    goal: &ScenarioGoal,
+    population: u32,
+    max_generation: u32,
+    species_count: u32,
+    tick: u64,
+    diversity: f32,
+    genomes: &std::collections::HashMap<u32, super::genome::FishGenome>,
+    fish: &[super::fish::Fish],
+) -> f32 {
+    match goal {
+        ScenarioGoal::ReachPopulation(_) => population as f32,
+        ScenarioGoal::ReachGeneration(_) => max_generation as f32,
+        ScenarioGoal::ReachSpeciesCount(_) => species_count as f32,
+        ScenarioGoal::SurviveTicks(_) => tick as f32,
+        ScenarioGoal::DiversityAbove(_) => diversity,
+        ScenarioGoal::TraitAbove { trait_name, .. } | ScenarioGoal::TraitBelow { trait_name, .. } => fish.iter()
+            .filter_map(|f| genomes.get(&f.genome_id))
+            .map(|g| get_trait(g, trait_name))
+            .fold(0.0, f32::max),
+    }
+}
+
+/// Least-squares slope per tick of (tick, value) samples; None with fewer
+/// than two distinct ticks
+pub fn trend_per_tick(samples: &[(u64, f32)]) -> Option<f32> {
+    if samples.len() < 2 {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let mean_t = samples.iter().map(|&(t, _)| t as f64).sum::<f64>() / n;
+    let mean_v = samples.iter().map(|&(_, v)| v as f64).sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for &(t, v) in samples {
+        let dt = t as f64 - mean_t;
+        cov += dt * (v as f64 - mean_v);
+        var += dt * dt;
+    }
+    (var > 0.0).then(|| (cov / var) as f32)
+}
+
+/// Project when `current` reaches `target` moving at `rate` per tick
+pub fn estimate_eta(complete: bool, current: f32, target: f32, rising: bool, rate: Option<f32>) -> GoalEta {
+    if complete {
+        return GoalEta::Complete;
+    }
+    let Some(rate) = rate else { return GoalEta::Unknown };
+    // Progress toward the target, per tick, whichever way the goal points
+    let (remaining, progress) = if rising { (target - current, rate) } else { (current - target, -rate) };
+    if progress.abs() < 1e-6 {
+        GoalEta::Stalled
+    } else if progress < 0.0 {
+        GoalEta::Regressing
+    } else {
+        GoalEta::OnTrack { ticks: (remaining.max(0.0) / progress).ceil() as u64 }
+    }
+}
+
+pub fn get_trait(g: &super::genome::FishGenome, name: &str) -> f32 {
     match name {
         "speed" => g.speed,
         "aggression" => g.aggression,
@@ -146,10 +335,44 @@ fn get_trait(g: &super::genome::FishGenome, name: &str) -> f32 {
         "metabolism" => g.metabolism,
         "size" | "body_length" => g.body_length,
         "disease_resistance" => g.disease_resistance,
+        "temp_tolerance" => g.temp_tolerance,
         _ => 0.0,
     }
 }
 
+/// Valid (min, max) of a trait name understood by `get_trait`
+pub fn trait_range(name: &str) -> Option<(f32, f32)> {
+    match name {
+        "speed" | "metabolism" => Some((0.5, 2.0)),
+        "aggression" | "boldness" | "school_affinity" | "disease_resistance" | "temp_tolerance" => Some((0.0, 1.0)),
+        "size" | "body_length" => Some((0.6, 2.0)),
+        _ => None,
+    }
+}
+
+/// Most bins a histogram may ask for; more would only allocate empty buckets
+pub const MAX_HISTOGRAM_BINS: u32 = 200;
+
+/// Count genomes per equal-width bin over the trait's valid range.
+/// Values at the top of the range fall in the last bin.
+pub fn trait_histogram<'a>(
+    genomes: impl Iterator<Item = &'a super::genome::FishGenome>,
+    trait_name: &str,
+    bins: u32,
+) -> Result<Vec<u32>, String> {
+    let (min, max) = trait_range(trait_name).ok_or_else(|| format!("Unknown trait: {}", trait_name))?;
+    if !(1..=MAX_HISTOGRAM_BINS).contains(&bins) {
+        return Err(format!("bins must be 1-{}, got {}", MAX_HISTOGRAM_BINS, bins));
+    }
+    let mut counts = vec![0u32; bins as usize];
+    for g in genomes {
+        let t = ((get_trait(g, trait_name) - min) / (max - min)).clamp(0.0, 1.0);
+        let idx = ((t * bins as f32) as usize).min(bins as usize - 1);
+        counts[idx] += 1;
+    }
+    Ok(counts)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,6 +418,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn daily_challenge_is_deterministic_per_date() {
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let next = day.succ_opt().unwrap();
+        let a = daily_challenge(day);
+        let b = daily_challenge(day);
+        assert_eq!(a.seed, b.seed);
+        assert_eq!(a.initial_fish_count, b.initial_fish_count);
+        assert_eq!(a.config_overrides, b.config_overrides);
+        let descs = |s: &Scenario| s.goals.iter().map(|g| g.description()).collect::<Vec<_>>();
+        assert_eq!(descs(&a), descs(&b));
+        assert_eq!(a.goals.len(), 2);
+        assert_ne!(a.seed, daily_challenge(next).seed);
+
+        let id = daily_challenge_id(day);
+        assert_eq!(id, "daily-2026-03-14");
+        assert_eq!(find_scenario(&id).unwrap().seed, a.seed);
+        assert!(find_scenario("daily-not-a-date").is_none());
+        assert_eq!(find_scenario("survival").unwrap().id, "survival");
+    }
+
     #[test]
     fn check_goals_population() {
         let scenario = &all_scenarios()[0]; // survival: ReachPopulation(30)
@@ -247,4 +491,63 @@ mod tests {
         let results = check_goals(scenario, 0, 0, 0, 0, 0.0, &genomes, &fish);
         assert!(!results[0].1, "Empty fish list should not meet TraitBelow");
     }
+
+    // --- Goal ETA ---
+
+    #[test]
+    fn trend_is_least_squares_slope() {
+        assert_eq!(trend_per_tick(&[(0, 5.0)]), None);
+        assert_eq!(trend_per_tick(&[(10, 5.0), (10, 6.0)]), None);
+        let slope = trend_per_tick(&[(0, 10.0), (100, 12.0), (200, 14.0)]).unwrap();
+        assert!((slope - 0.02).abs() < 1e-6);
+    }
+
+    #[test]
+    fn eta_follows_goal_direction() {
+        assert_eq!(estimate_eta(true, 0.0, 30.0, true, None), GoalEta::Complete);
+        assert_eq!(estimate_eta(false, 10.0, 30.0, true, None), GoalEta::Unknown);
+        assert_eq!(estimate_eta(false, 10.0, 30.0, true, Some(0.02)), GoalEta::OnTrack { ticks: 1000 });
+        assert_eq!(estimate_eta(false, 10.0, 30.0, true, Some(-0.02)), GoalEta::Regressing);
+        assert_eq!(estimate_eta(false, 10.0, 30.0, true, Some(0.0)), GoalEta::Stalled);
+        // Falling goals want a negative trend
+        assert_eq!(estimate_eta(false, 0.5, 0.2, false, Some(-0.001)), GoalEta::OnTrack { ticks: 300 });
+        assert_eq!(estimate_eta(false, 0.5, 0.2, false, Some(0.001)), GoalEta::Regressing);
+    }
+
+    #[test]
+    fn every_goal_has_a_measure() {
+        for s in all_scenarios() {
+            for g in &s.goals {
+                let tracked = g.snapshot_metric().is_some()
+                    || matches!(g, ScenarioGoal::ReachGeneration(_) | ScenarioGoal::SurviveTicks(_));
+                assert!(tracked, "{} has no way to estimate progress", g.description());
+            }
+        }
+    }
+
+    // --- Trait histogram ---
+
+    #[test]
+    fn histogram_buckets_over_trait_range() {
+        let mut rng = seeded_rng();
+        let genomes: Vec<FishGenome> = [0.0, 0.1, 0.55, 0.95, 1.0].iter().map(|&a| {
+            let mut g = FishGenome::random(&mut rng);
+            g.aggression = a;
+            g
+        }).collect();
+        let hist = trait_histogram(genomes.iter(), "aggression", 4).unwrap();
+        assert_eq!(hist, vec![2, 0, 1, 2]);
+        assert_eq!(hist.iter().sum::<u32>(), genomes.len() as u32);
+    }
+
+    #[test]
+    fn histogram_rejects_bad_input() {
+        let genomes: Vec<FishGenome> = Vec::new();
+        assert!(trait_histogram(genomes.iter(), "wingspan", 4).is_err());
+        assert!(trait_histogram(genomes.iter(), "speed", 0).is_err());
+        assert!(trait_histogram(genomes.iter(), "speed", MAX_HISTOGRAM_BINS + 1).is_err());
+        assert!(trait_histogram(genomes.iter(), "speed", u32::MAX).is_err());
+        assert_eq!(trait_histogram(genomes.iter(), "speed", MAX_HISTOGRAM_BINS).unwrap().len(), MAX_HISTOGRAM_BINS as usize);
+        assert_eq!(trait_histogram(genomes.iter(), "size", 3).unwrap(), vec![0, 0, 0]);
+    }
 }
@@ -0,0 +1,156 @@
+use crate::simulation::ecosystem::SimEvent;
+use serde::Serialize;
+
+/// Events queued beyond this are dropped oldest first, so an unreachable
+/// endpoint can't grow the queue without bound
+pub const MAX_PENDING: usize = 100;
+
+/// One significant happening, as posted to the webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub tick: u64,
+    pub kind: &'static str,
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    /// Births, deaths, speciation and extinction are forwarded; everything
+    /// else is too frequent or too minor to be worth a request
+    pub fn from_sim_event(tick: u64, ev: &SimEvent) -> Option<Self> {
+        let kind = match ev {
+            SimEvent::Birth { .. } => "birth",
+            SimEvent::Death { .. } => "death",
+            SimEvent::NewSpecies { .. } => "new_species",
+            SimEvent::Extinction { .. } => "extinction",
+            _ => return None,
+        };
+        // Unwrap the externally tagged variant so `data` holds just its fields
+        let data = match serde_json::to_value(ev).ok()? {
+            serde_json::Value::Object(map) => map.into_iter().next().map(|(_, v)| v)?,
+            other => other,
+        };
+        Some(Self { tick, kind, data })
+    }
+
+    pub fn achievement(tick: u64, name: &str) -> Self {
+        Self { tick, kind: "achievement", data: serde_json::json!({ "name": name }) }
+    }
+}
+
+/// A throttled POST body: everything that happened since the last one
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookBatch {
+    pub tick: u64,
+    pub events: Vec<WebhookEvent>,
+    pub dropped: u32,
+}
+
+/// Collects events between posts so the endpoint sees at most one request
+/// per interval, however busy the tank is
+#[derive(Debug, Default)]
+pub struct WebhookQueue {
+    pending: Vec<WebhookEvent>,
+    dropped: u32,
+    last_sent_tick: Option<u64>,
+}
+
+impl WebhookQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, ev: WebhookEvent) {
+        if self.pending.len() >= MAX_PENDING {
+            self.pending.remove(0);
+            self.dropped += 1;
+        }
+        self.pending.push(ev);
+    }
+
+    /// The batch to post now, if anything is waiting and at least
+    /// `interval` ticks have passed since the last post
+    pub fn take_due(&mut self, tick: u64, interval: u32) -> Option<WebhookBatch> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        if let Some(last) = self.last_sent_tick {
+            if tick.saturating_sub(last) < interval as u64 {
+                return None;
+            }
+        }
+        self.last_sent_tick = Some(tick);
+        Some(WebhookBatch {
+            tick,
+            events: std::mem::take(&mut self.pending),
+            dropped: std::mem::take(&mut self.dropped),
+        })
+    }
+}
+
+/// POST a batch as JSON. A failed post is not retried; a stream overlay
+/// would rather miss events than replay stale ones.
+pub async fn post(url: &str, batch: &WebhookBatch) {
+    let client = reqwest::Client::new();
+    let _ = client
+        .post(url)
+        .json(batch)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::ecosystem::DeathCause;
+
+    fn birth(fish_id: u32) -> SimEvent {
+        SimEvent::Birth { fish_id, genome_id: 1, parent_a: 2, parent_b: 3 }
+    }
+
+    #[test]
+    fn only_significant_events_are_forwarded() {
+        let ev = WebhookEvent::from_sim_event(10, &birth(7)).unwrap();
+        assert_eq!(ev.kind, "birth");
+        assert_eq!(ev.data["fish_id"], 7);
+
+        let death = SimEvent::Death {
+            fish_id: 4, genome_id: 1, cause: DeathCause::OldAge, custom_name: None, is_favorite: false,
+            age: 100, age_fraction: 1.0, speed: Some(1.0), body_length: Some(1.0), aggression: Some(0.2), reproduced: true,
+        };
+        assert_eq!(WebhookEvent::from_sim_event(10, &death).unwrap().kind, "death");
+        assert_eq!(WebhookEvent::from_sim_event(10, &SimEvent::NewSpecies { species_id: 2 }).unwrap().data["species_id"], 2);
+        assert!(WebhookEvent::from_sim_event(10, &SimEvent::FeedingDrop { x: 1.0, y: 2.0 }).is_none());
+        assert!(WebhookEvent::from_sim_event(10, &SimEvent::Predation { predator_id: 1, prey_id: 2 }).is_none());
+    }
+
+    #[test]
+    fn queue_batches_and_throttles() {
+        let mut q = WebhookQueue::new();
+        assert!(q.take_due(0, 150).is_none());
+
+        q.push(WebhookEvent::from_sim_event(1, &birth(1)).unwrap());
+        q.push(WebhookEvent::achievement(1, "First Birth"));
+        let batch = q.take_due(1, 150).unwrap();
+        assert_eq!(batch.events.len(), 2);
+        assert_eq!(batch.events[1].kind, "achievement");
+
+        q.push(WebhookEvent::from_sim_event(50, &birth(2)).unwrap());
+        assert!(q.take_due(50, 150).is_none());
+        let batch = q.take_due(151, 150).unwrap();
+        assert_eq!(batch.events.len(), 1);
+        assert!(q.take_due(400, 150).is_none());
+    }
+
+    #[test]
+    fn queue_drops_oldest_when_full() {
+        let mut q = WebhookQueue::new();
+        for i in 0..(MAX_PENDING as u32 + 5) {
+            q.push(WebhookEvent::from_sim_event(0, &birth(i)).unwrap());
+        }
+        let batch = q.take_due(0, 150).unwrap();
+        assert_eq!(batch.events.len(), MAX_PENDING);
+        assert_eq!(batch.dropped, 5);
+        assert_eq!(batch.events[0].data["fish_id"], 5);
+    }
+}
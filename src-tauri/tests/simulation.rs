@@ -0,0 +1,100 @@
+//! Whole-tank runs through the headless simulation API, checking emergent
+//! behavior rather than individual rules.
+
+use deeptank_lib::simulation::ecosystem::{DecorationType, FoodParticle, SimEvent};
+use deeptank_lib::simulation::genome::Sex;
+use deeptank_lib::simulation::SimulationState;
+
+/// A seeded tank planted densely enough that its starting fish don't foul the water
+fn planted_tank(seed: u64) -> SimulationState {
+    let mut sim = SimulationState::with_seed(seed);
+    let floor = sim.config.tank_height - 30.0;
+    for i in 0..12 {
+        let x = 60.0 + i as f32 * (sim.config.tank_width - 120.0) / 11.0;
+        sim.ecosystem.add_decoration(DecorationType::TallPlant, x, floor, 1.0, false);
+    }
+    sim
+}
+
+/// Replace the starting fish with adult clones of the first one, alternating
+/// sexes, so the whole tank is one interbreeding population
+fn founder_colony(seed: u64, size: u32) -> SimulationState {
+    let mut sim = planted_tank(seed);
+    let founder = sim.fish[0].id;
+    let lifespan = sim.config.max_lifespan;
+    let clones = sim.clone_fish(founder, size);
+    sim.fish.retain(|f| clones.contains(&f.id));
+    for (i, f) in sim.fish.iter_mut().enumerate() {
+        let g = sim.genomes.get_mut(&f.genome_id).unwrap();
+        g.sex = if i % 2 == 0 { Sex::Male } else { Sex::Female };
+        f.age = (lifespan as f32 * g.lifespan_factor * g.maturity_age) as u32 + 1;
+    }
+    sim
+}
+
+/// Run with a careful keeper who places a pellet at every peckish fish every
+/// 50 ticks, so nothing starves and no uneaten food fouls the water
+fn run_kept(sim: &mut SimulationState, ticks: u32) -> Vec<SimEvent> {
+    let mut events = Vec::new();
+    for _ in 0..ticks / 50 {
+        let pellets: Vec<FoodParticle> = sim.fish.iter()
+            .filter(|f| f.is_alive && f.hunger > 0.3)
+            .map(|f| FoodParticle::new(f.x, f.y))
+            .collect();
+        sim.ecosystem.food.extend(pellets);
+        events.extend(sim.step_n(50));
+    }
+    events
+}
+
+// --- Population ---
+
+#[test]
+fn kept_population_survives_10k_ticks() {
+    let mut sim = planted_tank(7);
+    run_kept(&mut sim, 10_000);
+    assert!(!sim.fish.is_empty(), "Population died out by tick {}", sim.tick);
+}
+
+#[test]
+fn unfed_tank_starves() {
+    let mut sim = planted_tank(7);
+    let events = sim.step_n(5_000);
+    assert!(sim.fish.is_empty());
+    assert!(events.iter().any(|e| matches!(e, SimEvent::Death { .. })));
+}
+
+// --- Breeding ---
+
+#[test]
+fn founder_colony_breeds_and_speciates_within_5000_ticks() {
+    let mut sim = founder_colony(11, 12);
+    let events = run_kept(&mut sim, 5_000);
+    assert!(events.iter().any(|e| matches!(e, SimEvent::Birth { .. })), "No births in 5000 ticks");
+    assert!(events.iter().any(|e| matches!(e, SimEvent::NewSpecies { .. })), "No species discovered in 5000 ticks");
+}
+
+// --- Invariants ---
+
+#[test]
+fn fish_stay_finite_and_in_bounds() {
+    let mut sim = planted_tank(3);
+    for _ in 0..6 {
+        run_kept(&mut sim, 500);
+        let (w, h) = (sim.config.tank_width, sim.config.tank_height);
+        for f in &sim.fish {
+            assert!(f.x.is_finite() && f.y.is_finite(), "Fish #{} at ({}, {})", f.id, f.x, f.y);
+            assert!((0.0..=w).contains(&f.x) && (0.0..=h).contains(&f.y), "Fish #{} escaped to ({}, {})", f.id, f.x, f.y);
+        }
+        assert!((0.0..=1.0).contains(&sim.ecosystem.water_quality));
+    }
+}
+
+#[test]
+fn same_seed_same_starting_tank() {
+    let a = SimulationState::with_seed(99);
+    let b = SimulationState::with_seed(99);
+    let hues = |s: &SimulationState| s.fish.iter().map(|f| s.genomes[&f.genome_id].base_hue).collect::<Vec<_>>();
+    assert_eq!(hues(&a), hues(&b));
+    assert_ne!(hues(&a), hues(&SimulationState::with_seed(100)));
+}
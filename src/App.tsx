@@ -1,7 +1,7 @@
 import { useRef, useEffect, useState, useCallback } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
-import type { FrameUpdate, FishGenome, FishDetail, Toast } from "./types";
+import type { FrameUpdate, FishGenome, FishDetail, Toast, ConfigUpdate } from "./types";
 import { CanvasRenderer, type ThemeName } from "./renderer/canvasRenderer";
 import { AudioEngine } from "./audio/audioEngine";
 import { Inspector } from "./components/Inspector";
@@ -25,26 +25,51 @@ const defaultSettings = {
   alignment_weight: 1.0,
   cohesion_weight: 1.0,
   wander_strength: 0.3,
+  school_size_strength: 0.5,
+  schooling_style_strength: 0.8,
+  schooling_mode: "mixed",
+  species_cohesion_bonus: 0.5,
+  absolute_max_speed: 5.0,
   hunger_rate: 0.0005,
+  forage_hunger_threshold: 0.6,
+  forage_stop_threshold: 0.3,
+  eat_hunger_threshold: 0.2,
   mutation_rate_small: 0.1,
   mutation_rate_large: 0.02,
+  appearance_mutation_rate_small: 0.1,
+  appearance_mutation_rate_large: 0.02,
+  genetic_drift_rate: 0.01,
+  max_lifespan: 20000,
+  senescence_onset: 0.7,
+  senescence_strength: 0.5,
   species_threshold: 2.5,
+  speciation_appearance_weight: 1.0,
+  speciation_behavior_weight: 1.0,
   day_night_cycle: true,
   day_night_speed: 1.0,
+  breeding_season: "always",
   bubble_rate: 1.0,
   current_strength: 0.0,
+  water_resilience_enabled: true,
+  water_resilience_strength: 4.0,
   auto_feed_enabled: false,
   auto_feed_interval: 600,
   auto_feed_amount: 4,
+  food_preference_enabled: true,
+  food_preference_bonus: 0.3,
+  food_preference_penalty: 0.3,
   ollama_enabled: true,
   ollama_url: "http://localhost:11434",
   ollama_model: "llama3.2",
+  webhook_url: "",
+  webhook_interval_ticks: 150,
   master_volume: 0.3,
   ambient_enabled: true,
   event_sounds_enabled: true,
   theme: "aquarium",
   environmental_events_enabled: true,
   event_frequency: 1.0,
+  max_concurrent_events: 1,
   territory_enabled: true,
   territory_claim_radius: 60,
   disease_enabled: false,
@@ -53,6 +78,24 @@ const defaultSettings = {
   disease_duration: 600,
   disease_damage: 0.0005,
   disease_spread_radius: 40.0,
+  immunity_gain: 0.5,
+  immunity_max: 0.9,
+  immunity_decay: 0.0,
+  carcass_decay_enabled: false,
+  carcass_decay_ticks: 900,
+  shelter_enabled: true,
+  shelter_seek_strength: 0.5,
+  shelter_strike_reduction: 0.6,
+  courtship_display_enabled: false,
+  courtship_min_condition: 0.4,
+  courtship_choosiness: 0.5,
+  courtship_conspicuousness: 1.5,
+  fin_nipping_enabled: true,
+  fin_nip_chance: 0.01,
+  fin_nip_damage: 0.03,
+  parental_care_enabled: true,
+  max_clutch_size: 3,
+  nest_guard_radius: 60,
 };
 
 function App() {
@@ -84,7 +127,7 @@ function App() {
   const toastId = useRef(0);
   const lastUiUpdate = useRef(0);
   const pendingGenomes = useRef(new Set<number>());
-  const lastActiveEvent = useRef<string | null>(null);
+  const lastActiveEvents = useRef<string[]>([]);
   const lowDiversityWarned = useRef(false);
   const [narrationText, setNarrationText] = useState<{ text: string; key: number } | null>(null);
   const narrationKey = useRef(0);
@@ -112,7 +155,9 @@ function App() {
 
     const handleResize = () => {
       renderer.resize();
-      invoke("update_tank_size", { width: window.innerWidth, height: window.innerHeight }).catch(() => {});
+      invoke<string | null>("update_tank_size", { width: window.innerWidth, height: window.innerHeight })
+        .then((warning) => { if (warning) console.warn(warning); })
+        .catch(() => {});
     };
     window.addEventListener("resize", handleResize);
     handleResize();
@@ -226,6 +271,11 @@ function App() {
         setFrame(f);
       }
 
+      const introduced = f.events.filter((ev) => "PredatorIntroduced" in ev).length;
+      if (introduced > 0) {
+        addToast(introduced === 1 ? "A predator has entered the tank" : `${introduced} predators have entered the tank`, "warning");
+      }
+
       for (const ev of f.events) {
         if ("NewSpecies" in ev) {
           addToast("New species detected!", "success");
@@ -248,22 +298,28 @@ function App() {
         }
       }
 
-      // Environmental event banner
-      const currentEvent = f.active_event ?? null;
-      if (currentEvent !== lastActiveEvent.current) {
-        if (currentEvent) {
-          const eventNames: Record<string, string> = {
-            algae_bloom: "Algae Bloom",
-            cold_snap: "Cold Snap",
-            heatwave: "Heatwave",
-            current_surge: "Current Surge",
-            plankton_bloom: "Plankton Bloom",
-          };
-          addToast(`Environmental event: ${eventNames[currentEvent] ?? currentEvent}`, "warning");
-        } else if (lastActiveEvent.current) {
-          addToast("Environmental event has ended", "info");
+      // Environmental event banners
+      const currentEvents = f.active_events ?? [];
+      const previousEvents = lastActiveEvents.current;
+      if (currentEvents.join() !== previousEvents.join()) {
+        const eventNames: Record<string, string> = {
+          algae_bloom: "Algae Bloom",
+          cold_snap: "Cold Snap",
+          heatwave: "Heatwave",
+          current_surge: "Current Surge",
+          plankton_bloom: "Plankton Bloom",
+        };
+        for (const event of currentEvents) {
+          if (!previousEvents.includes(event)) {
+            addToast(`Environmental event: ${eventNames[event] ?? event}`, "warning");
+          }
         }
-        lastActiveEvent.current = currentEvent;
+        for (const event of previousEvents) {
+          if (!currentEvents.includes(event)) {
+            addToast(`${eventNames[event] ?? "Environmental event"} has ended`, "info");
+          }
+        }
+        lastActiveEvents.current = currentEvents;
       }
 
       // Genetic diversity warning (check every ~300 ticks)
@@ -302,10 +358,20 @@ function App() {
       setNarrationText({ text: event.payload, key: ++narrationKey.current });
     });
 
+    // Persistent save failures (locked or deleted DB)
+    const unlistenDbError = listen<string>("db-error", (event) => {
+      addToast(event.payload, "danger");
+    });
+    const unlistenDbRecovered = listen("db-recovered", () => {
+      addToast("Saving works again", "success");
+    });
+
     return () => {
       unlisten.then((fn) => fn());
       unlistenAch.then((fn) => fn());
       unlistenNarr.then((fn) => fn());
+      unlistenDbError.then((fn) => fn());
+      unlistenDbRecovered.then((fn) => fn());
     };
   }, [addToast]);
 
@@ -525,7 +591,12 @@ function App() {
 
   const handleSettingUpdate = useCallback((key: string, value: number | boolean | string) => {
     setSettings((prev) => ({ ...prev, [key]: value }));
-    invoke("update_config", { key, value }).catch(() => {});
+    invoke<ConfigUpdate>("update_config", { key, value }).then((result) => {
+      // Show the bound the backend actually stored
+      if (result === "clamped") {
+        invoke<Record<string, unknown>>("get_config").then((cfg) => setSettings((prev) => ({ ...prev, ...cfg })));
+      }
+    }).catch(() => {});
     // Sync audio settings
     if (key === "master_volume" && audioRef.current) audioRef.current.masterVolume = value as number;
     if (key === "ambient_enabled" && audioRef.current) audioRef.current.ambientEnabled = value as boolean;
@@ -534,6 +605,13 @@ function App() {
     if (key === "theme") rendererRef.current?.setTheme(value as ThemeName);
   }, []);
 
+  const handleApplyPreset = useCallback((id: string) => {
+    invoke("apply_preset", { name: id })
+      .then(() => invoke<Record<string, unknown>>("get_config"))
+      .then((cfg) => setSettings((prev) => ({ ...prev, ...cfg })))
+      .catch(() => {});
+  }, []);
+
   return (
     <div style={{ position: "relative", width: "100vw", height: "100vh", overflow: "hidden" }}>
       <canvas
@@ -621,6 +699,7 @@ function App() {
             onClose={() => setSettingsOpen(false)}
             settings={settings}
             onUpdate={handleSettingUpdate}
+            onApplyPreset={handleApplyPreset}
           />
 
           {decorationMode && (
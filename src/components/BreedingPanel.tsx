@@ -1,5 +1,6 @@
 import { useEffect, useState, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../types";
 
 interface TraitRange {
   min: number;
@@ -89,7 +90,7 @@ export function BreedingPanel({ fishAId, fishBId, genomeAId, genomeBId, onClose,
     if (genomeAId == null || genomeBId == null) { setPreview(null); return; }
     invoke<BreedPreview>("get_breed_preview", { genomeAId, genomeBId })
       .then((p) => { setPreview(p); setError(null); })
-      .catch((e: unknown) => setError(String(e)));
+      .catch((e: unknown) => setError(errorMessage(e)));
   }, [genomeAId, genomeBId]);
 
   const handleBreed = useCallback(async () => {
@@ -99,7 +100,7 @@ export function BreedingPanel({ fishAId, fishBId, genomeAId, genomeBId, onClose,
       await invoke("breed_fish", { fishAId, fishBId });
       onBred();
     } catch (e: unknown) {
-      setError(String(e));
+      setError(errorMessage(e));
     } finally {
       setBreeding(false);
     }
@@ -5,6 +5,7 @@ const types = [
   { id: "tall_plant", label: "Tall Plant" },
   { id: "short_plant", label: "Short Plant" },
   { id: "coral", label: "Coral" },
+  { id: "feeder", label: "Feeder" },
 ] as const;
 
 const paletteStyle: React.CSSProperties = {
@@ -84,6 +84,16 @@ function lifeStageName(ageFrac: number, maturityAge: number): string {
   return "Elder";
 }
 
+/** Real time left before old age at the current speed, e.g. "4m 10s" */
+function lifespanLeft(seconds: number | null, ticks: number): string {
+  if (ticks === 0) return "past lifespan";
+  if (seconds == null) return `${ticks} ticks`;
+  const s = Math.round(seconds);
+  if (s < 60) return `${s}s`;
+  if (s < 3600) return `${Math.floor(s / 60)}m ${s % 60}s`;
+  return `${Math.floor(s / 3600)}h ${Math.floor((s % 3600) / 60)}m`;
+}
+
 export const Inspector = memo(function Inspector({ fish, onClose, onViewLineage, onFishUpdated }: {
   fish: FishDetail;
   onClose: () => void;
@@ -112,6 +122,25 @@ export const Inspector = memo(function Inspector({ fish, onClose, onViewLineage,
     onFishUpdated?.();
   }, [fish.id, onFishUpdated]);
 
+  const handleQuarantineToggle = useCallback(async () => {
+    await invoke(fish.is_quarantined ? "release_fish" : "quarantine_fish", { fishId: fish.id });
+    onFishUpdated?.();
+  }, [fish.id, fish.is_quarantined, onFishUpdated]);
+
+  const handleProtectToggle = useCallback(async () => {
+    if (fish.protected_lineage != null) {
+      await invoke("unprotect_lineage", { genomeId: fish.protected_lineage });
+    } else {
+      await invoke("protect_lineage", { genomeId: fish.genome_id });
+    }
+    onFishUpdated?.();
+  }, [fish.genome_id, fish.protected_lineage, onFishUpdated]);
+
+  const handleClone = useCallback(async () => {
+    await invoke("clone_fish", { fishId: fish.id, count: 1 });
+    onFishUpdated?.();
+  }, [fish.id, onFishUpdated]);
+
   return (
     <div style={panelStyle}>
       <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 8 }}>
@@ -170,6 +199,40 @@ export const Inspector = memo(function Inspector({ fish, onClose, onViewLineage,
         <span style={{ fontSize: 11 }}>
           Gen {g.generation} | {g.sex} | ID #{fish.id}
         </span>
+        <button
+          onClick={handleQuarantineToggle}
+          title={fish.is_quarantined ? "Return to the tank" : "Isolate from disease, predators and mates"}
+          style={{
+            marginLeft: "auto", background: "none", cursor: "pointer", fontSize: 10, padding: "1px 5px",
+            border: "1px solid rgba(255,255,255,0.2)", borderRadius: 3,
+            color: fish.is_quarantined ? "#fa6" : "rgba(255,255,255,0.5)",
+          }}
+        >
+          {fish.is_quarantined ? "Release" : "Quarantine"}
+        </button>
+        <button
+          onClick={handleClone}
+          title="Add a newborn with an identical genome"
+          style={{
+            background: "none", cursor: "pointer", fontSize: 10, padding: "1px 5px",
+            border: "1px solid rgba(255,255,255,0.2)", borderRadius: 3, color: "rgba(255,255,255,0.5)",
+          }}
+        >
+          Clone
+        </button>
+        <button
+          onClick={handleProtectToggle}
+          title={fish.protected_lineage != null
+            ? `End the conservation program for lineage #${fish.protected_lineage}`
+            : "Preserve this fish's traits in its descendants"}
+          style={{
+            background: "none", cursor: "pointer", fontSize: 10, padding: "1px 5px",
+            border: "1px solid rgba(255,255,255,0.2)", borderRadius: 3,
+            color: fish.protected_lineage != null ? "#6cf" : "rgba(255,255,255,0.5)",
+          }}
+        >
+          {fish.protected_lineage != null ? "Protected" : "Protect"}
+        </button>
       </div>
 
       <div style={{ fontSize: 11, marginBottom: 4 }}>
@@ -177,6 +240,10 @@ export const Inspector = memo(function Inspector({ fish, onClose, onViewLineage,
         {lifeStageName(fish.age / (BASE_LIFESPAN * g.lifespan_factor), g.maturity_age)}
         <span style={{ marginLeft: 8, ...labelStyle }}>State </span>
         {fish.behavior}
+        <span style={{ marginLeft: 8, ...labelStyle }}>Left </span>
+        <span title="Real time until old age at the current speed">
+          {lifespanLeft(fish.remaining_lifespan_seconds, fish.remaining_lifespan)}
+        </span>
         {fish.is_infected && (
           <span style={{ marginLeft: 6, color: "#6d4", fontSize: 10, fontWeight: 600 }}>INFECTED</span>
         )}
@@ -211,7 +278,9 @@ export const Inspector = memo(function Inspector({ fish, onClose, onViewLineage,
         <div style={{ marginTop: 6 }}>
           <TraitBar label="Speed" value={g.speed} min={0.5} max={2.0} />
           <TraitBar label="Aggression" value={g.aggression} min={0} max={1} />
+          <TraitBar label="Gape" value={g.gape_size} min={0.7} max={1.3} />
           <TraitBar label="Schooling" value={g.school_affinity} min={0} max={1} />
+          <TraitBar label="Polarization" value={g.polarization} min={0} max={1} />
           <TraitBar label="Curiosity" value={g.curiosity} min={0} max={1} />
           <TraitBar label="Boldness" value={g.boldness} min={0} max={1} />
           <TraitBar label="Metabolism" value={g.metabolism} min={0.5} max={2} />
@@ -219,6 +288,8 @@ export const Inspector = memo(function Inspector({ fish, onClose, onViewLineage,
           <TraitBar label="Lifespan" value={g.lifespan_factor} min={0.5} max={2} />
           <TraitBar label="Body size" value={g.body_length} min={0.6} max={2} />
           <TraitBar label="Disease res." value={g.disease_resistance} min={0} max={1} />
+          <TraitBar label="Temp. tolerance" value={g.temp_tolerance} min={0} max={1} />
+          <TraitBar label="Parental care" value={g.parental_care} min={0} max={1} />
         </div>
         <div style={{ fontSize: 10, color: "rgba(255,255,255,0.4)", marginTop: 4 }}>
           Pattern: {patternName(g.pattern)} | Hue: {Math.round(g.base_hue)}
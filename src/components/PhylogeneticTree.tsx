@@ -11,6 +11,8 @@ interface LineageNode {
   body_length: number;
   depth: number;
   is_alive: boolean;
+  lineage_root?: number;
+  lineage_color?: string;
 }
 
 interface Props {
@@ -91,10 +93,12 @@ export function PhylogeneticTree({ genomeId, onClose, onSelectFish }: Props) {
         const ppos = positions.get(pid);
         if (!ppos) continue;
         ctx.beginPath();
-        ctx.strokeStyle = "rgba(100,160,255,0.25)";
+        ctx.globalAlpha = 0.45;
+        ctx.strokeStyle = n.lineage_color ?? "rgba(100,160,255,0.55)";
         ctx.moveTo(pos.x, pos.y - nodeRadius);
         ctx.lineTo(ppos.x, ppos.y + nodeRadius);
         ctx.stroke();
+        ctx.globalAlpha = 1;
       }
     }
 
@@ -117,6 +121,11 @@ export function PhylogeneticTree({ genomeId, onClose, onSelectFish }: Props) {
         ctx.lineWidth = 2;
         ctx.strokeStyle = "#fff";
         ctx.stroke();
+      } else if (n.lineage_color) {
+        // Ring in the founder's color so branches read as one family
+        ctx.lineWidth = 1.5;
+        ctx.strokeStyle = n.lineage_color;
+        ctx.stroke();
       }
 
       // Label
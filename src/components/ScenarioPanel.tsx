@@ -1,5 +1,6 @@
 import { useEffect, useState, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import type { Dashboard, ScenarioProgress } from "../types";
 
 interface ScenarioDef {
   id: string;
@@ -8,13 +9,6 @@ interface ScenarioDef {
   goals: string[];
 }
 
-interface ScenarioProgress {
-  scenario_id: string;
-  scenario_name: string;
-  goals: { description: string; complete: boolean }[];
-  all_complete: boolean;
-}
-
 const panelStyle: React.CSSProperties = {
   position: "absolute",
   top: 68,
@@ -57,7 +51,7 @@ export function ScenarioPanel({ open, onClose }: Props) {
   const refresh = useCallback(async () => {
     const [list, prog] = await Promise.all([
       invoke<ScenarioDef[]>("get_scenarios").catch(() => []),
-      invoke<ScenarioProgress | null>("get_scenario_progress").catch(() => null),
+      invoke<Dashboard>("get_dashboard").then((d) => d.scenario).catch(() => null),
     ]);
     setScenarios(list);
     setProgress(prog);
@@ -1,5 +1,6 @@
 import { useState, useRef, useCallback, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import type { ConfigPreset } from "../types";
 
 interface Settings {
   // Boids
@@ -7,24 +8,49 @@ interface Settings {
   alignment_weight: number;
   cohesion_weight: number;
   wander_strength: number;
+  school_size_strength: number;
+  schooling_style_strength: number;
+  schooling_mode: string;
+  species_cohesion_bonus: number;
+  absolute_max_speed: number;
   // Ecosystem
   hunger_rate: number;
+  forage_hunger_threshold: number;
+  forage_stop_threshold: number;
+  eat_hunger_threshold: number;
   mutation_rate_small: number;
   mutation_rate_large: number;
+  appearance_mutation_rate_small: number;
+  appearance_mutation_rate_large: number;
+  genetic_drift_rate: number;
+  max_lifespan: number;
+  senescence_onset: number;
+  senescence_strength: number;
   species_threshold: number;
+  speciation_appearance_weight: number;
+  speciation_behavior_weight: number;
   // Environment
   day_night_cycle: boolean;
   day_night_speed: number;
+  breeding_season: string;
   bubble_rate: number;
   current_strength: number;
+  water_resilience_enabled: boolean;
+  water_resilience_strength: number;
   // Auto-feeder
   auto_feed_enabled: boolean;
   auto_feed_interval: number;
   auto_feed_amount: number;
+  food_preference_enabled: boolean;
+  food_preference_bonus: number;
+  food_preference_penalty: number;
   // Ollama
   ollama_enabled: boolean;
   ollama_url: string;
   ollama_model: string;
+  // Webhook
+  webhook_url: string;
+  webhook_interval_ticks: number;
   // Audio
   master_volume: number;
   ambient_enabled: boolean;
@@ -34,6 +60,7 @@ interface Settings {
   // Environmental Events
   environmental_events_enabled: boolean;
   event_frequency: number;
+  max_concurrent_events: number;
   // Territory
   territory_enabled: boolean;
   territory_claim_radius: number;
@@ -44,6 +71,29 @@ interface Settings {
   disease_duration: number;
   disease_damage: number;
   disease_spread_radius: number;
+  immunity_gain: number;
+  immunity_max: number;
+  immunity_decay: number;
+  // Carcasses
+  carcass_decay_enabled: boolean;
+  carcass_decay_ticks: number;
+  // Shelter
+  shelter_enabled: boolean;
+  shelter_seek_strength: number;
+  shelter_strike_reduction: number;
+  // Courtship
+  courtship_display_enabled: boolean;
+  courtship_min_condition: number;
+  courtship_choosiness: number;
+  courtship_conspicuousness: number;
+  // Fin-nipping
+  fin_nipping_enabled: boolean;
+  fin_nip_chance: number;
+  fin_nip_damage: number;
+  // Parental care
+  parental_care_enabled: boolean;
+  max_clutch_size: number;
+  nest_guard_radius: number;
 }
 
 interface Props {
@@ -51,6 +101,7 @@ interface Props {
   onClose: () => void;
   settings: Settings;
   onUpdate: (key: string, value: number | boolean | string) => void;
+  onApplyPreset: (id: string) => void;
 }
 
 const panelStyle: React.CSSProperties = {
@@ -194,8 +245,13 @@ function DebouncedInput({ value, onUpdate, label }: { value: string; onUpdate: (
   );
 }
 
-export function SettingsPanel({ open, onClose, settings, onUpdate }: Props) {
+export function SettingsPanel({ open, onClose, settings, onUpdate, onApplyPreset }: Props) {
   const [section, setSection] = useState<"sim" | "env" | "ai" | "audio">("sim");
+  const [presets, setPresets] = useState<ConfigPreset[]>([]);
+
+  useEffect(() => {
+    if (open) invoke<ConfigPreset[]>("list_presets").then(setPresets).catch(() => {});
+  }, [open]);
 
   if (!open) return null;
 
@@ -243,19 +299,55 @@ export function SettingsPanel({ open, onClose, settings, onUpdate }: Props) {
       <div style={{ flex: 1, overflow: "auto", padding: 16 }}>
         {section === "sim" && (
           <>
+            {presets.length > 0 && (
+              <div style={sectionStyle}>
+                <div style={sectionTitleStyle}>Presets</div>
+                <div style={{ display: "flex", gap: 6, flexWrap: "wrap" }}>
+                  {presets.map((p) => (
+                    <button
+                      key={p.id}
+                      onClick={() => onApplyPreset(p.id)}
+                      title={p.description}
+                      style={{
+                        padding: "4px 10px", border: "1px solid rgba(255,255,255,0.15)", borderRadius: 4,
+                        background: "rgba(255,255,255,0.06)", color: "#ccd", cursor: "pointer", fontSize: 11,
+                      }}
+                    >
+                      {p.name}
+                    </button>
+                  ))}
+                </div>
+              </div>
+            )}
             <div style={sectionStyle}>
               <div style={sectionTitleStyle}>Boids</div>
               <Slider label="Separation" value={settings.separation_weight} min={0} max={5} step={0.1} onChange={(v) => onUpdate("separation_weight", v)} />
               <Slider label="Alignment" value={settings.alignment_weight} min={0} max={5} step={0.1} onChange={(v) => onUpdate("alignment_weight", v)} />
               <Slider label="Cohesion" value={settings.cohesion_weight} min={0} max={5} step={0.1} onChange={(v) => onUpdate("cohesion_weight", v)} />
               <Slider label="Wander" value={settings.wander_strength} min={0} max={2} step={0.05} onChange={(v) => onUpdate("wander_strength", v)} />
+              <Slider label="School size preference" value={settings.school_size_strength} min={0} max={2} step={0.1} onChange={(v) => onUpdate("school_size_strength", v)} />
+              <Slider label="Shoal vs school contrast" value={settings.schooling_style_strength} min={0} max={1} step={0.05} onChange={(v) => onUpdate("schooling_style_strength", v)} />
+              <Slider label="Speed cap" value={settings.absolute_max_speed} min={0.5} max={20} step={0.5} onChange={(v) => onUpdate("absolute_max_speed", v)} />
+              <Slider label="Same-species cohesion" value={settings.species_cohesion_bonus} min={0} max={2} step={0.1} onChange={(v) => onUpdate("species_cohesion_bonus", v)} />
+              <Toggle label="Species-only schools" value={settings.schooling_mode === "species_only"} onChange={(v) => onUpdate("schooling_mode", v ? "species_only" : "mixed")} />
             </div>
             <div style={sectionStyle}>
               <div style={sectionTitleStyle}>Evolution</div>
               <Slider label="Hunger rate" value={settings.hunger_rate} min={0.0001} max={0.005} step={0.0001} onChange={(v) => onUpdate("hunger_rate", v)} />
-              <Slider label="Mutation (small)" value={settings.mutation_rate_small} min={0} max={0.5} step={0.01} onChange={(v) => onUpdate("mutation_rate_small", v)} />
-              <Slider label="Mutation (large)" value={settings.mutation_rate_large} min={0} max={0.2} step={0.005} onChange={(v) => onUpdate("mutation_rate_large", v)} />
+              <Slider label="Start foraging at hunger" value={settings.forage_hunger_threshold} min={0} max={1} step={0.05} onChange={(v) => onUpdate("forage_hunger_threshold", v)} />
+              <Slider label="Stop foraging below" value={settings.forage_stop_threshold} min={0} max={1} step={0.05} onChange={(v) => onUpdate("forage_stop_threshold", v)} />
+              <Slider label="Eat above hunger" value={settings.eat_hunger_threshold} min={0} max={1} step={0.05} onChange={(v) => onUpdate("eat_hunger_threshold", v)} />
+              <Slider label="Trait mutation (small)" value={settings.mutation_rate_small} min={0} max={0.5} step={0.01} onChange={(v) => onUpdate("mutation_rate_small", v)} />
+              <Slider label="Trait mutation (large)" value={settings.mutation_rate_large} min={0} max={0.2} step={0.005} onChange={(v) => onUpdate("mutation_rate_large", v)} />
+              <Slider label="Appearance mutation (small)" value={settings.appearance_mutation_rate_small} min={0} max={0.5} step={0.01} onChange={(v) => onUpdate("appearance_mutation_rate_small", v)} />
+              <Slider label="Appearance mutation (large)" value={settings.appearance_mutation_rate_large} min={0} max={0.2} step={0.005} onChange={(v) => onUpdate("appearance_mutation_rate_large", v)} />
+              <Slider label="Genetic drift" value={settings.genetic_drift_rate} min={0} max={0.1} step={0.005} onChange={(v) => onUpdate("genetic_drift_rate", v)} />
+              <Slider label="Max lifespan (ticks)" value={settings.max_lifespan} min={5000} max={100000} step={1000} onChange={(v) => onUpdate("max_lifespan", v)} />
+              <Slider label="Senescence onset" value={settings.senescence_onset} min={0.3} max={0.95} step={0.05} onChange={(v) => onUpdate("senescence_onset", v)} />
+              <Slider label="Senescence strength" value={settings.senescence_strength} min={0} max={1} step={0.05} onChange={(v) => onUpdate("senescence_strength", v)} />
               <Slider label="Species threshold" value={settings.species_threshold} min={0.5} max={5} step={0.1} onChange={(v) => onUpdate("species_threshold", v)} />
+              <Slider label="Appearance weight" value={settings.speciation_appearance_weight} min={0} max={3} step={0.1} onChange={(v) => onUpdate("speciation_appearance_weight", v)} />
+              <Slider label="Behavior weight" value={settings.speciation_behavior_weight} min={0} max={5} step={0.1} onChange={(v) => onUpdate("speciation_behavior_weight", v)} />
             </div>
             <div style={sectionStyle}>
               <div style={sectionTitleStyle}>Auto-Feeder</div>
@@ -263,6 +355,12 @@ export function SettingsPanel({ open, onClose, settings, onUpdate }: Props) {
               <Slider label="Interval (ticks)" value={settings.auto_feed_interval} min={100} max={2000} step={50} onChange={(v) => onUpdate("auto_feed_interval", v)} />
               <Slider label="Amount" value={settings.auto_feed_amount} min={1} max={10} step={1} onChange={(v) => onUpdate("auto_feed_amount", v)} />
             </div>
+            <div style={sectionStyle}>
+              <div style={sectionTitleStyle}>Diet</div>
+              <Toggle label="Food preferences" value={settings.food_preference_enabled} onChange={(v) => onUpdate("food_preference_enabled", v)} />
+              <Slider label="Preferred food bonus" value={settings.food_preference_bonus} min={0} max={1} step={0.05} onChange={(v) => onUpdate("food_preference_bonus", v)} />
+              <Slider label="Disliked food penalty" value={settings.food_preference_penalty} min={0} max={1} step={0.05} onChange={(v) => onUpdate("food_preference_penalty", v)} />
+            </div>
           </>
         )}
 
@@ -273,8 +371,36 @@ export function SettingsPanel({ open, onClose, settings, onUpdate }: Props) {
             {settings.day_night_cycle && (
               <Slider label="Day/Night speed" value={settings.day_night_speed} min={0} max={10} step={0.5} onChange={(v) => onUpdate("day_night_speed", v)} />
             )}
+            <div style={{ marginTop: 12 }}>
+              <div style={sectionTitleStyle}>Breeding Season</div>
+              <div style={{ display: "flex", gap: 6, flexWrap: "wrap" }}>
+                {["always", "day", "night", "twilight"].map((s) => (
+                  <button
+                    key={s}
+                    onClick={() => onUpdate("breeding_season", s)}
+                    style={{
+                      padding: "5px 10px",
+                      border: "1px solid rgba(255,255,255,0.15)",
+                      borderRadius: 4,
+                      background: settings.breeding_season === s ? "rgba(100,160,255,0.25)" : "rgba(255,255,255,0.06)",
+                      color: settings.breeding_season === s ? "#8bf" : "rgba(255,255,255,0.5)",
+                      fontSize: 11,
+                      cursor: "pointer",
+                      fontFamily: "system-ui",
+                      textTransform: "capitalize",
+                    }}
+                  >
+                    {s}
+                  </button>
+                ))}
+              </div>
+            </div>
             <Slider label="Bubble rate" value={settings.bubble_rate} min={0} max={3} step={0.1} onChange={(v) => onUpdate("bubble_rate", v)} />
             <Slider label="Current strength" value={settings.current_strength} min={0} max={1} step={0.05} onChange={(v) => onUpdate("current_strength", v)} />
+            <Toggle label="Bacterial bloom in crashed water" value={settings.water_resilience_enabled} onChange={(v) => onUpdate("water_resilience_enabled", v)} />
+            {settings.water_resilience_enabled && (
+              <Slider label="Bloom strength" value={settings.water_resilience_strength} min={0} max={20} step={0.5} onChange={(v) => onUpdate("water_resilience_strength", v)} />
+            )}
             <div style={{ marginTop: 12 }}>
               <div style={sectionTitleStyle}>Theme</div>
               <div style={{ display: "flex", gap: 6, flexWrap: "wrap" }}>
@@ -303,7 +429,10 @@ export function SettingsPanel({ open, onClose, settings, onUpdate }: Props) {
               <div style={sectionTitleStyle}>Environmental Events</div>
               <Toggle label="Enable events" value={settings.environmental_events_enabled} onChange={(v) => onUpdate("environmental_events_enabled", v)} />
               {settings.environmental_events_enabled && (
-                <Slider label="Event frequency" value={settings.event_frequency} min={0.1} max={5} step={0.1} onChange={(v) => onUpdate("event_frequency", v)} />
+                <>
+                  <Slider label="Event frequency" value={settings.event_frequency} min={0.1} max={5} step={0.1} onChange={(v) => onUpdate("event_frequency", v)} />
+                  <Slider label="Overlapping events" value={settings.max_concurrent_events} min={1} max={5} step={1} onChange={(v) => onUpdate("max_concurrent_events", v)} />
+                </>
               )}
               <div style={{ display: "flex", gap: 4, flexWrap: "wrap", marginTop: 4 }}>
                 {["algae_bloom", "cold_snap", "heatwave", "current_surge", "plankton_bloom"].map((evt) => (
@@ -344,9 +473,60 @@ export function SettingsPanel({ open, onClose, settings, onUpdate }: Props) {
                   <Slider label="Duration (ticks)" value={settings.disease_duration} min={100} max={2000} step={50} onChange={(v) => onUpdate("disease_duration", v)} />
                   <Slider label="Damage/tick" value={settings.disease_damage} min={0} max={0.005} step={0.0001} onChange={(v) => onUpdate("disease_damage", v)} />
                   <Slider label="Spread radius" value={settings.disease_spread_radius} min={10} max={100} step={5} onChange={(v) => onUpdate("disease_spread_radius", v)} />
+                  <Slider label="Immunity per recovery" value={settings.immunity_gain} min={0} max={1} step={0.05} onChange={(v) => onUpdate("immunity_gain", v)} />
+                  <Slider label="Max immunity" value={settings.immunity_max} min={0} max={1} step={0.05} onChange={(v) => onUpdate("immunity_max", v)} />
+                  <Slider label="Immunity fade/tick" value={settings.immunity_decay} min={0} max={0.001} step={0.00001} onChange={(v) => onUpdate("immunity_decay", v)} />
+                </>
+              )}
+            </div>
+            <div style={{ marginTop: 16 }}>
+              <div style={sectionTitleStyle}>Shelter</div>
+              <Toggle label="Fish hide by decorations" value={settings.shelter_enabled} onChange={(v) => onUpdate("shelter_enabled", v)} />
+              {settings.shelter_enabled && (
+                <>
+                  <Slider label="Seek strength" value={settings.shelter_seek_strength} min={0} max={2} step={0.1} onChange={(v) => onUpdate("shelter_seek_strength", v)} />
+                  <Slider label="Strike reduction" value={settings.shelter_strike_reduction} min={0} max={1} step={0.05} onChange={(v) => onUpdate("shelter_strike_reduction", v)} />
+                </>
+              )}
+            </div>
+            <div style={{ marginTop: 16 }}>
+              <div style={sectionTitleStyle}>Courtship</div>
+              <Toggle label="Female choice" value={settings.courtship_display_enabled} onChange={(v) => onUpdate("courtship_display_enabled", v)} />
+              {settings.courtship_display_enabled && (
+                <>
+                  <Slider label="Min male condition" value={settings.courtship_min_condition} min={0} max={1} step={0.05} onChange={(v) => onUpdate("courtship_min_condition", v)} />
+                  <Slider label="Choosiness" value={settings.courtship_choosiness} min={0} max={1} step={0.05} onChange={(v) => onUpdate("courtship_choosiness", v)} />
+                </>
+              )}
+              <Slider label="Display conspicuousness" value={settings.courtship_conspicuousness} min={1} max={5} step={0.1} onChange={(v) => onUpdate("courtship_conspicuousness", v)} />
+            </div>
+            <div style={{ marginTop: 16 }}>
+              <div style={sectionTitleStyle}>Fin-nipping</div>
+              <Toggle label="Aggressive fish skirmish" value={settings.fin_nipping_enabled} onChange={(v) => onUpdate("fin_nipping_enabled", v)} />
+              {settings.fin_nipping_enabled && (
+                <>
+                  <Slider label="Skirmish chance" value={settings.fin_nip_chance} min={0} max={0.1} step={0.005} onChange={(v) => onUpdate("fin_nip_chance", v)} />
+                  <Slider label="Damage" value={settings.fin_nip_damage} min={0} max={0.2} step={0.01} onChange={(v) => onUpdate("fin_nip_damage", v)} />
+                </>
+              )}
+            </div>
+            <div style={{ marginTop: 16 }}>
+              <div style={sectionTitleStyle}>Parental care</div>
+              <Toggle label="Parents guard eggs and fry" value={settings.parental_care_enabled} onChange={(v) => onUpdate("parental_care_enabled", v)} />
+              {settings.parental_care_enabled && (
+                <>
+                  <Slider label="Clutch size (no care)" value={settings.max_clutch_size} min={1} max={6} step={1} onChange={(v) => onUpdate("max_clutch_size", v)} />
+                  <Slider label="Guard radius" value={settings.nest_guard_radius} min={0} max={150} step={5} onChange={(v) => onUpdate("nest_guard_radius", v)} />
                 </>
               )}
             </div>
+            <div style={{ marginTop: 16 }}>
+              <div style={sectionTitleStyle}>Carcasses</div>
+              <Toggle label="Dead fish decay" value={settings.carcass_decay_enabled} onChange={(v) => onUpdate("carcass_decay_enabled", v)} />
+              {settings.carcass_decay_enabled && (
+                <Slider label="Decay time (ticks)" value={settings.carcass_decay_ticks} min={150} max={3000} step={50} onChange={(v) => onUpdate("carcass_decay_ticks", v)} />
+              )}
+            </div>
             <div style={{ marginTop: 16 }}>
               <div style={sectionTitleStyle}>Tank Sharing</div>
               <div style={{ display: "flex", gap: 8 }}>
@@ -371,6 +551,16 @@ export function SettingsPanel({ open, onClose, settings, onUpdate }: Props) {
                   Import Tank
                 </button>
               </div>
+              <button
+                onClick={() => invoke("export_chronicle").catch((e: unknown) => console.error("Chronicle export failed:", e))}
+                style={{
+                  width: "100%", marginTop: 8, padding: "8px 0", border: "1px solid rgba(180,140,255,0.3)",
+                  borderRadius: 4, background: "rgba(180,140,255,0.1)", color: "#c9f",
+                  fontSize: 11, cursor: "pointer", fontFamily: "system-ui",
+                }}
+              >
+                Export Chronicle
+              </button>
             </div>
           </div>
         )}
@@ -381,6 +571,9 @@ export function SettingsPanel({ open, onClose, settings, onUpdate }: Props) {
             <Toggle label="Enabled" value={settings.ollama_enabled} onChange={(v) => onUpdate("ollama_enabled", v)} />
             <DebouncedInput label="URL" value={settings.ollama_url} onUpdate={(v) => onUpdate("ollama_url", v)} />
             <DebouncedInput label="Model" value={settings.ollama_model} onUpdate={(v) => onUpdate("ollama_model", v)} />
+            <div style={sectionTitleStyle}>Webhook</div>
+            <DebouncedInput label="URL (blank to disable)" value={settings.webhook_url} onUpdate={(v) => onUpdate("webhook_url", v)} />
+            <Slider label="Min ticks between posts" value={settings.webhook_interval_ticks} min={30} max={1800} step={30} onChange={(v) => onUpdate("webhook_interval_ticks", v)} />
           </div>
         )}
 
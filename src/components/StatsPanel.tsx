@@ -1,5 +1,6 @@
 import { useEffect, useState, useRef, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import type { Dashboard } from "../types";
 
 interface Snapshot {
   tick: number;
@@ -13,6 +14,7 @@ interface Snapshot {
   avg_boldness: number;
   avg_school_affinity: number;
   avg_disease_resistance: number;
+  avg_temp_tolerance: number;
   min_speed: number;
   max_speed: number;
   min_size: number;
@@ -24,8 +26,16 @@ interface JournalEntry {
   tick: number;
   text: string;
   timestamp: string;
+  /** Tank state when written; null for entries predating mood tagging */
+  mood: "thriving" | "struggling" | "crisis" | null;
 }
 
+const MOOD_BORDER: Record<string, string> = {
+  thriving: "rgba(100,220,140,0.5)",
+  struggling: "rgba(255,190,90,0.5)",
+  crisis: "rgba(255,90,90,0.6)",
+};
+
 interface Props {
   open: boolean;
   onClose: () => void;
@@ -154,6 +164,34 @@ function MiniChart({
   );
 }
 
+/** Current vital signs above the history charts */
+function Vitals({ dashboard }: { dashboard: Dashboard }) {
+  const stats: [string, string][] = [
+    ["Population", String(dashboard.population)],
+    ["Species", String(dashboard.species_count)],
+    ["Generation", String(dashboard.max_generation)],
+    ["Effective size", dashboard.effective_population_size.toFixed(1)],
+    ["Enrichment", `${Math.round(dashboard.tank_enrichment * 100)}%`],
+  ];
+  const behaviors = Object.entries(dashboard.behaviors).sort((a, b) => b[1] - a[1]);
+  return (
+    <div style={{ marginBottom: 12, fontSize: 11 }}>
+      <div style={{ display: "flex", flexWrap: "wrap", gap: "4px 12px" }}>
+        {stats.map(([label, value]) => (
+          <span key={label} style={{ color: "rgba(255,255,255,0.5)" }}>
+            {label} <span style={{ color: "rgba(255,255,255,0.9)", fontWeight: 600 }}>{value}</span>
+          </span>
+        ))}
+      </div>
+      {behaviors.length > 0 && (
+        <div style={{ marginTop: 4, color: "rgba(255,255,255,0.4)" }}>
+          {behaviors.map(([state, count]) => `${state} ${count}`).join(" · ")}
+        </div>
+      )}
+    </div>
+  );
+}
+
 function SpeciesStackChart({ data }: { data: SpeciesSnapshot[] }) {
   const canvasRef = useRef<HTMLCanvasElement>(null);
 
@@ -287,6 +325,7 @@ const TRAIT_DEFS: TraitDef[] = [
   { key: "boldness", label: "Boldness", color: "#fa6", avg: (s) => s.avg_boldness, yMax: 1 },
   { key: "schooling", label: "Schooling", color: "#a6f", avg: (s) => s.avg_school_affinity, yMax: 1 },
   { key: "disease_res", label: "Disease Res", color: "#6f6", avg: (s) => s.avg_disease_resistance, yMax: 1 },
+  { key: "temp_tol", label: "Temp Tolerance", color: "#6ff", avg: (s) => s.avg_temp_tolerance, yMax: 1 },
 ];
 
 function TraitChart({ snapshots }: { snapshots: Snapshot[] }) {
@@ -502,10 +541,14 @@ export function StatsPanel({ open, onClose }: Props) {
   const [journal, setJournal] = useState<JournalEntry[]>([]);
   const [events, setEvents] = useState<EventEntry[]>([]);
   const [speciesSnapshots, setSpeciesSnapshots] = useState<SpeciesSnapshot[]>([]);
+  const [dashboard, setDashboard] = useState<Dashboard | null>(null);
 
   const fetchData = useCallback(async () => {
     const snaps = await invoke<Snapshot[]>("get_snapshots").catch(() => []);
     setSnapshots(snaps);
+    if (tab === "population") {
+      setDashboard(await invoke<Dashboard>("get_dashboard").catch(() => null));
+    }
     if (tab === "species") {
       const ss = await invoke<SpeciesSnapshot[]>("get_species_snapshots").catch(() => []);
       setSpeciesSnapshots(ss);
@@ -592,6 +635,7 @@ export function StatsPanel({ open, onClose }: Props) {
       <div style={{ flex: 1, overflow: "auto", padding: 16 }}>
         {tab === "population" && (
           <>
+            {dashboard && <Vitals dashboard={dashboard} />}
             <MiniChart data={popData} color="#6af" label="Population" />
             <MiniChart data={speciesData} color="#fa6" label="Species" />
             <MiniChart data={wqData} color="#6a6" label="Water Quality %" max={100} />
@@ -630,8 +674,9 @@ export function StatsPanel({ open, onClose }: Props) {
                   padding: 10,
                   background: "rgba(255,255,255,0.04)",
                   borderRadius: 6,
-                  borderLeft: "3px solid rgba(100,160,255,0.3)",
+                  borderLeft: `3px solid ${(entry.mood && MOOD_BORDER[entry.mood]) || "rgba(100,160,255,0.3)"}`,
                 }}
+                title={entry.mood ?? undefined}
               >
                 <div
                   style={{
@@ -660,7 +705,7 @@ export function StatsPanel({ open, onClose }: Props) {
             {events.map((ev, i) => {
               const colors: Record<string, string> = {
                 birth: "#4a4", death: "#a44", new_species: "#da4",
-                extinction: "#a44", predation: "#d84",
+                extinction: "#a44", predation: "#d84", species_revived: "#4ad",
               };
               const color = colors[ev.event_type] ?? "#68a";
               return (
@@ -6,6 +6,13 @@ interface Tank {
   active: boolean;
 }
 
+interface TankMetadata {
+  created_at: string;
+  last_saved_at: string;
+  description: string;
+  thumbnail_hue: number | null;
+}
+
 const barStyle: React.CSSProperties = {
   position: "absolute",
   top: 0,
@@ -44,10 +51,18 @@ export function TankSwitcher() {
   const [tanks, setTanks] = useState<Tank[]>([]);
   const [creating, setCreating] = useState(false);
   const [newName, setNewName] = useState("");
+  const [meta, setMeta] = useState<Record<string, TankMetadata>>({});
 
   const refresh = useCallback(async () => {
     const list = await invoke<Tank[]>("list_tanks").catch(() => []);
     setTanks(list);
+    const entries = await Promise.all(list.map(async (t) => {
+      const m = await invoke<TankMetadata>("get_tank_metadata", { name: t.name }).catch(() => null);
+      return [t.name, m] as const;
+    }));
+    const byName: Record<string, TankMetadata> = {};
+    for (const [name, m] of entries) if (m) byName[name] = m;
+    setMeta(byName);
   }, []);
 
   useEffect(() => { refresh(); }, [refresh]);
@@ -91,7 +106,18 @@ export function TankSwitcher() {
           <button
             style={t.active ? activeTabStyle : tabStyle}
             onClick={() => !t.active && handleSwitch(t.name)}
+            title={meta[t.name] ? [
+              meta[t.name].description,
+              `Created ${meta[t.name].created_at}`,
+              `Last played ${meta[t.name].last_saved_at}`,
+            ].filter(Boolean).join("\n") : undefined}
           >
+            {meta[t.name]?.thumbnail_hue != null && (
+              <span style={{
+                display: "inline-block", width: 7, height: 7, borderRadius: "50%", marginRight: 5,
+                background: `hsl(${meta[t.name].thumbnail_hue}, 65%, 55%)`,
+              }} />
+            )}
             {t.name}
           </button>
           {!t.active && t.name !== "My Aquarium" && (
@@ -1,4 +1,4 @@
-import type { FishState, FoodState, BubbleState, EggState, DecorationState, FrameUpdate, FishGenome } from "../types";
+import type { FishState, FoodState, BubbleState, EggState, AlgaeState, CarcassState, DecorationState, FrameUpdate, FishGenome } from "../types";
 import { getCachedSprite, renderFishSprite, hasCachedSprite, evictStaleSprites } from "./fishSprite";
 
 interface PrevFrame {
@@ -247,11 +247,16 @@ export class CanvasRenderer {
     }
 
     // Compute dayBrightness for use across all draw methods
-    const hour = this.currentHour;
-    if (hour < 5 || hour >= 21) this.dayBrightness = 0.4;
-    else if (hour < 7) this.dayBrightness = 0.4 + (hour - 5) / 2 * 0.6;
-    else if (hour > 18) this.dayBrightness = 1.0 - (hour - 18) / 3 * 0.6;
-    else this.dayBrightness = 1.0;
+    if (this.currentFrame && this.currentFrame.light_level !== undefined) {
+      // Sim provides a smoothed light level; crossfade between night (0.4) and day (1.0)
+      this.dayBrightness = 0.4 + this.currentFrame.light_level * 0.6;
+    } else {
+      const hour = this.currentHour;
+      if (hour < 5 || hour >= 21) this.dayBrightness = 0.4;
+      else if (hour < 7) this.dayBrightness = 0.4 + (hour - 5) / 2 * 0.6;
+      else if (hour > 18) this.dayBrightness = 1.0 - (hour - 18) / 3 * 0.6;
+      else this.dayBrightness = 1.0;
+    }
 
     // Apply viewport transform (zoom & pan)
     this.ctx.save();
@@ -285,6 +290,16 @@ export class CanvasRenderer {
     // Decorations (drawn before food/fish — they sit on sand)
     this.drawDecorations(this.currentFrame.decorations);
 
+    // Algae patches on the sand
+    if (this.currentFrame.algae) {
+      this.drawAlgae(this.currentFrame.algae);
+    }
+
+    // Carcasses sink and fade as they rot
+    if (this.currentFrame.carcasses) {
+      this.drawCarcasses(this.currentFrame.carcasses);
+    }
+
     // Eggs (sit near decorations/sand)
     if (this.currentFrame.eggs) {
       this.drawEggs(this.currentFrame.eggs);
@@ -319,7 +334,9 @@ export class CanvasRenderer {
         ctx.save();
         ctx.beginPath();
         ctx.arc(sel.territory_cx, sel.territory_cy, sel.territory_r, 0, Math.PI * 2);
-        ctx.strokeStyle = "rgba(255,180,100,0.15)";
+        // Fade the ring as the claim weakens
+        const strength = sel.territory_strength ?? 1;
+        ctx.strokeStyle = `rgba(255,180,100,${0.05 + 0.15 * strength})`;
         ctx.setLineDash([6, 4]);
         ctx.lineWidth = 1.5;
         ctx.stroke();
@@ -352,9 +369,9 @@ export class CanvasRenderer {
       }
     }
 
-    // Environmental event overlay
-    if (this.currentFrame.active_event) {
-      this.drawEventOverlay(this.currentFrame.active_event);
+    // Environmental event overlays
+    for (const event of this.currentFrame.active_events) {
+      this.drawEventOverlay(event);
     }
 
     // Tap ripples
@@ -528,6 +545,38 @@ export class CanvasRenderer {
     ctx.restore();
   }
 
+  private drawAlgae(algae: AlgaeState[]) {
+    const { ctx, time } = this;
+    for (const patch of algae) {
+      const r = 6 + patch.size * 24; // must match AlgaePatch::radius
+      const sway = Math.sin(time * 0.0015 + patch.id * 2.3) * 1.5;
+      ctx.save();
+      ctx.translate(patch.x + sway, patch.y);
+      ctx.beginPath();
+      ctx.ellipse(0, 0, r, r * 0.35, 0, 0, Math.PI * 2);
+      ctx.fillStyle = `rgba(70, 130, 50, ${(0.25 + patch.size * 0.35) * this.dayBrightness})`;
+      ctx.fill();
+      ctx.restore();
+    }
+  }
+
+  private drawCarcasses(carcasses: CarcassState[]) {
+    const { ctx } = this;
+    for (const c of carcasses) {
+      const len = 15 * c.body_length; // half of the sprite's 30 * body_length
+      ctx.save();
+      ctx.translate(c.x, c.y);
+      ctx.rotate(c.heading);
+      ctx.scale(1, -1); // belly-up
+      ctx.globalAlpha = 0.6 * (1 - c.decay) * this.dayBrightness;
+      ctx.beginPath();
+      ctx.ellipse(0, 0, len, len * 0.4, 0, 0, Math.PI * 2);
+      ctx.fillStyle = `hsl(${c.hue}, ${Math.round(20 * (1 - c.decay))}%, ${45 - c.decay * 15}%)`;
+      ctx.fill();
+      ctx.restore();
+    }
+  }
+
   private drawEggs(eggs: EggState[]) {
     const { ctx, time } = this;
     for (const egg of eggs) {
@@ -644,6 +693,11 @@ export class CanvasRenderer {
     // Brightness/depth tint
     ctx.globalAlpha = brightness;
 
+    // Elders render faded
+    if (fish.life_stage === "elder") {
+      ctx.globalAlpha *= 0.75;
+    }
+
     // Dying effect
     if (fish.behavior === "dying") {
       ctx.globalAlpha *= 0.5 + Math.sin(time * 0.01) * 0.2;
@@ -781,6 +835,9 @@ export class CanvasRenderer {
         case "coral":
           this.drawCoral(ctx, time);
           break;
+        case "feeder":
+          this.drawFeeder(ctx);
+          break;
       }
 
       ctx.restore();
@@ -826,6 +883,25 @@ export class CanvasRenderer {
     }
   }
 
+  private drawFeeder(ctx: CanvasRenderingContext2D) {
+    // Hopper on a short post, with pellets showing through the window
+    ctx.fillStyle = "rgba(150,155,165,0.8)";
+    ctx.fillRect(-2, -10, 4, 10);
+    ctx.beginPath();
+    ctx.moveTo(-10, -26);
+    ctx.lineTo(10, -26);
+    ctx.lineTo(5, -10);
+    ctx.lineTo(-5, -10);
+    ctx.closePath();
+    ctx.fillStyle = "rgba(170,180,195,0.75)";
+    ctx.fill();
+    ctx.strokeStyle = "rgba(80,85,95,0.5)";
+    ctx.lineWidth = 1;
+    ctx.stroke();
+    ctx.fillStyle = "rgba(190,140,70,0.8)";
+    ctx.fillRect(-5, -22, 10, 5);
+  }
+
   private drawCoral(ctx: CanvasRenderingContext2D, time: number) {
     const branches = 4;
     for (let b = 0; b < branches; b++) {
@@ -14,11 +14,33 @@ export interface FishState {
   energy: number;
   is_infected: boolean;
   is_juvenile: boolean;
+  life_stage: "juvenile" | "adult" | "elder";
   territory_cx?: number;
   territory_cy?: number;
   territory_r?: number;
+  /** Claim strength 0-1; the territory is abandoned when it reaches 0 */
+  territory_strength?: number;
   custom_name?: string;
   is_favorite?: boolean;
+  is_quarantined?: boolean;
+}
+
+/** Payload of the selected-fish-update event, emitted each frame while a fish is selected */
+export interface SelectedFishUpdate {
+  tick: number;
+  id: number;
+  x: number;
+  y: number;
+  z: number;
+  vx: number;
+  vy: number;
+  heading: number;
+  behavior: string;
+  hunger: number;
+  health: number;
+  energy: number;
+  /** Other fish within the cohesion radius */
+  neighbors: number;
 }
 
 export interface EggState {
@@ -29,6 +51,8 @@ export interface EggState {
   progress: number;
 }
 
+export type FoodKind = "flake" | "pellet" | "live";
+
 export interface FoodState {
   x: number;
   y: number;
@@ -43,11 +67,266 @@ export interface BubbleState {
 
 export interface SimEvent {
   Birth?: { fish_id: number; genome_id: number; parent_a: number; parent_b: number };
-  Death?: { fish_id: number; genome_id: number; cause: string };
+  Death?: {
+    fish_id: number;
+    genome_id: number;
+    cause: string;
+    age: number;
+    age_fraction: number;
+    /** Genome traits, null when the genome was already gone */
+    speed: number | null;
+    body_length: number | null;
+    aggression: number | null;
+    reproduced: boolean;
+  };
   FeedingDrop?: { x: number; y: number };
   Predation?: { predator_id: number; prey_id: number };
   NewSpecies?: { species_id: number };
   Extinction?: { species_id: number };
+  PopulationWarning?: { population: number; minimum: number };
+  PredatorIntroduced?: { fish_id: number; genome_id: number };
+  SpeciesRevived?: { species_id: number; count: number };
+}
+
+export interface DeathStats {
+  from_tick: number;
+  to_tick: number;
+  total: number;
+  /** Keyed by cause: old_age, starvation, poor_water, predation, plus any unrecognized */
+  by_cause: Record<string, number>;
+}
+
+export interface PairingRecommendation {
+  fish_a: number;
+  fish_b: number;
+  parent_a_value: number;
+  parent_b_value: number;
+  /** Mean trait value over simulated offspring */
+  expected: number;
+  min: number;
+  max: number;
+  chance_beyond_mean: number;
+  relatedness: number;
+  inbreeding_warning: boolean;
+}
+
+/** Result of recommend_pairing */
+export interface PairingReport {
+  trait_name: string;
+  maximize: boolean;
+  population_mean: number;
+  eligible_pairs: number;
+  pairs_considered: number;
+  recommendations: PairingRecommendation[];
+}
+
+/** One source × mutation cell of an inheritance table */
+export interface InheritanceCell {
+  source: "parent_a" | "parent_b" | "blend";
+  mutation: "none" | "small" | "large";
+  probability: number;
+  /** Value before mutation; blends spread evenly over low..high */
+  low: number;
+  high: number;
+  /** Standard deviation of the mutation step, 0 for none */
+  sigma: number;
+}
+
+/** Result of inheritance_table */
+export interface InheritanceTable {
+  trait_name: string;
+  parent_a_value: number;
+  parent_b_value: number;
+  relatedness: number;
+  cross_species: boolean;
+  expected: number;
+  cells: InheritanceCell[];
+}
+
+/** Simulation loop pacing from get_loop_diagnostics, over the last ~3s */
+export interface LoopDiagnostics {
+  frame_budget_ms: number;
+  avg_step_ms: number;
+  avg_frame_ms: number;
+  max_frame_ms: number;
+  /** False when frames overrun the 30Hz budget and the sim runs slower than requested */
+  keeping_up: boolean;
+  speed_multiplier: number;
+  steps_per_frame: number;
+  target_ticks_per_second: number;
+  achieved_ticks_per_second: number;
+  sampled_frames: number;
+}
+
+/** Average boids force magnitudes per fish from get_boids_diagnostics */
+export interface BoidsDiagnostics {
+  fish_count: number;
+  separation: number;
+  alignment: number;
+  cohesion: number;
+  wander: number;
+  boundary: number;
+  /** Decoration avoidance and shelter seeking */
+  obstacle: number;
+  hunger: number;
+  territory: number;
+  /** Current, quarantine, chasing and coordinated fleeing */
+  other: number;
+}
+
+/** One point of get_survivorship_curve, by fraction of nominal lifespan */
+export interface SurvivorshipPoint {
+  age_fraction: number;
+  /** Share of recorded deaths that lived at least this long */
+  surviving: number;
+  deaths: number;
+}
+
+export interface TraitSelection {
+  trait_name: "speed" | "body_length" | "aggression";
+  reproduced_mean: number;
+  failed_mean: number;
+  differential: number;
+  /** Differential in standard deviations, comparable across traits */
+  standardized: number;
+}
+
+export interface SelectionReport {
+  window_ticks: number;
+  reproduced_count: number;
+  failed_count: number;
+  traits: TraitSelection[];
+}
+
+/** Observation camera target from get_camera_suggestion: pan to (x, y), zoom to fit radius */
+export interface CameraSuggestion {
+  kind: "chase" | "courtship" | "hatchlings" | "school" | "overview";
+  x: number;
+  y: number;
+  radius: number;
+  fish_ids: number[];
+}
+
+export interface ScenarioProgress {
+  scenario_id: string;
+  scenario_name: string;
+  goals: { description: string; complete: boolean }[];
+  all_complete: boolean;
+}
+
+/** Projected completion of a scenario goal from recent trends */
+export type GoalEta =
+  | { status: "complete" }
+  | { status: "on_track"; ticks: number }
+  | { status: "stalled" }
+  | { status: "regressing" }
+  /** Too few snapshots to fit a trend */
+  | { status: "unknown" };
+
+export interface GoalEtaReport {
+  scenario_id: string;
+  tick: number;
+  goals: { description: string; complete: boolean; current: number; target: number; eta: GoalEta }[];
+}
+
+/** Tank vital signs from get_dashboard, read under one lock */
+export interface Dashboard {
+  tick: number;
+  population: number;
+  species_count: number;
+  max_generation: number;
+  genetic_diversity: number;
+  /** Genetic effective population size, from sex ratio and spawning variance */
+  effective_population_size: number;
+  /** Mean decoration-variety enrichment of living fish, 0-1 */
+  tank_enrichment: number;
+  water_quality: number;
+  active_event: string | null;
+  active_events: string[];
+  /** Living fish per behavior state, e.g. { swimming: 12, foraging: 3 } */
+  behaviors: Record<string, number>;
+  scenario: ScenarioProgress | null;
+}
+
+/** One genome trait's valid range and mechanical effects, from get_trait_info */
+export interface TraitInfo {
+  name: string;
+  category: "appearance" | "behavior" | "lifecycle";
+  /** [min, max], or null for categorical traits like sex and pattern */
+  range: [number, number] | null;
+  effects: string;
+}
+
+/** Two numeric traits that tend to come from the same parent; set via the trait_linkage config key */
+export interface TraitLinkage {
+  traits: [string, string];
+  /** 0..1 chance the second trait copies the first one's parent choice */
+  strength: number;
+}
+
+/** Field-by-field comparison of two genomes from diff_genomes */
+export interface GenomeDiff {
+  genome_a_id: number;
+  genome_b_id: number;
+  /** Keyed by trait name; hue diff is measured around the color wheel */
+  traits: Record<string, { a: number; b: number; diff: number }>;
+  sex: { a: "Male" | "Female"; b: "Male" | "Female" };
+  pattern: { a: PatternGene; b: PatternGene; same_type: boolean };
+  genome_distance: number;
+  species_threshold: number;
+  same_species: boolean;
+}
+
+/** Result of update_config: stored as given, or clamped into range */
+export type ConfigUpdate = "accepted" | "clamped";
+
+export interface ConfigPreset {
+  id: string;
+  name: string;
+  description: string;
+}
+
+export interface FounderShare {
+  founder_id: number;
+  share: number;
+  living_descendants: number;
+}
+
+export interface FounderAnalysis {
+  living_count: number;
+  founders: FounderShare[];
+  lost_lineages: number[];
+  untraced_share: number;
+}
+
+export interface GridStats {
+  cell_size: number;
+  cols: number;
+  rows: number;
+  total_cells: number;
+  empty_cells: number;
+  max_per_cell: number;
+  avg_per_occupied_cell: number;
+  population: number;
+  cohesion_radius: number;
+}
+
+export interface AlgaeState {
+  id: number;
+  x: number;
+  y: number;
+  size: number;
+}
+
+export interface CarcassState {
+  id: number;
+  x: number;
+  y: number;
+  heading: number;
+  body_length: number;
+  hue: number;
+  /** 0 = fresh, 1 = gone */
+  decay: number;
 }
 
 export interface DecorationState {
@@ -66,13 +345,18 @@ export interface FrameUpdate {
   bubbles: BubbleState[];
   eggs: EggState[];
   decorations: DecorationState[];
+  algae: AlgaeState[];
+  carcasses: CarcassState[];
   events: SimEvent[];
   water_quality: number;
   population: number;
   max_generation: number;
   species_count: number;
   time_of_day: number;
-  active_event: string | null;
+  /** 0 = night, 1 = day, eased through dawn/dusk */
+  light_level: number;
+  /** Concurrently active environmental events, oldest first */
+  active_events: string[];
   genetic_diversity: number;
 }
 
@@ -104,7 +388,10 @@ export interface FishGenome {
   eye_size: number;
   speed: number;
   aggression: number;
+  gape_size: number;
   school_affinity: number;
+  ideal_school_size: number;
+  polarization: number;
   curiosity: number;
   boldness: number;
   metabolism: number;
@@ -112,6 +399,20 @@ export interface FishGenome {
   lifespan_factor: number;
   maturity_age: number;
   disease_resistance: number;
+  temp_tolerance: number;
+  parental_care: number;
+}
+
+export interface SceneSnapshot {
+  tank_name: string;
+  captured_at: string;
+  tick: number;
+  population: number;
+  tank_width: number;
+  tank_height: number;
+  theme: string;
+  frame: FrameUpdate;
+  genomes: FishGenome[];
 }
 
 export interface Species {
@@ -125,6 +426,8 @@ export interface Species {
   centroid_size: number;
   centroid_pattern: string;
   member_count: number;
+  /** Shape token unique among living species where possible, for non-color cues */
+  distinct_marker: string | null;
 }
 
 export interface SpeciesHistoryEntry {
@@ -138,9 +441,19 @@ export interface SpeciesHistoryEntry {
   centroid_size: number;
   centroid_pattern: string;
   member_count: number;
+  distinct_marker: string | null;
   representative_genome_id: number | null;
 }
 
+/** Average genome of a species' last members, recorded when it went extinct */
+export interface ExtinctSpeciesProfile {
+  species_id: number;
+  name: string | null;
+  discovered_at_tick: number;
+  extinct_at_tick: number;
+  genome: FishGenome;
+}
+
 export interface FishDetail {
   id: number;
   genome_id: number;
@@ -149,6 +462,9 @@ export interface FishDetail {
   z: number;
   heading: number;
   age: number;
+  /** Ticks until old age, and the same in real seconds at the current speed (null when stopped) */
+  remaining_lifespan: number;
+  remaining_lifespan_seconds: number | null;
   hunger: number;
   health: number;
   energy: number;
@@ -156,8 +472,17 @@ export interface FishDetail {
   meals_eaten: number;
   is_alive: boolean;
   is_infected: boolean;
+  /** Lasting resistance from surviving past infections, 0-1 */
+  acquired_immunity: number;
+  diet: "herbivore" | "omnivore" | "carnivore";
+  /** Food that satisfies this fish more than usual, and food that satisfies it less */
+  preferred_food: FoodKind | null;
+  disliked_food: FoodKind | null;
   custom_name: string | null;
   is_favorite: boolean;
+  is_quarantined: boolean;
+  /** Founder genome id of the conservation program this fish belongs to */
+  protected_lineage: number | null;
   genome: FishGenome;
   species_name: string | null;
 }
@@ -171,3 +496,56 @@ export interface Toast {
   type: "info" | "warning" | "success" | "danger";
   timestamp: number;
 }
+
+/** Rejection value of every fallible command */
+export interface DeepTankError {
+  code: "not_found" | "invalid_argument" | "conflict" | "io" | "unavailable" | "cancelled";
+  message: string;
+}
+
+export function isDeepTankError(e: unknown): e is DeepTankError {
+  return typeof e === "object" && e !== null && "code" in e && "message" in e;
+}
+
+/** Display text for a rejected invoke */
+export function errorMessage(e: unknown): string {
+  return isDeepTankError(e) ? e.message : String(e);
+}
+
+/** Settings changed from their defaults, keyed by config field name */
+export type ConfigDiff = Record<string, { current: unknown; default: unknown }>;
+
+/** The shared scenario for today (UTC); pass `id` to `start_scenario` */
+export interface DailyChallenge {
+  id: string;
+  date: string;
+  seed: string | null;
+  name: string;
+  description: string;
+  goals: string[];
+  initial_fish_count: number;
+  config_overrides: Record<string, number>;
+}
+
+/** The tank as recorded at the snapshot nearest a requested tick */
+export interface SnapshotPreview {
+  requested_tick: number;
+  snapshot_tick: number;
+  ticks_ago: number;
+  /** Population, water quality and trait averages as logged */
+  snapshot: Record<string, number>;
+  species: { species_id: number; species_name: string; population: number }[];
+  /** Fish alive now that were already born at the snapshot */
+  surviving_fish: { fish_id: number; genome_id: number; born_at_tick: number }[];
+}
+
+/** Timing of `step()` on a throwaway seeded tank */
+export interface BenchmarkResult {
+  population: number;
+  final_population: number;
+  iterations: number;
+  min_ms: number;
+  avg_ms: number;
+  max_ms: number;
+  steps_per_second: number;
+}